@@ -3,13 +3,20 @@
 //! Commands for managing database connections.
 
 use serde::Serialize;
-use tauri::State;
+use tauri::{AppHandle, Emitter, State};
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 use uuid::Uuid;
 use tracing::instrument;
 
-use crate::engine::types::{ConnectionConfig, SshAuth};
-use crate::vault::VaultStorage;
+use crate::commands::vault::VaultResponse;
+use crate::engine::{DriverInfo, RetryAttempt, SessionDisplay, SessionManager};
+use crate::engine::types::{
+    ConnectionConfig, PrivilegeReport, SessionId, SessionStats, SshAuth, VariableScope,
+};
+use crate::policy::SafetyPolicy;
+use crate::vault::credentials::{ConnectionHealth, Environment};
+use crate::vault::{SavedConnection, VaultStorage};
 
 /// Response for connection operations
 #[derive(Debug, Serialize)]
@@ -17,25 +24,128 @@ pub struct ConnectionResponse {
     pub success: bool,
     pub session_id: Option<String>,
     pub error: Option<String>,
+    pub error_code: Option<String>,
+}
+
+/// Time allowed for a health probe before the server counts as unreachable
+const HEALTH_CHECK_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Response for connection health checks
+#[derive(Debug, Serialize)]
+pub struct ConnectionHealthResponse {
+    pub success: bool,
+    pub health: Option<ConnectionHealth>,
+    pub error: Option<String>,
+    pub error_code: Option<String>,
+}
+
+/// Response for privilege checks
+#[derive(Debug, Serialize)]
+pub struct PrivilegeResponse {
+    pub success: bool,
+    pub privileges: Option<PrivilegeReport>,
+    pub error: Option<String>,
+    pub error_code: Option<String>,
+}
+
+/// Response for SSH tunnel health checks
+#[derive(Debug, Serialize)]
+pub struct TunnelHealthResponse {
+    pub success: bool,
+    pub healthy: Option<bool>,
+    pub error: Option<String>,
+    pub error_code: Option<String>,
+}
+
+/// Response for read replica checks
+#[derive(Debug, Serialize)]
+pub struct ReplicaStatusResponse {
+    pub success: bool,
+    pub read_replica: Option<bool>,
+    pub error: Option<String>,
+    pub error_code: Option<String>,
+}
+
+/// Response for session query statistics
+#[derive(Debug, Serialize)]
+pub struct SessionStatsResponse {
+    pub success: bool,
+    pub stats: Option<SessionStats>,
+    pub error: Option<String>,
+    pub error_code: Option<String>,
+}
+
+/// Emitted when a session turns out to be connected to a read replica
+pub const SESSION_READ_REPLICA_EVENT: &str = "session_is_read_replica";
+
+/// Payload of the `session_is_read_replica` event
+#[derive(Debug, Clone, Serialize)]
+pub struct SessionReadReplicaEvent {
+    pub session_id: String,
+}
+
+/// Emitted before each retry of a connect or connection test that failed
+/// with a transient error
+pub const SESSION_RECONNECTING_EVENT: &str = "session_reconnecting";
+
+/// Payload of the `session_reconnecting` event
+///
+/// No session exists yet while connecting, so `session_id` is unset and
+/// the attempt is identified by the client-supplied `attempt_id`, if any.
+#[derive(Debug, Clone, Serialize)]
+pub struct SessionReconnectingEvent {
+    pub session_id: Option<String>,
+    pub attempt_id: Option<String>,
+    /// The failed attempt, starting at 1
+    pub attempt: u32,
+    pub next_delay_ms: u64,
+}
+
+/// Forwards connect retries to the frontend as `session_reconnecting` events
+fn emit_reconnecting(
+    app: &AppHandle,
+    attempt_id: Option<String>,
+) -> impl Fn(RetryAttempt) + Send + Sync + '_ {
+    move |retry| {
+        let _ = app.emit(
+            SESSION_RECONNECTING_EVENT,
+            SessionReconnectingEvent {
+                session_id: None,
+                attempt_id: attempt_id.clone(),
+                attempt: retry.attempt,
+                next_delay_ms: retry.next_delay_ms,
+            },
+        );
+    }
 }
 
 /// Session info for list response
 #[derive(Debug, Serialize)]
 pub struct SessionListItem {
     pub id: String,
+    /// The custom label if set, otherwise the generated `user@host:db` name
     pub display_name: String,
+    pub label: Option<String>,
+    pub stats: Option<SessionStats>,
 }
 
 fn load_saved_connection_config(
     project_id: &str,
     connection_id: &str,
 ) -> Result<ConnectionConfig, String> {
+    load_saved_connection(project_id, connection_id).map(|(_, config)| config)
+}
+
+fn load_saved_connection(
+    project_id: &str,
+    connection_id: &str,
+) -> Result<(SavedConnection, ConnectionConfig), String> {
     let storage = VaultStorage::new(project_id);
     let saved = storage
         .get_connection(connection_id)
         .map_err(|e| e.to_string())?;
 
-    if saved.project_id != project_id {
+    if saved.project_id != storage.project_id() {
         return Err("Connection project mismatch".to_string());
     }
 
@@ -43,20 +153,55 @@ fn load_saved_connection_config(
         .get_credentials(connection_id)
         .map_err(|e| e.to_string())?;
 
-    saved.to_connection_config(&creds).map_err(|e| e.to_string())
+    let config = saved.to_connection_config(&creds).map_err(|e| e.to_string())?;
+    Ok((saved, config))
+}
+
+/// Applies `mask_prod_connection_details` to a production session
+fn session_display(
+    policy: &SafetyPolicy,
+    config: &ConnectionConfig,
+    saved: Option<&SavedConnection>,
+) -> SessionDisplay {
+    SessionDisplay {
+        mask_details: policy.mask_prod_connection_details && config.environment == "production",
+        name: saved.map(|saved| saved.name.clone()),
+        host_alias: saved.and_then(|saved| saved.display_alias.clone()),
+    }
 }
 
 
-fn normalize_environment(env: &str) -> Result<String, String> {
-    let normalized = env.trim().to_ascii_lowercase();
-    if normalized.is_empty() {
-        return Ok("development".to_string());
+/// Lets the frontend badge a new session that `connect` found on a replica
+async fn notify_read_replica(
+    app: &AppHandle,
+    session_manager: &SessionManager,
+    session_id: SessionId,
+) {
+    if session_manager.is_read_replica(session_id).await.unwrap_or(false) {
+        let _ = app.emit(
+            SESSION_READ_REPLICA_EVENT,
+            SessionReadReplicaEvent {
+                session_id: session_id.0.to_string(),
+            },
+        );
     }
+}
 
-    match normalized.as_str() {
-        "development" | "staging" | "production" => Ok(normalized),
-        _ => Err(format!("Invalid environment: {}", env)),
+fn normalize_environment(env: &str) -> Result<String, String> {
+    if env.trim().is_empty() {
+        return Ok(Environment::default().as_str().to_string());
     }
+
+    Environment::parse(env)
+        .map(|environment| environment.as_str().to_string())
+        .ok_or_else(|| format!("Invalid environment: {}", env))
+}
+
+/// Trims a session label; a blank one means no label
+fn normalize_label(label: Option<String>) -> Option<String> {
+    label
+        .map(|label| label.trim().to_string())
+        .filter(|label| !label.is_empty())
 }
 
 fn normalize_config(mut config: ConnectionConfig) -> Result<ConnectionConfig, String> {
@@ -90,6 +235,7 @@ fn normalize_config(mut config: ConnectionConfig) -> Result<ConnectionConfig, St
     }
 
     config.environment = normalize_environment(&config.environment)?;
+    config.label = normalize_label(config.label);
 
     if let Some(ref mut ssh) = config.ssh_tunnel {
         let host = ssh.host.trim();
@@ -131,7 +277,7 @@ fn normalize_config(mut config: ConnectionConfig) -> Result<ConnectionConfig, St
 /// Tests a database connection without persisting it
 #[tauri::command]
 #[instrument(
-    skip(state, config),
+    skip(app, state, config),
     fields(
         driver = %config.driver,
         host = %config.host,
@@ -141,6 +287,7 @@ fn normalize_config(mut config: ConnectionConfig) -> Result<ConnectionConfig, St
     )
 )]
 pub async fn test_connection(
+    app: AppHandle,
     state: State<'_, crate::SharedState>,
     config: ConnectionConfig,
 ) -> Result<ConnectionResponse, String> {
@@ -156,28 +303,33 @@ pub async fn test_connection(
                 success: false,
                 session_id: None,
                 error: Some(e),
+                error_code: None,
             });
         }
     };
 
-    match session_manager.test_connection(&config).await {
+    let on_retry = emit_reconnecting(&app, None);
+    match session_manager.test_connection(&config, &on_retry).await {
         Ok(()) => Ok(ConnectionResponse {
             success: true,
             session_id: None,
             error: None,
+            error_code: None,
         }),
         Err(e) => Ok(ConnectionResponse {
             success: false,
             session_id: None,
             error: Some(e.to_string()),
+            error_code: Some(e.code().to_string()),
         }),
     }
 }
 
 /// Tests a saved connection using vault metadata + credentials
 #[tauri::command]
-#[instrument(skip(state), fields(project_id = %project_id, connection_id = %connection_id))]
+#[instrument(skip(app, state), fields(project_id = %project_id, connection_id = %connection_id))]
 pub async fn test_saved_connection(
+    app: AppHandle,
     state: State<'_, crate::SharedState>,
     project_id: String,
     connection_id: String,
@@ -189,6 +341,7 @@ pub async fn test_saved_connection(
                 success: false,
                 session_id: None,
                 error: Some("Vault is locked".to_string()),
+                error_code: None,
             });
         }
         Arc::clone(&state.session_manager)
@@ -203,51 +356,126 @@ pub async fn test_saved_connection(
                 success: false,
                 session_id: None,
                 error: Some(e),
+                error_code: None,
             });
         }
     };
 
-    match session_manager.test_connection(&config).await {
+    let on_retry = emit_reconnecting(&app, None);
+    match session_manager.test_connection(&config, &on_retry).await {
         Ok(()) => Ok(ConnectionResponse {
             success: true,
             session_id: None,
             error: None,
+            error_code: None,
         }),
         Err(e) => Ok(ConnectionResponse {
             success: false,
             session_id: None,
             error: Some(e.to_string()),
+            error_code: Some(e.code().to_string()),
         }),
     }
 }
 
+/// Probes a saved connection with `test_connection`, giving up after
+/// `HEALTH_CHECK_TIMEOUT`
+///
+/// The caller is responsible for checking that the vault is unlocked.
+pub(crate) async fn probe_saved_connection(
+    session_manager: &SessionManager,
+    project_id: &str,
+    connection_id: &str,
+) -> ConnectionHealth {
+    let unreachable = |error: String| ConnectionHealth {
+        reachable: false,
+        latency_ms: None,
+        error: Some(error),
+    };
+
+    let config = match load_saved_connection_config(project_id, connection_id)
+        .and_then(normalize_config)
+    {
+        Ok(cfg) => cfg,
+        Err(e) => return unreachable(e),
+    };
+
+    let start = Instant::now();
+    let probe = session_manager.test_connection(&config, &|_| {});
+    match tokio::time::timeout(HEALTH_CHECK_TIMEOUT, probe).await {
+        Ok(Ok(())) => ConnectionHealth {
+            reachable: true,
+            latency_ms: Some(start.elapsed().as_secs_f64() * 1000.0),
+            error: None,
+        },
+        Ok(Err(e)) => unreachable(e.to_string()),
+        Err(_) => unreachable(format!(
+            "No response within {} seconds",
+            HEALTH_CHECK_TIMEOUT.as_secs()
+        )),
+    }
+}
+
+/// Checks whether a saved connection's server is reachable
+#[tauri::command]
+#[instrument(skip(state), fields(project_id = %project_id, connection_id = %connection_id))]
+pub async fn check_connection_health(
+    state: State<'_, crate::SharedState>,
+    project_id: String,
+    connection_id: String,
+) -> Result<ConnectionHealthResponse, String> {
+    let session_manager = {
+        let state = state.lock().await;
+        if state.vault_lock.is_locked() {
+            return Ok(ConnectionHealthResponse {
+                success: false,
+                health: None,
+                error: Some("Vault is locked".to_string()),
+                error_code: None,
+            });
+        }
+        Arc::clone(&state.session_manager)
+    };
+
+    let health = probe_saved_connection(&session_manager, &project_id, &connection_id).await;
+    Ok(ConnectionHealthResponse {
+        success: true,
+        health: Some(health),
+        error: None,
+        error_code: None,
+    })
+}
+
 /// Establishes a new database connection
 #[tauri::command]
 #[instrument(
-    skip(state, config),
+    skip(app, state, config),
+    // The host is recorded by `SessionManager::connect`, which may mask it
     fields(
         driver = %config.driver,
-        host = %config.host,
         port = config.port,
         database = ?config.database,
         ssh = config.ssh_tunnel.is_some()
     )
 )]
 pub async fn connect(
+    app: AppHandle,
     state: State<'_, crate::SharedState>,
     config: ConnectionConfig,
+    attempt_id: Option<String>,
 ) -> Result<ConnectionResponse, String> {
     if !cfg!(debug_assertions) {
         return Ok(ConnectionResponse {
             success: false,
             session_id: None,
             error: Some("Direct connect is disabled in release builds. Save the connection and use connect_saved_connection.".to_string()),
+            error_code: None,
         });
     }
 
-    let session_manager = {
+    let (session_manager, policy) = {
         let state = state.lock().await;
-        Arc::clone(&state.session_manager)
+        (Arc::clone(&state.session_manager), state.policy.clone())
     };
 
     let config = match normalize_config(config) {
@@ -257,71 +485,131 @@ pub async fn connect(
                 success: false,
                 session_id: None,
                 error: Some(e),
+                error_code: None,
             });
         }
     };
 
-    match session_manager.connect(config).await {
-        Ok(session_id) => Ok(ConnectionResponse {
-            success: true,
-            session_id: Some(session_id.0.to_string()),
-            error: None,
-        }),
+    let display = session_display(&policy, &config, None);
+    let on_retry = emit_reconnecting(&app, attempt_id.clone());
+    match session_manager
+        .connect(config, attempt_id, display, &on_retry)
+        .await
+    {
+        Ok(session_id) => {
+            notify_read_replica(&app, &session_manager, session_id).await;
+            Ok(ConnectionResponse {
+                success: true,
+                session_id: Some(session_id.0.to_string()),
+                error: None,
+                error_code: None,
+            })
+        }
         Err(e) => Ok(ConnectionResponse {
             success: false,
             session_id: None,
             error: Some(e.to_string()),
+            error_code: Some(e.code().to_string()),
         }),
     }
 }
 
 /// Establishes a new database connection from a saved connection
 #[tauri::command]
-#[instrument(skip(state), fields(project_id = %project_id, connection_id = %connection_id))]
+#[instrument(skip(app, state), fields(project_id = %project_id, connection_id = %connection_id))]
 pub async fn connect_saved_connection(
+    app: AppHandle,
     state: State<'_, crate::SharedState>,
     project_id: String,
     connection_id: String,
+    attempt_id: Option<String>,
 ) -> Result<ConnectionResponse, String> {
-    let session_manager = {
+    let (session_manager, policy) = {
         let state = state.lock().await;
         if state.vault_lock.is_locked() {
             return Ok(ConnectionResponse {
                 success: false,
                 session_id: None,
                 error: Some("Vault is locked".to_string()),
+                error_code: None,
             });
         }
-        Arc::clone(&state.session_manager)
+        (Arc::clone(&state.session_manager), state.policy.clone())
     };
 
-    let config = match load_saved_connection_config(&project_id, &connection_id)
-        .and_then(normalize_config)
-    {
-        Ok(cfg) => cfg,
+    let loaded = load_saved_connection(&project_id, &connection_id)
+        .and_then(|(saved, config)| Ok((saved, normalize_config(config)?)));
+    let (saved, config) = match loaded {
+        Ok(loaded) => loaded,
         Err(e) => {
             return Ok(ConnectionResponse {
                 success: false,
                 session_id: None,
                 error: Some(e),
+                error_code: None,
             });
         }
     };
 
-    match session_manager.connect(config).await {
-        Ok(session_id) => Ok(ConnectionResponse {
-            success: true,
-            session_id: Some(session_id.0.to_string()),
-            error: None,
-        }),
+    let display = session_display(&policy, &config, Some(&saved));
+    let on_retry = emit_reconnecting(&app, attempt_id.clone());
+    match session_manager
+        .connect(config, attempt_id, display, &on_retry)
+        .await
+    {
+        Ok(session_id) => {
+            if let Err(e) = session_manager
+                .set_source_connection(session_id, &project_id, &connection_id)
+                .await
+            {
+                tracing::warn!(error = %e, "Failed to record session source connection");
+            }
+            notify_read_replica(&app, &session_manager, session_id).await;
+            Ok(ConnectionResponse {
+                success: true,
+                session_id: Some(session_id.0.to_string()),
+                error: None,
+                error_code: None,
+            })
+        }
         Err(e) => Ok(ConnectionResponse {
             success: false,
             session_id: None,
             error: Some(e.to_string()),
+            error_code: Some(e.code().to_string()),
         }),
     }
 }
 
+/// Aborts a pending `connect`/`connect_saved_connection` started with `attempt_id`
+#[tauri::command]
+#[instrument(skip(state), fields(attempt_id = %attempt_id))]
+pub async fn cancel_connect(
+    state: State<'_, crate::SharedState>,
+    attempt_id: String,
+) -> Result<ConnectionResponse, String> {
+    let session_manager = {
+        let state = state.lock().await;
+        Arc::clone(&state.session_manager)
+    };
+
+    if session_manager.cancel_connect(&attempt_id).await {
+        Ok(ConnectionResponse {
+            success: true,
+            session_id: None,
+            error: None,
+            error_code: None,
+        })
+    } else {
+        Ok(ConnectionResponse {
+            success: false,
+            session_id: None,
+            error: Some("No pending connection attempt with this ID".to_string()),
+            error_code: None,
+        })
+    }
+}
+
 /// Disconnects an active session
 #[tauri::command]
 #[instrument(skip(state), fields(session_id = %session_id))]
@@ -344,11 +632,207 @@ pub async fn disconnect(
             success: true,
             session_id: None,
             error: None,
+            error_code: None,
         }),
         Err(e) => Ok(ConnectionResponse {
             success: false,
             session_id: None,
             error: Some(e.to_string()),
+            error_code: Some(e.code().to_string()),
+        }),
+    }
+}
+
+/// Reconnects a session, re-reading its saved credentials from the vault
+///
+/// Sessions not opened from a saved connection reuse their stored config.
+async fn reconnect_one(
+    session_manager: &SessionManager,
+    vault_locked: bool,
+    session_id: SessionId,
+) -> ConnectionResponse {
+    let failure = |error: String, error_code: Option<String>| ConnectionResponse {
+        success: false,
+        session_id: Some(session_id.0.to_string()),
+        error: Some(error),
+        error_code,
+    };
+
+    let password = match session_manager.source_connection(session_id).await {
+        Ok(Some((project_id, connection_id))) => {
+            if vault_locked {
+                return failure("Vault is locked".to_string(), None);
+            }
+            match load_saved_connection_config(&project_id, &connection_id) {
+                Ok(config) => Some(config.password),
+                Err(e) => return failure(e, None),
+            }
+        }
+        Ok(None) => None,
+        Err(e) => return failure(e.to_string(), Some(e.code().to_string())),
+    };
+
+    match session_manager.reconnect(session_id, password).await {
+        Ok(()) => ConnectionResponse {
+            success: true,
+            session_id: Some(session_id.0.to_string()),
+            error: None,
+            error_code: None,
+        },
+        Err(e) => failure(e.to_string(), Some(e.code().to_string())),
+    }
+}
+
+/// Reopens a session's connection (e.g. after the vault was unlocked again)
+///
+/// The session keeps its ID.
+#[tauri::command]
+#[instrument(skip(state), fields(session_id = %session_id))]
+pub async fn reconnect_session(
+    state: State<'_, crate::SharedState>,
+    session_id: String,
+) -> Result<ConnectionResponse, String> {
+    let (session_manager, vault_locked) = {
+        let state = state.lock().await;
+        (Arc::clone(&state.session_manager), state.vault_lock.is_locked())
+    };
+
+    let uuid = Uuid::parse_str(&session_id)
+        .map_err(|e| format!("Invalid session ID: {}", e))?;
+
+    Ok(reconnect_one(&session_manager, vault_locked, SessionId(uuid)).await)
+}
+
+/// Reconnects every active session, returning one response per session
+#[tauri::command]
+pub async fn reconnect_all_sessions(
+    state: State<'_, crate::SharedState>,
+) -> Result<Vec<ConnectionResponse>, String> {
+    let (session_manager, vault_locked) = {
+        let state = state.lock().await;
+        (Arc::clone(&state.session_manager), state.vault_lock.is_locked())
+    };
+
+    let sessions = session_manager.list_sessions().await;
+    Ok(futures::future::join_all(
+        sessions
+            .into_iter()
+            .map(|(id, ..)| reconnect_one(&session_manager, vault_locked, id)),
+    )
+    .await)
+}
+
+/// Changes the name a session is shown under
+///
+/// A missing or blank `label` restores the generated `user@host:db` name.
+#[tauri::command]
+#[instrument(skip(state), fields(session_id = %session_id))]
+pub async fn rename_session(
+    state: State<'_, crate::SharedState>,
+    session_id: String,
+    label: Option<String>,
+) -> Result<VaultResponse, String> {
+    let session_manager = {
+        let state = state.lock().await;
+        Arc::clone(&state.session_manager)
+    };
+
+    let uuid = Uuid::parse_str(&session_id)
+        .map_err(|e| format!("Invalid session ID: {}", e))?;
+
+    match session_manager
+        .rename_session(SessionId(uuid), normalize_label(label))
+        .await
+    {
+        Ok(()) => Ok(VaultResponse {
+            success: true,
+            error: None,
+            error_code: None,
+        }),
+        Err(e) => Ok(VaultResponse {
+            success: false,
+            error: Some(e.to_string()),
+            error_code: Some(e.code().to_string()),
+        }),
+    }
+}
+
+/// Reclassifies a session's environment (e.g. `staging`) without reconnecting
+///
+/// The production and staging guards apply from the next query on. Moving a
+/// session out of production or staging needs `acknowledged_dangerous` when
+/// the policy requires confirmation there.
+#[tauri::command]
+#[instrument(skip(state), fields(session_id = %session_id, environment = %environment))]
+pub async fn set_environment(
+    state: State<'_, crate::SharedState>,
+    session_id: String,
+    environment: String,
+    acknowledged_dangerous: Option<bool>,
+) -> Result<VaultResponse, String> {
+    let (session_manager, policy) = {
+        let state = state.lock().await;
+        (Arc::clone(&state.session_manager), state.policy.clone())
+    };
+
+    let uuid = Uuid::parse_str(&session_id)
+        .map_err(|e| format!("Invalid session ID: {}", e))?;
+    let Some(environment) = Environment::parse(&environment) else {
+        return Ok(VaultResponse {
+            success: false,
+            error: Some(format!("Invalid environment: {}", environment)),
+            error_code: None,
+        });
+    };
+
+    match session_manager
+        .set_environment(
+            SessionId(uuid),
+            environment,
+            &policy,
+            acknowledged_dangerous.unwrap_or(false),
+        )
+        .await
+    {
+        Ok(()) => Ok(VaultResponse {
+            success: true,
+            error: None,
+            error_code: None,
+        }),
+        Err(e) => Ok(VaultResponse {
+            success: false,
+            error: Some(e.to_string()),
+            error_code: Some(e.code().to_string()),
+        }),
+    }
+}
+
+/// Returns the statistics of the queries run on a session since it (re)connected
+#[tauri::command]
+pub async fn get_session_stats(
+    state: State<'_, crate::SharedState>,
+    session_id: String,
+) -> Result<SessionStatsResponse, String> {
+    let session_manager = {
+        let state = state.lock().await;
+        Arc::clone(&state.session_manager)
+    };
+
+    let uuid = Uuid::parse_str(&session_id)
+        .map_err(|e| format!("Invalid session ID: {}", e))?;
+
+    match session_manager.session_stats(SessionId(uuid)).await {
+        Ok(stats) => Ok(SessionStatsResponse {
+            success: true,
+            stats: Some(stats.snapshot()),
+            error: None,
+            error_code: None,
+        }),
+        Err(e) => Ok(SessionStatsResponse {
+            success: false,
+            stats: None,
+            error: Some(e.to_string()),
+            error_code: Some(e.code().to_string()),
         }),
     }
 }
@@ -367,9 +851,223 @@ pub async fn list_sessions(
 
     Ok(sessions
         .into_iter()
-        .map(|(id, name)| SessionListItem {
+        .map(|(id, name, label, stats)| SessionListItem {
             id: id.0.to_string(),
             display_name: name,
+            label,
+            stats: Some(stats),
         })
         .collect())
 }
+
+/// Loads a driver plugin (`.so`, `.dylib`, `.dll`) and registers its driver
+#[tauri::command]
+#[instrument(skip(state))]
+pub async fn load_driver_plugin(
+    state: State<'_, crate::SharedState>,
+    path: String,
+) -> Result<VaultResponse, String> {
+    let registry = {
+        let state = state.lock().await;
+        Arc::clone(&state.registry)
+    };
+
+    match registry.load_plugin(std::path::Path::new(&path)) {
+        Ok(()) => Ok(VaultResponse {
+            success: true,
+            error: None,
+            error_code: None,
+        }),
+        Err(e) => Ok(VaultResponse {
+            success: false,
+            error: Some(e.to_string()),
+            error_code: Some(e.code().to_string()),
+        }),
+    }
+}
+
+/// Lists built-in and plugin drivers
+#[tauri::command]
+pub async fn list_registered_drivers(
+    state: State<'_, crate::SharedState>,
+) -> Result<Vec<DriverInfo>, String> {
+    let registry = {
+        let state = state.lock().await;
+        Arc::clone(&state.registry)
+    };
+
+    Ok(registry.list_drivers_info())
+}
+
+/// Lists the available drivers with what the connection form needs to know
+/// about each (default port, schemas, database field, capabilities)
+#[tauri::command]
+pub async fn list_available_drivers(
+    state: State<'_, crate::SharedState>,
+) -> Result<Vec<DriverInfo>, String> {
+    let registry = {
+        let state = state.lock().await;
+        Arc::clone(&state.registry)
+    };
+
+    Ok(registry.list_drivers_info())
+}
+
+/// Reports what the session's user may do, to adapt the UI after connecting
+#[tauri::command]
+#[instrument(skip(state), fields(session_id = %session_id))]
+pub async fn check_session_privileges(
+    state: State<'_, crate::SharedState>,
+    session_id: String,
+) -> Result<PrivilegeResponse, String> {
+    let session_manager = {
+        let state = state.lock().await;
+        Arc::clone(&state.session_manager)
+    };
+
+    let uuid = Uuid::parse_str(&session_id)
+        .map_err(|e| format!("Invalid session ID: {}", e))?;
+    let session = crate::engine::types::SessionId(uuid);
+
+    let driver = match session_manager.get_driver(session).await {
+        Ok(driver) => driver,
+        Err(e) => {
+            return Ok(PrivilegeResponse {
+                success: false,
+                privileges: None,
+                error: Some(e.to_string()),
+                error_code: Some(e.code().to_string()),
+            });
+        }
+    };
+
+    match driver.check_privileges(session).await {
+        Ok(privileges) => Ok(PrivilegeResponse {
+            success: true,
+            privileges: Some(privileges),
+            error: None,
+            error_code: None,
+        }),
+        Err(e) => Ok(PrivilegeResponse {
+            success: false,
+            privileges: None,
+            error: Some(e.to_string()),
+            error_code: Some(e.code().to_string()),
+        }),
+    }
+}
+
+/// Asks the server again whether the session is on a read replica
+///
+/// A replica session is made read-only and `session_is_read_replica` is emitted.
+#[tauri::command]
+#[instrument(skip(app, state), fields(session_id = %session_id))]
+pub async fn check_replica_status(
+    app: AppHandle,
+    state: State<'_, crate::SharedState>,
+    session_id: String,
+) -> Result<ReplicaStatusResponse, String> {
+    let session_manager = {
+        let state = state.lock().await;
+        Arc::clone(&state.session_manager)
+    };
+
+    let uuid = Uuid::parse_str(&session_id)
+        .map_err(|e| format!("Invalid session ID: {}", e))?;
+    let session = SessionId(uuid);
+
+    match session_manager.refresh_replica_status(session).await {
+        Ok(read_replica) => {
+            if read_replica {
+                let _ = app.emit(
+                    SESSION_READ_REPLICA_EVENT,
+                    SessionReadReplicaEvent { session_id },
+                );
+            }
+            Ok(ReplicaStatusResponse {
+                success: true,
+                read_replica: Some(read_replica),
+                error: None,
+                error_code: None,
+            })
+        }
+        Err(e) => Ok(ReplicaStatusResponse {
+            success: false,
+            read_replica: None,
+            error: Some(e.to_string()),
+            error_code: Some(e.code().to_string()),
+        }),
+    }
+}
+
+/// Checks whether the SSH tunnel of a session is still up
+///
+/// Sessions without a tunnel report a healthy tunnel.
+#[tauri::command]
+#[instrument(skip(state), fields(session_id = %session_id))]
+pub async fn check_tunnel_health(
+    state: State<'_, crate::SharedState>,
+    session_id: String,
+) -> Result<TunnelHealthResponse, String> {
+    let session_manager = {
+        let state = state.lock().await;
+        Arc::clone(&state.session_manager)
+    };
+
+    let uuid = Uuid::parse_str(&session_id)
+        .map_err(|e| format!("Invalid session ID: {}", e))?;
+    let session = SessionId(uuid);
+
+    match session_manager.tunnel_healthy(session).await {
+        Ok(healthy) => Ok(TunnelHealthResponse {
+            success: true,
+            healthy: Some(healthy),
+            error: None,
+            error_code: None,
+        }),
+        Err(e) => Ok(TunnelHealthResponse {
+            success: false,
+            healthy: None,
+            error: Some(e.to_string()),
+            error_code: Some(e.code().to_string()),
+        }),
+    }
+}
+
+/// Sets a server variable (e.g. `statement_timeout`) on a session
+///
+/// Only allowlisted variable names are accepted and the value is bound as a
+/// parameter, unlike a raw `SET` sent through `execute_query`.
+#[tauri::command]
+#[instrument(skip(state, value), fields(session_id = %session_id, name = %name))]
+pub async fn set_session_variable(
+    state: State<'_, crate::SharedState>,
+    session_id: String,
+    name: String,
+    value: String,
+    scope: VariableScope,
+) -> Result<VaultResponse, String> {
+    let session_manager = {
+        let state = state.lock().await;
+        Arc::clone(&state.session_manager)
+    };
+
+    let uuid = Uuid::parse_str(&session_id)
+        .map_err(|e| format!("Invalid session ID: {}", e))?;
+
+    match session_manager
+        .set_session_variable(crate::engine::types::SessionId(uuid), &name, &value, scope)
+        .await
+    {
+        Ok(()) => Ok(VaultResponse {
+            success: true,
+            error: None,
+            error_code: None,
+        }),
+        Err(e) => Ok(VaultResponse {
+            success: false,
+            error: Some(e.to_string()),
+            error_code: Some(e.code().to_string()),
+        }),
+    }
+}
@@ -0,0 +1,202 @@
+//! Explain Tauri Commands
+//!
+//! Commands for running long `EXPLAIN ANALYZE` jobs in the background,
+//! streaming the plan to the frontend through Tauri events.
+
+use serde::Serialize;
+use std::sync::Arc;
+use tauri::{AppHandle, Emitter, State};
+use tracing::instrument;
+use uuid::Uuid;
+
+use crate::commands::query::QueryGuard;
+use crate::engine::types::{QueryId, SessionId};
+
+/// Emitted for each plan line as it arrives
+pub const EXPLAIN_LINE_EVENT: &str = "explain_line";
+/// Emitted once the analysis finished, failed or was cancelled
+pub const EXPLAIN_COMPLETE_EVENT: &str = "explain_complete";
+
+/// Response for starting or cancelling an explain job
+#[derive(Debug, Serialize)]
+pub struct ExplainResponse {
+    pub success: bool,
+    pub explain_id: Option<String>,
+    pub error: Option<String>,
+    pub error_code: Option<String>,
+}
+
+/// Payload of the `explain_line` event
+#[derive(Debug, Clone, Serialize)]
+pub struct ExplainLineEvent {
+    pub explain_id: String,
+    pub line: String,
+}
+
+/// Payload of the `explain_complete` event
+#[derive(Debug, Clone, Serialize)]
+pub struct ExplainCompleteEvent {
+    pub explain_id: String,
+    pub success: bool,
+    /// Full plan text (all lines joined), when the analysis completed
+    pub plan: Option<String>,
+    pub error: Option<String>,
+    pub error_code: Option<String>,
+}
+
+/// Parses a session ID string into SessionId
+fn parse_session_id(id: &str) -> Result<SessionId, String> {
+    let uuid = Uuid::parse_str(id).map_err(|e| format!("Invalid session ID: {}", e))?;
+    Ok(SessionId(uuid))
+}
+
+fn failure(error: impl Into<String>) -> ExplainResponse {
+    ExplainResponse {
+        success: false,
+        explain_id: None,
+        error: Some(error.into()),
+        error_code: None,
+    }
+}
+
+/// Starts `EXPLAIN ANALYZE` for a query in the background
+///
+/// Returns immediately with an explain ID. Plan lines are emitted as
+/// `explain_line` events, followed by a single `explain_complete` event
+/// carrying the full plan. No timeout is applied; use `cancel_explain`.
+#[tauri::command]
+#[instrument(skip(app, state, query), fields(session_id = %session_id, query_len = query.len()))]
+pub async fn explain_analyze_stream(
+    app: AppHandle,
+    state: State<'_, crate::SharedState>,
+    session_id: String,
+    query: String,
+    acknowledged_dangerous: Option<bool>,
+) -> Result<ExplainResponse, String> {
+    let (session_manager, query_manager, policy) = {
+        let state = state.lock().await;
+        (
+            Arc::clone(&state.session_manager),
+            Arc::clone(&state.query_manager),
+            state.policy.clone(),
+        )
+    };
+    let session = parse_session_id(&session_id)?;
+
+    let driver = match session_manager.get_driver(session).await {
+        Ok(d) => d,
+        Err(e) => return Ok(failure(e.to_string())),
+    };
+
+    // EXPLAIN ANALYZE executes the statement, so the usual guards apply.
+    let dialect = driver.dialect_id(session).await;
+    let read_only = match session_manager.is_read_only(session).await {
+        Ok(read_only) => read_only,
+        Err(e) => return Ok(failure(e.to_string())),
+    };
+    let is_production = session_manager.is_production(session).await.unwrap_or(false);
+    let is_staging = session_manager.is_staging(session).await.unwrap_or(false);
+    let is_protected_host = session_manager
+        .is_protected_host(session, &policy)
+        .await
+        .unwrap_or(false);
+
+    let guard = QueryGuard {
+        policy: &policy,
+        driver_id: dialect,
+        read_only,
+        is_production,
+        is_staging,
+        is_protected_host,
+        acknowledged: acknowledged_dangerous.unwrap_or(false),
+    };
+    if let Some(error) = guard.check(&query) {
+        return Ok(failure(error));
+    }
+
+    let query_id = query_manager.register(session).await;
+    let explain_id = query_id.0.to_string();
+
+    let task_explain_id = explain_id.clone();
+    tauri::async_runtime::spawn(async move {
+        let line_app = app.clone();
+        let line_explain_id = task_explain_id.clone();
+        let on_line = move |line: &str| {
+            let _ = line_app.emit(
+                EXPLAIN_LINE_EVENT,
+                ExplainLineEvent {
+                    explain_id: line_explain_id.clone(),
+                    line: line.to_string(),
+                },
+            );
+        };
+
+        let result = driver
+            .explain_analyze(session, &query, query_id, &on_line)
+            .await;
+        query_manager.finish(query_id).await;
+
+        let event = match result {
+            Ok(lines) => ExplainCompleteEvent {
+                explain_id: task_explain_id,
+                success: true,
+                plan: Some(lines.join("\n")),
+                error: None,
+                error_code: None,
+            },
+            Err(e) => ExplainCompleteEvent {
+                explain_id: task_explain_id,
+                success: false,
+                plan: None,
+                error: Some(e.to_string()),
+                error_code: Some(e.code().to_string()),
+            },
+        };
+        let _ = app.emit(EXPLAIN_COMPLETE_EVENT, event);
+    });
+
+    Ok(ExplainResponse {
+        success: true,
+        explain_id: Some(explain_id),
+        error: None,
+        error_code: None,
+    })
+}
+
+/// Cancels a running `EXPLAIN ANALYZE` job
+#[tauri::command]
+#[instrument(skip(state), fields(session_id = %session_id, explain_id = %explain_id))]
+pub async fn cancel_explain(
+    state: State<'_, crate::SharedState>,
+    session_id: String,
+    explain_id: String,
+) -> Result<ExplainResponse, String> {
+    let session_manager = {
+        let state = state.lock().await;
+        Arc::clone(&state.session_manager)
+    };
+    let session = parse_session_id(&session_id)?;
+    let query_id = Uuid::parse_str(&explain_id)
+        .map(QueryId)
+        .map_err(|e| format!("Invalid explain ID: {}", e))?;
+
+    let driver = match session_manager.get_driver(session).await {
+        Ok(d) => d,
+        Err(e) => return Ok(failure(e.to_string())),
+    };
+
+    match driver.cancel(session, Some(query_id)).await {
+        Ok(()) => Ok(ExplainResponse {
+            success: true,
+            explain_id: Some(explain_id),
+            error: None,
+            error_code: None,
+        }),
+        Err(e) => Ok(ExplainResponse {
+            success: false,
+            explain_id: Some(explain_id),
+            error: Some(e.to_string()),
+            error_code: Some(e.code().to_string()),
+        }),
+    }
+}
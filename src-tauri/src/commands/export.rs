@@ -0,0 +1,112 @@
+//! Export Tauri Commands
+//!
+//! Commands for writing query results to files in binary formats the
+//! frontend cannot produce itself.
+
+use serde::Serialize;
+use std::sync::Arc;
+use tauri::State;
+use tracing::instrument;
+use uuid::Uuid;
+
+use crate::engine::{
+    export::{self, PARQUET_BATCH_SIZE},
+    types::QueryResult,
+    EngineError,
+};
+
+/// Response wrapper for export operations
+#[derive(Debug, Serialize)]
+pub struct ExportResponse {
+    pub success: bool,
+    pub export_id: Option<String>,
+    pub error: Option<String>,
+    pub error_code: Option<String>,
+}
+
+/// Writes a query result to `path` in the given format
+///
+/// Supported formats: `parquet`. Pass an `export_id` to be able to stop the
+/// export with `cancel_export`; a partially written file is removed.
+#[tauri::command]
+#[instrument(skip(state, result, path), fields(format = %format, rows = result.rows.len()))]
+pub async fn export_result(
+    state: State<'_, crate::SharedState>,
+    result: QueryResult,
+    format: String,
+    path: String,
+    export_id: Option<String>,
+) -> Result<ExportResponse, String> {
+    if !format.eq_ignore_ascii_case("parquet") {
+        return Ok(ExportResponse {
+            success: false,
+            export_id: None,
+            error: Some(format!("Unsupported export format: {}", format)),
+            error_code: None,
+        });
+    }
+
+    let export_manager = {
+        let state = state.lock().await;
+        Arc::clone(&state.export_manager)
+    };
+    let export_id = export_id.unwrap_or_else(|| Uuid::new_v4().to_string());
+    let cancelled = export_manager.register(&export_id);
+
+    let task_path = path.clone();
+    let outcome = tauri::async_runtime::spawn_blocking(move || {
+        let file = std::fs::File::create(&task_path)
+            .map_err(|e| EngineError::internal(format!("Failed to create file: {}", e)))?;
+        export::to_parquet(&result, file, PARQUET_BATCH_SIZE, &cancelled)
+    })
+    .await
+    .map_err(|e| e.to_string())?;
+
+    export_manager.finish(&export_id);
+
+    match outcome {
+        Ok(()) => Ok(ExportResponse {
+            success: true,
+            export_id: Some(export_id),
+            error: None,
+            error_code: None,
+        }),
+        Err(e) => {
+            let _ = std::fs::remove_file(&path);
+            Ok(ExportResponse {
+                success: false,
+                export_id: Some(export_id),
+                error: Some(e.to_string()),
+                error_code: Some(e.code().to_string()),
+            })
+        }
+    }
+}
+
+/// Cancels a running export
+#[tauri::command]
+pub async fn cancel_export(
+    state: State<'_, crate::SharedState>,
+    export_id: String,
+) -> Result<ExportResponse, String> {
+    let export_manager = {
+        let state = state.lock().await;
+        Arc::clone(&state.export_manager)
+    };
+
+    if export_manager.cancel(&export_id) {
+        Ok(ExportResponse {
+            success: true,
+            export_id: Some(export_id),
+            error: None,
+            error_code: None,
+        })
+    } else {
+        Ok(ExportResponse {
+            success: false,
+            export_id: Some(export_id),
+            error: Some("Export not found".to_string()),
+            error_code: None,
+        })
+    }
+}
@@ -0,0 +1,207 @@
+//! Import Tauri Commands
+//!
+//! Commands for bulk-loading files into tables with the server's bulk
+//! protocol, reporting progress through Tauri events.
+
+use serde::Serialize;
+use std::sync::Arc;
+use tauri::{AppHandle, Emitter, State};
+use tracing::instrument;
+use uuid::Uuid;
+
+use crate::commands::query::protected_host_error;
+use crate::engine::{
+    error::EngineResult,
+    types::{Namespace, SessionId, Value},
+    EngineError,
+};
+
+/// Emitted as rows are sent to the server
+pub const IMPORT_PROGRESS_EVENT: &str = "import_progress";
+
+const READ_ONLY_BLOCKED: &str = "Operation blocked: read-only mode";
+
+/// Rows read ahead of the server
+const READ_AHEAD_ROWS: usize = 1024;
+
+/// Response for import operations
+#[derive(Debug, Serialize)]
+pub struct ImportResponse {
+    pub success: bool,
+    pub import_id: Option<String>,
+    pub rows_imported: Option<u64>,
+    pub error: Option<String>,
+    pub error_code: Option<String>,
+}
+
+/// Payload of the `import_progress` event
+#[derive(Debug, Clone, Serialize)]
+pub struct ImportProgressEvent {
+    pub import_id: String,
+    /// Rows sent to the server so far
+    pub rows_sent: u64,
+}
+
+/// Parses a session ID string into SessionId
+fn parse_session_id(id: &str) -> Result<SessionId, String> {
+    let uuid = Uuid::parse_str(id).map_err(|e| format!("Invalid session ID: {}", e))?;
+    Ok(SessionId(uuid))
+}
+
+fn failure(error: impl Into<String>) -> ImportResponse {
+    ImportResponse {
+        success: false,
+        import_id: None,
+        rows_imported: None,
+        error: Some(error.into()),
+        error_code: None,
+    }
+}
+
+fn engine_failure(error: EngineError) -> ImportResponse {
+    ImportResponse {
+        error_code: Some(error.code().to_string()),
+        ..failure(error.to_string())
+    }
+}
+
+/// Converts a CSV record into row values; fields equal to `null_marker`
+/// become NULL
+fn record_values(record: &csv::StringRecord, null_marker: &str) -> Vec<Value> {
+    record
+        .iter()
+        .map(|field| {
+            if field == null_marker {
+                Value::Null
+            } else {
+                Value::Text(field.to_string())
+            }
+        })
+        .collect()
+}
+
+/// Imports a CSV file into a table with `COPY ... FROM STDIN` (PostgreSQL)
+///
+/// The first line of the file names the columns unless `columns` is given;
+/// set `has_header` to false for files without a header line. Fields equal
+/// to `null_marker` (an empty field by default) are imported as NULL.
+/// Progress is emitted as `import_progress` events tagged with `import_id`.
+/// Nothing is imported if any row fails.
+#[tauri::command]
+#[instrument(
+    skip(app, state, path, columns),
+    fields(session_id = %session_id, database = %namespace.database, table = %table)
+)]
+#[allow(clippy::too_many_arguments)]
+pub async fn import_copy(
+    app: AppHandle,
+    state: State<'_, crate::SharedState>,
+    session_id: String,
+    namespace: Namespace,
+    table: String,
+    path: String,
+    columns: Option<Vec<String>>,
+    has_header: Option<bool>,
+    null_marker: Option<String>,
+    import_id: Option<String>,
+    acknowledged_dangerous: Option<bool>,
+) -> Result<ImportResponse, String> {
+    let (session_manager, policy) = {
+        let state = state.lock().await;
+        (Arc::clone(&state.session_manager), state.policy.clone())
+    };
+    let session = parse_session_id(&session_id)?;
+
+    match session_manager.is_read_only(session).await {
+        Ok(true) => return Ok(failure(READ_ONLY_BLOCKED)),
+        Ok(false) => {}
+        Err(e) => return Ok(engine_failure(e)),
+    }
+
+    let is_protected_host = match session_manager.is_protected_host(session, &policy).await {
+        Ok(is_protected_host) => is_protected_host,
+        Err(e) => return Ok(engine_failure(e)),
+    };
+    if let Some(error) = protected_host_error(
+        &policy,
+        is_protected_host,
+        acknowledged_dangerous.unwrap_or(false),
+    ) {
+        return Ok(failure(error));
+    }
+
+    let driver = match session_manager.get_driver(session).await {
+        Ok(d) => d,
+        Err(e) => return Ok(engine_failure(e)),
+    };
+
+    let has_header = has_header.unwrap_or(true);
+    let mut reader = match csv::ReaderBuilder::new()
+        .has_headers(has_header)
+        .from_path(&path)
+    {
+        Ok(reader) => reader,
+        Err(e) => return Ok(failure(format!("Failed to open file: {}", e))),
+    };
+
+    let columns = match columns {
+        Some(columns) if !columns.is_empty() => columns,
+        _ if has_header => match reader.headers() {
+            Ok(headers) => headers.iter().map(str::to_string).collect(),
+            Err(e) => return Ok(failure(format!("Failed to read header: {}", e))),
+        },
+        _ => return Ok(failure("Columns are required for a file without header")),
+    };
+
+    // The CSV reader blocks, so records are parsed on a blocking thread and
+    // handed over through a bounded channel
+    let null_marker = null_marker.unwrap_or_default();
+    let (tx, rx) = tokio::sync::mpsc::channel::<EngineResult<Vec<Value>>>(READ_AHEAD_ROWS);
+    tauri::async_runtime::spawn_blocking(move || {
+        for record in reader.records() {
+            let row = record
+                .map(|record| record_values(&record, &null_marker))
+                .map_err(|e| EngineError::execution_error(format!("Invalid CSV: {}", e)));
+            let failed = row.is_err();
+            // A closed channel means the import already stopped
+            if tx.blocking_send(row).is_err() || failed {
+                break;
+            }
+        }
+    });
+    let rows = futures::stream::unfold(rx, |mut rx| async move {
+        rx.recv().await.map(|row| (row, rx))
+    });
+
+    let import_id = import_id.unwrap_or_else(|| Uuid::new_v4().to_string());
+    let progress_id = import_id.clone();
+    let on_progress = move |rows_sent: u64| {
+        let _ = app.emit(
+            IMPORT_PROGRESS_EVENT,
+            ImportProgressEvent {
+                import_id: progress_id.clone(),
+                rows_sent,
+            },
+        );
+    };
+
+    match driver
+        .import_copy(session, &namespace, &table, &columns, Box::pin(rows), &on_progress)
+        .await
+    {
+        Ok(rows_imported) => Ok(ImportResponse {
+            success: true,
+            import_id: Some(import_id),
+            rows_imported: Some(rows_imported),
+            error: None,
+            error_code: None,
+        }),
+        Err(e) => Ok(ImportResponse {
+            success: false,
+            import_id: Some(import_id),
+            rows_imported: None,
+            error: Some(e.to_string()),
+            error_code: Some(e.code().to_string()),
+        }),
+    }
+}
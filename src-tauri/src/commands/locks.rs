@@ -0,0 +1,135 @@
+//! Advisory Lock Tauri Commands
+//!
+//! Commands for taking and releasing server-side advisory locks, used to
+//! coordinate with other clients (migrations, scheduled jobs, ...).
+
+use serde::Serialize;
+use std::sync::Arc;
+use tauri::State;
+use tracing::instrument;
+use uuid::Uuid;
+
+use crate::engine::{error::EngineResult, types::SessionId, SessionManager};
+
+const READ_ONLY_BLOCKED: &str = "Operation blocked: read-only mode";
+
+/// Response for advisory lock commands
+#[derive(Debug, Serialize)]
+pub struct AdvisoryLockResponse {
+    pub success: bool,
+    /// Whether the lock was acquired, or released
+    pub granted: bool,
+    pub error: Option<String>,
+    pub error_code: Option<String>,
+}
+
+/// Parses a session ID string into SessionId
+fn parse_session_id(id: &str) -> Result<SessionId, String> {
+    let uuid = Uuid::parse_str(id).map_err(|e| format!("Invalid session ID: {}", e))?;
+    Ok(SessionId(uuid))
+}
+
+fn response(result: EngineResult<bool>) -> AdvisoryLockResponse {
+    match result {
+        Ok(granted) => AdvisoryLockResponse {
+            success: true,
+            granted,
+            error: None,
+            error_code: None,
+        },
+        Err(e) => AdvisoryLockResponse {
+            success: false,
+            granted: false,
+            error: Some(e.to_string()),
+            error_code: Some(e.code().to_string()),
+        },
+    }
+}
+
+/// Returns the response to send when the session may not use advisory
+/// locks: it is closed, or read-only
+async fn guard_error(
+    session_manager: &SessionManager,
+    session: SessionId,
+) -> Option<AdvisoryLockResponse> {
+    match session_manager.is_read_only(session).await {
+        Ok(false) => None,
+        Ok(true) => Some(AdvisoryLockResponse {
+            success: false,
+            granted: false,
+            error: Some(READ_ONLY_BLOCKED.to_string()),
+            error_code: None,
+        }),
+        Err(e) => Some(response(Err(e))),
+    }
+}
+
+/// Takes the advisory lock `key`
+///
+/// Without `timeout_ms` the lock is only tried, and `granted` is false when
+/// another session holds it. With `timeout_ms`, the command waits up to
+/// that long for the lock (0 waits indefinitely).
+#[tauri::command]
+#[instrument(skip(state), fields(session_id = %session_id))]
+pub async fn acquire_advisory_lock(
+    state: State<'_, crate::SharedState>,
+    session_id: String,
+    key: i64,
+    shared: Option<bool>,
+    timeout_ms: Option<u64>,
+) -> Result<AdvisoryLockResponse, String> {
+    let session_manager = {
+        let state = state.lock().await;
+        Arc::clone(&state.session_manager)
+    };
+    let session = parse_session_id(&session_id)?;
+
+    if let Some(error) = guard_error(&session_manager, session).await {
+        return Ok(error);
+    }
+
+    let shared = shared.unwrap_or(false);
+    let result = match session_manager.get_driver(session).await {
+        Ok(driver) => match timeout_ms {
+            Some(timeout_ms) => {
+                driver
+                    .acquire_advisory_lock_wait(session, key, shared, timeout_ms)
+                    .await
+            }
+            None => driver.acquire_advisory_lock(session, key, shared).await,
+        },
+        Err(e) => Err(e),
+    };
+    Ok(response(result))
+}
+
+/// Releases the advisory lock `key`; `granted` is false when the session
+/// did not hold it
+#[tauri::command]
+#[instrument(skip(state), fields(session_id = %session_id))]
+pub async fn release_advisory_lock(
+    state: State<'_, crate::SharedState>,
+    session_id: String,
+    key: i64,
+    shared: Option<bool>,
+) -> Result<AdvisoryLockResponse, String> {
+    let session_manager = {
+        let state = state.lock().await;
+        Arc::clone(&state.session_manager)
+    };
+    let session = parse_session_id(&session_id)?;
+
+    if let Some(error) = guard_error(&session_manager, session).await {
+        return Ok(error);
+    }
+
+    let result = match session_manager.get_driver(session).await {
+        Ok(driver) => {
+            driver
+                .release_advisory_lock(session, key, shared.unwrap_or(false))
+                .await
+        }
+        Err(e) => Err(e),
+    };
+    Ok(response(result))
+}
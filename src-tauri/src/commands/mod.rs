@@ -1,7 +1,13 @@
 // Tauri Commands Module
 
 pub mod connection;
+pub mod explain;
+pub mod export;
+pub mod import;
+pub mod locks;
 pub mod mutation;
 pub mod policy;
 pub mod query;
+pub mod snippets;
+pub mod ssh;
 pub mod vault;
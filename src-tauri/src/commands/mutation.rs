@@ -8,17 +8,90 @@ use uuid::Uuid;
 use std::sync::Arc;
 use tracing::instrument;
 
-use crate::engine::{types::{Namespace, QueryResult, RowData, SessionId}};
+use crate::commands::query::{protected_host_error, QueryGuard};
+use crate::engine::{
+    error::EngineError,
+    sql_safety,
+    traits::DataEngine,
+    types::{coerce_value, Namespace, QueryResult, RowData, SessionId},
+    ColumnTypes, SessionManager,
+};
 
 const READ_ONLY_BLOCKED: &str = "Operation blocked: read-only mode";
 const MUTATIONS_NOT_SUPPORTED: &str = "Mutations are not supported by this driver";
 
+/// Kind of write a mutation performed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MutationType {
+    Insert,
+    Update,
+    Delete,
+    Upsert,
+    BulkInsert,
+}
+
 /// Response wrapper for mutation results
+///
+/// Echoes the operation and its target so the caller can tell which table
+/// changed without tracking the request itself.
 #[derive(Debug, Serialize)]
 pub struct MutationResponse {
     pub success: bool,
     pub result: Option<QueryResult>,
     pub error: Option<String>,
+    pub error_code: Option<String>,
+    pub operation: MutationType,
+    pub database: String,
+    pub schema: Option<String>,
+    pub table: String,
+}
+
+/// Operation and target of a mutation command, used to build its response
+struct MutationTarget<'a> {
+    operation: MutationType,
+    namespace: &'a Namespace,
+    table: &'a str,
+}
+
+impl<'a> MutationTarget<'a> {
+    fn new(operation: MutationType, namespace: &'a Namespace, table: &'a str) -> Self {
+        Self {
+            operation,
+            namespace,
+            table,
+        }
+    }
+
+    fn response(
+        &self,
+        result: Option<QueryResult>,
+        error: Option<String>,
+        error_code: Option<String>,
+    ) -> MutationResponse {
+        MutationResponse {
+            success: error.is_none(),
+            result,
+            error,
+            error_code,
+            operation: self.operation,
+            database: self.namespace.database.clone(),
+            schema: self.namespace.schema.clone(),
+            table: self.table.to_string(),
+        }
+    }
+
+    fn success(&self, result: QueryResult) -> MutationResponse {
+        self.response(Some(result), None, None)
+    }
+
+    fn failure(&self, error: impl Into<String>) -> MutationResponse {
+        self.response(None, Some(error.into()), None)
+    }
+
+    fn engine_failure(&self, error: EngineError) -> MutationResponse {
+        self.response(None, Some(error.to_string()), Some(error.code().to_string()))
+    }
 }
 
 /// Parses a session ID string into SessionId
@@ -27,6 +100,54 @@ fn parse_session_id(id: &str) -> Result<SessionId, String> {
     Ok(SessionId(uuid))
 }
 
+/// Converts the text values of `rows` to the logical type of their column
+///
+/// The grid sends edited cells as strings, which typed columns reject when
+/// bound as text (uuid primary keys in a `WHERE` clause, for instance).
+/// Column types are cached per session, so editing a grid does not describe
+/// the table on every save. Rows are left as sent when the table cannot be
+/// described.
+async fn coerce_row_data(
+    session_manager: &SessionManager,
+    driver: &dyn DataEngine,
+    session: SessionId,
+    namespace: &Namespace,
+    table: &str,
+    rows: &mut [&mut RowData],
+) {
+    let types = match session_manager.cached_column_types(session, namespace, table).await {
+        Some(types) => types,
+        None => {
+            let schema = match driver.describe_table(session, namespace, table).await {
+                Ok(schema) => schema,
+                Err(e) => {
+                    tracing::debug!(error = %e, "Skipping value coercion: table not described");
+                    return;
+                }
+            };
+            let types: ColumnTypes = Arc::new(
+                schema
+                    .columns
+                    .iter()
+                    .map(|column| (column.name.clone(), column.logical_type()))
+                    .collect(),
+            );
+            session_manager
+                .cache_column_types(session, namespace, table, Arc::clone(&types))
+                .await;
+            types
+        }
+    };
+
+    for row in rows.iter_mut() {
+        for (column, value) in row.columns.iter_mut() {
+            if let Some(target) = types.get(column) {
+                *value = coerce_value(value, *target);
+            }
+        }
+    }
+}
+
 /// Inserts a row into a table
 #[tauri::command]
 #[instrument(
@@ -39,62 +160,144 @@ pub async fn insert_row(
     database: String,
     schema: Option<String>,
     table: String,
-    data: RowData,
+    mut data: RowData,
+    acknowledged_dangerous: Option<bool>,
 ) -> Result<MutationResponse, String> {
-    let session_manager = {
+    let (session_manager, policy) = {
         let state = state.lock().await;
-        Arc::clone(&state.session_manager)
+        (Arc::clone(&state.session_manager), state.policy.clone())
     };
     let session = parse_session_id(&session_id)?;
+    let namespace = Namespace {
+        database,
+        schema,
+    };
+    let target = MutationTarget::new(MutationType::Insert, &namespace, &table);
 
     if session_manager
         .is_read_only(session)
         .await
         .map_err(|e| e.to_string())?
     {
-        return Ok(MutationResponse {
-            success: false,
-            result: None,
-            error: Some(READ_ONLY_BLOCKED.to_string()),
-        });
+        return Ok(target.failure(READ_ONLY_BLOCKED));
+    }
+
+    let is_protected_host = session_manager
+        .is_protected_host(session, &policy)
+        .await
+        .map_err(|e| e.to_string())?;
+    if let Some(error) =
+        protected_host_error(&policy, is_protected_host, acknowledged_dangerous.unwrap_or(false))
+    {
+        return Ok(target.failure(error));
     }
 
     let driver = session_manager.get_driver(session).await
         .map_err(|e| e.to_string())?;
 
     if !driver.capabilities().mutations {
-        return Ok(MutationResponse {
-            success: false,
-            result: None,
-            error: Some(MUTATIONS_NOT_SUPPORTED.to_string()),
-        });
+        return Ok(target.failure(MUTATIONS_NOT_SUPPORTED));
+    }
+
+    coerce_row_data(
+        &session_manager,
+        driver.as_ref(),
+        session,
+        &namespace,
+        &table,
+        &mut [&mut data],
+    )
+    .await;
+
+    let start_time = std::time::Instant::now();
+    match driver.insert_row(session, &namespace, &table, &data).await {
+        Ok(mut result) => {
+            result.execution_time_ms = start_time.elapsed().as_micros() as f64 / 1000.0;
+            Ok(target.success(result))
+        },
+        Err(e) => Ok(target.engine_failure(e)),
     }
+}
 
+/// Inserts a row and returns it as stored, with server-generated values
+///
+/// Unlike `insert_row`, the result's `rows` holds the inserted row.
+#[tauri::command]
+#[instrument(
+    skip(state, data),
+    fields(session_id = %session_id, database = %database, schema = ?schema, table = %table)
+)]
+pub async fn insert_row_returning(
+    state: State<'_, crate::SharedState>,
+    session_id: String,
+    database: String,
+    schema: Option<String>,
+    table: String,
+    mut data: RowData,
+    acknowledged_dangerous: Option<bool>,
+) -> Result<MutationResponse, String> {
+    let (session_manager, policy) = {
+        let state = state.lock().await;
+        (Arc::clone(&state.session_manager), state.policy.clone())
+    };
+    let session = parse_session_id(&session_id)?;
     let namespace = Namespace {
         database,
         schema,
     };
+    let target = MutationTarget::new(MutationType::Insert, &namespace, &table);
+
+    if session_manager
+        .is_read_only(session)
+        .await
+        .map_err(|e| e.to_string())?
+    {
+        return Ok(target.failure(READ_ONLY_BLOCKED));
+    }
+
+    let is_protected_host = session_manager
+        .is_protected_host(session, &policy)
+        .await
+        .map_err(|e| e.to_string())?;
+    if let Some(error) =
+        protected_host_error(&policy, is_protected_host, acknowledged_dangerous.unwrap_or(false))
+    {
+        return Ok(target.failure(error));
+    }
+
+    let driver = session_manager.get_driver(session).await
+        .map_err(|e| e.to_string())?;
+
+    if !driver.capabilities().mutations {
+        return Ok(target.failure(MUTATIONS_NOT_SUPPORTED));
+    }
+
+    coerce_row_data(
+        &session_manager,
+        driver.as_ref(),
+        session,
+        &namespace,
+        &table,
+        &mut [&mut data],
+    )
+    .await;
 
     let start_time = std::time::Instant::now();
-    match driver.insert_row(session, &namespace, &table, &data).await {
+    match driver
+        .insert_row_returning(session, &namespace, &table, &data)
+        .await
+    {
         Ok(mut result) => {
             result.execution_time_ms = start_time.elapsed().as_micros() as f64 / 1000.0;
-            Ok(MutationResponse {
-                success: true,
-                result: Some(result),
-                error: None,
-            })
+            Ok(target.success(result))
         },
-        Err(e) => Ok(MutationResponse {
-            success: false,
-            result: None,
-            error: Some(e.to_string()),
-        }),
+        Err(e) => Ok(target.engine_failure(e)),
     }
 }
 
 /// Updates a row in a table
 #[tauri::command]
+#[allow(clippy::too_many_arguments)]
 #[instrument(
     skip(state, primary_key, data),
     fields(session_id = %session_id, database = %database, schema = ?schema, table = %table)
@@ -105,58 +308,63 @@ pub async fn update_row(
     database: String,
     schema: Option<String>,
     table: String,
-    primary_key: RowData,
-    data: RowData,
+    mut primary_key: RowData,
+    mut data: RowData,
+    acknowledged_dangerous: Option<bool>,
 ) -> Result<MutationResponse, String> {
-    let session_manager = {
+    let (session_manager, policy) = {
         let state = state.lock().await;
-        Arc::clone(&state.session_manager)
+        (Arc::clone(&state.session_manager), state.policy.clone())
     };
     let session = parse_session_id(&session_id)?;
+    let namespace = Namespace {
+        database,
+        schema,
+    };
+    let target = MutationTarget::new(MutationType::Update, &namespace, &table);
 
     if session_manager
         .is_read_only(session)
         .await
         .map_err(|e| e.to_string())?
     {
-        return Ok(MutationResponse {
-            success: false,
-            result: None,
-            error: Some(READ_ONLY_BLOCKED.to_string()),
-        });
+        return Ok(target.failure(READ_ONLY_BLOCKED));
+    }
+
+    let is_protected_host = session_manager
+        .is_protected_host(session, &policy)
+        .await
+        .map_err(|e| e.to_string())?;
+    if let Some(error) =
+        protected_host_error(&policy, is_protected_host, acknowledged_dangerous.unwrap_or(false))
+    {
+        return Ok(target.failure(error));
     }
 
     let driver = session_manager.get_driver(session).await
         .map_err(|e| e.to_string())?;
 
     if !driver.capabilities().mutations {
-        return Ok(MutationResponse {
-            success: false,
-            result: None,
-            error: Some(MUTATIONS_NOT_SUPPORTED.to_string()),
-        });
+        return Ok(target.failure(MUTATIONS_NOT_SUPPORTED));
     }
 
-    let namespace = Namespace {
-        database,
-        schema,
-    };
+    coerce_row_data(
+        &session_manager,
+        driver.as_ref(),
+        session,
+        &namespace,
+        &table,
+        &mut [&mut primary_key, &mut data],
+    )
+    .await;
 
     let start_time = std::time::Instant::now();
     match driver.update_row(session, &namespace, &table, &primary_key, &data).await {
         Ok(mut result) => {
             result.execution_time_ms = start_time.elapsed().as_micros() as f64 / 1000.0;
-            Ok(MutationResponse {
-                success: true,
-                result: Some(result),
-                error: None,
-            })
+            Ok(target.success(result))
         },
-        Err(e) => Ok(MutationResponse {
-            success: false,
-            result: None,
-            error: Some(e.to_string()),
-        }),
+        Err(e) => Ok(target.engine_failure(e)),
     }
 }
 
@@ -172,57 +380,222 @@ pub async fn delete_row(
     database: String,
     schema: Option<String>,
     table: String,
-    primary_key: RowData,
+    mut primary_key: RowData,
+    acknowledged_dangerous: Option<bool>,
 ) -> Result<MutationResponse, String> {
-    let session_manager = {
+    let (session_manager, policy) = {
         let state = state.lock().await;
-        Arc::clone(&state.session_manager)
+        (Arc::clone(&state.session_manager), state.policy.clone())
     };
     let session = parse_session_id(&session_id)?;
+    let namespace = Namespace {
+        database,
+        schema,
+    };
+    let target = MutationTarget::new(MutationType::Delete, &namespace, &table);
 
     if session_manager
         .is_read_only(session)
         .await
         .map_err(|e| e.to_string())?
     {
-        return Ok(MutationResponse {
-            success: false,
-            result: None,
-            error: Some(READ_ONLY_BLOCKED.to_string()),
-        });
+        return Ok(target.failure(READ_ONLY_BLOCKED));
+    }
+
+    let is_protected_host = session_manager
+        .is_protected_host(session, &policy)
+        .await
+        .map_err(|e| e.to_string())?;
+    if let Some(error) =
+        protected_host_error(&policy, is_protected_host, acknowledged_dangerous.unwrap_or(false))
+    {
+        return Ok(target.failure(error));
     }
 
     let driver = session_manager.get_driver(session).await
         .map_err(|e| e.to_string())?;
 
     if !driver.capabilities().mutations {
-        return Ok(MutationResponse {
-            success: false,
-            result: None,
-            error: Some(MUTATIONS_NOT_SUPPORTED.to_string()),
-        });
+        return Ok(target.failure(MUTATIONS_NOT_SUPPORTED));
     }
 
+    coerce_row_data(
+        &session_manager,
+        driver.as_ref(),
+        session,
+        &namespace,
+        &table,
+        &mut [&mut primary_key],
+    )
+    .await;
+
+    let start_time = std::time::Instant::now();
+    match driver.delete_row(session, &namespace, &table, &primary_key).await {
+        Ok(mut result) => {
+            result.execution_time_ms = start_time.elapsed().as_micros() as f64 / 1000.0;
+            Ok(target.success(result))
+        },
+        Err(e) => Ok(target.engine_failure(e)),
+    }
+}
+
+/// Deletes every row matching a filter
+///
+/// `filter` is a SQL condition (a JSON query document for MongoDB). Without
+/// a filter the whole table is emptied, which production, staging and
+/// protected-host connections treat like `TRUNCATE`, per the safety policy.
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+#[instrument(
+    skip(state, filter),
+    fields(session_id = %session_id, database = %database, schema = ?schema, table = %table)
+)]
+pub async fn delete_rows_where(
+    state: State<'_, crate::SharedState>,
+    session_id: String,
+    database: String,
+    schema: Option<String>,
+    table: String,
+    filter: Option<String>,
+    acknowledged_dangerous: Option<bool>,
+) -> Result<MutationResponse, String> {
+    let (session_manager, policy) = {
+        let state = state.lock().await;
+        (Arc::clone(&state.session_manager), state.policy.clone())
+    };
+    let session = parse_session_id(&session_id)?;
     let namespace = Namespace {
         database,
         schema,
     };
+    let target = MutationTarget::new(MutationType::Delete, &namespace, &table);
+    let acknowledged = acknowledged_dangerous.unwrap_or(false);
+
+    if session_manager
+        .is_read_only(session)
+        .await
+        .map_err(|e| e.to_string())?
+    {
+        return Ok(target.failure(READ_ONLY_BLOCKED));
+    }
+
+    let is_protected_host = session_manager
+        .is_protected_host(session, &policy)
+        .await
+        .map_err(|e| e.to_string())?;
+    if let Some(error) = protected_host_error(&policy, is_protected_host, acknowledged) {
+        return Ok(target.failure(error));
+    }
+
+    let driver = session_manager.get_driver(session).await
+        .map_err(|e| e.to_string())?;
+
+    // A delete without a filter empties the table
+    let analysis = sql_safety::analyze_delete_where(filter.as_deref());
+    let guard =
+        match QueryGuard::for_session(&session_manager, &*driver, session, &policy, acknowledged)
+            .await
+        {
+            Ok(guard) => guard,
+            Err(e) => return Ok(target.engine_failure(e)),
+        };
+    if let Some(error) = guard.check_operation(true, analysis.is_dangerous) {
+        return Ok(target.failure(error));
+    }
+
+    if !driver.capabilities().mutations {
+        return Ok(target.failure(MUTATIONS_NOT_SUPPORTED));
+    }
 
     let start_time = std::time::Instant::now();
-    match driver.delete_row(session, &namespace, &table, &primary_key).await {
+    match driver
+        .delete_rows_where(session, &namespace, &table, filter.as_deref().unwrap_or(""))
+        .await
+    {
         Ok(mut result) => {
             result.execution_time_ms = start_time.elapsed().as_micros() as f64 / 1000.0;
-            Ok(MutationResponse {
-                success: true,
-                result: Some(result),
-                error: None,
-            })
+            Ok(target.success(result))
         },
-        Err(e) => Ok(MutationResponse {
-            success: false,
-            result: None,
-            error: Some(e.to_string()),
-        }),
+        Err(e) => Ok(target.engine_failure(e)),
+    }
+}
+
+/// Empties a table with `TRUNCATE`
+///
+/// MongoDB has no `TRUNCATE`, so every document is deleted instead. Always
+/// treated as dangerous on production, staging and protected-host
+/// connections, per the safety policy.
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+#[instrument(
+    skip(state),
+    fields(session_id = %session_id, database = %database, schema = ?schema, table = %table)
+)]
+pub async fn truncate_table(
+    state: State<'_, crate::SharedState>,
+    session_id: String,
+    database: String,
+    schema: Option<String>,
+    table: String,
+    cascade: Option<bool>,
+    acknowledged_dangerous: Option<bool>,
+) -> Result<MutationResponse, String> {
+    let (session_manager, policy) = {
+        let state = state.lock().await;
+        (Arc::clone(&state.session_manager), state.policy.clone())
+    };
+    let session = parse_session_id(&session_id)?;
+    let namespace = Namespace {
+        database,
+        schema,
+    };
+    let target = MutationTarget::new(MutationType::Delete, &namespace, &table);
+    let acknowledged = acknowledged_dangerous.unwrap_or(false);
+
+    if session_manager
+        .is_read_only(session)
+        .await
+        .map_err(|e| e.to_string())?
+    {
+        return Ok(target.failure(READ_ONLY_BLOCKED));
+    }
+
+    let is_protected_host = session_manager
+        .is_protected_host(session, &policy)
+        .await
+        .map_err(|e| e.to_string())?;
+    if let Some(error) = protected_host_error(&policy, is_protected_host, acknowledged) {
+        return Ok(target.failure(error));
+    }
+
+    let driver = session_manager.get_driver(session).await
+        .map_err(|e| e.to_string())?;
+
+    let guard =
+        match QueryGuard::for_session(&session_manager, &*driver, session, &policy, acknowledged)
+            .await
+        {
+            Ok(guard) => guard,
+            Err(e) => return Ok(target.engine_failure(e)),
+        };
+    if let Some(error) = guard.check_operation(true, true) {
+        return Ok(target.failure(error));
+    }
+
+    if !driver.capabilities().mutations {
+        return Ok(target.failure(MUTATIONS_NOT_SUPPORTED));
+    }
+
+    let start_time = std::time::Instant::now();
+    match driver
+        .truncate_table(session, &namespace, &table, cascade.unwrap_or(false))
+        .await
+    {
+        Ok(mut result) => {
+            result.execution_time_ms = start_time.elapsed().as_micros() as f64 / 1000.0;
+            Ok(target.success(result))
+        },
+        Err(e) => Ok(target.engine_failure(e)),
     }
 }
 
@@ -243,3 +616,21 @@ pub async fn supports_mutations(
 
     Ok(driver.capabilities().mutations)
 }
+
+/// Checks if update/delete results include the affected primary keys
+#[tauri::command]
+pub async fn supports_returning_keys(
+    state: State<'_, crate::SharedState>,
+    session_id: String,
+) -> Result<bool, String> {
+    let session_manager = {
+        let state = state.lock().await;
+        Arc::clone(&state.session_manager)
+    };
+    let session = parse_session_id(&session_id)?;
+
+    let driver = session_manager.get_driver(session).await
+        .map_err(|e| e.to_string())?;
+
+    Ok(driver.capabilities().returning_keys)
+}
@@ -1,8 +1,9 @@
-//! Safety policy commands.
+//! Safety policy and logging commands.
 
 use serde::Serialize;
 use tauri::State;
 
+use crate::observability;
 use crate::policy::SafetyPolicy;
 use crate::SharedState;
 
@@ -11,6 +12,7 @@ pub struct SafetyPolicyResponse {
     pub success: bool,
     pub policy: Option<SafetyPolicy>,
     pub error: Option<String>,
+    pub error_code: Option<String>,
 }
 
 /// Returns the effective safety policy (env overrides applied).
@@ -23,6 +25,7 @@ pub async fn get_safety_policy(
         success: true,
         policy: Some(state.policy.clone()),
         error: None,
+        error_code: None,
     })
 }
 
@@ -37,6 +40,7 @@ pub async fn set_safety_policy(
             success: false,
             policy: None,
             error: Some(err),
+            error_code: None,
         });
     }
 
@@ -48,5 +52,12 @@ pub async fn set_safety_policy(
         success: true,
         policy: Some(effective),
         error: None,
+        error_code: None,
     })
 }
+
+/// Returns the directory holding the log files, so it can be opened.
+#[tauri::command]
+pub async fn get_log_directory() -> Result<String, String> {
+    Ok(observability::log_directory().display().to_string())
+}
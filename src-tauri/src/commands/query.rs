@@ -2,24 +2,48 @@
 //!
 //! Commands for executing queries and exploring database schema.
 
-use serde::Serialize;
-use tauri::State;
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use tauri::{AppHandle, Emitter, State};
 use uuid::Uuid;
 use std::sync::Arc;
 use tokio::time::{timeout, Duration};
 use tracing::{field, instrument};
 
 use crate::engine::{
+    error::{EngineError, EngineErrorCode},
+    named_params,
+    query_history,
     sql_safety,
+    DataEngine,
+    QueryManager,
+    SessionManager,
     TableSchema,
-    types::{Collection, Namespace, QueryId, QueryResult, SessionId},
+    types::{
+        CancelSupport, Collection, Namespace, NewColumnDef, NewTableSchema, QueryId, QueryResult,
+        Routine, RoutineType, RowData, RowLockMode, ServerQuery, ServerVariable, SessionId,
+        TableSizeInfo, TransactionStatus, Value, VariableScope, WalStatus,
+    },
 };
+use crate::policy::SafetyPolicy;
 
 const READ_ONLY_BLOCKED: &str = "Operation blocked: read-only mode";
 const DANGEROUS_BLOCKED: &str = "Dangerous query blocked: confirmation required";
 const DANGEROUS_BLOCKED_POLICY: &str = "Dangerous query blocked by policy";
+const PROTECTED_HOST_BLOCKED: &str = "Write to protected host blocked: confirmation required";
+const PROTECTED_HOST_BLOCKED_POLICY: &str = "Write to protected host blocked by policy";
 const SQL_PARSE_BLOCKED: &str = "Operation blocked: SQL parser could not classify the query";
 const TRANSACTIONS_NOT_SUPPORTED: &str = "Transactions are not supported by this driver";
+const CANCEL_NOT_SUPPORTED: &str = "Cancellation is not supported by this driver";
+
+/// Emitted by `execute_query` once the query is registered and about to run
+pub const QUERY_STARTED_EVENT: &str = "query://started";
+/// Emitted by `execute_query` when the query completed, failed or timed out
+pub const QUERY_FINISHED_EVENT: &str = "query://finished";
+/// Emitted when a query failed because the session's SSH tunnel went down
+/// and the tunnel is being re-opened (`SshTunnelConfig::auto_restart`)
+pub const TUNNEL_RECONNECTING_EVENT: &str = "tunnel://reconnecting";
 
 fn is_mongo_mutation(query: &str) -> bool {
     let normalized = query.to_ascii_lowercase();
@@ -59,13 +83,225 @@ fn is_mongo_mutation(query: &str) -> bool {
     json_patterns.iter().any(|pattern| compact.contains(pattern))
 }
 
+/// Session context the read-only and production guards are checked against
+pub(crate) struct QueryGuard<'a> {
+    pub(crate) policy: &'a SafetyPolicy,
+    /// Session dialect, see `DataEngine::dialect_id`
+    pub(crate) driver_id: &'a str,
+    pub(crate) read_only: bool,
+    pub(crate) is_production: bool,
+    pub(crate) is_staging: bool,
+    pub(crate) is_protected_host: bool,
+    pub(crate) acknowledged: bool,
+}
+
+impl<'a> QueryGuard<'a> {
+    /// Builds the guard of an open session
+    pub(crate) async fn for_session(
+        session_manager: &SessionManager,
+        driver: &dyn DataEngine,
+        session: SessionId,
+        policy: &'a SafetyPolicy,
+        acknowledged: bool,
+    ) -> Result<QueryGuard<'a>, EngineError> {
+        let read_only = session_manager.is_read_only(session).await?;
+        let is_production = session_manager.is_production(session).await.unwrap_or(false);
+        let is_staging = session_manager.is_staging(session).await.unwrap_or(false);
+        let is_protected_host = session_manager
+            .is_protected_host(session, policy)
+            .await
+            .unwrap_or(false);
+
+        Ok(QueryGuard {
+            policy,
+            driver_id: driver.dialect_id(session).await,
+            read_only,
+            is_production,
+            is_staging,
+            is_protected_host,
+            acknowledged,
+        })
+    }
+
+    fn is_mongo_driver(&self) -> bool {
+        self.driver_id.eq_ignore_ascii_case("mongodb")
+    }
+
+    fn is_sql_driver(&self) -> bool {
+        // The Redis driver only accepts a whitelist of read commands.
+        !self.is_mongo_driver() && !self.driver_id.eq_ignore_ascii_case("redis")
+    }
+
+    /// Caps a SQL query without a `LIMIT` at the policy's
+    /// `max_rows_without_limit`, returning the cap and the query to run.
+    /// One row past the cap is fetched so `apply_row_cap` can tell whether
+    /// anything was left out.
+    fn row_cap(&self, query: &str) -> Option<(u64, String)> {
+        let max_rows = self.policy.max_rows_without_limit.filter(|max_rows| *max_rows > 0)?;
+        if !self.is_sql_driver() {
+            return None;
+        }
+        sql_safety::cap_rows(self.driver_id, query, max_rows.saturating_add(1))
+            .map(|capped| (max_rows, capped))
+    }
+
+    /// Returns the error blocking the query, if any
+    pub(crate) fn check(&self, query: &str) -> Option<String> {
+        let policy = self.policy;
+        let is_sql_driver = self.is_sql_driver();
+        let sql_analysis = if is_sql_driver {
+            match sql_safety::analyze_sql(self.driver_id, query) {
+                Ok(analysis) => Some(analysis),
+                Err(err) => {
+                    if self.read_only {
+                        return Some(format!("{SQL_PARSE_BLOCKED}: {err}"));
+                    }
+
+                    if self.is_production || self.is_protected_host {
+                        if policy.prod_block_dangerous_sql {
+                            return Some(format!(
+                                "{DANGEROUS_BLOCKED_POLICY}: SQL parse error: {err}"
+                            ));
+                        }
+
+                        if policy.prod_require_confirmation && !self.acknowledged {
+                            return Some(format!("{DANGEROUS_BLOCKED}: SQL parse error: {err}"));
+                        }
+                    }
+
+                    if self.is_staging {
+                        if policy.staging_block_dangerous_sql {
+                            return Some(format!(
+                                "{DANGEROUS_BLOCKED_POLICY}: SQL parse error: {err}"
+                            ));
+                        }
+
+                        if policy.staging_require_confirmation && !self.acknowledged {
+                            return Some(format!("{DANGEROUS_BLOCKED}: SQL parse error: {err}"));
+                        }
+                    }
+
+                    None
+                }
+            }
+        } else {
+            None
+        };
+
+        let is_mutation = if is_sql_driver {
+            sql_analysis
+                .as_ref()
+                .map(|analysis| analysis.is_mutation)
+                .unwrap_or(false)
+        } else {
+            self.is_mongo_driver() && is_mongo_mutation(query)
+        };
+
+        let is_dangerous = sql_analysis
+            .as_ref()
+            .map(|analysis| analysis.is_dangerous)
+            .unwrap_or(false);
+
+        self.check_operation(is_mutation, is_dangerous)
+    }
+
+    /// Returns the error blocking an operation already classified as a
+    /// mutation and/or dangerous, if any
+    pub(crate) fn check_operation(&self, is_mutation: bool, is_dangerous: bool) -> Option<String> {
+        let policy = self.policy;
+        if self.read_only && is_mutation {
+            return Some(READ_ONLY_BLOCKED.to_string());
+        }
+
+        if self.is_production || self.is_protected_host {
+            // Protected hosts guard every write, not only dangerous statements.
+            if is_dangerous || (self.is_protected_host && is_mutation) {
+                if policy.prod_block_dangerous_sql {
+                    return Some(DANGEROUS_BLOCKED_POLICY.to_string());
+                }
+
+                if policy.prod_require_confirmation && !self.acknowledged {
+                    return Some(DANGEROUS_BLOCKED.to_string());
+                }
+            }
+        }
+
+        if self.is_staging && is_dangerous {
+            if policy.staging_block_dangerous_sql {
+                return Some(DANGEROUS_BLOCKED_POLICY.to_string());
+            }
+
+            if policy.staging_require_confirmation && !self.acknowledged {
+                return Some(DANGEROUS_BLOCKED.to_string());
+            }
+        }
+
+        None
+    }
+}
+
+/// Returns the error to report when a write targets a protected host
+pub(crate) fn protected_host_error(
+    policy: &SafetyPolicy,
+    is_protected_host: bool,
+    acknowledged: bool,
+) -> Option<&'static str> {
+    if !is_protected_host {
+        return None;
+    }
+    if policy.prod_block_dangerous_sql {
+        return Some(PROTECTED_HOST_BLOCKED_POLICY);
+    }
+    if policy.prod_require_confirmation && !acknowledged {
+        return Some(PROTECTED_HOST_BLOCKED);
+    }
+    None
+}
+
 /// Response wrapper for query results
 #[derive(Debug, Serialize)]
 pub struct QueryResponse {
     pub success: bool,
     pub result: Option<QueryResult>,
     pub error: Option<String>,
+    /// Kind of engine error, e.g. `deadlock`, so the UI can offer a retry
+    pub error_code: Option<String>,
     pub query_id: Option<String>,
+    /// Set when the query lost a conflict with another transaction:
+    /// `deadlock`, `serialization_failure` or `lock_timeout`
+    pub conflict_type: Option<String>,
+}
+
+/// Payload of the `query://started` event
+#[derive(Debug, Clone, Serialize)]
+pub struct QueryStartedEvent {
+    pub query_id: String,
+    pub session_id: String,
+}
+
+/// Payload of the `tunnel://reconnecting` event
+#[derive(Debug, Clone, Serialize)]
+pub struct TunnelReconnectingEvent {
+    pub session_id: String,
+}
+
+/// Payload of the `query://finished` event
+#[derive(Debug, Clone, Serialize)]
+pub struct QueryFinishedEvent {
+    pub query_id: String,
+    pub session_id: String,
+    pub success: bool,
+    pub execution_time_ms: f64,
+    pub error: Option<String>,
+    pub error_code: Option<String>,
+}
+
+/// Timeout suggested from past executions of similar queries
+#[derive(Debug, Serialize)]
+pub struct TimeoutSuggestion {
+    /// `None` until the query ran often enough
+    pub suggested_ms: Option<u64>,
+    pub based_on_samples: u32,
 }
 
 /// Response wrapper for namespace listing
@@ -74,6 +310,7 @@ pub struct NamespacesResponse {
     pub success: bool,
     pub namespaces: Option<Vec<Namespace>>,
     pub error: Option<String>,
+    pub error_code: Option<String>,
 }
 
 /// Response wrapper for collection listing
@@ -82,6 +319,161 @@ pub struct CollectionsResponse {
     pub success: bool,
     pub collections: Option<Vec<Collection>>,
     pub error: Option<String>,
+    pub error_code: Option<String>,
+}
+
+/// Response wrapper for routine listing
+#[derive(Debug, Serialize)]
+pub struct RoutinesResponse {
+    pub success: bool,
+    pub routines: Option<Vec<Routine>>,
+    pub error: Option<String>,
+    pub error_code: Option<String>,
+}
+
+/// Collections of a single namespace, as returned by `list_all_collections`
+#[derive(Debug, Serialize)]
+pub struct NamespaceCollections {
+    pub namespace: Namespace,
+    pub collections: Vec<Collection>,
+}
+
+/// Response wrapper for listing the collections of every namespace
+///
+/// A list rather than a map, since JSON object keys cannot be namespaces.
+#[derive(Debug, Serialize)]
+pub struct AllCollectionsResponse {
+    pub success: bool,
+    pub namespaces: Option<Vec<NamespaceCollections>>,
+    pub error: Option<String>,
+    pub error_code: Option<String>,
+}
+
+/// Optional settings for `execute_query` and `execute_multi_query`
+///
+/// Sent by the frontend as a single `options` object. Every field is
+/// optional, and the snake_case names are accepted as aliases.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct ExecuteOptions {
+    /// Confirms a dangerous query flagged by the guards
+    #[serde(alias = "acknowledged_dangerous")]
+    pub acknowledged_dangerous: bool,
+    /// Client-chosen query ID, so the query can be cancelled before it returns
+    #[serde(alias = "query_id")]
+    pub query_id: Option<String>,
+    /// Cancels the query if it runs longer than this
+    #[serde(alias = "timeout_ms")]
+    pub timeout_ms: Option<u64>,
+    /// Locks the returned rows (`update`, `share`, `no_key_update`, `key_share`);
+    /// requires an active transaction. Single statements only.
+    #[serde(alias = "lock_rows")]
+    pub lock_rows: Option<String>,
+    /// Comment sent with the query, to trace where it came from
+    /// (`execute_query` only)
+    pub comment: Option<String>,
+    /// Tab that issued the query, used in the default comment
+    #[serde(alias = "tab_id")]
+    pub tab_id: Option<String>,
+    /// Attaches the planner's cost and row estimates to SELECT results
+    /// (PostgreSQL and MySQL, `execute_query` only)
+    #[serde(alias = "include_plan_cost")]
+    pub include_plan_cost: bool,
+    /// Cuts text and binary values longer than this many bytes; the full
+    /// value can be loaded with `fetch_full_value`
+    #[serde(alias = "max_value_bytes")]
+    pub max_value_bytes: Option<usize>,
+}
+
+/// Default comment added when `SafetyPolicy::inject_query_comments` is set
+fn default_query_comment(query_id: QueryId, tab_id: Option<&str>) -> String {
+    match tab_id {
+        Some(tab_id) => format!("qoredb:query_id={},tab={}", query_id.0, tab_id),
+        None => format!("qoredb:query_id={}", query_id.0),
+    }
+}
+
+/// Drops the extra row fetched past a `QueryGuard::row_cap` cap and flags
+/// the result as truncated
+fn apply_row_cap(result: &mut QueryResult, max_rows: u64) {
+    if result.rows.len() as u64 > max_rows {
+        result.rows.truncate(max_rows as usize);
+        result.truncated = true;
+    }
+}
+
+/// Records the result's estimated size and returns an error when it exceeds
+/// the policy's `max_result_memory_mb`
+///
+/// The query has already run by then; this only keeps huge results away
+/// from the UI.
+fn check_result_memory(result: &mut QueryResult, policy: &SafetyPolicy) -> Option<String> {
+    const MB: u64 = 1024 * 1024;
+
+    let estimated = result.estimate_memory_bytes();
+    result.estimated_memory_bytes = Some(estimated);
+
+    let limit_mb = policy.max_result_memory_mb.filter(|limit| *limit > 0)?;
+    if estimated <= limit_mb.saturating_mul(MB) {
+        return None;
+    }
+    Some(format!(
+        "Result too large: estimated {} MB (limit {} MB). Add a LIMIT clause.",
+        estimated.div_ceil(MB),
+        limit_mb
+    ))
+}
+
+/// Attaches a comment to a query in the form the driver passes to the server
+///
+/// SQL gets a leading block comment; MongoDB JSON queries get a `comment`
+/// field, which the driver adds to the cursor's comment. Other queries are
+/// returned unchanged. Runs after the guards, which see the query as typed.
+fn annotate_query(driver_id: &str, query: &str, comment: &str) -> String {
+    if driver_id.eq_ignore_ascii_case("redis") {
+        return query.to_string();
+    }
+    if driver_id.eq_ignore_ascii_case("mongodb") {
+        return match serde_json::from_str::<serde_json::Value>(query.trim()) {
+            Ok(serde_json::Value::Object(mut object)) => {
+                object.insert("comment".to_string(), comment.into());
+                serde_json::Value::Object(object).to_string()
+            }
+            _ => query.to_string(),
+        };
+    }
+    sql_safety::prepend_comment(query, comment)
+}
+
+/// Parses a row lock mode sent by the frontend
+fn parse_lock_mode(raw: &str) -> Result<RowLockMode, String> {
+    match raw.trim().to_ascii_lowercase().replace([' ', '-'], "_").as_str() {
+        "update" | "for_update" => Ok(RowLockMode::Update),
+        "share" | "for_share" => Ok(RowLockMode::Share),
+        "no_key_update" | "for_no_key_update" => Ok(RowLockMode::NoKeyUpdate),
+        "key_share" | "for_key_share" => Ok(RowLockMode::KeyShare),
+        _ => Err(format!("Invalid row lock mode: {}", raw)),
+    }
+}
+
+/// Registers the query under the client-provided ID, or a fresh one
+async fn register_query(
+    query_manager: &QueryManager,
+    session: SessionId,
+    query_id: Option<&str>,
+) -> Result<QueryId, String> {
+    match query_id {
+        Some(raw) => {
+            let parsed = Uuid::parse_str(raw).map_err(|e| format!("Invalid query ID: {}", e))?;
+            let qid = QueryId(parsed);
+            query_manager
+                .register_with_id(session, qid)
+                .await
+                .map_err(|e| format!("Failed to register query ID: {}", e))?;
+            Ok(qid)
+        }
+        None => Ok(query_manager.register(session).await),
+    }
 }
 
 /// Parses a session ID string into SessionId
@@ -93,27 +485,31 @@ fn parse_session_id(id: &str) -> Result<SessionId, String> {
 /// Executes a query on the given session
 #[tauri::command]
 #[instrument(
-    skip(state, query),
+    skip(app, state, query, options),
     fields(
         session_id = %session_id,
-        query_id = ?query_id,
+        query_id = ?options.as_ref().and_then(|o| o.query_id.as_deref()),
         query_len = query.len(),
-        driver = field::Empty
+        query_masked = field::Empty,
+        driver = field::Empty,
+        execution_time_ms = field::Empty,
+        affected_rows = field::Empty
     )
 )]
 pub async fn execute_query(
+    app: AppHandle,
     state: State<'_, crate::SharedState>,
     session_id: String,
     query: String,
-    acknowledged_dangerous: Option<bool>,
-    query_id: Option<String>,
-    timeout_ms: Option<u64>,
+    options: Option<ExecuteOptions>,
 ) -> Result<QueryResponse, String> {
-    let (session_manager, query_manager, policy) = {
+    let options = options.unwrap_or_default();
+    let (session_manager, query_manager, query_history, policy) = {
         let state = state.lock().await;
         (
             Arc::clone(&state.session_manager),
             Arc::clone(&state.query_manager),
+            Arc::clone(&state.query_history),
             state.policy.clone(),
         )
     };
@@ -126,7 +522,9 @@ pub async fn execute_query(
                 success: false,
                 result: None,
                 error: Some(e.to_string()),
+                error_code: Some(e.code().to_string()),
                 query_id: None,
+                conflict_type: e.conflict_kind().map(|kind| kind.to_string()),
             });
         }
     };
@@ -138,141 +536,142 @@ pub async fn execute_query(
                 success: false,
                 result: None,
                 error: Some(e.to_string()),
+                error_code: Some(e.code().to_string()),
                 query_id: None,
+                conflict_type: e.conflict_kind().map(|kind| kind.to_string()),
             });
         }
     };
     tracing::Span::current().record("driver", &field::display(driver.driver_id()));
+    let dialect = driver.dialect_id(session).await;
+    let stats = session_manager.session_stats(session).await.ok();
 
     let is_production = match session_manager.is_production(session).await {
         Ok(value) => value,
         Err(_) => false,
     };
-
-    let acknowledged = acknowledged_dangerous.unwrap_or(false);
-    let is_sql_driver = !driver.driver_id().eq_ignore_ascii_case("mongodb");
-    let sql_analysis = if is_sql_driver {
-        match sql_safety::analyze_sql(driver.driver_id(), &query) {
-            Ok(analysis) => Some(analysis),
-            Err(err) => {
-                if read_only {
-                    return Ok(QueryResponse {
-                        success: false,
-                        result: None,
-                        error: Some(format!("{SQL_PARSE_BLOCKED}: {err}")),
-                        query_id: None,
-                    });
-                }
-
-                if is_production {
-                    if policy.prod_block_dangerous_sql {
-                        return Ok(QueryResponse {
-                            success: false,
-                            result: None,
-                            error: Some(format!(
-                                "{DANGEROUS_BLOCKED_POLICY}: SQL parse error: {err}"
-                            )),
-                            query_id: None,
-                        });
-                    }
-
-                    if policy.prod_require_confirmation && !acknowledged {
-                        return Ok(QueryResponse {
-                            success: false,
-                            result: None,
-                            error: Some(format!(
-                                "{DANGEROUS_BLOCKED}: SQL parse error: {err}"
-                            )),
-                            query_id: None,
-                        });
-                    }
-                }
-
-                None
-            }
-        }
-    } else {
-        None
+    let is_staging = session_manager.is_staging(session).await.unwrap_or(false);
+    let is_protected_host = session_manager
+        .is_protected_host(session, &policy)
+        .await
+        .unwrap_or(false);
+
+    let guard = QueryGuard {
+        policy: &policy,
+        driver_id: dialect,
+        read_only,
+        is_production,
+        is_staging,
+        is_protected_host,
+        acknowledged: options.acknowledged_dangerous,
     };
-
-    if read_only {
-        let is_mutation = if is_sql_driver {
-            sql_analysis
-                .as_ref()
-                .map(|analysis| analysis.is_mutation)
-                .unwrap_or(false)
-        } else {
-            is_mongo_mutation(&query)
-        };
-
-        if is_mutation {
-            return Ok(QueryResponse {
-                success: false,
-                result: None,
-                error: Some(READ_ONLY_BLOCKED.to_string()),
-                query_id: None,
-            });
-        }
+    if let Some(error) = guard.check(&query) {
+        return Ok(QueryResponse {
+            success: false,
+            result: None,
+            error: Some(error),
+            error_code: None,
+            query_id: None,
+            conflict_type: None,
+        });
     }
 
-    if is_production {
-        let is_dangerous = if is_sql_driver {
-            sql_analysis
-                .as_ref()
-                .map(|analysis| analysis.is_dangerous)
-                .unwrap_or(false)
-        } else {
-            false
-        };
-
-        if is_dangerous {
-            if policy.prod_block_dangerous_sql {
+    let query = match options.lock_rows.as_deref().map(parse_lock_mode).transpose() {
+        Ok(None) => query,
+        Ok(Some(mode)) => match driver.lock_rows_query(session, &query, mode).await {
+            Ok(locked) => locked,
+            Err(e) => {
                 return Ok(QueryResponse {
                     success: false,
                     result: None,
-                    error: Some(DANGEROUS_BLOCKED_POLICY.to_string()),
-                    query_id: None,
-                });
-            }
-
-            if policy.prod_require_confirmation && !acknowledged {
-                return Ok(QueryResponse {
-                    success: false,
-                    result: None,
-                    error: Some(DANGEROUS_BLOCKED.to_string()),
+                    error: Some(e.to_string()),
+                    error_code: Some(e.code().to_string()),
                     query_id: None,
+                    conflict_type: e.conflict_kind().map(|kind| kind.to_string()),
                 });
             }
+        },
+        Err(error) => {
+            return Ok(QueryResponse {
+                success: false,
+                result: None,
+                error: Some(error),
+                error_code: None,
+                query_id: None,
+                conflict_type: None,
+            });
         }
-    }
+    };
 
-    let query_id = if let Some(raw) = query_id {
-        let parsed = Uuid::parse_str(&raw).map_err(|e| format!("Invalid query ID: {}", e))?;
-        let qid = QueryId(parsed);
-        query_manager
-            .register_with_id(session, qid)
-            .await
-            .map_err(|e| format!("Failed to register query ID: {}", e))?;
-        qid
+    crate::observability::log_queries(&policy, &query);
+
+    let query_id =
+        register_query(&query_manager, session, options.query_id.as_deref()).await?;
+    let query_id_str = query_id.0.to_string();
+    let _ = app.emit(
+        QUERY_STARTED_EVENT,
+        QueryStartedEvent {
+            query_id: query_id_str.clone(),
+            session_id: session_id.clone(),
+        },
+    );
+
+    let comment = match options.comment.as_deref().map(str::trim) {
+        Some(comment) if !comment.is_empty() => Some(comment.to_string()),
+        _ if policy.inject_query_comments => {
+            Some(default_query_comment(query_id, options.tab_id.as_deref()))
+        }
+        _ => None,
+    };
+    let row_cap = guard.row_cap(&query);
+    let capped = row_cap.as_ref().map_or(query.as_str(), |(_, capped)| capped);
+    let annotated = comment
+        .as_deref()
+        .map(|comment| annotate_query(driver.driver_id(), capped, comment));
+
+    // A failing EXPLAIN only drops the estimate; the query still runs
+    let plan_estimate = if options.include_plan_cost
+        && guard.is_sql_driver()
+        && sql_safety::analyze_sql(dialect, &query).is_ok_and(|a| !a.is_mutation)
+    {
+        driver.estimate_plan(session, &query).await.ok()
     } else {
-        query_manager.register(session).await
+        None
     };
-    let query_id_str = query_id.0.to_string();
 
     let start_time = std::time::Instant::now();
-    let execution = driver.execute(session, &query, query_id);
+    let execution = driver.execute(session, annotated.as_deref().unwrap_or(capped), query_id);
 
-    let result = if let Some(timeout_value) = timeout_ms {
+    let result = if let Some(timeout_value) = options.timeout_ms {
         match timeout(Duration::from_millis(timeout_value), execution).await {
             Ok(res) => res,
             Err(_) => {
                 let _ = driver.cancel(session, Some(query_id)).await;
                 query_manager.finish(query_id).await;
+                if let Some(stats) = &stats {
+                    stats.record_error(start_time.elapsed());
+                }
+                let e = EngineError::Timeout {
+                    timeout_ms: timeout_value,
+                };
+                let _ = app.emit(
+                    QUERY_FINISHED_EVENT,
+                    QueryFinishedEvent {
+                        query_id: query_id_str.clone(),
+                        session_id,
+                        success: false,
+                        execution_time_ms: start_time.elapsed().as_micros() as f64 / 1000.0,
+                        error: Some(e.to_string()),
+                        error_code: Some(e.code().to_string()),
+                    },
+                );
                 return Ok(QueryResponse {
                     success: false,
                     result: None,
-                    error: Some(format!("Operation timed out after {}ms", timeout_value)),
+                    error: Some(e.to_string()),
+                    error_code: Some(e.code().to_string()),
                     query_id: Some(query_id_str),
+                    conflict_type: None,
                 });
             }
         }
@@ -280,255 +679,2184 @@ pub async fn execute_query(
         execution.await
     };
 
+    if let Some(stats) = &stats {
+        match &result {
+            Ok(result) => stats.record_success(
+                result.rows.len() as u64,
+                result.affected_rows.unwrap_or(0),
+                start_time.elapsed(),
+            ),
+            Err(_) => stats.record_error(start_time.elapsed()),
+        }
+    }
+    if result.is_ok() && sql_safety::is_schema_change(&query) {
+        session_manager.forget_column_types(session).await;
+    }
+
     let response = match result {
         Ok(mut result) => {
             let elapsed = start_time.elapsed().as_micros() as f64 / 1000.0;
             result.execution_time_ms = elapsed;
+            result.plan_estimate = plan_estimate;
+            if let Some((max_rows, _)) = row_cap {
+                apply_row_cap(&mut result, max_rows);
+            }
+            if let Some(max_bytes) = options.max_value_bytes {
+                result.truncate_values(max_bytes);
+            }
+
+            query_history
+                .record(query_history::fingerprint(&query), elapsed)
+                .await;
 
+            let span = tracing::Span::current();
+            span.record("execution_time_ms", elapsed);
+            if let Some(affected) = result.affected_rows {
+                span.record("affected_rows", affected);
+            }
+
+            match check_result_memory(&mut result, &policy) {
+                Some(error) => Ok(QueryResponse {
+                    success: false,
+                    result: None,
+                    error: Some(error),
+                    error_code: None,
+                    query_id: Some(query_id_str),
+                    conflict_type: None,
+                }),
+                None => Ok(QueryResponse {
+                    success: true,
+                    result: Some(result),
+                    error: None,
+                    error_code: None,
+                    query_id: Some(query_id_str),
+                    conflict_type: None,
+                }),
+            }
+        }
+        Err(e) => {
+            restart_tunnel_if_down(&app, &session_manager, session, &session_id).await;
             Ok(QueryResponse {
-                success: true,
-                result: Some(result),
-                error: None,
+                success: false,
+                result: None,
+                error: Some(e.to_string()),
+                error_code: Some(e.code().to_string()),
                 query_id: Some(query_id_str),
+                conflict_type: e.conflict_kind().map(|kind| kind.to_string()),
             })
         }
-        Err(e) => Ok(QueryResponse {
-            success: false,
-            result: None,
-            error: Some(e.to_string()),
-            query_id: Some(query_id_str),
-        }),
     };
 
     query_manager.finish(query_id).await;
+    if let Ok(response) = &response {
+        let _ = app.emit(
+            QUERY_FINISHED_EVENT,
+            QueryFinishedEvent {
+                query_id: response.query_id.clone().unwrap_or_default(),
+                session_id,
+                success: response.success,
+                execution_time_ms: start_time.elapsed().as_micros() as f64 / 1000.0,
+                error: response.error.clone(),
+                error_code: response.error_code.clone(),
+            },
+        );
+    }
     response
 }
 
-/// Cancels a running query
+/// Re-opens the SSH tunnel of a session after a failed query, if the tunnel
+/// is down and the session opted into `auto_restart`
+///
+/// The failed query is not retried: it may have been a partially applied
+/// mutation.
+async fn restart_tunnel_if_down(
+    app: &AppHandle,
+    session_manager: &SessionManager,
+    session: SessionId,
+    session_id: &str,
+) {
+    if !session_manager.should_restart_tunnel(session).await {
+        return;
+    }
+    let _ = app.emit(
+        TUNNEL_RECONNECTING_EVENT,
+        TunnelReconnectingEvent {
+            session_id: session_id.to_string(),
+        },
+    );
+    if let Err(e) = session_manager.restart_tunnel(session).await {
+        tracing::warn!(error = %e, "Failed to restart SSH tunnel");
+    }
+}
+
+/// Suggests a timeout for a query from past executions of the same fingerprint
+#[tauri::command]
+pub async fn suggest_query_timeout(
+    state: State<'_, crate::SharedState>,
+    session_id: String,
+    query: String,
+) -> Result<TimeoutSuggestion, String> {
+    let (session_manager, query_history) = {
+        let state = state.lock().await;
+        (
+            Arc::clone(&state.session_manager),
+            Arc::clone(&state.query_history),
+        )
+    };
+    let session = parse_session_id(&session_id)?;
+    session_manager
+        .get_driver(session)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let fingerprint = query_history::fingerprint(&query);
+    Ok(TimeoutSuggestion {
+        suggested_ms: query_history.suggest_timeout(&fingerprint).await,
+        based_on_samples: query_history.sample_count(&fingerprint).await,
+    })
+}
+
+/// Executes a query with `:name` parameters on the given session
+///
+/// Placeholders are rewritten to the driver's positional syntax and the
+/// values bound, so they never end up in the SQL text. Guards run on the
+/// rewritten query.
 #[tauri::command]
 #[instrument(
-    skip(state),
-    fields(session_id = %session_id, query_id = ?query_id, driver = field::Empty)
+    skip(state, query, params, options),
+    fields(
+        session_id = %session_id,
+        query_id = ?options.as_ref().and_then(|o| o.query_id.as_deref()),
+        query_len = query.len(),
+        param_count = params.len(),
+        driver = field::Empty
+    )
 )]
-pub async fn cancel_query(
+pub async fn execute_query_params(
     state: State<'_, crate::SharedState>,
     session_id: String,
-    query_id: Option<String>,
+    query: String,
+    params: HashMap<String, Value>,
+    options: Option<ExecuteOptions>,
 ) -> Result<QueryResponse, String> {
-    let (session_manager, query_manager) = {
+    let options = options.unwrap_or_default();
+    let (session_manager, query_manager, policy) = {
         let state = state.lock().await;
-        (Arc::clone(&state.session_manager), Arc::clone(&state.query_manager))
+        (
+            Arc::clone(&state.session_manager),
+            Arc::clone(&state.query_manager),
+            state.policy.clone(),
+        )
+    };
+    let session = parse_session_id(&session_id)?;
+
+    let failure = |error: String| QueryResponse {
+        success: false,
+        result: None,
+        error: Some(error),
+        error_code: None,
+        query_id: None,
+        conflict_type: None,
+    };
+    let engine_failure = |e: EngineError| QueryResponse {
+        success: false,
+        result: None,
+        error: Some(e.to_string()),
+        error_code: Some(e.code().to_string()),
+        query_id: None,
+        conflict_type: e.conflict_kind().map(|kind| kind.to_string()),
+    };
+
+    let read_only = match session_manager.is_read_only(session).await {
+        Ok(read_only) => read_only,
+        Err(e) => return Ok(engine_failure(e)),
+    };
+    let driver = match session_manager.get_driver(session).await {
+        Ok(d) => d,
+        Err(e) => return Ok(engine_failure(e)),
+    };
+    tracing::Span::current().record("driver", field::display(driver.driver_id()));
+    let dialect = driver.dialect_id(session).await;
+
+    let (query, values) =
+        match named_params::rewrite_named_params(driver.driver_id(), &query, &params) {
+            Ok(rewritten) => rewritten,
+            Err(error) => return Ok(failure(error)),
+        };
+
+    let is_production = session_manager.is_production(session).await.unwrap_or(false);
+    let is_staging = session_manager.is_staging(session).await.unwrap_or(false);
+    let is_protected_host = session_manager
+        .is_protected_host(session, &policy)
+        .await
+        .unwrap_or(false);
+
+    let guard = QueryGuard {
+        policy: &policy,
+        driver_id: dialect,
+        read_only,
+        is_production,
+        is_staging,
+        is_protected_host,
+        acknowledged: options.acknowledged_dangerous,
+    };
+    if let Some(error) = guard.check(&query) {
+        return Ok(failure(error));
+    }
+
+    let query = match options.lock_rows.as_deref().map(parse_lock_mode).transpose() {
+        Ok(None) => query,
+        Ok(Some(mode)) => match driver.lock_rows_query(session, &query, mode).await {
+            Ok(locked) => locked,
+            Err(e) => return Ok(engine_failure(e)),
+        },
+        Err(error) => return Ok(failure(error)),
+    };
+
+    crate::observability::log_queries(&policy, &query);
+
+    let query_id =
+        register_query(&query_manager, session, options.query_id.as_deref()).await?;
+    let query_id_str = query_id.0.to_string();
+
+    let row_cap = guard.row_cap(&query);
+    let capped = row_cap.as_ref().map_or(query.as_str(), |(_, capped)| capped);
+
+    let start_time = std::time::Instant::now();
+    let execution = driver.execute_with_params(session, capped, &values, query_id);
+
+    let result = if let Some(timeout_value) = options.timeout_ms {
+        match timeout(Duration::from_millis(timeout_value), execution).await {
+            Ok(res) => res,
+            Err(_) => {
+                let _ = driver.cancel(session, Some(query_id)).await;
+                query_manager.finish(query_id).await;
+                let e = EngineError::Timeout {
+                    timeout_ms: timeout_value,
+                };
+                return Ok(QueryResponse {
+                    success: false,
+                    result: None,
+                    error: Some(e.to_string()),
+                    error_code: Some(e.code().to_string()),
+                    query_id: Some(query_id_str),
+                    conflict_type: None,
+                });
+            }
+        }
+    } else {
+        execution.await
+    };
+
+    if result.is_ok() && sql_safety::is_schema_change(&query) {
+        session_manager.forget_column_types(session).await;
+    }
+
+    let response = match result {
+        Ok(mut result) => {
+            result.execution_time_ms = start_time.elapsed().as_micros() as f64 / 1000.0;
+            if let Some((max_rows, _)) = row_cap {
+                apply_row_cap(&mut result, max_rows);
+            }
+            if let Some(max_bytes) = options.max_value_bytes {
+                result.truncate_values(max_bytes);
+            }
+            QueryResponse {
+                success: true,
+                result: Some(result),
+                error: None,
+                error_code: None,
+                query_id: Some(query_id_str),
+                conflict_type: None,
+            }
+        }
+        Err(e) => QueryResponse {
+            success: false,
+            result: None,
+            error: Some(e.to_string()),
+            error_code: Some(e.code().to_string()),
+            query_id: Some(query_id_str),
+            conflict_type: e.conflict_kind().map(|kind| kind.to_string()),
+        },
+    };
+
+    query_manager.finish(query_id).await;
+    Ok(response)
+}
+
+/// Executes a multi-statement script on the given session
+///
+/// Returns one response per statement (or result set). Guards run on each
+/// statement individually; if any statement is blocked the whole batch is.
+#[tauri::command]
+#[instrument(
+    skip(state, query, options),
+    fields(
+        session_id = %session_id,
+        query_len = query.len(),
+        driver = field::Empty
+    )
+)]
+pub async fn execute_multi_query(
+    state: State<'_, crate::SharedState>,
+    session_id: String,
+    query: String,
+    options: Option<ExecuteOptions>,
+) -> Result<Vec<QueryResponse>, String> {
+    let options = options.unwrap_or_default();
+    let (session_manager, query_manager, policy) = {
+        let state = state.lock().await;
+        (
+            Arc::clone(&state.session_manager),
+            Arc::clone(&state.query_manager),
+            state.policy.clone(),
+        )
+    };
+    let session = parse_session_id(&session_id)?;
+
+    let failure = |error: String| -> Vec<QueryResponse> {
+        vec![QueryResponse {
+            success: false,
+            result: None,
+            error: Some(error),
+            error_code: None,
+            query_id: None,
+            conflict_type: None,
+        }]
+    };
+    let engine_failure = |e: EngineError| -> Vec<QueryResponse> {
+        vec![QueryResponse {
+            success: false,
+            result: None,
+            error: Some(e.to_string()),
+            error_code: Some(e.code().to_string()),
+            query_id: None,
+            conflict_type: e.conflict_kind().map(|kind| kind.to_string()),
+        }]
+    };
+
+    if options.lock_rows.is_some() {
+        return Ok(failure(
+            "Row locking is not supported for multi-statement scripts".to_string(),
+        ));
+    }
+
+    let read_only = match session_manager.is_read_only(session).await {
+        Ok(read_only) => read_only,
+        Err(e) => return Ok(engine_failure(e)),
+    };
+
+    let driver = match session_manager.get_driver(session).await {
+        Ok(d) => d,
+        Err(e) => return Ok(engine_failure(e)),
+    };
+    tracing::Span::current().record("driver", field::display(driver.driver_id()));
+    let dialect = driver.dialect_id(session).await;
+
+    let is_production = session_manager.is_production(session).await.unwrap_or(false);
+    let is_staging = session_manager.is_staging(session).await.unwrap_or(false);
+    let is_protected_host = session_manager
+        .is_protected_host(session, &policy)
+        .await
+        .unwrap_or(false);
+
+    let guard = QueryGuard {
+        policy: &policy,
+        driver_id: dialect,
+        read_only,
+        is_production,
+        is_staging,
+        is_protected_host,
+        acknowledged: options.acknowledged_dangerous,
+    };
+    let statements = if guard.is_sql_driver() {
+        sql_safety::split_statements(dialect, &query)
+            .unwrap_or_else(|_| vec![query.clone()])
+    } else {
+        vec![query.clone()]
+    };
+    if let Some(error) = statements.iter().find_map(|statement| guard.check(statement)) {
+        return Ok(failure(error));
+    }
+
+    let query_id =
+        register_query(&query_manager, session, options.query_id.as_deref()).await?;
+    let query_id_str = query_id.0.to_string();
+
+    let execution = driver.execute_multi(session, &query, query_id);
+
+    let result = if let Some(timeout_value) = options.timeout_ms {
+        match timeout(Duration::from_millis(timeout_value), execution).await {
+            Ok(res) => res,
+            Err(_) => {
+                let _ = driver.cancel(session, Some(query_id)).await;
+                query_manager.finish(query_id).await;
+                let e = EngineError::Timeout {
+                    timeout_ms: timeout_value,
+                };
+                return Ok(vec![QueryResponse {
+                    success: false,
+                    result: None,
+                    error: Some(e.to_string()),
+                    error_code: Some(e.code().to_string()),
+                    query_id: Some(query_id_str),
+                    conflict_type: None,
+                }]);
+            }
+        }
+    } else {
+        execution.await
+    };
+
+    if result.is_ok() && statements.iter().any(|s| sql_safety::is_schema_change(s)) {
+        session_manager.forget_column_types(session).await;
+    }
+
+    let responses = match result {
+        Ok(results) => results
+            .into_iter()
+            .map(|mut result| {
+                if let Some(max_bytes) = options.max_value_bytes {
+                    result.truncate_values(max_bytes);
+                }
+                QueryResponse {
+                    success: true,
+                    result: Some(result),
+                    error: None,
+                    error_code: None,
+                    query_id: Some(query_id_str.clone()),
+                    conflict_type: None,
+                }
+            })
+            .collect(),
+        Err(e) => vec![QueryResponse {
+            success: false,
+            result: None,
+            error: Some(e.to_string()),
+            error_code: Some(e.code().to_string()),
+            query_id: Some(query_id_str),
+            conflict_type: e.conflict_kind().map(|kind| kind.to_string()),
+        }],
+    };
+
+    query_manager.finish(query_id).await;
+    Ok(responses)
+}
+
+/// Cancels a running query
+///
+/// Drivers reporting `CancelSupport::None` are not asked to cancel; the
+/// response carries the `cancel_not_supported` code instead.
+#[tauri::command]
+#[instrument(
+    skip(state),
+    fields(session_id = %session_id, query_id = ?query_id, driver = field::Empty)
+)]
+pub async fn cancel_query(
+    state: State<'_, crate::SharedState>,
+    session_id: String,
+    query_id: Option<String>,
+) -> Result<QueryResponse, String> {
+    let (session_manager, query_manager) = {
+        let state = state.lock().await;
+        (Arc::clone(&state.session_manager), Arc::clone(&state.query_manager))
+    };
+    let session = parse_session_id(&session_id)?;
+
+    let driver = match session_manager.get_driver(session).await {
+        Ok(d) => d,
+        Err(e) => {
+            return Ok(QueryResponse {
+                success: false,
+                result: None,
+                error: Some(e.to_string()),
+                error_code: Some(e.code().to_string()),
+                query_id: None,
+                conflict_type: e.conflict_kind().map(|kind| kind.to_string()),
+            });
+        }
+    };
+    tracing::Span::current().record("driver", &field::display(driver.driver_id()));
+
+    if driver.cancel_support() == CancelSupport::None {
+        return Ok(QueryResponse {
+            success: false,
+            result: None,
+            error: Some(CANCEL_NOT_SUPPORTED.to_string()),
+            error_code: Some(EngineErrorCode::CancelNotSupported.to_string()),
+            query_id,
+            conflict_type: None,
+        });
+    }
+
+    let query_id = if let Some(raw) = query_id {
+        let parsed = Uuid::parse_str(&raw).map_err(|e| format!("Invalid query ID: {}", e))?;
+        QueryId(parsed)
+    } else {
+        match query_manager.last_for_session(session).await {
+            Some(qid) => qid,
+            None => {
+                return Ok(QueryResponse {
+                    success: false,
+                    result: None,
+                    error: Some("No active query found".to_string()),
+                    error_code: None,
+                    query_id: None,
+                    conflict_type: None,
+                });
+            }
+        }
+    };
+    let query_id_str = query_id.0.to_string();
+
+    match driver.cancel(session, Some(query_id)).await {
+        Ok(()) => Ok(QueryResponse {
+            success: true,
+            result: None,
+            error: None,
+            error_code: None,
+            query_id: Some(query_id_str),
+            conflict_type: None,
+        }),
+        Err(e) => Ok(QueryResponse {
+            success: false,
+            result: None,
+            error: Some(e.to_string()),
+            error_code: Some(e.code().to_string()),
+            query_id: Some(query_id_str),
+            conflict_type: e.conflict_kind().map(|kind| kind.to_string()),
+        }),
+    }
+}
+
+/// Lists all namespaces (databases/schemas) for a session
+#[tauri::command]
+pub async fn list_namespaces(
+    state: State<'_, crate::SharedState>,
+    session_id: String,
+) -> Result<NamespacesResponse, String> {
+    let session_manager = {
+        let state = state.lock().await;
+        Arc::clone(&state.session_manager)
+    };
+    let session = parse_session_id(&session_id)?;
+
+    let driver = match session_manager.get_driver(session).await {
+        Ok(d) => d,
+        Err(e) => {
+            return Ok(NamespacesResponse {
+                success: false,
+                namespaces: None,
+                error: Some(e.to_string()),
+                error_code: Some(e.code().to_string()),
+            });
+        }
+    };
+
+    match driver.list_namespaces(session).await {
+        Ok(namespaces) => Ok(NamespacesResponse {
+            success: true,
+            namespaces: Some(namespaces),
+            error: None,
+            error_code: None,
+        }),
+        Err(e) => Ok(NamespacesResponse {
+            success: false,
+            namespaces: None,
+            error: Some(e.to_string()),
+            error_code: Some(e.code().to_string()),
+        }),
+    }
+}
+
+/// Lists all collections (tables/views) in a namespace
+#[tauri::command]
+pub async fn list_collections(
+    state: State<'_, crate::SharedState>,
+    session_id: String,
+    namespace: Namespace,
+) -> Result<CollectionsResponse, String> {
+    let session_manager = {
+        let state = state.lock().await;
+        Arc::clone(&state.session_manager)
+    };
+    let session = parse_session_id(&session_id)?;
+
+    let driver = match session_manager.get_driver(session).await {
+        Ok(d) => d,
+        Err(e) => {
+            return Ok(CollectionsResponse {
+                success: false,
+                collections: None,
+                error: Some(e.to_string()),
+                error_code: Some(e.code().to_string()),
+            });
+        }
+    };
+
+    match driver.list_collections(session, &namespace).await {
+        Ok(collections) => Ok(CollectionsResponse {
+            success: true,
+            collections: Some(collections),
+            error: None,
+            error_code: None,
+        }),
+        Err(e) => Ok(CollectionsResponse {
+            success: false,
+            collections: None,
+            error: Some(e.to_string()),
+            error_code: Some(e.code().to_string()),
+        }),
+    }
+}
+
+/// Lists the stored functions and procedures of a namespace
+#[tauri::command]
+pub async fn list_routines(
+    state: State<'_, crate::SharedState>,
+    session_id: String,
+    namespace: Namespace,
+) -> Result<RoutinesResponse, String> {
+    let session_manager = {
+        let state = state.lock().await;
+        Arc::clone(&state.session_manager)
+    };
+    let session = parse_session_id(&session_id)?;
+
+    let driver = match session_manager.get_driver(session).await {
+        Ok(d) => d,
+        Err(e) => {
+            return Ok(RoutinesResponse {
+                success: false,
+                routines: None,
+                error: Some(e.to_string()),
+                error_code: Some(e.code().to_string()),
+            });
+        }
+    };
+
+    match driver.list_routines(session, &namespace).await {
+        Ok(routines) => Ok(RoutinesResponse {
+            success: true,
+            routines: Some(routines),
+            error: None,
+            error_code: None,
+        }),
+        Err(e) => Ok(RoutinesResponse {
+            success: false,
+            routines: None,
+            error: Some(e.to_string()),
+            error_code: Some(e.code().to_string()),
+        }),
+    }
+}
+
+/// Statement the guards check for a routine call
+///
+/// Arguments are bound as parameters, so only the kind of call matters: a
+/// function runs in a `SELECT`, a procedure through `CALL`, which counts as
+/// a write.
+fn routine_call_statement(routine_type: RoutineType) -> &'static str {
+    match routine_type {
+        RoutineType::Function => "SELECT routine()",
+        RoutineType::Procedure => "CALL routine()",
+    }
+}
+
+/// Calls a stored function or procedure with positional arguments
+///
+/// Returns one response per result set, as `execute_multi_query` does.
+/// Read-only and production guards apply as for `execute_query`.
+#[tauri::command]
+#[instrument(
+    skip(state, args, options),
+    fields(
+        session_id = %session_id,
+        routine = %routine,
+        routine_type = ?routine_type,
+        arg_count = args.len()
+    )
+)]
+pub async fn call_routine(
+    state: State<'_, crate::SharedState>,
+    session_id: String,
+    namespace: Namespace,
+    routine: String,
+    routine_type: RoutineType,
+    args: Vec<Value>,
+    options: Option<ExecuteOptions>,
+) -> Result<Vec<QueryResponse>, String> {
+    let options = options.unwrap_or_default();
+    let (session_manager, query_manager, policy) = {
+        let state = state.lock().await;
+        (
+            Arc::clone(&state.session_manager),
+            Arc::clone(&state.query_manager),
+            state.policy.clone(),
+        )
+    };
+    let session = parse_session_id(&session_id)?;
+
+    let failure = |error: String| -> Vec<QueryResponse> {
+        vec![QueryResponse {
+            success: false,
+            result: None,
+            error: Some(error),
+            error_code: None,
+            query_id: None,
+            conflict_type: None,
+        }]
+    };
+    let engine_failure = |e: EngineError| -> Vec<QueryResponse> {
+        vec![QueryResponse {
+            success: false,
+            result: None,
+            error: Some(e.to_string()),
+            error_code: Some(e.code().to_string()),
+            query_id: None,
+            conflict_type: e.conflict_kind().map(|kind| kind.to_string()),
+        }]
+    };
+
+    let read_only = match session_manager.is_read_only(session).await {
+        Ok(read_only) => read_only,
+        Err(e) => return Ok(engine_failure(e)),
+    };
+    let driver = match session_manager.get_driver(session).await {
+        Ok(d) => d,
+        Err(e) => return Ok(engine_failure(e)),
+    };
+    let dialect = driver.dialect_id(session).await;
+
+    let is_production = session_manager.is_production(session).await.unwrap_or(false);
+    let is_staging = session_manager.is_staging(session).await.unwrap_or(false);
+    let is_protected_host = session_manager
+        .is_protected_host(session, &policy)
+        .await
+        .unwrap_or(false);
+
+    let guard = QueryGuard {
+        policy: &policy,
+        driver_id: dialect,
+        read_only,
+        is_production,
+        is_staging,
+        is_protected_host,
+        acknowledged: options.acknowledged_dangerous,
+    };
+    if let Some(error) = guard.check(routine_call_statement(routine_type)) {
+        return Ok(failure(error));
+    }
+
+    let query_id =
+        register_query(&query_manager, session, options.query_id.as_deref()).await?;
+    let query_id_str = query_id.0.to_string();
+
+    let execution =
+        driver.call_routine(session, &namespace, &routine, routine_type, &args, query_id);
+
+    let result = if let Some(timeout_value) = options.timeout_ms {
+        match timeout(Duration::from_millis(timeout_value), execution).await {
+            Ok(res) => res,
+            Err(_) => {
+                let _ = driver.cancel(session, Some(query_id)).await;
+                query_manager.finish(query_id).await;
+                let e = EngineError::Timeout {
+                    timeout_ms: timeout_value,
+                };
+                return Ok(vec![QueryResponse {
+                    success: false,
+                    result: None,
+                    error: Some(e.to_string()),
+                    error_code: Some(e.code().to_string()),
+                    query_id: Some(query_id_str),
+                    conflict_type: None,
+                }]);
+            }
+        }
+    } else {
+        execution.await
+    };
+
+    let responses = match result {
+        Ok(results) => results
+            .into_iter()
+            .map(|mut result| {
+                if let Some(max_bytes) = options.max_value_bytes {
+                    result.truncate_values(max_bytes);
+                }
+                QueryResponse {
+                    success: true,
+                    result: Some(result),
+                    error: None,
+                    error_code: None,
+                    query_id: Some(query_id_str.clone()),
+                    conflict_type: None,
+                }
+            })
+            .collect(),
+        Err(e) => vec![QueryResponse {
+            success: false,
+            result: None,
+            error: Some(e.to_string()),
+            error_code: Some(e.code().to_string()),
+            query_id: Some(query_id_str),
+            conflict_type: e.conflict_kind().map(|kind| kind.to_string()),
+        }],
+    };
+
+    query_manager.finish(query_id).await;
+    Ok(responses)
+}
+
+/// Lists the collections of every namespace in one call
+///
+/// Namespaces are sorted by database, then schema.
+#[tauri::command]
+pub async fn list_all_collections(
+    state: State<'_, crate::SharedState>,
+    session_id: String,
+) -> Result<AllCollectionsResponse, String> {
+    let session_manager = {
+        let state = state.lock().await;
+        Arc::clone(&state.session_manager)
+    };
+    let session = parse_session_id(&session_id)?;
+
+    let driver = match session_manager.get_driver(session).await {
+        Ok(d) => d,
+        Err(e) => {
+            return Ok(AllCollectionsResponse {
+                success: false,
+                namespaces: None,
+                error: Some(e.to_string()),
+                error_code: Some(e.code().to_string()),
+            });
+        }
+    };
+
+    match driver.list_all_collections(session).await {
+        Ok(all) => {
+            let mut namespaces: Vec<NamespaceCollections> = all
+                .into_iter()
+                .map(|(namespace, collections)| NamespaceCollections {
+                    namespace,
+                    collections,
+                })
+                .collect();
+            namespaces.sort_by(|a, b| {
+                (&a.namespace.database, &a.namespace.schema)
+                    .cmp(&(&b.namespace.database, &b.namespace.schema))
+            });
+            Ok(AllCollectionsResponse {
+                success: true,
+                namespaces: Some(namespaces),
+                error: None,
+                error_code: None,
+            })
+        }
+        Err(e) => Ok(AllCollectionsResponse {
+            success: false,
+            namespaces: None,
+            error: Some(e.to_string()),
+            error_code: Some(e.code().to_string()),
+        }),
+    }
+}
+
+/// Response wrapper for table schema
+#[derive(Debug, Serialize)]
+pub struct TableSchemaResponse {
+    pub success: bool,
+    pub schema: Option<TableSchema>,
+    pub error: Option<String>,
+    pub error_code: Option<String>,
+}
+
+/// Gets the schema of a table/collection
+#[tauri::command]
+pub async fn describe_table(
+    state: State<'_, crate::SharedState>,
+    session_id: String,
+    namespace: Namespace,
+    table: String,
+) -> Result<TableSchemaResponse, String> {
+    let session_manager = {
+        let state = state.lock().await;
+        Arc::clone(&state.session_manager)
+    };
+    let session = parse_session_id(&session_id)?;
+
+    let driver = match session_manager.get_driver(session).await {
+        Ok(d) => d,
+        Err(e) => {
+            return Ok(TableSchemaResponse {
+                success: false,
+                schema: None,
+                error: Some(e.to_string()),
+                error_code: Some(e.code().to_string()),
+            });
+        }
+    };
+
+    match driver.describe_table(session, &namespace, &table).await {
+        Ok(schema) => Ok(TableSchemaResponse {
+            success: true,
+            schema: Some(schema),
+            error: None,
+            error_code: None,
+        }),
+        Err(e) => Ok(TableSchemaResponse {
+            success: false,
+            schema: None,
+            error: Some(e.to_string()),
+            error_code: Some(e.code().to_string()),
+        }),
+    }
+}
+
+/// Response wrapper for namespace-wide schema loading
+#[derive(Debug, Serialize)]
+pub struct NamespaceSchemaResponse {
+    pub success: bool,
+    pub tables: Option<HashMap<String, TableSchema>>,
+    pub error: Option<String>,
+    pub error_code: Option<String>,
+}
+
+/// Gets the schema of every table/collection in a namespace at once
+#[tauri::command]
+pub async fn describe_namespace(
+    state: State<'_, crate::SharedState>,
+    session_id: String,
+    namespace: Namespace,
+) -> Result<NamespaceSchemaResponse, String> {
+    let session_manager = {
+        let state = state.lock().await;
+        Arc::clone(&state.session_manager)
+    };
+    let session = parse_session_id(&session_id)?;
+
+    let driver = match session_manager.get_driver(session).await {
+        Ok(d) => d,
+        Err(e) => {
+            return Ok(NamespaceSchemaResponse {
+                success: false,
+                tables: None,
+                error: Some(e.to_string()),
+                error_code: Some(e.code().to_string()),
+            });
+        }
+    };
+
+    match driver.describe_namespace(session, &namespace).await {
+        Ok(tables) => Ok(NamespaceSchemaResponse {
+            success: true,
+            tables: Some(tables),
+            error: None,
+            error_code: None,
+        }),
+        Err(e) => Ok(NamespaceSchemaResponse {
+            success: false,
+            tables: None,
+            error: Some(e.to_string()),
+            error_code: Some(e.code().to_string()),
+        }),
+    }
+}
+
+/// Response wrapper for table size statistics
+#[derive(Debug, Serialize)]
+pub struct TableSizesResponse {
+    pub success: bool,
+    pub sizes: Option<Vec<TableSizeInfo>>,
+    pub error: Option<String>,
+    pub error_code: Option<String>,
+}
+
+/// Gets disk usage statistics for the tables of a namespace
+#[tauri::command]
+pub async fn get_table_sizes(
+    state: State<'_, crate::SharedState>,
+    session_id: String,
+    namespace: Namespace,
+) -> Result<TableSizesResponse, String> {
+    let session_manager = {
+        let state = state.lock().await;
+        Arc::clone(&state.session_manager)
+    };
+    let session = parse_session_id(&session_id)?;
+
+    let driver = match session_manager.get_driver(session).await {
+        Ok(d) => d,
+        Err(e) => {
+            return Ok(TableSizesResponse {
+                success: false,
+                sizes: None,
+                error: Some(e.to_string()),
+                error_code: Some(e.code().to_string()),
+            });
+        }
+    };
+
+    match driver.get_table_sizes(session, &namespace).await {
+        Ok(sizes) => Ok(TableSizesResponse {
+            success: true,
+            sizes: Some(sizes),
+            error: None,
+            error_code: None,
+        }),
+        Err(e) => Ok(TableSizesResponse {
+            success: false,
+            sizes: None,
+            error: Some(e.to_string()),
+            error_code: Some(e.code().to_string()),
+        }),
+    }
+}
+
+/// Response wrapper for WAL status
+#[derive(Debug, Serialize)]
+pub struct WalStatusResponse {
+    pub success: bool,
+    pub status: Option<WalStatus>,
+    pub error: Option<String>,
+    pub error_code: Option<String>,
+}
+
+/// Response wrapper for a forced checkpoint
+#[derive(Debug, Serialize)]
+pub struct CheckpointResponse {
+    pub success: bool,
+    pub error: Option<String>,
+    pub error_code: Option<String>,
+}
+
+/// Gets the WAL and checkpoint position of the server
+#[tauri::command]
+pub async fn get_wal_status(
+    state: State<'_, crate::SharedState>,
+    session_id: String,
+) -> Result<WalStatusResponse, String> {
+    let session_manager = {
+        let state = state.lock().await;
+        Arc::clone(&state.session_manager)
+    };
+    let session = parse_session_id(&session_id)?;
+
+    let driver = match session_manager.get_driver(session).await {
+        Ok(d) => d,
+        Err(e) => {
+            return Ok(WalStatusResponse {
+                success: false,
+                status: None,
+                error: Some(e.to_string()),
+                error_code: Some(e.code().to_string()),
+            });
+        }
+    };
+
+    match driver.get_wal_status(session).await {
+        Ok(status) => Ok(WalStatusResponse {
+            success: true,
+            status: Some(status),
+            error: None,
+            error_code: None,
+        }),
+        Err(e) => Ok(WalStatusResponse {
+            success: false,
+            status: None,
+            error: Some(e.to_string()),
+            error_code: Some(e.code().to_string()),
+        }),
+    }
+}
+
+/// Forces a checkpoint on the server
+///
+/// A checkpoint flushes every dirty buffer and can stall a busy server, so
+/// it is blocked on read-only sessions and treated as a dangerous statement
+/// on production, staging and protected-host connections.
+#[tauri::command]
+#[instrument(skip(state), fields(session_id = %session_id))]
+pub async fn force_checkpoint(
+    state: State<'_, crate::SharedState>,
+    session_id: String,
+    acknowledged_dangerous: Option<bool>,
+) -> Result<CheckpointResponse, String> {
+    let (session_manager, policy) = {
+        let state = state.lock().await;
+        (Arc::clone(&state.session_manager), state.policy.clone())
+    };
+    let session = parse_session_id(&session_id)?;
+
+    let failure = |error: String| {
+        Ok(CheckpointResponse {
+            success: false,
+            error: Some(error),
+            error_code: None,
+        })
+    };
+    let engine_failure = |e: EngineError| {
+        Ok(CheckpointResponse {
+            success: false,
+            error: Some(e.to_string()),
+            error_code: Some(e.code().to_string()),
+        })
+    };
+
+    let driver = match session_manager.get_driver(session).await {
+        Ok(d) => d,
+        Err(e) => return engine_failure(e),
+    };
+
+    let acknowledged = acknowledged_dangerous.unwrap_or(false);
+    let guard =
+        match QueryGuard::for_session(&session_manager, &*driver, session, &policy, acknowledged)
+            .await
+        {
+            Ok(guard) => guard,
+            Err(e) => return engine_failure(e),
+        };
+    if let Some(error) = guard.check_operation(true, true) {
+        return failure(error);
+    }
+
+    match driver.force_checkpoint(session).await {
+        Ok(()) => Ok(CheckpointResponse {
+            success: true,
+            error: None,
+            error_code: None,
+        }),
+        Err(e) => engine_failure(e),
+    }
+}
+
+/// Response wrapper for server variable listing
+#[derive(Debug, Serialize)]
+pub struct ServerVariablesResponse {
+    pub success: bool,
+    pub variables: Option<Vec<ServerVariable>>,
+    pub error: Option<String>,
+    pub error_code: Option<String>,
+}
+
+/// Response wrapper for `set_server_variable`
+#[derive(Debug, Serialize)]
+pub struct SetServerVariableResponse {
+    pub success: bool,
+    pub error: Option<String>,
+    pub error_code: Option<String>,
+}
+
+/// Lists server variables (`SHOW VARIABLES`, `SHOW ALL`)
+///
+/// `filter` is a `LIKE` pattern on the variable name.
+#[tauri::command]
+pub async fn list_server_variables(
+    state: State<'_, crate::SharedState>,
+    session_id: String,
+    scope: VariableScope,
+    filter: Option<String>,
+) -> Result<ServerVariablesResponse, String> {
+    let session_manager = {
+        let state = state.lock().await;
+        Arc::clone(&state.session_manager)
+    };
+    let session = parse_session_id(&session_id)?;
+
+    let driver = match session_manager.get_driver(session).await {
+        Ok(d) => d,
+        Err(e) => {
+            return Ok(ServerVariablesResponse {
+                success: false,
+                variables: None,
+                error: Some(e.to_string()),
+                error_code: Some(e.code().to_string()),
+            });
+        }
+    };
+
+    let filter = filter.as_deref().map(str::trim).filter(|f| !f.is_empty());
+    match driver.list_server_variables(session, scope, filter).await {
+        Ok(variables) => Ok(ServerVariablesResponse {
+            success: true,
+            variables: Some(variables),
+            error: None,
+            error_code: None,
+        }),
+        Err(e) => Ok(ServerVariablesResponse {
+            success: false,
+            variables: None,
+            error: Some(e.to_string()),
+            error_code: Some(e.code().to_string()),
+        }),
+    }
+}
+
+/// Sets a server variable that is not on the driver's blocklist
+///
+/// Global changes affect every client of the server, so they are blocked on
+/// read-only sessions and treated as dangerous statements on production,
+/// staging and protected-host connections, like `force_checkpoint`.
+#[tauri::command]
+#[instrument(skip(state, value), fields(session_id = %session_id, name = %name))]
+pub async fn set_server_variable(
+    state: State<'_, crate::SharedState>,
+    session_id: String,
+    name: String,
+    value: String,
+    scope: VariableScope,
+    acknowledged_dangerous: Option<bool>,
+) -> Result<SetServerVariableResponse, String> {
+    let (session_manager, policy) = {
+        let state = state.lock().await;
+        (Arc::clone(&state.session_manager), state.policy.clone())
+    };
+    let session = parse_session_id(&session_id)?;
+
+    let failure = |error: String| {
+        Ok(SetServerVariableResponse {
+            success: false,
+            error: Some(error),
+            error_code: None,
+        })
+    };
+    let engine_failure = |e: EngineError| {
+        Ok(SetServerVariableResponse {
+            success: false,
+            error: Some(e.to_string()),
+            error_code: Some(e.code().to_string()),
+        })
+    };
+
+    let driver = match session_manager.get_driver(session).await {
+        Ok(d) => d,
+        Err(e) => return engine_failure(e),
+    };
+
+    if scope == VariableScope::Global {
+        let acknowledged = acknowledged_dangerous.unwrap_or(false);
+        match QueryGuard::for_session(&session_manager, &*driver, session, &policy, acknowledged)
+            .await
+        {
+            Ok(guard) => {
+                if let Some(error) = guard.check_operation(true, true) {
+                    return failure(error);
+                }
+            }
+            Err(e) => return engine_failure(e),
+        }
+    }
+
+    match driver.set_server_variable(session, &name, &value, scope).await {
+        Ok(()) => Ok(SetServerVariableResponse {
+            success: true,
+            error: None,
+            error_code: None,
+        }),
+        Err(e) => engine_failure(e),
+    }
+}
+
+/// Response wrapper for server query listing
+#[derive(Debug, Serialize)]
+pub struct ServerQueriesResponse {
+    pub success: bool,
+    pub queries: Option<Vec<ServerQuery>>,
+    pub error: Option<String>,
+    pub error_code: Option<String>,
+}
+
+/// Response wrapper for `cancel_by_pid`
+#[derive(Debug, Serialize)]
+pub struct CancelByPidResponse {
+    pub success: bool,
+    pub error: Option<String>,
+    pub error_code: Option<String>,
+}
+
+/// Lists the client sessions on the server and their current queries
+/// (`pg_stat_activity`, or the MySQL process list)
+#[tauri::command]
+#[instrument(skip(state), fields(session_id = %session_id))]
+pub async fn list_server_queries(
+    state: State<'_, crate::SharedState>,
+    session_id: String,
+) -> Result<ServerQueriesResponse, String> {
+    let session_manager = {
+        let state = state.lock().await;
+        Arc::clone(&state.session_manager)
+    };
+    let session = parse_session_id(&session_id)?;
+
+    let driver = match session_manager.get_driver(session).await {
+        Ok(d) => d,
+        Err(e) => {
+            return Ok(ServerQueriesResponse {
+                success: false,
+                queries: None,
+                error: Some(e.to_string()),
+                error_code: Some(e.code().to_string()),
+            })
+        }
+    };
+
+    match driver.list_server_queries(session).await {
+        Ok(queries) => Ok(ServerQueriesResponse {
+            success: true,
+            queries: Some(queries),
+            error: None,
+            error_code: None,
+        }),
+        Err(e) => Ok(ServerQueriesResponse {
+            success: false,
+            queries: None,
+            error: Some(e.to_string()),
+            error_code: Some(e.code().to_string()),
+        }),
+    }
+}
+
+/// Cancels a server-side query by backend PID
+///
+/// With `force`, the whole session is terminated instead, as with
+/// `terminate_server_session`.
+#[tauri::command]
+#[instrument(skip(state), fields(session_id = %session_id, pid = %pid, force = ?force))]
+pub async fn cancel_by_pid(
+    state: State<'_, crate::SharedState>,
+    session_id: String,
+    pid: i64,
+    force: Option<bool>,
+    acknowledged_dangerous: Option<bool>,
+) -> Result<CancelByPidResponse, String> {
+    let (session_manager, policy) = {
+        let state = state.lock().await;
+        (Arc::clone(&state.session_manager), state.policy.clone())
+    };
+    let session = parse_session_id(&session_id)?;
+    let terminate = force.unwrap_or(false);
+
+    let failure = |error: String| {
+        Ok(CancelByPidResponse {
+            success: false,
+            error: Some(error),
+            error_code: None,
+        })
+    };
+    let engine_failure = |e: EngineError| {
+        Ok(CancelByPidResponse {
+            success: false,
+            error: Some(e.to_string()),
+            error_code: Some(e.code().to_string()),
+        })
+    };
+
+    let driver = match session_manager.get_driver(session).await {
+        Ok(d) => d,
+        Err(e) => return engine_failure(e),
+    };
+
+    if terminate {
+        let acknowledged = acknowledged_dangerous.unwrap_or(false);
+        // Terminating drops the connection and rolls back its transaction,
+        // so it counts as a dangerous write
+        match QueryGuard::for_session(&session_manager, &*driver, session, &policy, acknowledged)
+            .await
+        {
+            Ok(guard) => {
+                if let Some(error) = guard.check_operation(true, true) {
+                    return failure(error);
+                }
+            }
+            Err(e) => return engine_failure(e),
+        }
+    }
+
+    match driver.cancel_backend(session, pid, terminate).await {
+        Ok(()) => Ok(CancelByPidResponse {
+            success: true,
+            error: None,
+            error_code: None,
+        }),
+        Err(e) => engine_failure(e),
+    }
+}
+
+/// Response wrapper for server session listing
+#[derive(Debug, Serialize)]
+pub struct ServerSessionsResponse {
+    pub success: bool,
+    pub sessions: Option<Vec<ServerQuery>>,
+    pub error: Option<String>,
+    pub error_code: Option<String>,
+}
+
+/// Lists the client sessions connected to the server
+///
+/// Same listing as `list_server_queries`, under the `sessions` key.
+#[tauri::command]
+#[instrument(skip(state), fields(session_id = %session_id))]
+pub async fn list_server_sessions(
+    state: State<'_, crate::SharedState>,
+    session_id: String,
+) -> Result<ServerSessionsResponse, String> {
+    let response = list_server_queries(state, session_id).await?;
+    Ok(ServerSessionsResponse {
+        success: response.success,
+        sessions: response.queries,
+        error: response.error,
+        error_code: response.error_code,
+    })
+}
+
+/// Terminates another client's session on the server
+/// (`pg_terminate_backend`, MySQL `KILL CONNECTION`)
+///
+/// Same as `cancel_by_pid` with `force`, and guarded the same way.
+#[tauri::command]
+#[instrument(skip(state), fields(session_id = %session_id, pid = %pid))]
+pub async fn terminate_server_session(
+    state: State<'_, crate::SharedState>,
+    session_id: String,
+    pid: i64,
+    acknowledged_dangerous: Option<bool>,
+) -> Result<CancelByPidResponse, String> {
+    cancel_by_pid(state, session_id, pid, Some(true), acknowledged_dangerous).await
+}
+
+/// Response wrapper for `clear_statement_cache`
+#[derive(Debug, Serialize)]
+pub struct ClearStatementCacheResponse {
+    pub success: bool,
+    pub error: Option<String>,
+    pub error_code: Option<String>,
+}
+
+/// Drops the prepared statements cached by a session's connections
+///
+/// The cache is already cleared after DDL run through the session; this is
+/// for schema changes made by other clients.
+#[tauri::command]
+#[instrument(skip(state), fields(session_id = %session_id))]
+pub async fn clear_statement_cache(
+    state: State<'_, crate::SharedState>,
+    session_id: String,
+) -> Result<ClearStatementCacheResponse, String> {
+    let session_manager = {
+        let state = state.lock().await;
+        Arc::clone(&state.session_manager)
+    };
+    let session = parse_session_id(&session_id)?;
+
+    let result = match session_manager.get_driver(session).await {
+        Ok(driver) => driver.clear_statement_cache(session).await,
+        Err(e) => Err(e),
+    };
+    session_manager.forget_column_types(session).await;
+    match result {
+        Ok(()) => Ok(ClearStatementCacheResponse {
+            success: true,
+            error: None,
+            error_code: None,
+        }),
+        Err(e) => Ok(ClearStatementCacheResponse {
+            success: false,
+            error: Some(e.to_string()),
+            error_code: Some(e.code().to_string()),
+        }),
+    }
+}
+
+/// Response wrapper for `send_db_notification`
+#[derive(Debug, Serialize)]
+pub struct NotificationResponse {
+    pub success: bool,
+    pub error: Option<String>,
+    pub error_code: Option<String>,
+}
+
+/// Returns true if `channel` is a plain identifier (`[A-Za-z_][A-Za-z0-9_]*`)
+fn is_valid_channel_name(channel: &str) -> bool {
+    let mut chars = channel.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// Sends a notification to the sessions listening on `channel`
+/// (PostgreSQL `NOTIFY`)
+///
+/// Listeners may act on it, so it is guarded like a write: blocked on
+/// read-only sessions and confirmed on production connections.
+#[tauri::command]
+#[instrument(skip(state, payload), fields(session_id = %session_id, channel = %channel))]
+pub async fn send_db_notification(
+    state: State<'_, crate::SharedState>,
+    session_id: String,
+    channel: String,
+    payload: Option<String>,
+    acknowledged_dangerous: Option<bool>,
+) -> Result<NotificationResponse, String> {
+    let (session_manager, policy) = {
+        let state = state.lock().await;
+        (Arc::clone(&state.session_manager), state.policy.clone())
+    };
+    let session = parse_session_id(&session_id)?;
+
+    let failure = |error: String| {
+        Ok(NotificationResponse {
+            success: false,
+            error: Some(error),
+            error_code: None,
+        })
+    };
+    let engine_failure = |e: EngineError| {
+        Ok(NotificationResponse {
+            success: false,
+            error: Some(e.to_string()),
+            error_code: Some(e.code().to_string()),
+        })
+    };
+
+    if !is_valid_channel_name(&channel) {
+        return failure(format!("Invalid channel name: {}", channel));
+    }
+
+    match session_manager.is_read_only(session).await {
+        Ok(true) => return failure(READ_ONLY_BLOCKED.to_string()),
+        Ok(false) => {}
+        Err(e) => return engine_failure(e),
+    }
+
+    let is_production = session_manager.is_production(session).await.unwrap_or(false);
+    let is_protected_host = session_manager
+        .is_protected_host(session, &policy)
+        .await
+        .unwrap_or(false);
+    if is_production || is_protected_host {
+        if policy.prod_block_dangerous_sql {
+            return failure(DANGEROUS_BLOCKED_POLICY.to_string());
+        }
+        if policy.prod_require_confirmation && !acknowledged_dangerous.unwrap_or(false) {
+            return failure(DANGEROUS_BLOCKED.to_string());
+        }
+    }
+
+    let driver = match session_manager.get_driver(session).await {
+        Ok(d) => d,
+        Err(e) => return engine_failure(e),
+    };
+
+    match driver.send_notify(session, &channel, payload.as_deref()).await {
+        Ok(()) => Ok(NotificationResponse {
+            success: true,
+            error: None,
+            error_code: None,
+        }),
+        Err(e) => engine_failure(e),
+    }
+}
+
+/// Response wrapper for namespace creation and deletion
+#[derive(Debug, Serialize)]
+pub struct NamespaceResponse {
+    pub success: bool,
+    pub error: Option<String>,
+    pub error_code: Option<String>,
+}
+
+/// Creates a database or schema
+///
+/// For MongoDB, `namespace.schema` names the first collection of the new
+/// database. Like other DDL it is blocked on read-only sessions and counts
+/// as dangerous on production, staging and protected-host connections.
+#[tauri::command]
+#[instrument(
+    skip(state),
+    fields(session_id = %session_id, database = %namespace.database, schema = ?namespace.schema)
+)]
+pub async fn create_namespace(
+    state: State<'_, crate::SharedState>,
+    session_id: String,
+    namespace: Namespace,
+    acknowledged_dangerous: Option<bool>,
+) -> Result<NamespaceResponse, String> {
+    let (session_manager, policy) = {
+        let state = state.lock().await;
+        (Arc::clone(&state.session_manager), state.policy.clone())
+    };
+    let session = parse_session_id(&session_id)?;
+
+    let failure = |error: String| {
+        Ok(NamespaceResponse {
+            success: false,
+            error: Some(error),
+            error_code: None,
+        })
+    };
+    let engine_failure = |e: EngineError| {
+        Ok(NamespaceResponse {
+            success: false,
+            error: Some(e.to_string()),
+            error_code: Some(e.code().to_string()),
+        })
+    };
+
+    let driver = match session_manager.get_driver(session).await {
+        Ok(d) => d,
+        Err(e) => return engine_failure(e),
+    };
+
+    let acknowledged = acknowledged_dangerous.unwrap_or(false);
+    match QueryGuard::for_session(&session_manager, &*driver, session, &policy, acknowledged).await {
+        Ok(guard) => {
+            if let Some(error) = guard.check_operation(true, true) {
+                return failure(error);
+            }
+        }
+        Err(e) => return engine_failure(e),
+    }
+
+    match driver.create_namespace(session, &namespace).await {
+        Ok(()) => Ok(NamespaceResponse {
+            success: true,
+            error: None,
+            error_code: None,
+        }),
+        Err(e) => engine_failure(e),
+    }
+}
+
+/// Drops a database or schema
+///
+/// Always treated as dangerous: it needs `acknowledged_dangerous` on every
+/// connection, and is refused where the policy blocks dangerous SQL.
+#[tauri::command]
+#[instrument(
+    skip(state),
+    fields(session_id = %session_id, database = %namespace.database, schema = ?namespace.schema)
+)]
+pub async fn drop_namespace(
+    state: State<'_, crate::SharedState>,
+    session_id: String,
+    namespace: Namespace,
+    cascade: Option<bool>,
+    acknowledged_dangerous: Option<bool>,
+) -> Result<NamespaceResponse, String> {
+    let (session_manager, policy) = {
+        let state = state.lock().await;
+        (Arc::clone(&state.session_manager), state.policy.clone())
+    };
+    let session = parse_session_id(&session_id)?;
+
+    let failure = |error: String| {
+        Ok(NamespaceResponse {
+            success: false,
+            error: Some(error),
+            error_code: None,
+        })
+    };
+    let engine_failure = |e: EngineError| {
+        Ok(NamespaceResponse {
+            success: false,
+            error: Some(e.to_string()),
+            error_code: Some(e.code().to_string()),
+        })
+    };
+
+    let driver = match session_manager.get_driver(session).await {
+        Ok(d) => d,
+        Err(e) => return engine_failure(e),
+    };
+
+    let acknowledged = acknowledged_dangerous.unwrap_or(false);
+    match QueryGuard::for_session(&session_manager, &*driver, session, &policy, acknowledged).await {
+        Ok(guard) => {
+            if let Some(error) = guard.check_operation(true, true) {
+                return failure(error);
+            }
+        }
+        Err(e) => return engine_failure(e),
+    }
+    if !acknowledged {
+        return failure(DANGEROUS_BLOCKED.to_string());
+    }
+
+    match driver
+        .drop_namespace(session, &namespace, cascade.unwrap_or(false))
+        .await
+    {
+        Ok(()) => {
+            session_manager.forget_column_types(session).await;
+            Ok(NamespaceResponse {
+                success: true,
+                error: None,
+                error_code: None,
+            })
+        }
+        Err(e) => engine_failure(e),
+    }
+}
+
+/// Response wrapper for table creation
+#[derive(Debug, Serialize)]
+pub struct CreateTableResponse {
+    pub success: bool,
+    pub error: Option<String>,
+    pub error_code: Option<String>,
+}
+
+/// Creates a table from column definitions
+///
+/// DDL always counts as a dangerous write: it is blocked on read-only
+/// sessions and goes through the dangerous-statement policy on production,
+/// staging and protected-host connections.
+#[tauri::command]
+#[instrument(
+    skip(state, schema),
+    fields(session_id = %session_id, database = %namespace.database, table = %schema.table_name)
+)]
+pub async fn create_table(
+    state: State<'_, crate::SharedState>,
+    session_id: String,
+    namespace: Namespace,
+    schema: NewTableSchema,
+    acknowledged_dangerous: Option<bool>,
+) -> Result<CreateTableResponse, String> {
+    let (session_manager, policy) = {
+        let state = state.lock().await;
+        (Arc::clone(&state.session_manager), state.policy.clone())
+    };
+    let session = parse_session_id(&session_id)?;
+
+    let failure = |error: String| {
+        Ok(CreateTableResponse {
+            success: false,
+            error: Some(error),
+            error_code: None,
+        })
+    };
+    let engine_failure = |e: EngineError| {
+        Ok(CreateTableResponse {
+            success: false,
+            error: Some(e.to_string()),
+            error_code: Some(e.code().to_string()),
+        })
+    };
+
+    if let Err(error) = schema.validate() {
+        return failure(error);
+    }
+
+    let driver = match session_manager.get_driver(session).await {
+        Ok(d) => d,
+        Err(e) => return engine_failure(e),
+    };
+
+    // DDL always counts as a dangerous write
+    let acknowledged = acknowledged_dangerous.unwrap_or(false);
+    match QueryGuard::for_session(&session_manager, &*driver, session, &policy, acknowledged).await {
+        Ok(guard) => {
+            if let Some(error) = guard.check_operation(true, true) {
+                return failure(error);
+            }
+        }
+        Err(e) => return engine_failure(e),
+    }
+
+    match driver.create_table(session, &namespace, &schema).await {
+        Ok(()) => Ok(CreateTableResponse {
+            success: true,
+            error: None,
+            error_code: None,
+        }),
+        Err(e) => engine_failure(e),
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct ColumnResponse {
+    pub success: bool,
+    pub error: Option<String>,
+    pub error_code: Option<String>,
+}
+
+/// Adds a column to an existing table
+///
+/// Goes through the same guards as `create_table`.
+#[tauri::command]
+pub async fn add_table_column(
+    state: State<'_, crate::SharedState>,
+    session_id: String,
+    namespace: Namespace,
+    table: String,
+    column: NewColumnDef,
+    acknowledged_dangerous: Option<bool>,
+) -> Result<ColumnResponse, String> {
+    let (session_manager, policy) = {
+        let state = state.lock().await;
+        (Arc::clone(&state.session_manager), state.policy.clone())
+    };
+    let session = parse_session_id(&session_id)?;
+
+    let failure = |error: String| {
+        Ok(ColumnResponse {
+            success: false,
+            error: Some(error),
+            error_code: None,
+        })
+    };
+    let engine_failure = |e: EngineError| {
+        Ok(ColumnResponse {
+            success: false,
+            error: Some(e.to_string()),
+            error_code: Some(e.code().to_string()),
+        })
+    };
+
+    if let Err(error) = column.validate() {
+        return failure(error);
+    }
+
+    let driver = match session_manager.get_driver(session).await {
+        Ok(d) => d,
+        Err(e) => return engine_failure(e),
+    };
+
+    // DDL always counts as a dangerous write
+    let acknowledged = acknowledged_dangerous.unwrap_or(false);
+    match QueryGuard::for_session(&session_manager, &*driver, session, &policy, acknowledged).await {
+        Ok(guard) => {
+            if let Some(error) = guard.check_operation(true, true) {
+                return failure(error);
+            }
+        }
+        Err(e) => return engine_failure(e),
+    }
+
+    match driver
+        .add_column(session, &namespace, &table, &column)
+        .await
+    {
+        Ok(()) => {
+            session_manager.forget_column_types(session).await;
+            Ok(ColumnResponse {
+                success: true,
+                error: None,
+                error_code: None,
+            })
+        }
+        Err(e) => engine_failure(e),
+    }
+}
+
+/// Drops a column from a table
+///
+/// Goes through the same guards as `create_table`.
+#[tauri::command]
+pub async fn drop_table_column(
+    state: State<'_, crate::SharedState>,
+    session_id: String,
+    namespace: Namespace,
+    table: String,
+    column_name: String,
+    acknowledged_dangerous: Option<bool>,
+) -> Result<ColumnResponse, String> {
+    let (session_manager, policy) = {
+        let state = state.lock().await;
+        (Arc::clone(&state.session_manager), state.policy.clone())
+    };
+    let session = parse_session_id(&session_id)?;
+
+    let failure = |error: String| {
+        Ok(ColumnResponse {
+            success: false,
+            error: Some(error),
+            error_code: None,
+        })
+    };
+    let engine_failure = |e: EngineError| {
+        Ok(ColumnResponse {
+            success: false,
+            error: Some(e.to_string()),
+            error_code: Some(e.code().to_string()),
+        })
     };
-    let session = parse_session_id(&session_id)?;
+
+    if column_name.trim().is_empty() {
+        return failure("Column name is required".to_string());
+    }
 
     let driver = match session_manager.get_driver(session).await {
         Ok(d) => d,
-        Err(e) => {
-            return Ok(QueryResponse {
-                success: false,
-                result: None,
-                error: Some(e.to_string()),
-                query_id: None,
-            });
-        }
+        Err(e) => return engine_failure(e),
     };
-    tracing::Span::current().record("driver", &field::display(driver.driver_id()));
 
-    let query_id = if let Some(raw) = query_id {
-        let parsed = Uuid::parse_str(&raw).map_err(|e| format!("Invalid query ID: {}", e))?;
-        QueryId(parsed)
-    } else {
-        match query_manager.last_for_session(session).await {
-            Some(qid) => qid,
-            None => {
-                return Ok(QueryResponse {
-                    success: false,
-                    result: None,
-                    error: Some("No active query found".to_string()),
-                    query_id: None,
-                });
+    // DDL always counts as a dangerous write
+    let acknowledged = acknowledged_dangerous.unwrap_or(false);
+    match QueryGuard::for_session(&session_manager, &*driver, session, &policy, acknowledged).await {
+        Ok(guard) => {
+            if let Some(error) = guard.check_operation(true, true) {
+                return failure(error);
             }
         }
-    };
-    let query_id_str = query_id.0.to_string();
+        Err(e) => return engine_failure(e),
+    }
 
-    match driver.cancel(session, Some(query_id)).await {
-        Ok(()) => Ok(QueryResponse {
-            success: true,
-            result: None,
-            error: None,
-            query_id: Some(query_id_str),
-        }),
-        Err(e) => Ok(QueryResponse {
-            success: false,
-            result: None,
-            error: Some(e.to_string()),
-            query_id: Some(query_id_str),
-        }),
+    match driver
+        .drop_column(session, &namespace, &table, &column_name)
+        .await
+    {
+        Ok(()) => {
+            session_manager.forget_column_types(session).await;
+            Ok(ColumnResponse {
+                success: true,
+                error: None,
+                error_code: None,
+            })
+        }
+        Err(e) => engine_failure(e),
     }
 }
 
-/// Lists all namespaces (databases/schemas) for a session
+/// Renames a column of a table
+///
+/// A rename breaks queries and views still using the old name, so like
+/// other DDL it is blocked on read-only sessions and always counts as
+/// dangerous on production, staging and protected-host connections.
 #[tauri::command]
-pub async fn list_namespaces(
+#[instrument(
+    skip(state),
+    fields(session_id = %session_id, database = %namespace.database, table = %table)
+)]
+pub async fn rename_column(
     state: State<'_, crate::SharedState>,
     session_id: String,
-) -> Result<NamespacesResponse, String> {
-    let session_manager = {
+    namespace: Namespace,
+    table: String,
+    old_name: String,
+    new_name: String,
+    acknowledged_dangerous: Option<bool>,
+) -> Result<ColumnResponse, String> {
+    let (session_manager, policy) = {
         let state = state.lock().await;
-        Arc::clone(&state.session_manager)
+        (Arc::clone(&state.session_manager), state.policy.clone())
     };
     let session = parse_session_id(&session_id)?;
 
+    let failure = |error: String| {
+        Ok(ColumnResponse {
+            success: false,
+            error: Some(error),
+            error_code: None,
+        })
+    };
+    let engine_failure = |e: EngineError| {
+        Ok(ColumnResponse {
+            success: false,
+            error: Some(e.to_string()),
+            error_code: Some(e.code().to_string()),
+        })
+    };
+
+    if old_name.trim().is_empty() || new_name.trim().is_empty() {
+        return failure("Column name is required".to_string());
+    }
+
     let driver = match session_manager.get_driver(session).await {
         Ok(d) => d,
-        Err(e) => {
-            return Ok(NamespacesResponse {
-                success: false,
-                namespaces: None,
-                error: Some(e.to_string()),
-            });
-        }
+        Err(e) => return engine_failure(e),
     };
 
-    match driver.list_namespaces(session).await {
-        Ok(namespaces) => Ok(NamespacesResponse {
-            success: true,
-            namespaces: Some(namespaces),
-            error: None,
-        }),
-        Err(e) => Ok(NamespacesResponse {
-            success: false,
-            namespaces: None,
-            error: Some(e.to_string()),
-        }),
+    // DDL always counts as a dangerous write
+    let acknowledged = acknowledged_dangerous.unwrap_or(false);
+    match QueryGuard::for_session(&session_manager, &*driver, session, &policy, acknowledged).await {
+        Ok(guard) => {
+            if let Some(error) = guard.check_operation(true, true) {
+                return failure(error);
+            }
+        }
+        Err(e) => return engine_failure(e),
+    }
+
+    match driver
+        .rename_column(session, &namespace, &table, &old_name, &new_name)
+        .await
+    {
+        Ok(()) => {
+            session_manager.forget_column_types(session).await;
+            Ok(ColumnResponse {
+                success: true,
+                error: None,
+                error_code: None,
+            })
+        }
+        Err(e) => engine_failure(e),
     }
 }
 
-/// Lists all collections (tables/views) in a namespace
+/// Response for `preview_table`
+#[derive(Debug, Serialize)]
+pub struct PreviewResponse {
+    pub success: bool,
+    pub result: Option<QueryResult>,
+    pub error: Option<String>,
+    pub error_code: Option<String>,
+    /// Limit actually applied, after clamping to `preview_max_rows`
+    pub limit: u32,
+    /// The requested limit was above `preview_max_rows`
+    pub limit_capped: bool,
+}
+
+/// Gets a preview of table data (first N rows)
+///
+/// `limit` is clamped to the policy's `preview_max_rows`.
+/// `lock_rows` locks the previewed rows until the active transaction ends.
 #[tauri::command]
-pub async fn list_collections(
+pub async fn preview_table(
     state: State<'_, crate::SharedState>,
     session_id: String,
     namespace: Namespace,
-) -> Result<CollectionsResponse, String> {
-    let session_manager = {
+    table: String,
+    limit: u32,
+    lock_rows: Option<String>,
+) -> Result<PreviewResponse, String> {
+    let (session_manager, policy) = {
         let state = state.lock().await;
-        Arc::clone(&state.session_manager)
+        (Arc::clone(&state.session_manager), state.policy.clone())
     };
     let session = parse_session_id(&session_id)?;
+    let lock_rows = lock_rows.as_deref().map(parse_lock_mode).transpose()?;
+
+    let effective_limit = policy.preview_limit(limit);
+    let failure = |e: EngineError| PreviewResponse {
+        success: false,
+        result: None,
+        error: Some(e.to_string()),
+        error_code: Some(e.code().to_string()),
+        limit: effective_limit,
+        limit_capped: effective_limit < limit,
+    };
 
     let driver = match session_manager.get_driver(session).await {
         Ok(d) => d,
-        Err(e) => {
-            return Ok(CollectionsResponse {
-                success: false,
-                collections: None,
-                error: Some(e.to_string()),
-            });
-        }
+        Err(e) => return Ok(failure(e)),
     };
 
-    match driver.list_collections(session, &namespace).await {
-        Ok(collections) => Ok(CollectionsResponse {
+    match driver
+        .preview_table(session, &namespace, &table, effective_limit, lock_rows)
+        .await
+    {
+        Ok(result) => Ok(PreviewResponse {
             success: true,
-            collections: Some(collections),
+            result: Some(result),
             error: None,
+            error_code: None,
+            limit: effective_limit,
+            limit_capped: effective_limit < limit,
         }),
-        Err(e) => Ok(CollectionsResponse {
-            success: false,
-            collections: None,
-            error: Some(e.to_string()),
-        }),
+        Err(e) => Ok(failure(e)),
     }
 }
 
-/// Response wrapper for table schema
+/// Response for `fetch_full_value`
 #[derive(Debug, Serialize)]
-pub struct TableSchemaResponse {
+pub struct ValueResponse {
     pub success: bool,
-    pub schema: Option<TableSchema>,
+    pub value: Option<Value>,
     pub error: Option<String>,
+    pub error_code: Option<String>,
 }
 
-/// Gets the schema of a table/collection
+/// Loads one value of a row in full, after `max_value_bytes` truncated it
 #[tauri::command]
-pub async fn describe_table(
+#[instrument(
+    skip(state, primary_key),
+    fields(session_id = %session_id, table = %table, column = %column)
+)]
+pub async fn fetch_full_value(
     state: State<'_, crate::SharedState>,
     session_id: String,
     namespace: Namespace,
     table: String,
-) -> Result<TableSchemaResponse, String> {
+    primary_key: RowData,
+    column: String,
+) -> Result<ValueResponse, String> {
     let session_manager = {
         let state = state.lock().await;
         Arc::clone(&state.session_manager)
     };
     let session = parse_session_id(&session_id)?;
 
-    let driver = match session_manager.get_driver(session).await {
-        Ok(d) => d,
-        Err(e) => {
-            return Ok(TableSchemaResponse {
-                success: false,
-                schema: None,
-                error: Some(e.to_string()),
-            });
+    let result = match session_manager.get_driver(session).await {
+        Ok(driver) => {
+            driver
+                .fetch_value(session, &namespace, &table, &primary_key, &column)
+                .await
         }
+        Err(e) => Err(e),
     };
 
-    match driver.describe_table(session, &namespace, &table).await {
-        Ok(schema) => Ok(TableSchemaResponse {
+    match result {
+        Ok(value) => Ok(ValueResponse {
             success: true,
-            schema: Some(schema),
+            value: Some(value),
             error: None,
+            error_code: None,
         }),
-        Err(e) => Ok(TableSchemaResponse {
+        Err(e) => Ok(ValueResponse {
             success: false,
-            schema: None,
+            value: None,
             error: Some(e.to_string()),
+            error_code: Some(e.code().to_string()),
         }),
     }
 }
 
-/// Gets a preview of table data (first N rows)
+/// Response for `read_large_object`
+#[derive(Debug, Serialize)]
+pub struct LargeObjectResponse {
+    pub success: bool,
+    /// Content of the large object, base64-encoded
+    pub data: Option<String>,
+    pub error: Option<String>,
+    pub error_code: Option<String>,
+}
+
+/// Reads a PostgreSQL large object, referenced by the OID held in `lo` columns
 #[tauri::command]
-pub async fn preview_table(
+#[instrument(skip(state), fields(session_id = %session_id, oid = oid))]
+pub async fn read_large_object(
     state: State<'_, crate::SharedState>,
     session_id: String,
-    namespace: Namespace,
-    table: String,
-    limit: u32,
-) -> Result<QueryResponse, String> {
+    oid: u32,
+) -> Result<LargeObjectResponse, String> {
     let session_manager = {
         let state = state.lock().await;
         Arc::clone(&state.session_manager)
     };
     let session = parse_session_id(&session_id)?;
 
-    let driver = match session_manager.get_driver(session).await {
-        Ok(d) => d,
-        Err(e) => {
-            return Ok(QueryResponse {
-                success: false,
-                result: None,
-                error: Some(e.to_string()),
-                query_id: None,
-            });
-        }
+    let result = match session_manager.get_driver(session).await {
+        Ok(driver) => driver.read_large_object(session, oid).await,
+        Err(e) => Err(e),
     };
 
-    match driver.preview_table(session, &namespace, &table, limit).await {
-        Ok(result) => Ok(QueryResponse {
+    match result {
+        Ok(data) => Ok(LargeObjectResponse {
             success: true,
-            result: Some(result),
+            data: Some(BASE64.encode(data)),
             error: None,
-            query_id: None,
+            error_code: None,
         }),
-        Err(e) => Ok(QueryResponse {
+        Err(e) => Ok(LargeObjectResponse {
             success: false,
-            result: None,
+            data: None,
             error: Some(e.to_string()),
-            query_id: None,
+            error_code: Some(e.code().to_string()),
         }),
     }
 }
@@ -540,6 +2868,7 @@ pub async fn preview_table(
 pub struct TransactionResponse {
     pub success: bool,
     pub error: Option<String>,
+    pub error_code: Option<String>,
 }
 
 /// Response for transaction support check
@@ -548,6 +2877,15 @@ pub struct TransactionSupportResponse {
     pub supported: bool,
 }
 
+/// Response for transaction status check
+#[derive(Debug, Serialize)]
+pub struct TransactionStatusResponse {
+    pub success: bool,
+    pub status: Option<TransactionStatus>,
+    pub error: Option<String>,
+    pub error_code: Option<String>,
+}
+
 /// Begins a transaction on the given session
 ///
 /// Acquires a dedicated connection from the pool and executes BEGIN.
@@ -570,6 +2908,7 @@ pub async fn begin_transaction(
             return Ok(TransactionResponse {
                 success: false,
                 error: Some(e.to_string()),
+                error_code: Some(e.code().to_string()),
             });
         }
     };
@@ -578,6 +2917,7 @@ pub async fn begin_transaction(
         return Ok(TransactionResponse {
             success: false,
             error: Some(TRANSACTIONS_NOT_SUPPORTED.to_string()),
+            error_code: Some(EngineErrorCode::NotSupported.to_string()),
         });
     }
 
@@ -585,10 +2925,12 @@ pub async fn begin_transaction(
         Ok(()) => Ok(TransactionResponse {
             success: true,
             error: None,
+            error_code: None,
         }),
         Err(e) => Ok(TransactionResponse {
             success: false,
             error: Some(e.to_string()),
+            error_code: Some(e.code().to_string()),
         }),
     }
 }
@@ -613,6 +2955,7 @@ pub async fn commit_transaction(
             return Ok(TransactionResponse {
                 success: false,
                 error: Some(e.to_string()),
+                error_code: Some(e.code().to_string()),
             });
         }
     };
@@ -621,6 +2964,7 @@ pub async fn commit_transaction(
         return Ok(TransactionResponse {
             success: false,
             error: Some(TRANSACTIONS_NOT_SUPPORTED.to_string()),
+            error_code: Some(EngineErrorCode::NotSupported.to_string()),
         });
     }
 
@@ -628,10 +2972,12 @@ pub async fn commit_transaction(
         Ok(()) => Ok(TransactionResponse {
             success: true,
             error: None,
+            error_code: None,
         }),
         Err(e) => Ok(TransactionResponse {
             success: false,
             error: Some(e.to_string()),
+            error_code: Some(e.code().to_string()),
         }),
     }
 }
@@ -656,6 +3002,7 @@ pub async fn rollback_transaction(
             return Ok(TransactionResponse {
                 success: false,
                 error: Some(e.to_string()),
+                error_code: Some(e.code().to_string()),
             });
         }
     };
@@ -664,6 +3011,7 @@ pub async fn rollback_transaction(
         return Ok(TransactionResponse {
             success: false,
             error: Some(TRANSACTIONS_NOT_SUPPORTED.to_string()),
+            error_code: Some(EngineErrorCode::NotSupported.to_string()),
         });
     }
 
@@ -671,10 +3019,64 @@ pub async fn rollback_transaction(
         Ok(()) => Ok(TransactionResponse {
             success: true,
             error: None,
+            error_code: None,
         }),
         Err(e) => Ok(TransactionResponse {
             success: false,
             error: Some(e.to_string()),
+            error_code: Some(e.code().to_string()),
+        }),
+    }
+}
+
+/// Reports whether a transaction is active on the given session
+///
+/// Used by the UI to detect transactions left open (e.g. after a crash)
+/// and offer to roll them back.
+#[tauri::command]
+pub async fn transaction_status(
+    state: State<'_, crate::SharedState>,
+    session_id: String,
+) -> Result<TransactionStatusResponse, String> {
+    let session_manager = {
+        let state = state.lock().await;
+        Arc::clone(&state.session_manager)
+    };
+    let session = parse_session_id(&session_id)?;
+
+    let driver = match session_manager.get_driver(session).await {
+        Ok(d) => d,
+        Err(e) => {
+            return Ok(TransactionStatusResponse {
+                success: false,
+                status: None,
+                error: Some(e.to_string()),
+                error_code: Some(e.code().to_string()),
+            });
+        }
+    };
+
+    if !driver.capabilities().transactions {
+        return Ok(TransactionStatusResponse {
+            success: false,
+            status: None,
+            error: Some(TRANSACTIONS_NOT_SUPPORTED.to_string()),
+            error_code: Some(EngineErrorCode::NotSupported.to_string()),
+        });
+    }
+
+    match driver.transaction_status(session).await {
+        Ok(status) => Ok(TransactionStatusResponse {
+            success: true,
+            status: Some(status),
+            error: None,
+            error_code: None,
+        }),
+        Err(e) => Ok(TransactionStatusResponse {
+            success: false,
+            status: None,
+            error: Some(e.to_string()),
+            error_code: Some(e.code().to_string()),
         }),
     }
 }
@@ -704,3 +3106,235 @@ pub async fn supports_transactions(
         supported: driver.capabilities().transactions,
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn local_sessions_skip_dangerous_query_confirmation() {
+        let mut policy = SafetyPolicy::defaults();
+        policy.prod_require_confirmation = true;
+        policy.prod_block_dangerous_sql = false;
+        policy.protected_host_patterns = vec!["localhost".to_string()];
+
+        let guard_for = |environment: &str| QueryGuard {
+            policy: &policy,
+            driver_id: "postgres",
+            read_only: false,
+            is_production: false,
+            is_staging: false,
+            is_protected_host: policy.is_protected_connection(environment, "localhost"),
+            acknowledged: false,
+        };
+
+        for query in ["DELETE FROM users", "DROP TABLE users", "UPDATE users SET a = 1"] {
+            assert_eq!(guard_for("local").check(query), None);
+            assert_eq!(
+                guard_for("development").check(query).as_deref(),
+                Some(DANGEROUS_BLOCKED)
+            );
+        }
+    }
+
+    #[test]
+    fn staging_sessions_follow_the_staging_policy() {
+        let mut policy = SafetyPolicy::defaults();
+        policy.protected_host_patterns.clear();
+        policy.staging_require_confirmation = false;
+        policy.staging_block_dangerous_sql = false;
+        let check = |policy: &SafetyPolicy, query: &str| {
+            QueryGuard {
+                policy,
+                driver_id: "postgres",
+                read_only: false,
+                is_production: false,
+                is_staging: true,
+                is_protected_host: false,
+                acknowledged: false,
+            }
+            .check(query)
+        };
+
+        assert_eq!(check(&policy, "DROP TABLE users"), None);
+
+        policy.staging_require_confirmation = true;
+        assert_eq!(check(&policy, "DROP TABLE users").as_deref(), Some(DANGEROUS_BLOCKED));
+        assert_eq!(check(&policy, "UPDATE users SET a = 1 WHERE id = 1"), None);
+
+        policy.staging_block_dangerous_sql = true;
+        assert_eq!(
+            check(&policy, "DROP TABLE users").as_deref(),
+            Some(DANGEROUS_BLOCKED_POLICY)
+        );
+    }
+
+    #[test]
+    fn session_termination_is_guarded_as_a_dangerous_write() {
+        let mut policy = SafetyPolicy::defaults();
+        policy.prod_require_confirmation = true;
+        policy.prod_block_dangerous_sql = false;
+        policy.staging_require_confirmation = true;
+        policy.staging_block_dangerous_sql = false;
+        let guard = |read_only, is_staging, is_protected_host| QueryGuard {
+            policy: &policy,
+            driver_id: "mysql",
+            read_only,
+            is_production: false,
+            is_staging,
+            is_protected_host,
+            acknowledged: false,
+        };
+
+        assert_eq!(guard(false, false, false).check_operation(true, true), None);
+        assert_eq!(
+            guard(true, false, false).check_operation(true, true).as_deref(),
+            Some(READ_ONLY_BLOCKED)
+        );
+        assert_eq!(
+            guard(false, true, false).check_operation(true, true).as_deref(),
+            Some(DANGEROUS_BLOCKED)
+        );
+        assert_eq!(
+            guard(false, false, true).check_operation(true, true).as_deref(),
+            Some(DANGEROUS_BLOCKED)
+        );
+    }
+
+    #[test]
+    fn caps_sql_queries_one_row_past_the_limit() {
+        let mut policy = SafetyPolicy::defaults();
+        policy.max_rows_without_limit = Some(2);
+        let guard_for = |driver_id| QueryGuard {
+            policy: &policy,
+            driver_id,
+            read_only: false,
+            is_production: false,
+            is_staging: false,
+            is_protected_host: false,
+            acknowledged: false,
+        };
+
+        let (max_rows, capped) = guard_for("postgres")
+            .row_cap("SELECT * FROM users")
+            .expect("should cap");
+        assert_eq!(capped, "SELECT * FROM users\nLIMIT 3");
+        assert!(guard_for("mongodb").row_cap("{}").is_none());
+
+        let mut result = QueryResult::empty();
+        result.rows = (0..3)
+            .map(|i| crate::engine::types::Row {
+                values: vec![Value::Int(i)],
+            })
+            .collect();
+        apply_row_cap(&mut result, max_rows);
+        assert_eq!(result.rows.len(), 2);
+        assert!(result.truncated);
+    }
+
+    #[test]
+    fn rejects_results_estimated_above_the_memory_limit() {
+        let mut policy = SafetyPolicy::defaults();
+        policy.max_result_memory_mb = Some(1);
+
+        let mut result = QueryResult::empty();
+        result.rows = (0..2)
+            .map(|_| crate::engine::types::Row {
+                values: vec![Value::Text("x".repeat(300 * 1024))],
+            })
+            .collect();
+        assert_eq!(check_result_memory(&mut result, &policy), None);
+        assert!(result.estimated_memory_bytes.unwrap() > 600 * 1024);
+
+        result.rows.extend(result.rows.clone());
+        let error = check_result_memory(&mut result, &policy).expect("should reject");
+        assert!(error.starts_with("Result too large: estimated 2 MB (limit 1 MB)"));
+
+        policy.max_result_memory_mb = None;
+        assert_eq!(check_result_memory(&mut result, &policy), None);
+    }
+
+    #[test]
+    fn annotates_queries_per_driver() {
+        assert_eq!(
+            annotate_query("postgres", "SELECT 1", "tab=2"),
+            "/* tab=2 */ SELECT 1"
+        );
+        let annotated = annotate_query("mongodb", r#"{"database":"db","collection":"c"}"#, "tab=2");
+        let parsed: serde_json::Value = serde_json::from_str(&annotated).unwrap();
+        assert_eq!(parsed["comment"], "tab=2");
+        assert_eq!(parsed["collection"], "c");
+        assert_eq!(annotate_query("redis", "GET k", "tab=2"), "GET k");
+    }
+
+    #[test]
+    fn execute_options_round_trip() {
+        let options = ExecuteOptions {
+            acknowledged_dangerous: true,
+            query_id: Some("7f1f0c1e-5a4b-4b53-9d43-2b8f8f3c1a10".to_string()),
+            timeout_ms: Some(30_000),
+            lock_rows: None,
+            comment: None,
+            tab_id: Some("tab-1".to_string()),
+            include_plan_cost: false,
+            max_value_bytes: Some(1024),
+        };
+        let json = serde_json::to_string(&options).expect("serialize");
+        assert_eq!(
+            json,
+            r#"{"acknowledgedDangerous":true,"queryId":"7f1f0c1e-5a4b-4b53-9d43-2b8f8f3c1a10","timeoutMs":30000,"lockRows":null,"comment":null,"tabId":"tab-1","includePlanCost":false,"maxValueBytes":1024}"#
+        );
+        let parsed: ExecuteOptions = serde_json::from_str(&json).expect("deserialize");
+        assert_eq!(parsed, options);
+
+        // Missing fields fall back to defaults; snake_case names still work
+        let parsed: ExecuteOptions =
+            serde_json::from_str(r#"{"timeout_ms":500}"#).expect("deserialize");
+        assert_eq!(
+            parsed,
+            ExecuteOptions {
+                timeout_ms: Some(500),
+                ..Default::default()
+            }
+        );
+    }
+
+    #[test]
+    fn parses_row_lock_modes() {
+        assert_eq!(parse_lock_mode("update"), Ok(RowLockMode::Update));
+        assert_eq!(parse_lock_mode("FOR SHARE"), Ok(RowLockMode::Share));
+        assert_eq!(parse_lock_mode("no-key-update"), Ok(RowLockMode::NoKeyUpdate));
+        assert_eq!(parse_lock_mode("key_share"), Ok(RowLockMode::KeyShare));
+        assert!(parse_lock_mode("exclusive").is_err());
+    }
+
+    #[test]
+    fn read_only_sessions_only_call_functions() {
+        let policy = SafetyPolicy::defaults();
+        let guard = QueryGuard {
+            policy: &policy,
+            driver_id: "postgres",
+            read_only: true,
+            is_production: false,
+            is_staging: false,
+            is_protected_host: false,
+            acknowledged: false,
+        };
+
+        assert_eq!(guard.check(routine_call_statement(RoutineType::Function)), None);
+        assert_eq!(
+            guard.check(routine_call_statement(RoutineType::Procedure)).as_deref(),
+            Some(READ_ONLY_BLOCKED)
+        );
+    }
+
+    #[test]
+    fn validates_notification_channels() {
+        assert!(is_valid_channel_name("orders_changed"));
+        assert!(is_valid_channel_name("_jobs2"));
+        assert!(!is_valid_channel_name(""));
+        assert!(!is_valid_channel_name("2jobs"));
+        assert!(!is_valid_channel_name("jobs; DROP TABLE t"));
+        assert!(!is_valid_channel_name("jobs-queue"));
+    }
+}
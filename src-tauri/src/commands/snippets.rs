@@ -0,0 +1,204 @@
+//! Snippet Tauri Commands
+//!
+//! CRUD for saved query snippets. Without a `project_id` the commands work on
+//! the personal snippets; with one, on the snippets shared within that
+//! project, which live in the vault and need it unlocked. Snippets are
+//! executed through the regular `execute_query` command.
+
+use serde::Serialize;
+use std::sync::Arc;
+use tauri::State;
+use uuid::Uuid;
+
+use crate::engine::error::{EngineError, EngineResult};
+use crate::engine::snippets::{Snippet, SnippetStore};
+use crate::engine::types::SessionId;
+use crate::vault::snippets::SnippetStorage;
+
+/// Response for save/get/update/delete operations
+#[derive(Debug, Serialize)]
+pub struct SnippetResponse {
+    pub success: bool,
+    pub snippet: Option<Snippet>,
+    pub error: Option<String>,
+    pub error_code: Option<String>,
+}
+
+/// Response for list operations
+#[derive(Debug, Serialize)]
+pub struct SnippetListResponse {
+    pub success: bool,
+    pub snippets: Vec<Snippet>,
+    pub error: Option<String>,
+    pub error_code: Option<String>,
+}
+
+fn snippet_response(result: EngineResult<Snippet>) -> SnippetResponse {
+    match result {
+        Ok(snippet) => SnippetResponse {
+            success: true,
+            snippet: Some(snippet),
+            error: None,
+            error_code: None,
+        },
+        Err(e) => SnippetResponse {
+            success: false,
+            snippet: None,
+            error: Some(e.to_string()),
+            error_code: Some(e.code().to_string()),
+        },
+    }
+}
+
+/// Opens the project's snippets, or the personal ones without a project
+async fn open_store(
+    state: &State<'_, crate::SharedState>,
+    project_id: Option<&str>,
+) -> EngineResult<SnippetStore> {
+    match project_id {
+        Some(project_id) => {
+            if state.lock().await.vault_lock.is_locked() {
+                return Err(EngineError::permission_denied("Vault is locked"));
+            }
+            let storage = SnippetStorage::new(project_id);
+            Ok(SnippetStore::with_backend(Box::new(storage)))
+        }
+        None => Ok(SnippetStore::new()),
+    }
+}
+
+/// Creates or updates a snippet (a new ID is generated when empty)
+#[tauri::command]
+pub async fn save_snippet(
+    state: State<'_, crate::SharedState>,
+    snippet: Snippet,
+    project_id: Option<String>,
+) -> Result<SnippetResponse, String> {
+    let result = open_store(&state, project_id.as_deref())
+        .await
+        .and_then(|store| store.save(snippet));
+    Ok(snippet_response(result))
+}
+
+/// Lists snippets sorted by name
+///
+/// Restricted to `driver_id_filter` when given, otherwise to the session's
+/// driver when a session is given.
+#[tauri::command]
+pub async fn list_snippets(
+    state: State<'_, crate::SharedState>,
+    project_id: Option<String>,
+    session_id: Option<String>,
+    driver_id_filter: Option<String>,
+) -> Result<SnippetListResponse, String> {
+    let driver_id = match (driver_id_filter, session_id) {
+        (Some(driver_id), _) => Some(driver_id),
+        (None, Some(session_id)) => {
+            let session_manager = {
+                let state = state.lock().await;
+                Arc::clone(&state.session_manager)
+            };
+            let uuid = Uuid::parse_str(&session_id)
+                .map_err(|e| format!("Invalid session ID: {}", e))?;
+
+            match session_manager.get_driver(SessionId(uuid)).await {
+                Ok(driver) => Some(driver.driver_id().to_string()),
+                Err(e) => {
+                    return Ok(SnippetListResponse {
+                        success: false,
+                        snippets: Vec::new(),
+                        error: Some(e.to_string()),
+                        error_code: Some(e.code().to_string()),
+                    });
+                }
+            }
+        }
+        (None, None) => None,
+    };
+
+    let result = open_store(&state, project_id.as_deref())
+        .await
+        .and_then(|store| store.list(driver_id.as_deref()));
+
+    match result {
+        Ok(snippets) => Ok(SnippetListResponse {
+            success: true,
+            snippets,
+            error: None,
+            error_code: None,
+        }),
+        Err(e) => Ok(SnippetListResponse {
+            success: false,
+            snippets: Vec::new(),
+            error: Some(e.to_string()),
+            error_code: Some(e.code().to_string()),
+        }),
+    }
+}
+
+/// Gets a snippet by ID
+#[tauri::command]
+pub async fn get_snippet(
+    state: State<'_, crate::SharedState>,
+    id: String,
+    project_id: Option<String>,
+) -> Result<SnippetResponse, String> {
+    let result = open_store(&state, project_id.as_deref())
+        .await
+        .and_then(|store| store.get(&id));
+    Ok(snippet_response(result))
+}
+
+/// Replaces an existing snippet
+#[tauri::command]
+pub async fn update_snippet(
+    state: State<'_, crate::SharedState>,
+    snippet: Snippet,
+    project_id: Option<String>,
+) -> Result<SnippetResponse, String> {
+    let result = open_store(&state, project_id.as_deref())
+        .await
+        .and_then(|store| store.update(snippet));
+    Ok(snippet_response(result))
+}
+
+/// Deletes a snippet by ID
+#[tauri::command]
+pub async fn delete_snippet(
+    state: State<'_, crate::SharedState>,
+    id: String,
+    project_id: Option<String>,
+) -> Result<SnippetResponse, String> {
+    let result = open_store(&state, project_id.as_deref())
+        .await
+        .and_then(|store| store.delete(&id));
+
+    match result {
+        Ok(true) => Ok(SnippetResponse {
+            success: true,
+            snippet: None,
+            error: None,
+            error_code: None,
+        }),
+        Ok(false) => {
+            let e = EngineError::internal(format!("Snippet not found: {}", id));
+            Ok(snippet_response(Err(e)))
+        }
+        Err(e) => Ok(snippet_response(Err(e))),
+    }
+}
+
+/// Returns a snippet's query with `{{table_name}}` replaced
+#[tauri::command]
+pub async fn render_snippet(
+    state: State<'_, crate::SharedState>,
+    id: String,
+    table_name: String,
+    project_id: Option<String>,
+) -> Result<String, String> {
+    open_store(&state, project_id.as_deref())
+        .await
+        .and_then(|store| store.get(&id))
+        .map(|snippet| snippet.render(&table_name))
+        .map_err(|e| e.to_string())
+}
@@ -0,0 +1,221 @@
+//! SSH known hosts commands.
+//!
+//! Manage the app-owned `known_hosts` file used by SSH tunnels, so hosts can
+//! be trusted ahead of time under `SshHostKeyPolicy::Strict`.
+
+use std::fs;
+use std::path::Path;
+
+use base64::engine::general_purpose::{STANDARD, STANDARD_NO_PAD};
+use base64::Engine;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+
+use crate::engine::ssh_tunnel::default_known_hosts_path;
+
+/// Key types accepted by `add_known_host`
+const SUPPORTED_KEY_TYPES: [&str; 7] = [
+    "ssh-ed25519",
+    "ssh-rsa",
+    "ecdsa-sha2-nistp256",
+    "ecdsa-sha2-nistp384",
+    "ecdsa-sha2-nistp521",
+    "sk-ssh-ed25519@openssh.com",
+    "sk-ecdsa-sha2-nistp256@openssh.com",
+];
+
+#[derive(Debug, Serialize, PartialEq)]
+pub struct KnownHostEntry {
+    /// Host pattern(s) as written in the file (comma-separated, possibly hashed)
+    pub hostname: String,
+    pub key_type: String,
+    /// OpenSSH-style `SHA256:` fingerprint of the key
+    pub key_fingerprint: String,
+}
+
+/// Lists the entries of the app-owned known_hosts file
+#[tauri::command]
+pub async fn list_known_hosts() -> Result<Vec<KnownHostEntry>, String> {
+    let content = read_known_hosts(&default_known_hosts_path())?;
+    Ok(parse_known_hosts(&content))
+}
+
+/// Appends a host key after validating it
+#[tauri::command]
+pub async fn add_known_host(
+    hostname: String,
+    key_type: String,
+    public_key: String,
+) -> Result<(), String> {
+    let line = known_host_line(&hostname, &key_type, &public_key)?;
+
+    let path = default_known_hosts_path();
+    let mut content = read_known_hosts(&path)?;
+    if !content.is_empty() && !content.ends_with('\n') {
+        content.push('\n');
+    }
+    content.push_str(&line);
+    content.push('\n');
+
+    write_known_hosts(&path, &content)
+}
+
+/// Removes `hostname` from every entry with the given key type
+#[tauri::command]
+pub async fn remove_known_host(hostname: String, key_type: String) -> Result<(), String> {
+    let path = default_known_hosts_path();
+    let content = read_known_hosts(&path)?;
+    write_known_hosts(&path, &remove_host(&content, &hostname, &key_type))
+}
+
+/// Empties the known_hosts file
+#[tauri::command]
+pub async fn clear_known_hosts() -> Result<(), String> {
+    let path = default_known_hosts_path();
+    if !Path::new(&path).exists() {
+        return Ok(());
+    }
+    write_known_hosts(&path, "")
+}
+
+fn read_known_hosts(path: &str) -> Result<String, String> {
+    match fs::read_to_string(path) {
+        Ok(content) => Ok(content),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(String::new()),
+        Err(e) => Err(format!("Failed to read known_hosts: {}", e)),
+    }
+}
+
+fn write_known_hosts(path: &str, content: &str) -> Result<(), String> {
+    if let Some(parent) = Path::new(path).parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create SSH config directory: {}", e))?;
+    }
+    fs::write(path, content).map_err(|e| format!("Failed to write known_hosts: {}", e))
+}
+
+/// Splits a known_hosts line into (hosts, key type, base64 key), skipping
+/// comments, blank lines and the optional `@cert-authority`/`@revoked` marker
+fn split_line(line: &str) -> Option<(&str, &str, &str)> {
+    let line = line.trim();
+    if line.is_empty() || line.starts_with('#') {
+        return None;
+    }
+
+    let mut fields = line.split_whitespace();
+    let mut hosts = fields.next()?;
+    if hosts.starts_with('@') {
+        hosts = fields.next()?;
+    }
+    Some((hosts, fields.next()?, fields.next()?))
+}
+
+fn parse_known_hosts(content: &str) -> Vec<KnownHostEntry> {
+    content
+        .lines()
+        .filter_map(split_line)
+        .map(|(hosts, key_type, key)| KnownHostEntry {
+            hostname: hosts.to_string(),
+            key_type: key_type.to_string(),
+            key_fingerprint: fingerprint(key).unwrap_or_else(|| "invalid key".to_string()),
+        })
+        .collect()
+}
+
+fn fingerprint(key: &str) -> Option<String> {
+    let blob = STANDARD.decode(key).ok()?;
+    Some(format!(
+        "SHA256:{}",
+        STANDARD_NO_PAD.encode(Sha256::digest(&blob))
+    ))
+}
+
+/// Validates the input and formats it as a known_hosts line
+fn known_host_line(hostname: &str, key_type: &str, public_key: &str) -> Result<String, String> {
+    let hostname = hostname.trim();
+    if hostname.is_empty() || hostname.chars().any(|c| c.is_whitespace() || c == '#') {
+        return Err("Invalid hostname".to_string());
+    }
+
+    let key_type = key_type.trim();
+    if !SUPPORTED_KEY_TYPES.contains(&key_type) {
+        return Err(format!("Unsupported key type: {}", key_type));
+    }
+
+    let public_key = public_key.trim();
+    let blob = STANDARD
+        .decode(public_key)
+        .map_err(|_| "Public key is not valid base64".to_string())?;
+
+    // The key blob starts with its own type name as an SSH string
+    let embedded_type = blob
+        .get(..4)
+        .map(|len| u32::from_be_bytes([len[0], len[1], len[2], len[3]]) as usize)
+        .and_then(|len| blob.get(4..4 + len));
+    if embedded_type != Some(key_type.as_bytes()) {
+        return Err(format!("Public key is not a {} key", key_type));
+    }
+
+    Ok(format!("{} {} {}", hostname, key_type, public_key))
+}
+
+/// Drops `hostname` from matching lines, removing lines left without hosts
+fn remove_host(content: &str, hostname: &str, key_type: &str) -> String {
+    let mut output = String::with_capacity(content.len());
+
+    for line in content.lines() {
+        let Some((hosts, line_key_type, _)) = split_line(line) else {
+            output.push_str(line);
+            output.push('\n');
+            continue;
+        };
+        if line_key_type != key_type || !hosts.split(',').any(|host| host == hostname) {
+            output.push_str(line);
+            output.push('\n');
+            continue;
+        }
+
+        let remaining: Vec<&str> = hosts.split(',').filter(|host| *host != hostname).collect();
+        if !remaining.is_empty() {
+            output.push_str(&line.replacen(hosts, &remaining.join(","), 1));
+            output.push('\n');
+        }
+    }
+
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ED25519_KEY: &str =
+        "AAAAC3NzaC1lZDI1NTE5AAAAIAECAwQFBgcICQoLDA0ODxAREhMUFRYXGBkaGxwdHh8g";
+
+    #[test]
+    fn validates_key_type_against_key_blob() {
+        let line = known_host_line("db.example.com", "ssh-ed25519", ED25519_KEY).unwrap();
+        assert_eq!(line, format!("db.example.com ssh-ed25519 {}", ED25519_KEY));
+
+        assert!(known_host_line("db.example.com", "ssh-rsa", ED25519_KEY).is_err());
+        assert!(known_host_line("db.example.com", "ssh-ed25519", "not base64!").is_err());
+        assert!(known_host_line("db example", "ssh-ed25519", ED25519_KEY).is_err());
+    }
+
+    #[test]
+    fn removes_host_and_keeps_other_entries() {
+        let content = format!(
+            "# comment\na.example.com,b.example.com ssh-ed25519 {key}\nc.example.com ssh-ed25519 {key}\na.example.com ssh-rsa AAAA\n",
+            key = ED25519_KEY
+        );
+
+        let updated = remove_host(&content, "a.example.com", "ssh-ed25519");
+        let entries = parse_known_hosts(&updated);
+        assert!(updated.starts_with("# comment\n"));
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[0].hostname, "b.example.com");
+        assert!(entries[0].key_fingerprint.starts_with("SHA256:"));
+        assert_eq!(entries[2].hostname, "a.example.com");
+        assert_eq!(entries[2].key_type, "ssh-rsa");
+    }
+}
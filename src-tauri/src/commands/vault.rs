@@ -3,10 +3,21 @@
 //! Commands for managing saved connections and vault lock.
 
 use serde::{Deserialize, Serialize};
+use std::sync::Arc;
 use tauri::State;
 
-use crate::vault::credentials::{Environment, SavedConnection, SshTunnelInfo, StoredCredentials};
+use crate::commands::connection::probe_saved_connection;
+use crate::policy::Argon2Params;
+use crate::engine::error::EngineResult;
+use crate::engine::types::{
+    RetryPolicy, SslMode, DEFAULT_SSH_STARTUP_POLL_INTERVAL_MS, DEFAULT_SSH_STARTUP_TIMEOUT_MS,
+};
+use crate::vault::credentials::{
+    normalize_color, normalize_tags, ConnectionGroup, Environment, ProjectEntry, SavedConnection,
+    SshTunnelInfo, StoredCredentials,
+};
 use crate::vault::storage::VaultStorage;
+use crate::vault::StorageBackend;
 use crate::SharedState;
 
 /// Response for vault operations
@@ -14,6 +25,7 @@ use crate::SharedState;
 pub struct VaultResponse {
     pub success: bool,
     pub error: Option<String>,
+    pub error_code: Option<String>,
 }
 
 /// Response for checking vault status
@@ -21,6 +33,8 @@ pub struct VaultResponse {
 pub struct VaultStatusResponse {
     pub is_locked: bool,
     pub has_master_password: bool,
+    /// Where secrets are stored; `encrypted_file` when no keychain is available
+    pub storage_backend: StorageBackend,
 }
 
 /// Input for saving a connection
@@ -37,8 +51,37 @@ pub struct SaveConnectionInput {
     pub password: String,
     pub database: Option<String>,
     pub ssl: bool,
+    /// Takes precedence over `ssl` when set
+    #[serde(default)]
+    pub ssl_mode: Option<SslMode>,
+    #[serde(default)]
+    pub ssl_ca_path: Option<String>,
     pub project_id: String,
     pub ssh_tunnel: Option<SshTunnelInput>,
+    #[serde(default)]
+    pub display_alias: Option<String>,
+    #[serde(default)]
+    pub mongo_auth_source: Option<String>,
+    #[serde(default)]
+    pub keepalive_query_secs: Option<u64>,
+    #[serde(default)]
+    pub label: Option<String>,
+    #[serde(default)]
+    pub charset: Option<String>,
+    #[serde(default)]
+    pub retry_policy: Option<RetryPolicy>,
+    #[serde(default)]
+    pub statement_cache_capacity: Option<usize>,
+    #[serde(default)]
+    pub group_id: Option<String>,
+    /// Hex color such as "#3b82f6"
+    #[serde(default)]
+    pub color: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Used verbatim instead of the fields above; stored with the secrets
+    #[serde(default)]
+    pub raw_connection_string: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -58,6 +101,20 @@ pub struct SshTunnelInput {
     pub connect_timeout_secs: u32,
     pub keepalive_interval_secs: u32,
     pub keepalive_count_max: u32,
+    #[serde(default = "default_ssh_startup_timeout_ms")]
+    pub startup_timeout_ms: u64,
+    #[serde(default = "default_ssh_startup_poll_interval_ms")]
+    pub startup_poll_interval_ms: u64,
+    #[serde(default)]
+    pub auto_restart: bool,
+}
+
+fn default_ssh_startup_timeout_ms() -> u64 {
+    DEFAULT_SSH_STARTUP_TIMEOUT_MS
+}
+
+fn default_ssh_startup_poll_interval_ms() -> u64 {
+    DEFAULT_SSH_STARTUP_POLL_INTERVAL_MS
 }
 
 /// Checks the vault lock status
@@ -73,6 +130,7 @@ pub async fn get_vault_status(
     Ok(VaultStatusResponse {
         is_locked: state.vault_lock.is_locked(),
         has_master_password,
+        storage_backend: crate::vault::storage::active_backend(),
     })
 }
 
@@ -83,15 +141,61 @@ pub async fn setup_master_password(
     password: String,
 ) -> Result<VaultResponse, String> {
     let mut state = state.lock().await;
+    let params = state.policy.argon2_params.unwrap_or_default();
 
-    match state.vault_lock.setup_master_password(&password) {
+    match state.vault_lock.setup_master_password(&password, &params) {
         Ok(()) => Ok(VaultResponse {
             success: true,
             error: None,
+            error_code: None,
         }),
         Err(e) => Ok(VaultResponse {
             success: false,
             error: Some(e.to_string()),
+            error_code: Some(e.code().to_string()),
+        }),
+    }
+}
+
+/// Time taken to hash a password with the policy's Argon2 parameters
+#[derive(Debug, Serialize)]
+pub struct Argon2BenchmarkResponse {
+    pub success: bool,
+    pub params: Argon2Params,
+    pub duration_ms: Option<f64>,
+    pub error: Option<String>,
+    pub error_code: Option<String>,
+}
+
+/// Measures how long a master password hash takes with the current parameters
+#[tauri::command]
+pub async fn benchmark_argon2(
+    state: State<'_, SharedState>,
+) -> Result<Argon2BenchmarkResponse, String> {
+    let params = {
+        let state = state.lock().await;
+        state.policy.argon2_params.unwrap_or_default()
+    };
+
+    let benchmark =
+        tokio::task::spawn_blocking(move || crate::vault::VaultLock::benchmark(&params))
+            .await
+            .map_err(|e| e.to_string())?;
+
+    match benchmark {
+        Ok(duration) => Ok(Argon2BenchmarkResponse {
+            success: true,
+            params,
+            duration_ms: Some(duration.as_micros() as f64 / 1000.0),
+            error: None,
+            error_code: None,
+        }),
+        Err(e) => Ok(Argon2BenchmarkResponse {
+            success: false,
+            params,
+            duration_ms: None,
+            error: Some(e.to_string()),
+            error_code: Some(e.code().to_string()),
         }),
     }
 }
@@ -108,14 +212,17 @@ pub async fn unlock_vault(
         Ok(true) => Ok(VaultResponse {
             success: true,
             error: None,
+            error_code: None,
         }),
         Ok(false) => Ok(VaultResponse {
             success: false,
             error: Some("Invalid password".to_string()),
+            error_code: None,
         }),
         Err(e) => Ok(VaultResponse {
             success: false,
             error: Some(e.to_string()),
+            error_code: Some(e.code().to_string()),
         }),
     }
 }
@@ -129,6 +236,7 @@ pub async fn lock_vault(state: State<'_, SharedState>) -> Result<VaultResponse,
     Ok(VaultResponse {
         success: true,
         error: None,
+        error_code: None,
     })
 }
 
@@ -144,9 +252,34 @@ pub async fn save_connection(
         return Ok(VaultResponse {
             success: false,
             error: Some("Vault is locked".to_string()),
+            error_code: None,
+        });
+    }
+
+    let raw_connection_string = input
+        .raw_connection_string
+        .filter(|raw| !raw.trim().is_empty());
+    if raw_connection_string.is_some() && input.ssh_tunnel.is_some() {
+        return Ok(VaultResponse {
+            success: false,
+            error: Some(
+                "A raw connection string cannot be combined with an SSH tunnel".to_string(),
+            ),
+            error_code: None,
         });
     }
 
+    let color = match normalize_color(input.color.as_deref()) {
+        Ok(color) => color,
+        Err(error) => {
+            return Ok(VaultResponse {
+                success: false,
+                error: Some(error),
+                error_code: None,
+            });
+        }
+    };
+
     let storage = VaultStorage::new(&input.project_id);
 
     let ssh_tunnel = input.ssh_tunnel.as_ref().map(|ssh| SshTunnelInfo {
@@ -160,6 +293,9 @@ pub async fn save_connection(
         connect_timeout_secs: ssh.connect_timeout_secs,
         keepalive_interval_secs: ssh.keepalive_interval_secs,
         keepalive_count_max: ssh.keepalive_count_max,
+        startup_timeout_ms: ssh.startup_timeout_ms,
+        startup_poll_interval_ms: ssh.startup_poll_interval_ms,
+        auto_restart: ssh.auto_restart,
     });
 
     let connection = SavedConnection {
@@ -172,46 +308,84 @@ pub async fn save_connection(
         port: input.port,
         username: input.username,
         database: input.database,
-        ssl: input.ssl,
+        ssl: input
+            .ssl_mode
+            .map_or(input.ssl, |mode| mode != SslMode::Disabled),
+        ssl_mode: input.ssl_mode,
+        ssl_ca_path: input.ssl_ca_path,
         ssh_tunnel,
         project_id: input.project_id,
+        display_alias: input.display_alias,
+        mongo_auth_source: input.mongo_auth_source,
+        keepalive_query_secs: input.keepalive_query_secs,
+        label: input.label,
+        charset: input.charset,
+        retry_policy: input.retry_policy,
+        statement_cache_capacity: input.statement_cache_capacity,
+        group_id: input.group_id,
+        color,
+        tags: normalize_tags(input.tags),
+        group_name: None,
+        health: None,
     };
 
     let credentials = StoredCredentials {
         db_password: input.password,
         ssh_password: input.ssh_tunnel.as_ref().and_then(|s| s.password.clone()),
         ssh_key_passphrase: input.ssh_tunnel.as_ref().and_then(|s| s.key_passphrase.clone()),
+        raw_connection_string,
     };
 
     match storage.save_connection(&connection, &credentials) {
         Ok(()) => Ok(VaultResponse {
             success: true,
             error: None,
+            error_code: None,
         }),
         Err(e) => Ok(VaultResponse {
             success: false,
             error: Some(e.to_string()),
+            error_code: Some(e.code().to_string()),
         }),
     }
 }
 
 /// Lists all saved connections (metadata only, no passwords)
+///
+/// With `include_health`, every connection is probed in parallel and its
+/// `health` filled in; each probe gives up after two seconds.
 #[tauri::command]
 pub async fn list_saved_connections(
     state: State<'_, SharedState>,
     project_id: String,
+    include_health: Option<bool>,
 ) -> Result<Vec<SavedConnection>, String> {
-    let state = state.lock().await;
+    let session_manager = {
+        let state = state.lock().await;
 
-    if state.vault_lock.is_locked() {
-        return Err("Vault is locked".to_string());
-    }
+        if state.vault_lock.is_locked() {
+            return Err("Vault is locked".to_string());
+        }
+        Arc::clone(&state.session_manager)
+    };
 
     let storage = VaultStorage::new(&project_id);
 
-    storage
+    let mut connections = storage
         .list_connections_full()
-        .map_err(|e| e.to_string())
+        .map_err(|e| e.to_string())?;
+
+    if include_health.unwrap_or(false) {
+        let probes = connections.iter().map(|connection| {
+            probe_saved_connection(&session_manager, &project_id, &connection.id)
+        });
+        let healths = futures::future::join_all(probes).await;
+        for (connection, health) in connections.iter_mut().zip(healths) {
+            connection.health = Some(health);
+        }
+    }
+
+    Ok(connections)
 }
 
 /// Deletes a saved connection
@@ -227,6 +401,7 @@ pub async fn delete_saved_connection(
         return Ok(VaultResponse {
             success: false,
             error: Some("Vault is locked".to_string()),
+            error_code: None,
         });
     }
 
@@ -236,23 +411,245 @@ pub async fn delete_saved_connection(
         Ok(()) => Ok(VaultResponse {
             success: true,
             error: None,
+            error_code: None,
         }),
         Err(e) => Ok(VaultResponse {
             success: false,
             error: Some(e.to_string()),
+            error_code: Some(e.code().to_string()),
         }),
     }
 }
 
+/// Converts a vault operation result into a response
+fn vault_response(result: EngineResult<()>) -> VaultResponse {
+    match result {
+        Ok(()) => VaultResponse {
+            success: true,
+            error: None,
+            error_code: None,
+        },
+        Err(e) => VaultResponse {
+            success: false,
+            error: Some(e.to_string()),
+            error_code: Some(e.code().to_string()),
+        },
+    }
+}
+
+fn locked_response() -> VaultResponse {
+    VaultResponse {
+        success: false,
+        error: Some("Vault is locked".to_string()),
+        error_code: None,
+    }
+}
+
+/// Trims a group name, rejecting empty names
+fn normalize_group_name(name: &str) -> Result<String, String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err("Group name cannot be empty".to_string());
+    }
+    Ok(name.to_string())
+}
+
+/// Lists the connection groups of a project
+#[tauri::command]
+pub async fn list_connection_groups(
+    state: State<'_, SharedState>,
+    project_id: String,
+) -> Result<Vec<ConnectionGroup>, String> {
+    let state = state.lock().await;
+
+    if state.vault_lock.is_locked() {
+        return Err("Vault is locked".to_string());
+    }
+
+    VaultStorage::new(&project_id)
+        .list_groups()
+        .map_err(|e| e.to_string())
+}
+
+/// Creates a group for organizing saved connections
+#[tauri::command]
+pub async fn create_connection_group(
+    state: State<'_, SharedState>,
+    project_id: String,
+    group_id: String,
+    name: String,
+    color: Option<String>,
+) -> Result<VaultResponse, String> {
+    let state = state.lock().await;
+
+    if state.vault_lock.is_locked() {
+        return Ok(locked_response());
+    }
+
+    let name = match normalize_group_name(&name) {
+        Ok(name) => name,
+        Err(error) => {
+            return Ok(VaultResponse {
+                success: false,
+                error: Some(error),
+                error_code: None,
+            })
+        }
+    };
+
+    let group = ConnectionGroup {
+        id: group_id,
+        name,
+        color: color.filter(|c| !c.trim().is_empty()),
+    };
+
+    Ok(vault_response(VaultStorage::new(&project_id).create_group(group)))
+}
+
+/// Renames a connection group
+#[tauri::command]
+pub async fn rename_connection_group(
+    state: State<'_, SharedState>,
+    project_id: String,
+    group_id: String,
+    new_name: String,
+) -> Result<VaultResponse, String> {
+    let state = state.lock().await;
+
+    if state.vault_lock.is_locked() {
+        return Ok(locked_response());
+    }
+
+    let new_name = match normalize_group_name(&new_name) {
+        Ok(name) => name,
+        Err(error) => {
+            return Ok(VaultResponse {
+                success: false,
+                error: Some(error),
+                error_code: None,
+            })
+        }
+    };
+
+    Ok(vault_response(
+        VaultStorage::new(&project_id).rename_group(&group_id, &new_name),
+    ))
+}
+
+/// Deletes a connection group
+///
+/// Its connections move to `move_connections_to`, or to the top level when
+/// `None`; saved connections are never deleted with their group.
+#[tauri::command]
+pub async fn delete_connection_group(
+    state: State<'_, SharedState>,
+    project_id: String,
+    group_id: String,
+    move_connections_to: Option<String>,
+) -> Result<VaultResponse, String> {
+    let state = state.lock().await;
+
+    if state.vault_lock.is_locked() {
+        return Ok(locked_response());
+    }
+
+    Ok(vault_response(
+        VaultStorage::new(&project_id).delete_group(&group_id, move_connections_to.as_deref()),
+    ))
+}
+
+/// Moves a saved connection into a group, or to the top level with `None`
+#[tauri::command]
+pub async fn move_connection_to_group(
+    state: State<'_, SharedState>,
+    project_id: String,
+    connection_id: String,
+    group_id: Option<String>,
+) -> Result<VaultResponse, String> {
+    let state = state.lock().await;
+
+    if state.vault_lock.is_locked() {
+        return Ok(locked_response());
+    }
+
+    let storage = VaultStorage::new(&project_id);
+
+    Ok(vault_response(
+        storage.move_connection_to_group(&connection_id, group_id.as_deref()),
+    ))
+}
+
+/// Lists the projects known to the vault
+#[tauri::command]
+pub async fn list_projects(state: State<'_, SharedState>) -> Result<Vec<ProjectEntry>, String> {
+    let state = state.lock().await;
+
+    if state.vault_lock.is_locked() {
+        return Err("Vault is locked".to_string());
+    }
+
+    VaultStorage::list_project_entries().map_err(|e| e.to_string())
+}
+
+/// Creates a project to isolate a set of saved connections
+#[tauri::command]
+pub async fn create_project(
+    state: State<'_, SharedState>,
+    project_id: String,
+    display_name: String,
+) -> Result<VaultResponse, String> {
+    let state = state.lock().await;
+
+    if state.vault_lock.is_locked() {
+        return Ok(locked_response());
+    }
+
+    let project_id = project_id.trim();
+    let display_name = display_name.trim();
+    if project_id.is_empty() || display_name.is_empty() {
+        return Ok(VaultResponse {
+            success: false,
+            error: Some("Project ID and name cannot be empty".to_string()),
+            error_code: None,
+        });
+    }
+
+    Ok(vault_response(VaultStorage::create_project(
+        project_id,
+        display_name,
+    )))
+}
+
+/// Deletes a project, and all its saved connections with `delete_connections`
+#[tauri::command]
+pub async fn delete_project(
+    state: State<'_, SharedState>,
+    project_id: String,
+    delete_connections: bool,
+) -> Result<VaultResponse, String> {
+    let state = state.lock().await;
+
+    if state.vault_lock.is_locked() {
+        return Ok(locked_response());
+    }
+
+    Ok(vault_response(VaultStorage::delete_project(
+        &project_id,
+        delete_connections,
+    )))
+}
+
 /// Response for getting credentials
 #[derive(Debug, Serialize)]
 pub struct CredentialsResponse {
     pub success: bool,
     pub password: Option<String>,
+    pub raw_connection_string: Option<String>,
     pub error: Option<String>,
+    pub error_code: Option<String>,
 }
 
-/// Gets the password for a saved connection
+/// Gets the password and raw connection string of a saved connection
 #[tauri::command]
 pub async fn get_connection_credentials(
     state: State<'_, SharedState>,
@@ -265,7 +662,9 @@ pub async fn get_connection_credentials(
         return Ok(CredentialsResponse {
             success: false,
             password: None,
+            raw_connection_string: None,
             error: Some("Vault is locked".to_string()),
+            error_code: None,
         });
     }
 
@@ -275,12 +674,58 @@ pub async fn get_connection_credentials(
         Ok(creds) => Ok(CredentialsResponse {
             success: true,
             password: Some(creds.db_password),
+            raw_connection_string: creds.raw_connection_string,
             error: None,
+            error_code: None,
         }),
         Err(e) => Ok(CredentialsResponse {
             success: false,
             password: None,
+            raw_connection_string: None,
             error: Some(e.to_string()),
+            error_code: Some(e.code().to_string()),
         }),
     }
 }
+
+/// Builds a connection URI of a saved connection, for use in other tools
+///
+/// The password is replaced by a placeholder unless `include_password` is
+/// set; revealing it is recorded in the audit log.
+#[tauri::command]
+pub async fn export_connection_uri(
+    state: State<'_, SharedState>,
+    project_id: String,
+    connection_id: String,
+    include_password: bool,
+) -> Result<String, String> {
+    {
+        let state = state.lock().await;
+        if state.vault_lock.is_locked() {
+            return Err("Vault is locked".to_string());
+        }
+    }
+
+    let storage = VaultStorage::new(&project_id);
+    let connection = storage
+        .get_connection(&connection_id)
+        .map_err(|e| e.to_string())?;
+
+    if !include_password {
+        return connection.connection_uri(None).map_err(|e| e.to_string());
+    }
+
+    let creds = storage
+        .get_credentials(&connection_id)
+        .map_err(|e| e.to_string())?;
+    let uri = connection
+        .connection_uri(Some(&creds.db_password))
+        .map_err(|e| e.to_string())?;
+    tracing::info!(
+        target: "qoredb::audit",
+        project_id = %project_id,
+        connection_id = %connection_id,
+        "Connection URI exported with its password"
+    );
+    Ok(uri)
+}
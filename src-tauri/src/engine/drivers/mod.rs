@@ -1,5 +1,52 @@
 // Database drivers module
 
+pub mod cockroachdb;
 pub mod mongodb;
 pub mod mysql;
+pub mod neon;
+pub mod planetscale;
 pub mod postgres;
+pub mod redis;
+
+use percent_encoding::{utf8_percent_encode, AsciiSet, NON_ALPHANUMERIC};
+use sqlx::database::HasStatementCache;
+use sqlx::{Connection, Database, Pool};
+
+/// Characters left as-is in connection URL components (RFC 3986 unreserved)
+const URL_COMPONENT: &AsciiSet = &NON_ALPHANUMERIC
+    .remove(b'-')
+    .remove(b'.')
+    .remove(b'_')
+    .remove(b'~');
+
+/// Percent-encodes a username, password or database name for a connection URL
+///
+/// Without it, characters such as `@`, `:`, `/` or `?` in a password change
+/// how the URL is split and authentication fails.
+pub(crate) fn url_encode(component: &str) -> String {
+    utf8_percent_encode(component, URL_COMPONENT).to_string()
+}
+
+/// Drops the prepared statements cached by `conn` and by the idle
+/// connections of `pool`
+///
+/// SQLx keeps an LRU of prepared statements per connection; after DDL their
+/// plans and result types may be stale. Connections in use by other queries
+/// keep theirs.
+pub(crate) async fn clear_statement_caches<DB: Database + HasStatementCache>(
+    conn: Option<&mut DB::Connection>,
+    pool: &Pool<DB>,
+) -> Result<(), sqlx::Error> {
+    if let Some(conn) = conn {
+        conn.clear_cached_statements().await?;
+    }
+
+    let mut idle = Vec::new();
+    while let Some(conn) = pool.try_acquire() {
+        idle.push(conn);
+    }
+    for conn in &mut idle {
+        conn.clear_cached_statements().await?;
+    }
+    Ok(())
+}
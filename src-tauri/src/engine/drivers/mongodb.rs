@@ -3,22 +3,56 @@
 //! Implements the DataEngine trait for MongoDB using the official MongoDB driver.
 
 use std::collections::HashMap;
+use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::Instant;
 
 use async_trait::async_trait;
 use futures::future::{AbortHandle, Abortable};
 use mongodb::bson::{doc, Document};
-use mongodb::{Client, options::ClientOptions};
+use mongodb::options::{ClientOptions, Credential, ServerAddress, Tls, TlsOptions};
+use mongodb::Client;
 use tokio::sync::{Mutex, RwLock};
 
 use crate::engine::error::{EngineError, EngineResult};
 use crate::engine::traits::DataEngine;
 use crate::engine::types::{
-    CancelSupport, Collection, CollectionType, ColumnInfo, ConnectionConfig, Namespace, QueryId,
-    QueryResult, Row as QRow, SessionId, TableColumn, TableSchema, Value,
+    CancelSupport, Collection, CollectionType, ColumnInfo, ConnectionConfig, Namespace,
+    NewTableSchema, QueryId, QueryResult, Routine, Row as QRow, RowLockMode, SessionId,
+    TableColumn, TableSchema, TableSizeInfo, Value,
 };
 
+/// Collections sampled concurrently by `describe_namespace`
+const DESCRIBE_CONCURRENCY: usize = 8;
+
+const SRV_SCHEME: &str = "mongodb+srv://";
+
+/// Used when no auth source is configured, as for root users
+const DEFAULT_AUTH_SOURCE: &str = "admin";
+
+/// `RowData` key holding a raw update document (`{"$inc": {"views": 1}}`),
+/// which `update_row` sends as is instead of wrapping the row in `$set`
+pub const RAW_UPDATE_KEY: &str = "__raw_update";
+
+/// Operators allowed at the top level of a raw update document
+const UPDATE_OPERATORS: &[&str] = &[
+    "$set",
+    "$unset",
+    "$setOnInsert",
+    "$inc",
+    "$mul",
+    "$min",
+    "$max",
+    "$rename",
+    "$currentDate",
+    "$push",
+    "$pull",
+    "$pullAll",
+    "$addToSet",
+    "$pop",
+    "$bit",
+];
+
 /// MongoDB driver implementation
 pub struct MongoDriver {
     sessions: Arc<RwLock<HashMap<SessionId, Client>>>,
@@ -33,20 +67,158 @@ impl MongoDriver {
         }
     }
 
-    /// Builds a connection string from config
-    fn build_connection_string(config: &ConnectionConfig) -> String {
-        let db = config.database.as_deref().unwrap_or("admin");
-        let tls = if config.ssl { "true" } else { "false" };
+    /// Returns true for DNS seedlist (`mongodb+srv://`) connections
+    ///
+    /// Selected by a `mongodb+srv://` host or a missing port (0): the servers
+    /// and their ports then come from the SRV records.
+    fn is_srv(config: &ConnectionConfig) -> bool {
+        config.port == 0 || config.host.starts_with(SRV_SCHEME)
+    }
 
-        format!(
-            "mongodb://{}:{}@{}:{}/{}?authSource=admin&tls={}",
-            config.username, config.password, config.host, config.port, db, tls
-        )
+    /// Seedlist URI for SRV connections, without credentials or options
+    fn srv_uri(config: &ConnectionConfig) -> String {
+        let host = config
+            .host
+            .strip_prefix(SRV_SCHEME)
+            .unwrap_or(&config.host)
+            .trim_end_matches('/');
+        format!("{}{}/", SRV_SCHEME, host)
+    }
+
+    /// Builds client options from the config fields, or parses the raw
+    /// connection string when one is set
+    ///
+    /// Credentials and TLS are set on the options rather than in a URI, so
+    /// passwords need no URL encoding. Only SRV connections go through the
+    /// URI parser, which performs the SRV and TXT lookups.
+    async fn build_client_options(config: &ConnectionConfig) -> EngineResult<ClientOptions> {
+        if let Some(raw) = config.connection_string_override(&["mongodb://", SRV_SCHEME])? {
+            return ClientOptions::parse(raw)
+                .await
+                .map_err(|e| EngineError::connection_failed(e.to_string()));
+        }
+
+        let srv = Self::is_srv(config);
+        let mut options = if srv {
+            ClientOptions::parse(Self::srv_uri(config))
+                .await
+                .map_err(|e| EngineError::connection_failed(e.to_string()))?
+        } else {
+            let mut options = ClientOptions::default();
+            options.hosts = vec![ServerAddress::Tcp {
+                host: config.host.clone(),
+                port: Some(config.port),
+            }];
+            options
+        };
+
+        if !config.username.is_empty() {
+            let source = config
+                .mongo_auth_source
+                .clone()
+                .filter(|source| !source.is_empty())
+                .unwrap_or_else(|| DEFAULT_AUTH_SOURCE.to_string());
+            options.credential = Some(
+                Credential::builder()
+                    .username(config.username.clone())
+                    .password(config.password.clone())
+                    .source(source)
+                    .build(),
+            );
+        }
+        options.default_database = config.database.clone();
+
+        // SRV connections default to TLS, which an unset SSL mode keeps
+        if config.ssl_mode.is_required() {
+            let mut tls = TlsOptions::default();
+            if config.ssl_mode.verifies_certificate() {
+                tls.ca_file_path = config.ssl_ca_path.as_ref().map(PathBuf::from);
+            }
+            options.tls = Some(Tls::Enabled(tls));
+        } else if !srv {
+            options.tls = Some(Tls::Disabled);
+        }
+
+        Ok(options)
+    }
+
+    /// Reads a numeric statistic, whatever BSON number type the server used
+    fn stat_number(stats: &Document, key: &str) -> i64 {
+        use mongodb::bson::Bson;
+
+        match stats.get(key) {
+            Some(Bson::Int32(value)) => *value as i64,
+            Some(Bson::Int64(value)) => *value,
+            Some(Bson::Double(value)) => *value as i64,
+            _ => 0,
+        }
+    }
+
+    fn stat_bytes(stats: &Document, key: &str) -> u64 {
+        Self::stat_number(stats, key).max(0) as u64
+    }
+
+    /// Comment attached to the operations of a query, used to find them on the server
+    fn operation_comment(query_id: QueryId) -> String {
+        format!("qoredb:{}", query_id.0)
+    }
+
+    /// Cursor comment for a query: the operation comment, followed by the
+    /// query's own `comment` field when it has one
+    fn cursor_comment(query_id: QueryId, query: &str) -> String {
+        let comment = Self::operation_comment(query_id);
+        let annotation = serde_json::from_str::<serde_json::Value>(query.trim())
+            .ok()
+            .and_then(|parsed| parsed.get("comment")?.as_str().map(str::to_string));
+        match annotation {
+            Some(annotation) => format!("{} {}", comment, annotation),
+            None => comment,
+        }
+    }
+
+    /// Runs `killOp` for every server operation tagged with the query's comment
+    async fn kill_server_operations(client: &Client, query_id: QueryId) -> EngineResult<()> {
+        use futures::TryStreamExt;
+
+        // Cursor comments may carry an annotation after the operation comment
+        let comment = format!("^{}", Self::operation_comment(query_id));
+        let admin = client.database("admin");
+
+        let operations: Vec<Document> = admin
+            .aggregate(vec![
+                doc! { "$currentOp": { "allUsers": true } },
+                doc! { "$match": { "$or": [
+                    { "command.comment": { "$regex": &comment } },
+                    { "cursor.originatingCommand.comment": { "$regex": &comment } },
+                ] } },
+            ])
+            .await
+            .map_err(|e| EngineError::execution_error(e.to_string()))?
+            .try_collect()
+            .await
+            .map_err(|e| EngineError::execution_error(e.to_string()))?;
+
+        for operation in operations {
+            // opid is an integer on mongod and a "shard:opid" string on mongos
+            if let Some(opid) = operation.get("opid") {
+                admin
+                    .run_command(doc! { "killOp": 1, "op": opid.clone() })
+                    .await
+                    .map_err(|e| EngineError::execution_error(e.to_string()))?;
+            }
+        }
+
+        Ok(())
     }
 
     /// Converts a BSON document to our universal Row type
-    fn document_to_row(doc: &Document) -> QRow {
-        let values: Vec<Value> = doc.values().map(Self::bson_to_value).collect();
+    ///
+    /// Values follow the order of `columns`; missing keys become `Value::Null`.
+    fn document_to_row(doc: &Document, columns: &[String]) -> QRow {
+        let values: Vec<Value> = columns
+            .iter()
+            .map(|column| doc.get(column).map(Self::bson_to_value).unwrap_or(Value::Null))
+            .collect();
         QRow { values }
     }
 
@@ -74,18 +246,83 @@ impl MongoDriver {
         }
     }
 
-    /// Gets column info from a document
-    fn get_column_info(doc: &Document) -> Vec<ColumnInfo> {
-        doc.keys()
-            .map(|key| ColumnInfo {
-                name: key.clone(),
+    /// Union of the keys of all documents, in first-seen order with `_id` first
+    fn column_names(documents: &[Document]) -> Vec<String> {
+        let mut names: Vec<String> = Vec::new();
+        for doc in documents {
+            for key in doc.keys() {
+                if !names.contains(key) {
+                    names.push(key.clone());
+                }
+            }
+        }
+        if let Some(pos) = names.iter().position(|name| name == "_id") {
+            let id = names.remove(pos);
+            names.insert(0, id);
+        }
+        names
+    }
+
+    /// Gets column info from column names
+    fn get_column_info(names: &[String]) -> Vec<ColumnInfo> {
+        names
+            .iter()
+            .map(|name| ColumnInfo {
+                name: name.clone(),
                 data_type: "mixed".to_string(), // MongoDB is schemaless
                 nullable: true,
+                ..Default::default()
             })
             .collect()
     }
 
+    /// Builds a result whose columns cover the keys of every document
+    fn documents_to_result(documents: &[Document], execution_time_ms: f64) -> QueryResult {
+        let names = Self::column_names(documents);
+        QueryResult {
+            columns: Self::get_column_info(&names),
+            rows: documents
+                .iter()
+                .map(|doc| Self::document_to_row(doc, &names))
+                .collect(),
+            affected_rows: None,
+            execution_time_ms,
+            plan_estimate: None,
+            truncated: false,
+            estimated_memory_bytes: None,
+            truncated_values: Vec::new(),
+        }
+    }
+
     /// Parses a MongoDB query string (JSON format)
+    /// Parses a JSON query document; an empty filter matches every document
+    fn parse_filter(filter: &str) -> EngineResult<Document> {
+        let filter = filter.trim();
+        if filter.is_empty() {
+            return Ok(doc! {});
+        }
+
+        match serde_json::from_str::<serde_json::Value>(filter) {
+            Ok(serde_json::Value::Object(map)) => Document::try_from(map)
+                .map_err(|e| EngineError::syntax_error(format!("Invalid filter: {}", e))),
+            Ok(_) => Err(EngineError::syntax_error("Filter must be a JSON object")),
+            Err(e) => Err(EngineError::syntax_error(format!("Invalid JSON: {}", e))),
+        }
+    }
+
+    /// Returns true for the `{"operation": "create_collection", ...}` command
+    fn is_create_collection(query: &str) -> bool {
+        serde_json::from_str::<serde_json::Value>(query.trim())
+            .ok()
+            .and_then(|parsed| {
+                parsed
+                    .get("operation")
+                    .and_then(|v| v.as_str())
+                    .map(|operation| operation == "create_collection")
+            })
+            .unwrap_or(false)
+    }
+
     fn parse_query(query: &str) -> EngineResult<(String, String, Document)> {
         // Expected format: db.collection.method({...})
         // or JSON: {"database": "db", "collection": "col", "operation": "find", "query": {...}}
@@ -148,6 +385,10 @@ impl MongoDriver {
                     Bson::String(s.clone())
                 }
             },
+            Value::Timestamp(ts) => Bson::DateTime(mongodb::bson::DateTime::from_millis(
+                ts.and_utc().timestamp_millis(),
+            )),
+            Value::Uuid(uuid) => Bson::String(uuid.to_string()),
             Value::Bytes(b) => Bson::Binary(mongodb::bson::Binary {
                 subtype: mongodb::bson::spec::BinarySubtype::Generic,
                 bytes: b.clone(),
@@ -159,6 +400,42 @@ impl MongoDriver {
         }
     }
 
+    /// Builds the update document of `update_row`: the row's fields under
+    /// `$set`, or the raw update document held under `RAW_UPDATE_KEY`
+    fn update_document(data: &QRowData) -> EngineResult<Document> {
+        let Some(raw) = data.columns.get(RAW_UPDATE_KEY) else {
+            return Ok(doc! { "$set": Self::row_data_to_document(data) });
+        };
+        if data.columns.len() > 1 {
+            return Err(EngineError::syntax_error(format!(
+                "{} cannot be combined with other fields",
+                RAW_UPDATE_KEY
+            )));
+        }
+
+        let spec = match raw {
+            Value::Json(json) => json.clone(),
+            Value::Text(text) => serde_json::from_str(text)
+                .map_err(|e| EngineError::syntax_error(format!("Invalid JSON: {}", e)))?,
+            _ => serde_json::Value::Null,
+        };
+        let serde_json::Value::Object(spec) = spec else {
+            return Err(EngineError::syntax_error("Raw update must be a JSON object"));
+        };
+        if spec.is_empty() {
+            return Err(EngineError::syntax_error("Raw update must not be empty"));
+        }
+        if let Some(key) = spec.keys().find(|key| !UPDATE_OPERATORS.contains(&key.as_str())) {
+            return Err(EngineError::syntax_error(format!(
+                "'{}' is not an update operator",
+                key
+            )));
+        }
+
+        Document::try_from(spec)
+            .map_err(|e| EngineError::syntax_error(format!("Invalid update: {}", e)))
+    }
+
     // Helper to convert RowData to Document
     fn row_data_to_document(data: &QRowData) -> Document {
         let mut doc = Document::new();
@@ -200,12 +477,12 @@ impl DataEngine for MongoDriver {
         "MongoDB"
     }
 
-    async fn test_connection(&self, config: &ConnectionConfig) -> EngineResult<()> {
-        let conn_str = Self::build_connection_string(config);
+    fn default_port(&self) -> u16 {
+        27017
+    }
 
-        let options = ClientOptions::parse(&conn_str)
-            .await
-            .map_err(|e| EngineError::connection_failed(e.to_string()))?;
+    async fn test_connection(&self, config: &ConnectionConfig) -> EngineResult<()> {
+        let options = Self::build_client_options(config).await?;
 
         let client = Client::with_options(options)
             .map_err(|e| EngineError::connection_failed(e.to_string()))?;
@@ -228,11 +505,9 @@ impl DataEngine for MongoDriver {
     }
 
     async fn connect(&self, config: &ConnectionConfig) -> EngineResult<SessionId> {
-        let conn_str = Self::build_connection_string(config);
-
-        let options = ClientOptions::parse(&conn_str)
-            .await
-            .map_err(|e| EngineError::connection_failed(e.to_string()))?;
+        let mut options = Self::build_client_options(config).await?;
+        // The driver has no max lifetime; idle recycling covers dropped sockets
+        options.max_idle_time = config.pool_idle_timeout();
 
         let client = Client::with_options(options)
             .map_err(|e| EngineError::connection_failed(e.to_string()))?;
@@ -262,6 +537,33 @@ impl DataEngine for MongoDriver {
         }
     }
 
+    async fn rebind_session(&self, from: SessionId, to: SessionId) -> EngineResult<()> {
+        let mut sessions = self.sessions.write().await;
+        let session = sessions
+            .remove(&from)
+            .ok_or_else(|| EngineError::session_not_found(from.0.to_string()))?;
+        if let Some(stale) = sessions.insert(to, session) {
+            sessions.insert(from, stale);
+        }
+        Ok(())
+    }
+
+    async fn ping(&self, session: SessionId) -> EngineResult<()> {
+        let client = {
+            let sessions = self.sessions.read().await;
+            sessions
+                .get(&session)
+                .cloned()
+                .ok_or_else(|| EngineError::session_not_found(session.0.to_string()))?
+        };
+        client
+            .database("admin")
+            .run_command(doc! { "ping": 1 })
+            .await
+            .map_err(|e| EngineError::connection_failed(e.to_string()))?;
+        Ok(())
+    }
+
     async fn list_namespaces(&self, session: SessionId) -> EngineResult<Vec<Namespace>> {
         let sessions = self.sessions.read().await;
         let client = sessions
@@ -282,6 +584,82 @@ impl DataEngine for MongoDriver {
         Ok(namespaces)
     }
 
+    /// Databases only exist once they hold a collection, so this creates the
+    /// collection named by `namespace.schema`
+    async fn create_namespace(
+        &self,
+        session: SessionId,
+        namespace: &Namespace,
+    ) -> EngineResult<()> {
+        let collection = namespace
+            .schema
+            .as_deref()
+            .map(str::trim)
+            .filter(|collection| !collection.is_empty())
+            .ok_or_else(|| EngineError::syntax_error("Missing first collection name"))?;
+
+        let sessions = self.sessions.read().await;
+        let client = sessions
+            .get(&session)
+            .ok_or_else(|| EngineError::session_not_found(session.0.to_string()))?;
+
+        client
+            .database(&namespace.database)
+            .create_collection(collection)
+            .await
+            .map_err(|e| EngineError::execution_error(e.to_string()))
+    }
+
+    /// Creates the collection; column definitions do not apply to MongoDB
+    async fn create_table(
+        &self,
+        session: SessionId,
+        namespace: &Namespace,
+        schema: &NewTableSchema,
+    ) -> EngineResult<()> {
+        let sessions = self.sessions.read().await;
+        let client = sessions
+            .get(&session)
+            .ok_or_else(|| EngineError::session_not_found(session.0.to_string()))?;
+        let database = client.database(&namespace.database);
+
+        if schema.if_not_exists {
+            let existing = database
+                .list_collection_names()
+                .filter(doc! { "name": &schema.table_name })
+                .await
+                .map_err(|e| EngineError::execution_error(e.to_string()))?;
+            if !existing.is_empty() {
+                return Ok(());
+            }
+        }
+
+        database
+            .create_collection(&schema.table_name)
+            .await
+            .map_err(|e| EngineError::execution_error(e.to_string()))
+    }
+
+    /// Drops the whole database; `cascade` is ignored
+    async fn drop_namespace(
+        &self,
+        session: SessionId,
+        namespace: &Namespace,
+        cascade: bool,
+    ) -> EngineResult<()> {
+        let _ = cascade;
+        let sessions = self.sessions.read().await;
+        let client = sessions
+            .get(&session)
+            .ok_or_else(|| EngineError::session_not_found(session.0.to_string()))?;
+
+        client
+            .database(&namespace.database)
+            .drop()
+            .await
+            .map_err(|e| EngineError::execution_error(e.to_string()))
+    }
+
     async fn list_collections(
         &self,
         session: SessionId,
@@ -310,6 +688,15 @@ impl DataEngine for MongoDriver {
         Ok(collections)
     }
 
+    /// MongoDB has no stored routines
+    async fn list_routines(
+        &self,
+        _session: SessionId,
+        _namespace: &Namespace,
+    ) -> EngineResult<Vec<Routine>> {
+        Ok(Vec::new())
+    }
+
     async fn execute(
         &self,
         session: SessionId,
@@ -330,6 +717,7 @@ impl DataEngine for MongoDriver {
         }
 
         let query = query.to_string();
+        let comment = Self::cursor_comment(query_id, &query);
         let result = Abortable::new(
             async move {
                 let start = Instant::now();
@@ -360,6 +748,10 @@ impl DataEngine for MongoDriver {
                                 rows: Vec::new(),
                                 affected_rows: None,
                                 execution_time_ms,
+                                plan_estimate: None,
+                                truncated: false,
+                                estimated_memory_bytes: None,
+                                truncated_values: Vec::new(),
                             });
                         }
                     }
@@ -371,6 +763,7 @@ impl DataEngine for MongoDriver {
 
                 let mut cursor = collection
                     .find(filter)
+                    .comment(comment)
                     .await
                     .map_err(|e| EngineError::execution_error(e.to_string()))?;
 
@@ -390,24 +783,93 @@ impl DataEngine for MongoDriver {
 
                 let execution_time_ms = start.elapsed().as_micros() as f64 / 1000.0;
 
-                if documents.is_empty() {
-                    return Ok(QueryResult {
-                        columns: Vec::new(),
-                        rows: Vec::new(),
-                        affected_rows: None,
-                        execution_time_ms,
-                    });
-                }
+                Ok(Self::documents_to_result(&documents, execution_time_ms))
+            },
+            abort_reg,
+        )
+        .await;
+
+        {
+            let mut active = self.active_queries.lock().await;
+            active.remove(&query_id);
+        }
 
-                let columns = Self::get_column_info(&documents[0]);
-                let rows: Vec<QRow> = documents.iter().map(Self::document_to_row).collect();
+        match result {
+            Ok(inner) => inner,
+            Err(_) => Err(EngineError::Cancelled),
+        }
+    }
 
-                Ok(QueryResult {
-                    columns,
-                    rows,
-                    affected_rows: None,
-                    execution_time_ms,
-                })
+    /// Reads documents from the server cursor, `batch_size` at a time
+    ///
+    /// Unlike `execute`, the result is not capped at 1000 documents. Columns
+    /// are the keys of each batch's documents, so later batches may add some.
+    async fn execute_stream(
+        &self,
+        session: SessionId,
+        query: &str,
+        query_id: QueryId,
+        batch_size: usize,
+        on_batch: &(dyn Fn(QueryResult) + Send + Sync),
+    ) -> EngineResult<u64> {
+        if Self::is_create_collection(query) {
+            on_batch(self.execute(session, query, query_id).await?);
+            return Ok(0);
+        }
+
+        let sessions = self.sessions.read().await;
+        let client = sessions
+            .get(&session)
+            .ok_or_else(|| EngineError::session_not_found(session.0.to_string()))?
+            .clone();
+        drop(sessions);
+
+        let (abort_handle, abort_reg) = AbortHandle::new_pair();
+        {
+            let mut active = self.active_queries.lock().await;
+            active.insert(query_id, (session, abort_handle));
+        }
+
+        let comment = Self::cursor_comment(query_id, query);
+        let result = Abortable::new(
+            async {
+                use futures::TryStreamExt;
+
+                let start = Instant::now();
+                let batch_size = batch_size.max(1);
+                let (database, collection_name, filter) = Self::parse_query(query)?;
+
+                let collection = client.database(&database).collection::<Document>(&collection_name);
+
+                let mut cursor = collection
+                    .find(filter)
+                    .batch_size(u32::try_from(batch_size).unwrap_or(u32::MAX))
+                    .comment(comment)
+                    .await
+                    .map_err(|e| EngineError::execution_error(e.to_string()))?;
+
+                let mut documents: Vec<Document> = Vec::with_capacity(batch_size);
+                let mut total_rows = 0u64;
+                while let Some(doc) = cursor
+                    .try_next()
+                    .await
+                    .map_err(|e| EngineError::execution_error(e.to_string()))?
+                {
+                    documents.push(doc);
+                    total_rows += 1;
+
+                    if documents.len() == batch_size {
+                        let execution_time_ms = start.elapsed().as_micros() as f64 / 1000.0;
+                        on_batch(Self::documents_to_result(&documents, execution_time_ms));
+                        documents.clear();
+                    }
+                }
+
+                if total_rows == 0 || !documents.is_empty() {
+                    let execution_time_ms = start.elapsed().as_micros() as f64 / 1000.0;
+                    on_batch(Self::documents_to_result(&documents, execution_time_ms));
+                }
+                Ok(total_rows)
             },
             abort_reg,
         )
@@ -485,6 +947,9 @@ impl DataEngine for MongoDriver {
                 data_type,
                 nullable: true, // MongoDB fields are always nullable
                 default_value: None,
+                comment: None,
+                allowed_values: None,
+                charset: None,
             })
             .collect();
 
@@ -509,16 +974,47 @@ impl DataEngine for MongoDriver {
             columns,
             primary_key: Some(vec!["_id".to_string()]),
             row_count_estimate: count,
+            comment: None,
         })
     }
 
+    /// Samples every collection, a few at a time
+    async fn describe_namespace(
+        &self,
+        session: SessionId,
+        namespace: &Namespace,
+    ) -> EngineResult<HashMap<String, TableSchema>> {
+        use futures::{StreamExt, TryStreamExt};
+
+        let collections = self.list_collections(session, namespace).await?;
+
+        futures::stream::iter(collections)
+            .map(|collection| async move {
+                let schema = self
+                    .describe_table(session, namespace, &collection.name)
+                    .await?;
+                Ok::<_, EngineError>((collection.name, schema))
+            })
+            .buffer_unordered(DESCRIBE_CONCURRENCY)
+            .try_collect()
+            .await
+    }
+
     async fn preview_table(
         &self,
         session: SessionId,
         namespace: &Namespace,
         table: &str,
         limit: u32,
+        lock_rows: Option<RowLockMode>,
     ) -> EngineResult<QueryResult> {
+        if lock_rows.is_some() {
+            // The closest MongoDB equivalent is a transaction with majority read concern
+            return Err(EngineError::not_supported(
+                "Row locking is not supported by MongoDB",
+            ));
+        }
+
         let sessions = self.sessions.read().await;
         let client = sessions
             .get(&session)
@@ -544,54 +1040,130 @@ impl DataEngine for MongoDriver {
 
         let execution_time_ms = start.elapsed().as_micros() as f64 / 1000.0;
 
-        if documents.is_empty() {
-            return Ok(QueryResult {
-                columns: Vec::new(),
-                rows: Vec::new(),
-                affected_rows: None,
-                execution_time_ms,
-            });
+        Ok(Self::documents_to_result(&documents, execution_time_ms))
+    }
+
+    async fn fetch_value(
+        &self,
+        session: SessionId,
+        namespace: &Namespace,
+        table: &str,
+        primary_key: &QRowData,
+        column: &str,
+    ) -> EngineResult<Value> {
+        let sessions = self.sessions.read().await;
+        let client = sessions
+            .get(&session)
+            .ok_or_else(|| EngineError::session_not_found(session.0.to_string()))?;
+
+        let collection = client
+            .database(&namespace.database)
+            .collection::<Document>(table);
+
+        let mut filter = Document::new();
+        for (key, value) in &primary_key.columns {
+            filter.insert(key, Self::value_to_bson(value));
         }
 
-        let columns = Self::get_column_info(&documents[0]);
-        let rows: Vec<QRow> = documents.iter().map(Self::document_to_row).collect();
+        let document = collection
+            .find_one(filter)
+            .projection(doc! { column: 1 })
+            .await
+            .map_err(|e| EngineError::execution_error(e.to_string()))?
+            .ok_or_else(|| EngineError::execution_error("Document not found".to_string()))?;
 
-        Ok(QueryResult {
-            columns,
-            rows,
-            affected_rows: None,
-            execution_time_ms,
-        })
+        Ok(document.get(column).map(Self::bson_to_value).unwrap_or(Value::Null))
     }
 
-    async fn cancel(&self, session: SessionId, query_id: Option<QueryId>) -> EngineResult<()> {
+    async fn get_table_sizes(
+        &self,
+        session: SessionId,
+        namespace: &Namespace,
+    ) -> EngineResult<Vec<TableSizeInfo>> {
         let sessions = self.sessions.read().await;
-        if !sessions.contains_key(&session) {
-            return Err(EngineError::session_not_found(session.0.to_string()));
+        let client = sessions
+            .get(&session)
+            .ok_or_else(|| EngineError::session_not_found(session.0.to_string()))?
+            .clone();
+        drop(sessions);
+
+        let database = client.database(&namespace.database);
+
+        let names = database
+            .list_collection_names()
+            .filter(doc! { "type": "collection" })
+            .await
+            .map_err(|e| EngineError::execution_error(e.to_string()))?;
+
+        let mut sizes = Vec::with_capacity(names.len());
+        for name in names {
+            let stats = database
+                .run_command(doc! { "collStats": &name })
+                .await
+                .map_err(|e| EngineError::execution_error(e.to_string()))?;
+
+            let table_bytes = Self::stat_bytes(&stats, "storageSize");
+            let index_bytes = Self::stat_bytes(&stats, "totalIndexSize");
+            let total_bytes = match Self::stat_bytes(&stats, "totalSize") {
+                0 => table_bytes + index_bytes,
+                total => total,
+            };
+
+            sizes.push(TableSizeInfo {
+                table_name: name,
+                row_count: Self::stat_number(&stats, "count"),
+                total_bytes,
+                table_bytes,
+                index_bytes,
+                toast_bytes: None,
+            });
         }
+
+        sizes.sort_by(|a, b| {
+            b.total_bytes
+                .cmp(&a.total_bytes)
+                .then_with(|| a.table_name.cmp(&b.table_name))
+        });
+        Ok(sizes)
+    }
+
+    /// Aborts the local future, then kills the matching server operations.
+    ///
+    /// The Rust driver cannot cancel an in-flight operation, so every `find`
+    /// is tagged with a per-query comment and matched through `$currentOp`.
+    async fn cancel(&self, session: SessionId, query_id: Option<QueryId>) -> EngineResult<()> {
+        let sessions = self.sessions.read().await;
+        let client = sessions
+            .get(&session)
+            .ok_or_else(|| EngineError::session_not_found(session.0.to_string()))?
+            .clone();
         drop(sessions);
 
-        let mut active = self.active_queries.lock().await;
+        let cancelled: Vec<QueryId> = {
+            let mut active = self.active_queries.lock().await;
 
-        if let Some(qid) = query_id {
-            if let Some((sid, handle)) = active.get(&qid) {
-                if *sid == session {
+            let to_cancel: Vec<QueryId> = match query_id {
+                Some(qid) => match active.get(&qid) {
+                    Some((sid, _)) if *sid == session => vec![qid],
+                    _ => return Err(EngineError::execution_error("Query not found")),
+                },
+                None => active
+                    .iter()
+                    .filter_map(|(qid, (sid, _))| if *sid == session { Some(*qid) } else { None })
+                    .collect(),
+            };
+
+            for qid in &to_cancel {
+                if let Some((_, handle)) = active.remove(qid) {
                     handle.abort();
-                    active.remove(&qid);
-                    return Ok(());
                 }
             }
-            return Err(EngineError::execution_error("Query not found"));
-        }
-
-        let to_cancel: Vec<QueryId> = active
-            .iter()
-            .filter_map(|(qid, (sid, _))| if *sid == session { Some(*qid) } else { None })
-            .collect();
+            to_cancel
+        };
 
-        for qid in to_cancel {
-            if let Some((_, handle)) = active.remove(&qid) {
-                handle.abort();
+        for qid in cancelled {
+            if let Err(e) = Self::kill_server_operations(&client, qid).await {
+                tracing::warn!(query_id = %qid.0, error = %e, "Failed to kill MongoDB operation");
             }
         }
 
@@ -602,6 +1174,10 @@ impl DataEngine for MongoDriver {
         CancelSupport::BestEffort
     }
 
+    fn supports_streaming(&self) -> bool {
+        true
+    }
+
     // ==================== Transaction Methods ====================
     // MongoDB transactions require a replica set configuration.
     // Standalone MongoDB instances do not support multi-document transactions.
@@ -661,6 +1237,46 @@ impl DataEngine for MongoDriver {
         Ok(QueryResult::with_affected_rows(1, execution_time_ms))
     }
 
+    /// Reads the document back by the `_id` the server assigned, or the one
+    /// given in `data`
+    async fn insert_row_returning(
+        &self,
+        session: SessionId,
+        namespace: &Namespace,
+        table: &str,
+        data: &QRowData,
+    ) -> EngineResult<QueryResult> {
+        let sessions = self.sessions.read().await;
+        let client = sessions
+            .get(&session)
+            .ok_or_else(|| EngineError::session_not_found(session.0.to_string()))?;
+
+        let start = Instant::now();
+
+        let collection = client
+            .database(&namespace.database)
+            .collection::<Document>(table);
+
+        let doc = Self::row_data_to_document(data);
+
+        let inserted = collection
+            .insert_one(doc)
+            .await
+            .map_err(|e| EngineError::execution_error(e.to_string()))?;
+        let documents: Vec<Document> = collection
+            .find_one(doc! { "_id": inserted.inserted_id })
+            .await
+            .map_err(|e| EngineError::execution_error(e.to_string()))?
+            .into_iter()
+            .collect();
+
+        let execution_time_ms = start.elapsed().as_micros() as f64 / 1000.0;
+
+        let mut result = Self::documents_to_result(&documents, execution_time_ms);
+        result.affected_rows = Some(1);
+        Ok(result)
+    }
+
     async fn update_row(
         &self,
         session: SessionId,
@@ -686,9 +1302,7 @@ impl DataEngine for MongoDriver {
             filter.insert(key, Self::value_to_bson(value));
         }
 
-        // Construct update document
-        let update_doc = Self::row_data_to_document(data);
-        let update = doc! { "$set": update_doc };
+        let update = Self::update_document(data)?;
 
         let result = collection
             .update_one(filter, update)
@@ -734,7 +1348,204 @@ impl DataEngine for MongoDriver {
         Ok(QueryResult::with_affected_rows(result.deleted_count, execution_time_ms))
     }
 
+    /// Parses `filter` as an Extended JSON query document for `deleteMany`
+    async fn delete_rows_where(
+        &self,
+        session: SessionId,
+        namespace: &Namespace,
+        table: &str,
+        filter: &str,
+    ) -> EngineResult<QueryResult> {
+        let filter = Self::parse_filter(filter)?;
+
+        let sessions = self.sessions.read().await;
+        let client = sessions
+            .get(&session)
+            .ok_or_else(|| EngineError::session_not_found(session.0.to_string()))?;
+
+        let start = Instant::now();
+
+        let collection = client
+            .database(&namespace.database)
+            .collection::<Document>(table);
+
+        let result = collection
+            .delete_many(filter)
+            .await
+            .map_err(|e| EngineError::execution_error(e.to_string()))?;
+
+        let execution_time_ms = start.elapsed().as_micros() as f64 / 1000.0;
+
+        Ok(QueryResult::with_affected_rows(result.deleted_count, execution_time_ms))
+    }
+
+    /// MongoDB has no `TRUNCATE`: every document is deleted, keeping the
+    /// collection and its indexes. `cascade` has nothing to apply to.
+    async fn truncate_table(
+        &self,
+        session: SessionId,
+        namespace: &Namespace,
+        table: &str,
+        _cascade: bool,
+    ) -> EngineResult<QueryResult> {
+        let sessions = self.sessions.read().await;
+        let client = sessions
+            .get(&session)
+            .ok_or_else(|| EngineError::session_not_found(session.0.to_string()))?;
+
+        let start = Instant::now();
+
+        let result = client
+            .database(&namespace.database)
+            .collection::<Document>(table)
+            .delete_many(doc! {})
+            .await
+            .map_err(|e| EngineError::execution_error(e.to_string()))?;
+
+        let execution_time_ms = start.elapsed().as_micros() as f64 / 1000.0;
+
+        Ok(QueryResult::with_affected_rows(result.deleted_count, execution_time_ms))
+    }
+
     fn supports_mutations(&self) -> bool {
         true
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::types::SslMode;
+
+    fn mongo_config(host: &str, port: u16) -> ConnectionConfig {
+        ConnectionConfig {
+            driver: "mongodb".to_string(),
+            host: host.to_string(),
+            port,
+            username: "app".to_string(),
+            password: "p@ss:w/ord?".to_string(),
+            database: Some("shop".to_string()),
+            ssl_mode: SslMode::Disabled,
+            ssl_ca_path: None,
+            environment: "development".to_string(),
+            read_only: false,
+            ssh_tunnel: None,
+            pool_idle_timeout_secs: 0,
+            pool_max_lifetime_secs: 0,
+            use_http_api: false,
+            boost: None,
+            mongo_auth_source: None,
+            keepalive_query_secs: None,
+            label: None,
+            charset: None,
+            raw_connection_string: None,
+            retry_policy: None,
+            statement_cache_capacity: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn passes_special_characters_in_password_verbatim() {
+        let options = MongoDriver::build_client_options(&mongo_config("db.local", 27017))
+            .await
+            .unwrap();
+
+        let credential = options.credential.expect("credential");
+        assert_eq!(credential.password.as_deref(), Some("p@ss:w/ord?"));
+        assert_eq!(credential.source.as_deref(), Some("admin"));
+        assert!(matches!(
+            options.hosts.as_slice(),
+            [ServerAddress::Tcp { host, port: Some(27017) }] if host == "db.local"
+        ));
+        assert!(matches!(options.tls, Some(Tls::Disabled)));
+    }
+
+    #[test]
+    fn srv_uri_has_no_port_or_credentials() {
+        let mut config = mongo_config("mongodb+srv://cluster0.example.net", 27017);
+        assert!(MongoDriver::is_srv(&config));
+        assert_eq!(
+            MongoDriver::srv_uri(&config),
+            "mongodb+srv://cluster0.example.net/"
+        );
+
+        config.host = "cluster0.example.net".to_string();
+        assert!(!MongoDriver::is_srv(&config));
+        config.port = 0;
+        assert!(MongoDriver::is_srv(&config));
+    }
+
+    #[test]
+    fn aligns_heterogeneous_documents_to_all_keys() {
+        let documents = vec![
+            doc! { "name": "a", "_id": 1 },
+            doc! { "_id": 2, "age": 30 },
+            doc! { "_id": 3, "name": "c", "tags": ["x"] },
+        ];
+
+        let result = MongoDriver::documents_to_result(&documents, 0.0);
+        let names: Vec<&str> = result.columns.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, vec!["_id", "name", "age", "tags"]);
+
+        assert!(matches!(result.rows[0].values[0], Value::Int(1)));
+        assert!(matches!(result.rows[0].values[2], Value::Null));
+        assert!(matches!(result.rows[1].values[1], Value::Null));
+        assert!(matches!(result.rows[1].values[2], Value::Int(30)));
+        assert!(matches!(result.rows[2].values[3], Value::Array(_)));
+    }
+
+    fn row(columns: &[(&str, Value)]) -> QRowData {
+        QRowData {
+            columns: columns
+                .iter()
+                .map(|(key, value)| (key.to_string(), value.clone()))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn builds_set_and_raw_update_documents() {
+        let update = MongoDriver::update_document(&row(&[("name", Value::Text("a".into()))]))
+            .unwrap();
+        assert_eq!(update, doc! { "$set": { "name": "a" } });
+
+        let raw = r#"{"$inc": {"views": 1}, "$push": {"tags": "new"}}"#;
+        let update =
+            MongoDriver::update_document(&row(&[(RAW_UPDATE_KEY, Value::Text(raw.into()))]))
+                .unwrap();
+        assert_eq!(update, doc! { "$inc": { "views": 1 }, "$push": { "tags": "new" } });
+
+        let json = serde_json::json!({ "$unset": { "legacy": "" } });
+        let update = MongoDriver::update_document(&row(&[(RAW_UPDATE_KEY, Value::Json(json))]))
+            .unwrap();
+        assert_eq!(update, doc! { "$unset": { "legacy": "" } });
+    }
+
+    #[test]
+    fn rejects_invalid_raw_updates() {
+        let raw = |spec: &str| row(&[(RAW_UPDATE_KEY, Value::Text(spec.into()))]);
+
+        for spec in [
+            r#"{"views": 1}"#,
+            r#"{"$inc": {"views": 1}, "name": "a"}"#,
+            r#"{"$where": "1"}"#,
+            "{}",
+            "[]",
+            "not json",
+        ] {
+            assert!(
+                matches!(
+                    MongoDriver::update_document(&raw(spec)),
+                    Err(EngineError::SyntaxError { .. })
+                ),
+                "{spec}"
+            );
+        }
+
+        let mixed = row(&[
+            (RAW_UPDATE_KEY, Value::Text(r#"{"$inc": {"views": 1}}"#.into())),
+            ("name", Value::Text("a".into())),
+        ]);
+        assert!(MongoDriver::update_document(&mixed).is_err());
+    }
+}
@@ -13,18 +13,101 @@ use std::time::Instant;
 
 use async_trait::async_trait;
 use rust_decimal::Decimal;
-use sqlx::mysql::{MySql, MySqlPool, MySqlPoolOptions, MySqlRow};
+use futures::stream::BoxStream;
+use sqlx::mysql::{
+    MySql, MySqlColumn, MySqlConnectOptions, MySqlConnection, MySqlPool, MySqlPoolOptions,
+    MySqlQueryResult, MySqlRow,
+};
 use sqlx::pool::PoolConnection;
-use sqlx::{Column, Row, TypeInfo};
+use sqlx::{Column, Either, Executor, Row, TypeInfo};
 use tokio::sync::{Mutex, RwLock};
 
+use crate::engine::drivers::{clear_statement_caches, url_encode};
 use crate::engine::error::{EngineError, EngineResult};
+use crate::engine::sql_safety;
 use crate::engine::traits::DataEngine;
 use crate::engine::types::{
-    CancelSupport, Collection, CollectionType, ColumnInfo, ConnectionConfig, Namespace, QueryId,
-    QueryResult, Row as QRow, RowData, SessionId, TableColumn, TableSchema, Value,
+    CancelSupport, Collection, CollectionType, ColumnInfo, ConnectionConfig, Namespace,
+    NewColumnDef, NewTableSchema, PlanEstimate, PrivilegeReport, QueryId, QueryResult, Row as QRow,
+    Routine, RoutineType, RowData, RowLockMode, ServerQuery, ServerVariable, SessionId, SslMode,
+    TableColumn, TableSchema, TableSizeInfo, TransactionStatus, Value, VariableScope,
 };
 
+/// Variables `set_session_variable` may change
+const SETTABLE_VARIABLES: &[&str] = &[
+    "character_set_results",
+    "collation_connection",
+    "group_concat_max_len",
+    "innodb_lock_wait_timeout",
+    "lock_wait_timeout",
+    "max_execution_time",
+    "net_read_timeout",
+    "net_write_timeout",
+    "sql_mode",
+    "sql_safe_updates",
+    "sql_select_limit",
+    "time_zone",
+    "transaction_isolation",
+    "wait_timeout",
+];
+
+/// Variables `set_server_variable` refuses: they reach the filesystem,
+/// logging or replication, or weaken server security
+const BLOCKED_VARIABLES: &[&str] = &[
+    "basedir",
+    "datadir",
+    "default_authentication_plugin",
+    "general_log",
+    "general_log_file",
+    "init_connect",
+    "init_file",
+    "init_replica",
+    "init_slave",
+    "local_infile",
+    "log_error",
+    "plugin_dir",
+    "read_only",
+    "relay_log",
+    "require_secure_transport",
+    "secure_file_priv",
+    "slow_query_log",
+    "slow_query_log_file",
+    "sql_log_bin",
+    "super_read_only",
+    "tmpdir",
+];
+
+/// Tells MariaDB apart from MySQL by its `VERSION()` string
+///
+/// MariaDB reports versions like `10.11.6-MariaDB-log` or `11.4.2-MariaDB`;
+/// MySQL has no 10.x or 11.x release.
+fn is_mariadb_version(version: &str) -> bool {
+    version.starts_with("10.")
+        || version.starts_with("11.")
+        || version.to_ascii_lowercase().contains("mariadb")
+}
+
+/// Whether a server accepts `ALTER TABLE ... RENAME COLUMN`, added in
+/// MySQL 8.0 and MariaDB 10.5.2
+///
+/// Older MariaDB releases prefix their version with `5.5.5-` for the sake
+/// of replication with MySQL 5.5.
+fn supports_rename_column(version: &str) -> bool {
+    let minimum: &[u32] = if is_mariadb_version(version) {
+        &[10, 5, 2]
+    } else {
+        &[8, 0, 0]
+    };
+    let parsed: Vec<u32> = version
+        .strip_prefix("5.5.5-")
+        .unwrap_or(version)
+        .split(|c: char| !c.is_ascii_digit())
+        .take(3)
+        .map(|part| part.parse().unwrap_or(0))
+        .collect();
+    parsed.as_slice() >= minimum
+}
+
 /// Holds the connection state for a MySQL session.
 pub struct MySqlSession {
     /// The connection pool for this session
@@ -33,14 +116,68 @@ pub struct MySqlSession {
     pub transaction_conn: Mutex<Option<PoolConnection<MySql>>>,
     /// Active queries (query_id -> connection_id)
     pub active_queries: Mutex<HashMap<QueryId, u64>>,
+    /// Session variables, re-applied to every pooled connection used for queries
+    pub session_variables: Mutex<HashMap<String, String>>,
+    /// True when the server reported a MariaDB version at connect time
+    pub is_mariadb: bool,
+    /// False on servers older than MySQL 8.0 / MariaDB 10.5.2, where columns
+    /// are renamed with `CHANGE COLUMN`
+    pub supports_rename_column: bool,
 }
 
 impl MySqlSession {
-    pub fn new(pool: MySqlPool) -> Self {
+    pub fn new(pool: MySqlPool, version: Option<&str>) -> Self {
         Self {
             pool,
             transaction_conn: Mutex::new(None),
             active_queries: Mutex::new(HashMap::new()),
+            session_variables: Mutex::new(HashMap::new()),
+            is_mariadb: version.is_some_and(is_mariadb_version),
+            supports_rename_column: version.is_none_or(supports_rename_column),
+        }
+    }
+
+    /// Drops the prepared statements cached by the transaction connection
+    /// and the idle pooled ones
+    pub async fn clear_statement_cache(&self) -> EngineResult<()> {
+        let mut tx_guard = self.transaction_conn.lock().await;
+        clear_statement_caches(tx_guard.as_deref_mut(), &self.pool)
+            .await
+            .map_err(|e| EngineError::execution_error(e.to_string()))
+    }
+
+    /// Applies the session variables to a connection taken from the pool
+    async fn apply_session_variables(&self, conn: &mut PoolConnection<MySql>) -> EngineResult<()> {
+        let variables: Vec<(String, String)> = {
+            let variables = self.session_variables.lock().await;
+            variables.iter().map(|(n, v)| (n.clone(), v.clone())).collect()
+        };
+        if variables.is_empty() {
+            return Ok(());
+        }
+
+        // Names are validated identifiers, values are bound
+        let assignments: Vec<String> = variables
+            .iter()
+            .map(|(name, _)| format!("SESSION {} = ?", name))
+            .collect();
+        let statement = format!("SET {}", assignments.join(", "));
+        let mut query = sqlx::query(&statement);
+        for (_, value) in &variables {
+            query = MySqlDriver::bind_variable_value(query, value);
+        }
+        query
+            .execute(&mut **conn)
+            .await
+            .map_err(|e| EngineError::execution_error(e.to_string()))?;
+        Ok(())
+    }
+
+    /// Returns true if a transaction is currently active
+    pub fn has_active_transaction(&self) -> bool {
+        match self.transaction_conn.try_lock() {
+            Ok(guard) => guard.is_some(),
+            Err(_) => true,
         }
     }
 }
@@ -76,6 +213,9 @@ impl MySqlDriver {
             Value::Int(i) => query.bind(i),
             Value::Float(f) => query.bind(f),
             Value::Text(s) => query.bind(s),
+            Value::Timestamp(ts) => query.bind(ts),
+            // UUIDs are usually stored as CHAR(36); sqlx would encode them as 16 bytes
+            Value::Uuid(uuid) => query.bind(uuid.to_string()),
             Value::Bytes(b) => query.bind(b),
             Value::Json(j) => query.bind(j),
             // Fallback for arrays
@@ -92,15 +232,144 @@ impl MySqlDriver {
             .map_err(|e| EngineError::execution_error(e.to_string()))
     }
 
+    /// Binds a variable value, as an integer when it looks like one
+    ///
+    /// MySQL rejects string values for numeric system variables.
+    fn bind_variable_value<'q>(
+        query: sqlx::query::Query<'q, MySql, sqlx::mysql::MySqlArguments>,
+        value: &'q str,
+    ) -> sqlx::query::Query<'q, MySql, sqlx::mysql::MySqlArguments> {
+        match value.trim().parse::<i64>() {
+            Ok(number) => query.bind(number),
+            Err(_) => query.bind(value),
+        }
+    }
+
+    /// Runs `SET [SESSION|GLOBAL] name = ?` on the session
+    ///
+    /// `name` must already be validated. Session values are kept so that
+    /// every pooled connection gets them.
+    async fn apply_variable(
+        &self,
+        session: SessionId,
+        name: String,
+        value: &str,
+        scope: VariableScope,
+    ) -> EngineResult<()> {
+        let mysql_session = self.get_session(session).await?;
+        let statement = match scope {
+            VariableScope::Session => format!("SET SESSION {} = ?", name),
+            VariableScope::Global => format!("SET GLOBAL {} = ?", name),
+            VariableScope::Local => {
+                return Err(EngineError::not_supported(
+                    "MySQL has no transaction-local variables; use the session scope",
+                ));
+            }
+        };
+        let query = Self::bind_variable_value(sqlx::query(&statement), value);
+
+        let mut tx = mysql_session.transaction_conn.lock().await;
+        match tx.as_mut() {
+            Some(conn) => query.execute(&mut **conn).await,
+            None => query.execute(&mysql_session.pool).await,
+        }
+        .map_err(|e| EngineError::execution_error(e.to_string()))?;
+
+        if scope == VariableScope::Session {
+            let mut variables = mysql_session.session_variables.lock().await;
+            variables.insert(name, value.to_string());
+        }
+        Ok(())
+    }
+
+    /// Opens a session whose pooled connections all run `init_statements` first
+    ///
+    /// Lets MySQL-compatible drivers (PlanetScale) set vendor-specific
+    /// variables that are not in `SETTABLE_VARIABLES`.
+    pub(crate) async fn connect_with_init(
+        &self,
+        config: &ConnectionConfig,
+        init_statements: Vec<String>,
+    ) -> EngineResult<SessionId> {
+        let conn_str = Self::connection_string(config)?;
+        let init_statements = Arc::new(init_statements);
+
+        let options = conn_str
+            .parse::<MySqlConnectOptions>()
+            .map_err(|e| EngineError::connection_failed(e.to_string()))?
+            .statement_cache_capacity(config.statement_cache_capacity());
+        let pool = MySqlPoolOptions::new()
+            .max_connections(5)
+            .acquire_timeout(std::time::Duration::from_secs(30))
+            .idle_timeout(config.pool_idle_timeout())
+            .max_lifetime(config.pool_max_lifetime())
+            .after_connect(move |conn, _meta| {
+                let init_statements = Arc::clone(&init_statements);
+                Box::pin(async move {
+                    for statement in init_statements.iter() {
+                        conn.execute(statement.as_str()).await?;
+                    }
+                    Ok(())
+                })
+            })
+            .connect_with(options)
+            .await
+            .map_err(|e| EngineError::connection_failed(e.to_string()))?;
+
+        // A failed probe keeps the MySQL 8 dialect, which MariaDB mostly accepts
+        let version = sqlx::query_scalar::<_, String>("SELECT VERSION()")
+            .fetch_one(&pool)
+            .await
+            .ok();
+
+        let session_id = SessionId::new();
+        let session = Arc::new(MySqlSession::new(pool, version.as_deref()));
+
+        let mut sessions = self.sessions.write().await;
+        sessions.insert(session_id, session);
+
+        Ok(session_id)
+    }
+
+    /// Connection string of a config: its raw string if set, otherwise one
+    /// built from its fields
+    fn connection_string(config: &ConnectionConfig) -> EngineResult<String> {
+        Ok(match config.connection_string_override(&["mysql://", "mariadb://"])? {
+            Some(raw) => raw.to_string(),
+            None => Self::build_connection_string(config),
+        })
+    }
+
     /// Builds a connection string from config
     fn build_connection_string(config: &ConnectionConfig) -> String {
         let db = config.database.as_deref().unwrap_or("mysql");
-        let ssl_mode = if config.ssl { "REQUIRED" } else { "DISABLED" };
+        let ssl_mode = match config.ssl_mode {
+            SslMode::Disabled => "DISABLED",
+            SslMode::Preferred => "PREFERRED",
+            SslMode::Required => "REQUIRED",
+            SslMode::VerifyCa => "VERIFY_CA",
+            SslMode::VerifyIdentity => "VERIFY_IDENTITY",
+        };
 
-        format!(
+        let mut conn_str = format!(
             "mysql://{}:{}@{}:{}/{}?ssl-mode={}",
-            config.username, config.password, config.host, config.port, db, ssl_mode
-        )
+            url_encode(&config.username),
+            url_encode(&config.password),
+            config.host,
+            config.port,
+            url_encode(db),
+            ssl_mode
+        );
+
+        if config.ssl_mode.verifies_certificate() {
+            if let Some(ca_path) = config.ssl_ca_path.as_deref() {
+                conn_str.push_str(&format!("&ssl-ca={}", ca_path));
+            }
+        }
+
+        conn_str.push_str(&format!("&charset={}", url_encode(config.mysql_charset())));
+
+        conn_str
     }
 
     /// Converts a SQLx row to our universal Row type
@@ -181,17 +450,556 @@ impl MySqlDriver {
         Value::Null
     }
 
-    /// Gets column info from a MySqlRow
-    fn get_column_info(row: &MySqlRow) -> Vec<ColumnInfo> {
-        row.columns()
+    /// Gets column info from result columns (of a row or a described statement)
+    fn get_column_info(mysql_columns: &[MySqlColumn]) -> Vec<ColumnInfo> {
+        mysql_columns
             .iter()
-            .map(|col| ColumnInfo {
-                name: col.name().to_string(),
-                data_type: col.type_info().name().to_string(),
-                nullable: true,
+            .map(|col| {
+                let data_type = col.type_info().name().to_string();
+                ColumnInfo {
+                    name: col.name().to_string(),
+                    display_size: ColumnInfo::display_size_for(&data_type),
+                    data_type,
+                    nullable: true,
+                    ..Default::default()
+                }
             })
             .collect()
     }
+
+    /// Describes the result columns of a statement that returned no rows
+    ///
+    /// Best effort: statements that cannot be prepared yield no columns.
+    async fn describe_columns(conn: &mut PoolConnection<MySql>, query: &str) -> Vec<ColumnInfo> {
+        match (&mut **conn).describe(query).await {
+            Ok(describe) => Self::get_column_info(describe.columns()),
+            Err(_) => Vec::new(),
+        }
+    }
+
+    /// Reads every result set of a statement stream in order: one
+    /// `QueryResult` per result set, or per statement without rows
+    async fn collect_result_sets(
+        mut stream: BoxStream<'_, Result<Either<MySqlQueryResult, MySqlRow>, sqlx::Error>>,
+    ) -> EngineResult<Vec<QueryResult>> {
+        use futures::TryStreamExt;
+
+        let mut results = Vec::new();
+        let mut rows: Vec<MySqlRow> = Vec::new();
+        let mut start = Instant::now();
+
+        while let Some(item) = stream.try_next().await.map_err(|e| {
+            let msg = e.to_string();
+            if msg.contains("syntax") {
+                EngineError::syntax_error(msg)
+            } else {
+                EngineError::execution_error(msg)
+            }
+        })? {
+            match item {
+                Either::Right(row) => rows.push(row),
+                Either::Left(done) => {
+                    let execution_time_ms = start.elapsed().as_micros() as f64 / 1000.0;
+                    if rows.is_empty() {
+                        results.push(QueryResult::with_affected_rows(
+                            done.rows_affected(),
+                            execution_time_ms,
+                        ));
+                    } else {
+                        results.push(QueryResult {
+                            columns: Self::get_column_info(rows[0].columns()),
+                            rows: rows.iter().map(Self::convert_row).collect(),
+                            affected_rows: None,
+                            execution_time_ms,
+                            plan_estimate: None,
+                            truncated: false,
+                            estimated_memory_bytes: None,
+                            truncated_values: Vec::new(),
+                        });
+                        rows.clear();
+                    }
+                    start = Instant::now();
+                }
+            }
+        }
+        Ok(results)
+    }
+
+    /// Runs a single statement on the given connection
+    /// Clears the cached prepared statements after DDL ran on `conn`; a
+    /// failure is only logged since the DDL itself succeeded
+    async fn forget_stale_statements(conn: Option<&mut MySqlConnection>, pool: &MySqlPool) {
+        if let Err(e) = clear_statement_caches(conn, pool).await {
+            tracing::warn!(error = %e, "Failed to clear prepared statements after DDL");
+        }
+    }
+
+    async fn run_statement(
+        conn: &mut PoolConnection<MySql>,
+        query: &str,
+        params: &[Value],
+    ) -> EngineResult<QueryResult> {
+        let start = Instant::now();
+
+        let mut sql_query = sqlx::query(query);
+        for param in params {
+            sql_query = Self::bind_param(sql_query, param);
+        }
+
+        if Self::is_select(query) {
+            let mysql_rows: Vec<MySqlRow> = sql_query
+                .fetch_all(&mut **conn)
+                .await
+                .map_err(Self::map_query_error)?;
+
+            let execution_time_ms = start.elapsed().as_micros() as f64 / 1000.0;
+
+            if mysql_rows.is_empty() {
+                return Ok(QueryResult {
+                    columns: Self::describe_columns(conn, query).await,
+                    rows: Vec::new(),
+                    affected_rows: None,
+                    execution_time_ms,
+                    plan_estimate: None,
+                    truncated: false,
+                    estimated_memory_bytes: None,
+                    truncated_values: Vec::new(),
+                });
+            }
+
+            let columns = Self::get_column_info(mysql_rows[0].columns());
+            let rows: Vec<QRow> = mysql_rows.iter().map(Self::convert_row).collect();
+
+            Ok(QueryResult {
+                columns,
+                rows,
+                affected_rows: None,
+                execution_time_ms,
+                plan_estimate: None,
+                truncated: false,
+                estimated_memory_bytes: None,
+                truncated_values: Vec::new(),
+            })
+        } else {
+            let result = sql_query
+                .execute(&mut **conn)
+                .await
+                .map_err(Self::map_query_error)?;
+
+            let execution_time_ms = start.elapsed().as_micros() as f64 / 1000.0;
+
+            Ok(QueryResult::with_affected_rows(
+                result.rows_affected(),
+                execution_time_ms,
+            ))
+        }
+    }
+
+    /// Streams the rows of a single statement in batches
+    ///
+    /// Statements that return no rows run through `run_statement` and are
+    /// reported as one batch.
+    async fn stream_statement(
+        conn: &mut PoolConnection<MySql>,
+        query: &str,
+        batch_size: usize,
+        on_batch: &(dyn Fn(QueryResult) + Send + Sync),
+    ) -> EngineResult<u64> {
+        use futures::TryStreamExt;
+
+        if !Self::is_select(query) {
+            on_batch(Self::run_statement(conn, query, &[]).await?);
+            return Ok(0);
+        }
+
+        let start = Instant::now();
+        let batch_size = batch_size.max(1);
+        let mut columns: Option<Vec<ColumnInfo>> = None;
+        let mut batch: Vec<QRow> = Vec::with_capacity(batch_size);
+        let mut total_rows = 0u64;
+
+        {
+            let mut stream = sqlx::query(query).fetch(&mut **conn);
+            while let Some(mysql_row) = stream.try_next().await.map_err(Self::map_query_error)? {
+                let columns =
+                    columns.get_or_insert_with(|| Self::get_column_info(mysql_row.columns()));
+                batch.push(Self::convert_row(&mysql_row));
+                total_rows += 1;
+
+                if batch.len() == batch_size {
+                    on_batch(QueryResult {
+                        columns: columns.clone(),
+                        rows: std::mem::replace(&mut batch, Vec::with_capacity(batch_size)),
+                        affected_rows: None,
+                        execution_time_ms: start.elapsed().as_micros() as f64 / 1000.0,
+                        plan_estimate: None,
+                        truncated: false,
+                        estimated_memory_bytes: None,
+                        truncated_values: Vec::new(),
+                    });
+                }
+            }
+        }
+
+        if total_rows == 0 || !batch.is_empty() {
+            let columns = match columns {
+                Some(columns) => columns,
+                None => Self::describe_columns(conn, query).await,
+            };
+            on_batch(QueryResult {
+                columns,
+                rows: batch,
+                affected_rows: None,
+                execution_time_ms: start.elapsed().as_micros() as f64 / 1000.0,
+                plan_estimate: None,
+                truncated: false,
+                estimated_memory_bytes: None,
+                truncated_values: Vec::new(),
+            });
+        }
+
+        Ok(total_rows)
+    }
+
+    /// Returns true for statements that produce a result set
+    fn is_select(query: &str) -> bool {
+        let trimmed = sql_safety::strip_leading_comments(query).to_uppercase();
+        trimmed.starts_with("SELECT")
+            || trimmed.starts_with("SHOW")
+            || trimmed.starts_with("DESCRIBE")
+            || trimmed.starts_with("EXPLAIN")
+            // Procedures may return result sets
+            || trimmed.starts_with("CALL")
+    }
+
+    /// Builds a column definition for `CREATE TABLE` and `ADD COLUMN`
+    fn column_definition(column: &NewColumnDef) -> String {
+        let mut definition = format!(
+            "`{}` {}",
+            column.name.replace("`", "``"),
+            column.data_type.trim()
+        );
+        if !column.nullable {
+            definition.push_str(" NOT NULL");
+        }
+        if let Some(default_value) = &column.default_value {
+            definition.push_str(&format!(" DEFAULT {}", default_value));
+        }
+        if column.auto_increment {
+            definition.push_str(" AUTO_INCREMENT");
+        }
+        definition
+    }
+
+    /// `CHANGE COLUMN` statement renaming a column, for servers without
+    /// `RENAME COLUMN`
+    ///
+    /// The statement replaces the whole column definition, so the described
+    /// type, character set, nullability, default and comment are restated,
+    /// along with the `AUTO_INCREMENT` and `ON UPDATE` parts of `extra`
+    /// (`information_schema.COLUMNS.EXTRA`).
+    fn change_column_sql(
+        namespace: &Namespace,
+        table: &str,
+        column: &TableColumn,
+        extra: &str,
+        new_name: &str,
+    ) -> String {
+        let literal = |text: &str| format!("'{}'", text.replace('\\', "\\\\").replace('\'', "''"));
+
+        let mut sql = format!(
+            "ALTER TABLE `{}`.`{}` CHANGE COLUMN `{}` `{}` {}",
+            namespace.database.replace("`", "``"),
+            table.replace("`", "``"),
+            column.name.replace("`", "``"),
+            new_name.replace("`", "``"),
+            column.data_type
+        );
+        if let Some(charset) = &column.charset {
+            sql.push_str(&format!(" CHARACTER SET {}", charset));
+        }
+        sql.push_str(if column.nullable { " NULL" } else { " NOT NULL" });
+        // MySQL 5.7 reports literal defaults unquoted
+        if let Some(default_value) = &column.default_value {
+            let is_timestamp = default_value
+                .to_ascii_uppercase()
+                .starts_with("CURRENT_TIMESTAMP");
+            let default_value = if is_timestamp {
+                default_value.clone()
+            } else {
+                literal(default_value)
+            };
+            sql.push_str(&format!(" DEFAULT {}", default_value));
+        }
+        let extra_lower = extra.to_ascii_lowercase();
+        if extra_lower.contains("auto_increment") {
+            sql.push_str(" AUTO_INCREMENT");
+        }
+        if let Some(start) = extra_lower.find("on update ") {
+            sql.push_str(&format!(" {}", &extra[start..]));
+        }
+        if let Some(comment) = &column.comment {
+            sql.push_str(&format!(" COMMENT {}", literal(comment)));
+        }
+        sql
+    }
+
+    /// Parses the values of an `enum('a','b')` or `set('a','b')` column type
+    ///
+    /// `COLUMN_TYPE` doubles quotes inside values (`'it''s'`); backslash
+    /// escapes are accepted too. Returns `None` for any other type.
+    pub(crate) fn parse_enum_values(column_type: &str) -> Option<Vec<String>> {
+        let column_type = column_type.trim();
+        let lower = column_type.to_ascii_lowercase();
+        let body = if lower.starts_with("enum(") {
+            &column_type[5..]
+        } else if lower.starts_with("set(") {
+            &column_type[4..]
+        } else {
+            return None;
+        };
+        let mut chars = body.strip_suffix(')')?.chars().peekable();
+
+        let mut values = Vec::new();
+        loop {
+            while chars.next_if(|c| c.is_whitespace()).is_some() {}
+            if chars.next()? != '\'' {
+                return None;
+            }
+            let mut value = String::new();
+            loop {
+                match chars.next()? {
+                    '\'' if chars.next_if_eq(&'\'').is_some() => value.push('\''),
+                    '\'' => break,
+                    '\\' => value.push(chars.next()?),
+                    c => value.push(c),
+                }
+            }
+            values.push(value);
+
+            while chars.next_if(|c| c.is_whitespace()).is_some() {}
+            match chars.next() {
+                None => return Some(values),
+                Some(',') => {}
+                Some(_) => return None,
+            }
+        }
+    }
+
+    /// Builds the parameterized `INSERT` for the given (sorted) columns
+    fn insert_sql(namespace: &Namespace, table: &str, keys: &[&String]) -> String {
+        // MySQL uses backticks for identifiers
+        let table_name = format!("`{}`.`{}`", 
+            namespace.database.replace("`", "``"), 
+            table.replace("`", "``")
+        );
+
+        if keys.is_empty() {
+             // MySQL: INSERT INTO table () VALUES ()
+             format!("INSERT INTO {} () VALUES ()", table_name)
+        } else {
+            let cols_str = keys.iter().map(|k| format!("`{}`", k.replace("`", "``"))).collect::<Vec<_>>().join(", ");
+            let params_str = vec!["?"; keys.len()].join(", ");
+            format!("INSERT INTO {} ({}) VALUES ({})", table_name, cols_str, params_str)
+        }
+    }
+
+    /// Builds the statement for `call_routine`: `SELECT` for a function,
+    /// `CALL` for a procedure
+    fn call_routine_sql(
+        namespace: &Namespace,
+        routine: &str,
+        routine_type: RoutineType,
+        arg_count: usize,
+    ) -> String {
+        let quote = |name: &str| format!("`{}`", name.replace("`", "``"));
+        let routine_name = format!("{}.{}", quote(&namespace.database), quote(routine));
+        let params = vec!["?"; arg_count].join(", ");
+        match routine_type {
+            RoutineType::Function => format!("SELECT {}({})", routine_name, params),
+            RoutineType::Procedure => format!("CALL {}({})", routine_name, params),
+        }
+    }
+
+    /// Builds the `CREATE TABLE` statement for `create_table`
+    fn create_table_sql(namespace: &Namespace, schema: &NewTableSchema) -> String {
+        let quote = |name: &str| format!("`{}`", name.replace("`", "``"));
+
+        let mut definitions: Vec<String> =
+            schema.columns.iter().map(Self::column_definition).collect();
+
+        let primary_key = schema.primary_key_columns();
+        if !primary_key.is_empty() {
+            let columns: Vec<String> = primary_key.into_iter().map(quote).collect();
+            definitions.push(format!("PRIMARY KEY ({})", columns.join(", ")));
+        }
+
+        let if_not_exists = if schema.if_not_exists { "IF NOT EXISTS " } else { "" };
+        format!(
+            "CREATE TABLE {}{}.{} ({})",
+            if_not_exists,
+            quote(&namespace.database),
+            quote(&schema.table_name),
+            definitions.join(", ")
+        )
+    }
+
+    /// Reads the estimates of an `EXPLAIN FORMAT=JSON` plan
+    ///
+    /// The cost is the whole query's; rows are those produced by the last
+    /// table of the join, which is what the query returns before any
+    /// grouping or `LIMIT`.
+    fn parse_plan_estimate(plan: &serde_json::Value) -> PlanEstimate {
+        let as_f64 = |value: &serde_json::Value| match value {
+            serde_json::Value::String(text) => text.parse::<f64>().ok(),
+            other => other.as_f64(),
+        };
+
+        let block = &plan["query_block"];
+        let table = match block["nested_loop"]
+            .as_array()
+            .and_then(|tables| tables.last())
+        {
+            Some(last) => &last["table"],
+            None => &block["table"],
+        };
+
+        PlanEstimate {
+            total_cost: as_f64(&block["cost_info"]["query_cost"]),
+            estimated_rows: as_f64(&table["rows_produced_per_join"]),
+        }
+    }
+
+    fn map_query_error(e: sqlx::Error) -> EngineError {
+        let msg = e.to_string();
+        let number = e
+            .as_database_error()
+            .and_then(|db| db.try_downcast_ref::<sqlx::mysql::MySqlDatabaseError>())
+            .map(|db| db.number().to_string());
+        if let Some(error) =
+            number.and_then(|number| EngineError::from_server_code(&number, &msg))
+        {
+            return error;
+        }
+        if msg.contains("syntax") {
+            EngineError::syntax_error(msg)
+        } else {
+            EngineError::execution_error(msg)
+        }
+    }
+
+    /// Fills schema metadata for columns read from a known table.
+    ///
+    /// SQLx does not expose the origin table of MySQL result columns, so this
+    /// is only possible when the caller knows which table was queried.
+    /// Best effort: failures leave the inferred metadata untouched.
+    async fn enrich_column_info(
+        pool: &MySqlPool,
+        database: &str,
+        table: &str,
+        columns: &mut [ColumnInfo],
+    ) {
+        let metadata = sqlx::query(
+            r#"
+            SELECT CAST(COLUMN_NAME AS CHAR) AS column_name,
+                   IS_NULLABLE = 'YES' AS nullable,
+                   CAST(COLUMN_COMMENT AS CHAR) AS column_comment,
+                   CAST(EXTRA AS CHAR) AS extra,
+                   CAST(CHARACTER_SET_NAME AS CHAR) AS character_set,
+                   CHARACTER_MAXIMUM_LENGTH AS max_length
+            FROM information_schema.COLUMNS
+            WHERE TABLE_SCHEMA = ? AND TABLE_NAME = ?
+            "#,
+        )
+        .bind(database)
+        .bind(table)
+        .fetch_all(pool)
+        .await;
+
+        let Ok(metadata) = metadata else {
+            return;
+        };
+
+        for meta in &metadata {
+            let name: String = meta.try_get("column_name").unwrap_or_default();
+            let Some(column) = columns.iter_mut().find(|c| c.name == name) else {
+                continue;
+            };
+
+            let extra: String = meta.try_get("extra").unwrap_or_default();
+            let extra = extra.to_lowercase();
+            let nullable: i64 = meta.try_get("nullable").unwrap_or(1);
+            let comment: Option<String> = meta.try_get("column_comment").unwrap_or(None);
+
+            column.nullable = nullable != 0;
+            column.comment = comment.filter(|c| !c.is_empty());
+            column.is_auto_increment = extra.contains("auto_increment");
+            column.is_generated = extra.contains("generated");
+            column.character_set = meta.try_get("character_set").unwrap_or(None);
+            if let Ok(Some(max_length)) = meta.try_get::<Option<i64>, _>("max_length") {
+                column.display_size = u32::try_from(max_length).ok();
+            }
+        }
+    }
+
+    /// Folds `SHOW GRANTS` output into a privilege report
+    ///
+    /// Returns the report and whether any privilege was granted on `*.*`, in
+    /// which case the databases are not listed in the grants themselves.
+    fn parse_grants(grants: &[String]) -> (PrivilegeReport, bool) {
+        let mut report = PrivilegeReport::default();
+        let mut has_global = false;
+
+        for grant in grants {
+            // Role grants (`GRANT role TO user`) have no ON clause
+            let Some(rest) = grant.strip_prefix("GRANT ") else { continue };
+            let Some((privileges, rest)) = rest.split_once(" ON ") else { continue };
+            let Some((object, _)) = rest.split_once(" TO ") else { continue };
+
+            // Drop column lists such as `SELECT (`a`, `b`)` before splitting
+            let mut stripped = String::with_capacity(privileges.len());
+            let mut depth = 0;
+            for c in privileges.chars() {
+                match c {
+                    '(' => depth += 1,
+                    ')' => depth -= 1,
+                    _ if depth == 0 => stripped.push(c),
+                    _ => {}
+                }
+            }
+            let privileges: Vec<String> = stripped
+                .split(',')
+                .map(|p| p.trim().to_uppercase())
+                .filter(|p| !p.is_empty() && p != "USAGE")
+                .collect();
+            if privileges.is_empty() {
+                continue;
+            }
+
+            let all = privileges.iter().any(|p| p == "ALL" || p == "ALL PRIVILEGES");
+            let has = |name: &str| all || privileges.iter().any(|p| p == name);
+            report.can_select |= has("SELECT");
+            report.can_insert |= has("INSERT");
+            report.can_update |= has("UPDATE");
+            report.can_delete |= has("DELETE");
+            report.can_create |= has("CREATE");
+            report.can_drop |= has("DROP");
+
+            let object = object.trim();
+            if object == "*.*" {
+                has_global = true;
+                report.is_superuser |= all || privileges.iter().any(|p| p == "SUPER");
+            } else if let Some((database, _)) = object.split_once('.') {
+                let database = database.trim_matches('`').replace("``", "`");
+                if !report.granted_databases.contains(&database) {
+                    report.granted_databases.push(database);
+                }
+            }
+        }
+
+        report.granted_databases.sort();
+        (report, has_global)
+    }
 }
 
 impl Default for MySqlDriver {
@@ -210,8 +1018,19 @@ impl DataEngine for MySqlDriver {
         "MySQL / MariaDB"
     }
 
+    async fn dialect_id(&self, session: SessionId) -> &'static str {
+        match self.get_session(session).await {
+            Ok(mysql_session) if mysql_session.is_mariadb => "mariadb",
+            _ => self.driver_id(),
+        }
+    }
+
+    fn default_port(&self) -> u16 {
+        3306
+    }
+
     async fn test_connection(&self, config: &ConnectionConfig) -> EngineResult<()> {
-        let conn_str = Self::build_connection_string(config);
+        let conn_str = Self::connection_string(config)?;
 
         let pool = MySqlPoolOptions::new()
             .max_connections(1)
@@ -220,7 +1039,12 @@ impl DataEngine for MySqlDriver {
             .await
             .map_err(|e| {
                 let msg = e.to_string();
-                if msg.contains("Access denied") {
+                let number = e
+                    .as_database_error()
+                    .and_then(|db| db.try_downcast_ref::<sqlx::mysql::MySqlDatabaseError>())
+                    .map(|db| db.number());
+                // ER_ACCESS_DENIED_ERROR
+                if number == Some(1045) {
                     EngineError::auth_failed(msg)
                 } else {
                     EngineError::connection_failed(msg)
@@ -237,22 +1061,7 @@ impl DataEngine for MySqlDriver {
     }
 
     async fn connect(&self, config: &ConnectionConfig) -> EngineResult<SessionId> {
-        let conn_str = Self::build_connection_string(config);
-
-        let pool = MySqlPoolOptions::new()
-            .max_connections(5)
-            .acquire_timeout(std::time::Duration::from_secs(30))
-            .connect(&conn_str)
-            .await
-            .map_err(|e| EngineError::connection_failed(e.to_string()))?;
-
-        let session_id = SessionId::new();
-        let session = Arc::new(MySqlSession::new(pool));
-
-        let mut sessions = self.sessions.write().await;
-        sessions.insert(session_id, session);
-
-        Ok(session_id)
+        self.connect_with_init(config, Vec::new()).await
     }
 
     async fn disconnect(&self, session: SessionId) -> EngineResult<()> {
@@ -272,12 +1081,32 @@ impl DataEngine for MySqlDriver {
         Ok(())
     }
 
-    async fn list_namespaces(&self, session: SessionId) -> EngineResult<Vec<Namespace>> {
-        let mysql_session = self.get_session(session).await?;
-        let pool = &mysql_session.pool;
+    async fn rebind_session(&self, from: SessionId, to: SessionId) -> EngineResult<()> {
+        let mut sessions = self.sessions.write().await;
+        let session = sessions
+            .remove(&from)
+            .ok_or_else(|| EngineError::session_not_found(from.0.to_string()))?;
+        if let Some(stale) = sessions.insert(to, session) {
+            sessions.insert(from, stale);
+        }
+        Ok(())
+    }
 
-        let rows: Vec<(String,)> = sqlx::query_as(
-            r#"
+    async fn ping(&self, session: SessionId) -> EngineResult<()> {
+        let session = self.get_session(session).await?;
+        sqlx::query("SELECT 1")
+            .execute(&session.pool)
+            .await
+            .map_err(|e| EngineError::connection_failed(e.to_string()))?;
+        Ok(())
+    }
+
+    async fn list_namespaces(&self, session: SessionId) -> EngineResult<Vec<Namespace>> {
+        let mysql_session = self.get_session(session).await?;
+        let pool = &mysql_session.pool;
+
+        let rows: Vec<(String,)> = sqlx::query_as(
+            r#"
             SELECT schema_name
             FROM information_schema.schemata
             WHERE schema_name NOT IN ('information_schema', 'mysql', 'performance_schema', 'sys')
@@ -293,6 +1122,42 @@ impl DataEngine for MySqlDriver {
         Ok(namespaces)
     }
 
+    /// DDL commits implicitly in MySQL, so this runs outside any active
+    /// transaction, on a pooled connection
+    async fn create_namespace(
+        &self,
+        session: SessionId,
+        namespace: &Namespace,
+    ) -> EngineResult<()> {
+        let mysql_session = self.get_session(session).await?;
+
+        let sql = format!("CREATE DATABASE `{}`", namespace.database.replace("`", "``"));
+        sqlx::query(&sql)
+            .execute(&mysql_session.pool)
+            .await
+            .map_err(|e| EngineError::execution_error(e.to_string()))?;
+        Ok(())
+    }
+
+    /// Dropping a database always drops its tables; `cascade` is ignored
+    async fn drop_namespace(
+        &self,
+        session: SessionId,
+        namespace: &Namespace,
+        cascade: bool,
+    ) -> EngineResult<()> {
+        let _ = cascade;
+        let mysql_session = self.get_session(session).await?;
+
+        let sql = format!("DROP DATABASE `{}`", namespace.database.replace("`", "``"));
+        sqlx::query(&sql)
+            .execute(&mysql_session.pool)
+            .await
+            .map_err(|e| EngineError::execution_error(e.to_string()))?;
+        Self::forget_stale_statements(None, &mysql_session.pool).await;
+        Ok(())
+    }
+
     async fn list_collections(
         &self,
         session: SessionId,
@@ -333,163 +1198,310 @@ impl DataEngine for MySqlDriver {
         Ok(collections)
     }
 
+    async fn list_routines(
+        &self,
+        session: SessionId,
+        namespace: &Namespace,
+    ) -> EngineResult<Vec<Routine>> {
+        let mysql_session = self.get_session(session).await?;
+
+        // Parameter 0 of a function is its return value
+        let rows: Vec<(String, String, Option<String>, String)> = sqlx::query_as(
+            r#"
+            SELECT
+                CAST(r.ROUTINE_NAME AS CHAR),
+                CAST(r.ROUTINE_TYPE AS CHAR),
+                CAST(r.DTD_IDENTIFIER AS CHAR),
+                CAST(COALESCE(GROUP_CONCAT(
+                    CONCAT_WS(' ', p.PARAMETER_MODE, p.PARAMETER_NAME, p.DTD_IDENTIFIER)
+                    ORDER BY p.ORDINAL_POSITION SEPARATOR ', '
+                ), '') AS CHAR)
+            FROM information_schema.ROUTINES r
+            LEFT JOIN information_schema.PARAMETERS p
+                ON p.SPECIFIC_SCHEMA = r.ROUTINE_SCHEMA
+                AND p.SPECIFIC_NAME = r.SPECIFIC_NAME
+                AND p.ORDINAL_POSITION > 0
+            WHERE r.ROUTINE_SCHEMA = ?
+            GROUP BY r.ROUTINE_NAME, r.ROUTINE_TYPE, r.DTD_IDENTIFIER
+            ORDER BY r.ROUTINE_NAME
+            "#,
+        )
+        .bind(&namespace.database)
+        .fetch_all(&mysql_session.pool)
+        .await
+        .map_err(|e| EngineError::execution_error(e.to_string()))?;
+
+        Ok(rows
+            .into_iter()
+            .map(|(name, kind, return_type, arguments)| {
+                let routine_type = if kind.eq_ignore_ascii_case("PROCEDURE") {
+                    RoutineType::Procedure
+                } else {
+                    RoutineType::Function
+                };
+                Routine {
+                    namespace: namespace.clone(),
+                    name,
+                    routine_type,
+                    return_type: return_type.filter(|_| routine_type == RoutineType::Function),
+                    arguments,
+                }
+            })
+            .collect())
+    }
+
+    /// Loads the tables of every database in one round-trip. Databases
+    /// without tables are kept (with an empty list) through the `LEFT JOIN`.
+    async fn list_all_collections(
+        &self,
+        session: SessionId,
+    ) -> EngineResult<HashMap<Namespace, Vec<Collection>>> {
+        let mysql_session = self.get_session(session).await?;
+        let pool = &mysql_session.pool;
+
+        // Cast to CHAR to avoid BINARY type mismatch with Rust String
+        let rows: Vec<(String, Option<String>, Option<String>)> = sqlx::query_as(
+            r#"
+            SELECT CAST(s.SCHEMA_NAME AS CHAR) AS schema_name,
+                   CAST(t.TABLE_NAME AS CHAR) AS table_name,
+                   CAST(t.TABLE_TYPE AS CHAR) AS table_type
+            FROM information_schema.SCHEMATA s
+            LEFT JOIN information_schema.TABLES t ON t.TABLE_SCHEMA = s.SCHEMA_NAME
+            WHERE s.SCHEMA_NAME NOT IN ('information_schema', 'mysql', 'performance_schema', 'sys')
+            ORDER BY s.SCHEMA_NAME, t.TABLE_NAME
+            "#,
+        )
+        .fetch_all(pool)
+        .await
+        .map_err(|e| EngineError::execution_error(e.to_string()))?;
+
+        let mut collections: HashMap<Namespace, Vec<Collection>> = HashMap::new();
+        for (db, name, table_type) in rows {
+            let namespace = Namespace::new(db);
+            let entry = collections.entry(namespace.clone()).or_default();
+            let Some(name) = name else {
+                continue;
+            };
+            let collection_type = match table_type.as_deref() {
+                Some("VIEW") => CollectionType::View,
+                _ => CollectionType::Table,
+            };
+            entry.push(Collection {
+                namespace,
+                name,
+                collection_type,
+            });
+        }
+
+        Ok(collections)
+    }
+
     /// Executes a query and returns the result
-    /// 
-    /// Routes to transaction connection if active, otherwise uses pool.
     async fn execute(
         &self,
         session: SessionId,
         query: &str,
         query_id: QueryId,
     ) -> EngineResult<QueryResult> {
-        let mysql_session = self.get_session(session).await?;
-        let start = Instant::now();
+        self.execute_with_params(session, query, &[], query_id).await
+    }
 
-        let trimmed = query.trim().to_uppercase();
-        let is_select = trimmed.starts_with("SELECT")
-            || trimmed.starts_with("SHOW")
-            || trimmed.starts_with("DESCRIBE")
-            || trimmed.starts_with("EXPLAIN");
+    /// Binds `params` to the `?` placeholders, in order
+    ///
+    /// Routes to transaction connection if active, otherwise uses pool.
+    async fn execute_with_params(
+        &self,
+        session: SessionId,
+        query: &str,
+        params: &[Value],
+        query_id: QueryId,
+    ) -> EngineResult<QueryResult> {
+        let mysql_session = self.get_session(session).await?;
 
         let mut tx_guard = mysql_session.transaction_conn.lock().await;
-        let result = if let Some(ref mut conn) = *tx_guard {
-            let connection_id = Self::fetch_connection_id(conn).await?;
-            {
-                let mut active = mysql_session.active_queries.lock().await;
-                active.insert(query_id, connection_id);
+        let mut pooled: Option<PoolConnection<MySql>> = None;
+        let conn = match tx_guard.as_mut() {
+            Some(conn) => conn,
+            None => {
+                let conn = pooled.insert(
+                    mysql_session
+                        .pool
+                        .acquire()
+                        .await
+                        .map_err(|e| EngineError::connection_failed(e.to_string()))?,
+                );
+                mysql_session.apply_session_variables(conn).await?;
+                conn
             }
+        };
 
-            let result = if is_select {
-                let mysql_rows: Vec<MySqlRow> = sqlx::query(query)
-                    .fetch_all(&mut **conn)
-                    .await
-                    .map_err(|e| {
-                        let msg = e.to_string();
-                        if msg.contains("syntax") {
-                            EngineError::syntax_error(msg)
-                        } else {
-                            EngineError::execution_error(msg)
-                        }
-                    })?;
-
-                let execution_time_ms = start.elapsed().as_micros() as f64 / 1000.0;
-
-                if mysql_rows.is_empty() {
-                    Ok(QueryResult {
-                        columns: Vec::new(),
-                        rows: Vec::new(),
-                        affected_rows: None,
-                        execution_time_ms,
-                    })
-                } else {
-                    let columns = Self::get_column_info(&mysql_rows[0]);
-                    let rows: Vec<QRow> = mysql_rows.iter().map(Self::convert_row).collect();
+        let connection_id = Self::fetch_connection_id(conn).await?;
+        {
+            let mut active = mysql_session.active_queries.lock().await;
+            active.insert(query_id, connection_id);
+        }
 
-                    Ok(QueryResult {
-                        columns,
-                        rows,
-                        affected_rows: None,
-                        execution_time_ms,
-                    })
-                }
-            } else {
-                let result = sqlx::query(query)
-                    .execute(&mut **conn)
-                    .await
-                    .map_err(|e| {
-                        let msg = e.to_string();
-                        if msg.contains("syntax") {
-                            EngineError::syntax_error(msg)
-                        } else {
-                            EngineError::execution_error(msg)
-                        }
-                    })?;
-
-                let execution_time_ms = start.elapsed().as_micros() as f64 / 1000.0;
-
-                Ok(QueryResult::with_affected_rows(
-                    result.rows_affected(),
-                    execution_time_ms,
-                ))
-            };
+        let result = Self::run_statement(conn, query, params).await;
+        if result.is_ok() && sql_safety::is_schema_change(query) {
+            Self::forget_stale_statements(Some(&mut **conn), &mysql_session.pool).await;
+        }
 
-            let mut active = mysql_session.active_queries.lock().await;
-            active.remove(&query_id);
-            result
-        } else {
-            let mut conn = mysql_session
-                .pool
-                .acquire()
-                .await
-                .map_err(|e| EngineError::connection_failed(e.to_string()))?;
-            let connection_id = Self::fetch_connection_id(&mut conn).await?;
-            {
-                let mut active = mysql_session.active_queries.lock().await;
-                active.insert(query_id, connection_id);
-            }
+        let mut active = mysql_session.active_queries.lock().await;
+        active.remove(&query_id);
+        result
+    }
 
-            let result = if is_select {
-                let mysql_rows: Vec<MySqlRow> = sqlx::query(query)
-                    .fetch_all(&mut *conn)
-                    .await
-                    .map_err(|e| {
-                        let msg = e.to_string();
-                        if msg.contains("syntax") {
-                            EngineError::syntax_error(msg)
-                        } else {
-                            EngineError::execution_error(msg)
-                        }
-                    })?;
-
-                let execution_time_ms = start.elapsed().as_micros() as f64 / 1000.0;
-
-                if mysql_rows.is_empty() {
-                    Ok(QueryResult {
-                        columns: Vec::new(),
-                        rows: Vec::new(),
-                        affected_rows: None,
-                        execution_time_ms,
-                    })
-                } else {
-                    let columns = Self::get_column_info(&mysql_rows[0]);
-                    let rows: Vec<QRow> = mysql_rows.iter().map(Self::convert_row).collect();
+    async fn call_routine(
+        &self,
+        session: SessionId,
+        namespace: &Namespace,
+        routine: &str,
+        routine_type: RoutineType,
+        args: &[Value],
+        query_id: QueryId,
+    ) -> EngineResult<Vec<QueryResult>> {
+        let sql = Self::call_routine_sql(namespace, routine, routine_type, args.len());
+        if routine_type == RoutineType::Function {
+            return Ok(vec![self.execute_with_params(session, &sql, args, query_id).await?]);
+        }
 
-                    Ok(QueryResult {
-                        columns,
-                        rows,
-                        affected_rows: None,
-                        execution_time_ms,
-                    })
-                }
-            } else {
-                let result = sqlx::query(query)
-                    .execute(&mut *conn)
-                    .await
-                    .map_err(|e| {
-                        let msg = e.to_string();
-                        if msg.contains("syntax") {
-                            EngineError::syntax_error(msg)
-                        } else {
-                            EngineError::execution_error(msg)
-                        }
-                    })?;
-
-                let execution_time_ms = start.elapsed().as_micros() as f64 / 1000.0;
-
-                Ok(QueryResult::with_affected_rows(
-                    result.rows_affected(),
-                    execution_time_ms,
-                ))
-            };
+        // A procedure may return any number of result sets, which `fetch_all`
+        // would cut to the first one
+        let mysql_session = self.get_session(session).await?;
+
+        let mut tx_guard = mysql_session.transaction_conn.lock().await;
+        let mut pooled: Option<PoolConnection<MySql>> = None;
+        let conn = match tx_guard.as_mut() {
+            Some(conn) => conn,
+            None => {
+                let conn = pooled.insert(
+                    mysql_session
+                        .pool
+                        .acquire()
+                        .await
+                        .map_err(|e| EngineError::connection_failed(e.to_string()))?,
+                );
+                mysql_session.apply_session_variables(conn).await?;
+                conn
+            }
+        };
 
+        let connection_id = Self::fetch_connection_id(conn).await?;
+        {
             let mut active = mysql_session.active_queries.lock().await;
-            active.remove(&query_id);
-            result
+            active.insert(query_id, connection_id);
+        }
+
+        let mut query = sqlx::query(&sql);
+        for arg in args {
+            query = Self::bind_param(query, arg);
+        }
+        // `Executor::fetch_many` runs the single CALL statement and yields a
+        // `Left` summary after each result set; `Query::fetch_many` is
+        // deprecated because it targets several statements per query
+        let outcome = Self::collect_result_sets((&mut **conn).fetch_many(query)).await;
+
+        let mut active = mysql_session.active_queries.lock().await;
+        active.remove(&query_id);
+
+        outcome
+    }
+
+    /// Reads rows through a cursor on the transaction connection if one is
+    /// active, otherwise on a pooled connection.
+    async fn execute_stream(
+        &self,
+        session: SessionId,
+        query: &str,
+        query_id: QueryId,
+        batch_size: usize,
+        on_batch: &(dyn Fn(QueryResult) + Send + Sync),
+    ) -> EngineResult<u64> {
+        let mysql_session = self.get_session(session).await?;
+
+        let mut tx_guard = mysql_session.transaction_conn.lock().await;
+        let mut pooled: Option<PoolConnection<MySql>> = None;
+        let conn = match tx_guard.as_mut() {
+            Some(conn) => conn,
+            None => {
+                let conn = pooled.insert(
+                    mysql_session
+                        .pool
+                        .acquire()
+                        .await
+                        .map_err(|e| EngineError::connection_failed(e.to_string()))?,
+                );
+                mysql_session.apply_session_variables(conn).await?;
+                conn
+            }
         };
 
+        let connection_id = Self::fetch_connection_id(conn).await?;
+        {
+            let mut active = mysql_session.active_queries.lock().await;
+            active.insert(query_id, connection_id);
+        }
+
+        let result = Self::stream_statement(conn, query, batch_size, on_batch).await;
+
+        let mut active = mysql_session.active_queries.lock().await;
+        active.remove(&query_id);
         result
     }
 
+    /// Sends the whole script through the text protocol, so multiple
+    /// statements and the result sets of stored procedures (`CALL`) come
+    /// back in order, one `QueryResult` per result set or statement.
+    ///
+    /// A `CALL` ends with an extra status result carrying the affected rows.
+    async fn execute_multi(
+        &self,
+        session: SessionId,
+        query: &str,
+        query_id: QueryId,
+    ) -> EngineResult<Vec<QueryResult>> {
+        let mysql_session = self.get_session(session).await?;
+
+        let mut tx_guard = mysql_session.transaction_conn.lock().await;
+        let mut pooled: Option<PoolConnection<MySql>> = None;
+        let conn = match tx_guard.as_mut() {
+            Some(conn) => conn,
+            None => {
+                let conn = pooled.insert(
+                    mysql_session
+                        .pool
+                        .acquire()
+                        .await
+                        .map_err(|e| EngineError::connection_failed(e.to_string()))?,
+                );
+                mysql_session.apply_session_variables(conn).await?;
+                conn
+            }
+        };
+
+        let connection_id = Self::fetch_connection_id(conn).await?;
+        {
+            let mut active = mysql_session.active_queries.lock().await;
+            active.insert(query_id, connection_id);
+        }
+
+        let outcome =
+            Self::collect_result_sets(sqlx::raw_sql(query).fetch_many(&mut **conn)).await;
+        // A script that cannot be split is assumed to change the schema
+        let schema_changed = sql_safety::split_statements(self.driver_id(), query)
+            .map_or(true, |statements| {
+                statements.iter().any(|s| sql_safety::is_schema_change(s))
+            });
+        if outcome.is_ok() && schema_changed {
+            Self::forget_stale_statements(Some(&mut **conn), &mysql_session.pool).await;
+        }
+
+        let mut active = mysql_session.active_queries.lock().await;
+        active.remove(&query_id);
+
+        outcome
+    }
+
     async fn describe_table(
         &self,
         session: SessionId,
@@ -501,14 +1513,27 @@ impl DataEngine for MySqlDriver {
 
         let database = &namespace.database;
         // Cast to CHAR to avoid BINARY type mismatch with Rust String
-        let column_rows: Vec<(String, String, String, Option<String>, String)> = sqlx::query_as(
+        // CHARACTER_SET_NAME lets the UI flag text columns that are not
+        // utf8mb4, which silently mangle 4-byte characters such as emoji
+        #[allow(clippy::type_complexity)]
+        let column_rows: Vec<(
+            String,
+            String,
+            String,
+            Option<String>,
+            String,
+            Option<String>,
+            Option<String>,
+        )> = sqlx::query_as(
             r#"
-            SELECT 
+            SELECT
                 CAST(c.COLUMN_NAME AS CHAR) AS column_name,
                 CAST(c.COLUMN_TYPE AS CHAR) AS column_type,
                 CAST(c.IS_NULLABLE AS CHAR) AS is_nullable,
                 CAST(c.COLUMN_DEFAULT AS CHAR) AS column_default,
-                CAST(c.COLUMN_KEY AS CHAR) AS column_key
+                CAST(c.COLUMN_KEY AS CHAR) AS column_key,
+                CAST(NULLIF(c.COLUMN_COMMENT, '') AS CHAR) AS column_comment,
+                CAST(c.CHARACTER_SET_NAME AS CHAR) AS character_set
             FROM information_schema.COLUMNS c
             WHERE c.TABLE_SCHEMA = ? AND c.TABLE_NAME = ?
             ORDER BY c.ORDINAL_POSITION
@@ -524,25 +1549,36 @@ impl DataEngine for MySqlDriver {
         let mut pk_columns: Vec<String> = Vec::new();
         let columns: Vec<TableColumn> = column_rows
             .into_iter()
-            .map(|(name, data_type, is_nullable, default_value, column_key)| {
-                let is_primary_key = column_key == "PRI";
-                if is_primary_key {
-                    pk_columns.push(name.clone());
-                }
-                TableColumn {
-                    name,
-                    data_type,
-                    nullable: is_nullable == "YES",
-                    default_value,
-                    is_primary_key,
-                }
-            })
+            .map(
+                |(name, data_type, is_nullable, default_value, column_key, comment, charset)| {
+                    let is_primary_key = column_key == "PRI";
+                    if is_primary_key {
+                        pk_columns.push(name.clone());
+                    }
+                    TableColumn {
+                        allowed_values: Self::parse_enum_values(&data_type),
+                        name,
+                        data_type,
+                        nullable: is_nullable == "YES",
+                        default_value,
+                        is_primary_key,
+                        comment,
+                        charset,
+                    }
+                },
+            )
             .collect();
 
         // Get row count estimate from table_rows (u64 for BIGINT UNSIGNED)
-        let count_row: Option<(u64,)> = sqlx::query_as(
+        // and the table comment, which views report as 'VIEW'
+        let count_row: Option<(Option<u64>, Option<String>)> = sqlx::query_as(
             r#"
-            SELECT TABLE_ROWS
+            SELECT
+                TABLE_ROWS,
+                CAST(
+                    CASE WHEN TABLE_TYPE = 'VIEW' THEN NULL ELSE NULLIF(TABLE_COMMENT, '') END
+                    AS CHAR
+                ) AS table_comment
             FROM information_schema.TABLES
             WHERE TABLE_SCHEMA = ? AND TABLE_NAME = ?
             "#,
@@ -553,28 +1589,375 @@ impl DataEngine for MySqlDriver {
         .await
         .map_err(|e| EngineError::execution_error(e.to_string()))?;
 
-        let row_count_estimate = count_row.map(|(c,)| c);
+        let (row_count_estimate, comment) = count_row.unwrap_or_default();
 
         Ok(TableSchema {
             columns,
             primary_key: if pk_columns.is_empty() { None } else { Some(pk_columns) },
             row_count_estimate,
+            comment,
         })
     }
 
+    async fn describe_namespace(
+        &self,
+        session: SessionId,
+        namespace: &Namespace,
+    ) -> EngineResult<HashMap<String, TableSchema>> {
+        let mysql_session = self.get_session(session).await?;
+        let pool = &mysql_session.pool;
+
+        let database = &namespace.database;
+        // Cast to CHAR to avoid BINARY type mismatch with Rust String
+        #[allow(clippy::type_complexity)]
+        let column_rows: Vec<(
+            String,
+            String,
+            String,
+            String,
+            Option<String>,
+            String,
+            Option<String>,
+            Option<String>,
+        )> = sqlx::query_as(
+            r#"
+            SELECT
+                CAST(c.TABLE_NAME AS CHAR) AS table_name,
+                CAST(c.COLUMN_NAME AS CHAR) AS column_name,
+                CAST(c.COLUMN_TYPE AS CHAR) AS column_type,
+                CAST(c.IS_NULLABLE AS CHAR) AS is_nullable,
+                CAST(c.COLUMN_DEFAULT AS CHAR) AS column_default,
+                CAST(c.COLUMN_KEY AS CHAR) AS column_key,
+                CAST(NULLIF(c.COLUMN_COMMENT, '') AS CHAR) AS column_comment,
+                CAST(c.CHARACTER_SET_NAME AS CHAR) AS character_set
+            FROM information_schema.COLUMNS c
+            WHERE c.TABLE_SCHEMA = ?
+            ORDER BY c.TABLE_NAME, c.ORDINAL_POSITION
+            "#,
+        )
+        .bind(database)
+        .fetch_all(pool)
+        .await
+        .map_err(|e| EngineError::execution_error(e.to_string()))?;
+
+        let count_rows: Vec<(String, Option<u64>, Option<String>)> = sqlx::query_as(
+            r#"
+            SELECT
+                CAST(TABLE_NAME AS CHAR) AS table_name,
+                TABLE_ROWS,
+                CAST(
+                    CASE WHEN TABLE_TYPE = 'VIEW' THEN NULL ELSE NULLIF(TABLE_COMMENT, '') END
+                    AS CHAR
+                ) AS table_comment
+            FROM information_schema.TABLES
+            WHERE TABLE_SCHEMA = ?
+            "#,
+        )
+        .bind(database)
+        .fetch_all(pool)
+        .await
+        .map_err(|e| EngineError::execution_error(e.to_string()))?;
+
+        let table_info: HashMap<String, (Option<u64>, Option<String>)> = count_rows
+            .into_iter()
+            .map(|(table, count, comment)| (table, (count, comment)))
+            .collect();
+
+        let mut schemas: HashMap<String, TableSchema> = HashMap::new();
+        for (table, name, data_type, is_nullable, default_value, column_key, comment, charset) in
+            column_rows
+        {
+            let entry = schemas.entry(table.clone()).or_insert_with(|| {
+                let (row_count_estimate, table_comment) =
+                    table_info.get(&table).cloned().unwrap_or_default();
+                TableSchema {
+                    columns: Vec::new(),
+                    primary_key: None,
+                    row_count_estimate,
+                    comment: table_comment,
+                }
+            });
+
+            let is_primary_key = column_key == "PRI";
+            if is_primary_key {
+                entry
+                    .primary_key
+                    .get_or_insert_with(Vec::new)
+                    .push(name.clone());
+            }
+            entry.columns.push(TableColumn {
+                allowed_values: Self::parse_enum_values(&data_type),
+                name,
+                data_type,
+                nullable: is_nullable == "YES",
+                default_value,
+                is_primary_key,
+                comment,
+                charset,
+            });
+        }
+
+        Ok(schemas)
+    }
+
     async fn preview_table(
         &self,
         session: SessionId,
         namespace: &Namespace,
         table: &str,
         limit: u32,
+        lock_rows: Option<RowLockMode>,
     ) -> EngineResult<QueryResult> {
         // Use backticks for MySQL identifier quoting
-        let query = format!(
+        let mut query = format!(
             "SELECT * FROM `{}`.`{}` LIMIT {}",
             namespace.database, table, limit
         );
-        self.execute(session, &query, QueryId::new()).await
+        if let Some(mode) = lock_rows {
+            query = self.lock_rows_query(session, &query, mode).await?;
+        }
+        let mut result = self.execute(session, &query, QueryId::new()).await?;
+
+        let mysql_session = self.get_session(session).await?;
+        Self::enrich_column_info(
+            &mysql_session.pool,
+            &namespace.database,
+            table,
+            &mut result.columns,
+        )
+        .await;
+
+        Ok(result)
+    }
+
+    async fn fetch_value(
+        &self,
+        session: SessionId,
+        namespace: &Namespace,
+        table: &str,
+        primary_key: &RowData,
+        column: &str,
+    ) -> EngineResult<Value> {
+        let mysql_session = self.get_session(session).await?;
+
+        if primary_key.columns.is_empty() {
+            return Err(EngineError::execution_error("Primary key required to fetch a value".to_string()));
+        }
+
+        let quote = |name: &str| format!("`{}`", name.replace("`", "``"));
+
+        let mut pk_keys: Vec<&String> = primary_key.columns.keys().collect();
+        pk_keys.sort();
+        let where_clauses: Vec<String> =
+            pk_keys.iter().map(|k| format!("{} = ?", quote(k))).collect();
+        let sql = format!(
+            "SELECT {} FROM {}.{} WHERE {} LIMIT 1",
+            quote(column),
+            quote(&namespace.database),
+            quote(table),
+            where_clauses.join(" AND ")
+        );
+
+        let mut query = sqlx::query(&sql);
+        for k in &pk_keys {
+            query = Self::bind_param(query, &primary_key.columns[*k]);
+        }
+
+        let mut tx_guard = mysql_session.transaction_conn.lock().await;
+        let row = if let Some(ref mut conn) = *tx_guard {
+            query.fetch_optional(&mut **conn).await
+        } else {
+            query.fetch_optional(&mysql_session.pool).await
+        };
+
+        match row.map_err(|e| EngineError::execution_error(e.to_string()))? {
+            Some(row) => Ok(Self::extract_value(&row, 0)),
+            None => Err(EngineError::execution_error("Row not found".to_string())),
+        }
+    }
+
+    /// MySQL has no key-only lock modes, only `FOR UPDATE` and `FOR SHARE`
+    async fn lock_rows_query(
+        &self,
+        session: SessionId,
+        query: &str,
+        mode: RowLockMode,
+    ) -> EngineResult<String> {
+        if matches!(mode, RowLockMode::NoKeyUpdate | RowLockMode::KeyShare) {
+            return Err(EngineError::not_supported(format!(
+                "{} is not supported by MySQL",
+                mode.sql_clause()
+            )));
+        }
+
+        let mysql_session = self.get_session(session).await?;
+        if mysql_session.transaction_conn.lock().await.is_none() {
+            return Err(EngineError::transaction_error(
+                "Row locking requires an active transaction",
+            ));
+        }
+        Ok(mode.wrap_query(query))
+    }
+
+    async fn get_table_sizes(
+        &self,
+        session: SessionId,
+        namespace: &Namespace,
+    ) -> EngineResult<Vec<TableSizeInfo>> {
+        let mysql_session = self.get_session(session).await?;
+        let pool = &mysql_session.pool;
+
+        // Sizes come from the storage engine statistics (estimates for InnoDB)
+        let rows: Vec<(String, i64, i64, i64)> = sqlx::query_as(
+            r#"
+            SELECT CAST(TABLE_NAME AS CHAR) AS table_name,
+                   CAST(COALESCE(TABLE_ROWS, 0) AS SIGNED) AS row_count,
+                   CAST(COALESCE(DATA_LENGTH, 0) AS SIGNED) AS data_length,
+                   CAST(COALESCE(INDEX_LENGTH, 0) AS SIGNED) AS index_length
+            FROM information_schema.TABLES
+            WHERE TABLE_SCHEMA = ? AND TABLE_TYPE = 'BASE TABLE'
+            ORDER BY DATA_LENGTH + INDEX_LENGTH DESC, TABLE_NAME
+            "#,
+        )
+        .bind(&namespace.database)
+        .fetch_all(pool)
+        .await
+        .map_err(|e| EngineError::execution_error(e.to_string()))?;
+
+        Ok(rows
+            .into_iter()
+            .map(|(table_name, row_count, data_length, index_length)| {
+                let table_bytes = data_length.max(0) as u64;
+                let index_bytes = index_length.max(0) as u64;
+                TableSizeInfo {
+                    table_name,
+                    row_count,
+                    total_bytes: table_bytes + index_bytes,
+                    table_bytes,
+                    index_bytes,
+                    toast_bytes: None,
+                }
+            })
+            .collect())
+    }
+
+    /// Parses `SHOW GRANTS FOR CURRENT_USER()`
+    ///
+    /// Privileges count if granted at any level (global, database or table).
+    async fn check_privileges(&self, session: SessionId) -> EngineResult<PrivilegeReport> {
+        let mysql_session = self.get_session(session).await?;
+        let pool = &mysql_session.pool;
+
+        let grants: Vec<String> = sqlx::query("SHOW GRANTS FOR CURRENT_USER()")
+            .fetch_all(pool)
+            .await
+            .map_err(|e| EngineError::execution_error(e.to_string()))?
+            .iter()
+            .filter_map(|row| {
+                row.try_get::<String, _>(0).ok().or_else(|| {
+                    row.try_get::<Vec<u8>, _>(0)
+                        .ok()
+                        .map(|bytes| String::from_utf8_lossy(&bytes).into_owned())
+                })
+            })
+            .collect();
+
+        let (mut report, has_global) = Self::parse_grants(&grants);
+
+        // Global grants cover every database; list the ones visible to the user
+        if has_global {
+            report.granted_databases = sqlx::query_scalar::<_, String>(
+                "SELECT CAST(SCHEMA_NAME AS CHAR) FROM information_schema.SCHEMATA ORDER BY SCHEMA_NAME",
+            )
+            .fetch_all(pool)
+            .await
+            .map_err(|e| EngineError::execution_error(e.to_string()))?;
+        }
+
+        Ok(report)
+    }
+
+    /// Runs `SET GLOBAL|SESSION name = ?` with the value bound
+    ///
+    /// `Session` values are also remembered and re-applied to pooled
+    /// connections. MySQL has no transaction-local scope.
+    async fn set_session_variable(
+        &self,
+        session: SessionId,
+        name: &str,
+        value: &str,
+        scope: VariableScope,
+    ) -> EngineResult<()> {
+        let name = name.trim().to_ascii_lowercase();
+        if !SETTABLE_VARIABLES.contains(&name.as_str()) {
+            return Err(EngineError::not_supported(format!(
+                "Variable '{}' cannot be set from QoreDB",
+                name
+            )));
+        }
+
+        self.apply_variable(session, name, value, scope).await
+    }
+
+    async fn list_server_variables(
+        &self,
+        session: SessionId,
+        scope: VariableScope,
+        filter: Option<&str>,
+    ) -> EngineResult<Vec<ServerVariable>> {
+        let mut statement = match scope {
+            VariableScope::Session => "SHOW SESSION VARIABLES".to_string(),
+            VariableScope::Global => "SHOW GLOBAL VARIABLES".to_string(),
+            VariableScope::Local => {
+                return Err(EngineError::not_supported(
+                    "MySQL has no transaction-local variables; use the session scope",
+                ));
+            }
+        };
+        if let Some(filter) = filter {
+            statement.push_str(&format!(
+                " LIKE '{}'",
+                filter.replace('\\', "\\\\").replace('\'', "''")
+            ));
+        }
+
+        let mysql_session = self.get_session(session).await?;
+        let mut tx = mysql_session.transaction_conn.lock().await;
+        let rows: Vec<(String, String)> = match tx.as_mut() {
+            Some(conn) => sqlx::query_as(&statement).fetch_all(&mut **conn).await,
+            None => sqlx::query_as(&statement).fetch_all(&mysql_session.pool).await,
+        }
+        .map_err(|e| EngineError::execution_error(e.to_string()))?;
+
+        Ok(rows
+            .into_iter()
+            .map(|(name, value)| ServerVariable { name, value, scope })
+            .collect())
+    }
+
+    async fn set_server_variable(
+        &self,
+        session: SessionId,
+        name: &str,
+        value: &str,
+        scope: VariableScope,
+    ) -> EngineResult<()> {
+        let name = name.trim().to_ascii_lowercase();
+        // The name is formatted into the statement, so it must be a plain identifier
+        if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+            return Err(EngineError::syntax_error(format!(
+                "Invalid variable name '{}'",
+                name
+            )));
+        }
+        if BLOCKED_VARIABLES.contains(&name.as_str()) {
+            return Err(EngineError::not_supported(format!(
+                "Variable '{}' cannot be set from QoreDB",
+                name
+            )));
+        }
+
+        self.apply_variable(session, name, value, scope).await
     }
 
     async fn cancel(&self, session: SessionId, query_id: Option<QueryId>) -> EngineResult<()> {
@@ -613,10 +1996,252 @@ impl DataEngine for MySqlDriver {
         Ok(())
     }
 
+    /// Reads `information_schema.PROCESSLIST`, the table behind
+    /// `SHOW PROCESSLIST`; other users' threads need the PROCESS privilege
+    async fn list_server_queries(&self, session: SessionId) -> EngineResult<Vec<ServerQuery>> {
+        let mysql_session = self.get_session(session).await?;
+
+        let rows = sqlx::query(
+            r#"
+            SELECT CAST(ID AS SIGNED) AS pid, USER AS username, DB AS `database`,
+                   HOST AS client_addr, COMMAND AS state, INFO AS query,
+                   CAST(TIME AS SIGNED) AS seconds
+            FROM information_schema.PROCESSLIST
+            WHERE ID <> CONNECTION_ID() AND COMMAND NOT IN ('Daemon', 'Binlog Dump')
+            ORDER BY TIME DESC
+            "#,
+        )
+        .fetch_all(&mysql_session.pool)
+        .await
+        .map_err(|e| EngineError::execution_error(e.to_string()))?;
+
+        rows.iter()
+            .map(|row| {
+                Ok(ServerQuery {
+                    pid: row.try_get("pid")?,
+                    username: row.try_get("username")?,
+                    database: row.try_get("database")?,
+                    client_addr: row.try_get("client_addr")?,
+                    state: row.try_get("state")?,
+                    query: row.try_get("query")?,
+                    duration_ms: row
+                        .try_get::<Option<i64>, _>("seconds")?
+                        .map(|seconds| seconds as f64 * 1000.0),
+                })
+            })
+            .collect::<Result<_, sqlx::Error>>()
+            .map_err(|e| EngineError::execution_error(e.to_string()))
+    }
+
+    /// `KILL QUERY` stops the running statement; `KILL CONNECTION` also
+    /// closes the thread, rolling back its transaction
+    async fn clear_statement_cache(&self, session: SessionId) -> EngineResult<()> {
+        self.get_session(session).await?.clear_statement_cache().await
+    }
+
+    async fn cancel_backend(
+        &self,
+        session: SessionId,
+        pid: i64,
+        terminate: bool,
+    ) -> EngineResult<()> {
+        let mysql_session = self.get_session(session).await?;
+        if pid <= 0 {
+            return Err(EngineError::execution_error(format!("Invalid thread ID {}", pid)));
+        }
+
+        let sql = if terminate {
+            format!("KILL CONNECTION {}", pid)
+        } else {
+            format!("KILL QUERY {}", pid)
+        };
+        sqlx::query(&sql)
+            .execute(&mysql_session.pool)
+            .await
+            .map_err(|e| EngineError::execution_error(e.to_string()))?;
+
+        Ok(())
+    }
+
+    /// DDL commits implicitly in MySQL, so this runs outside any active
+    /// transaction, on a pooled connection
+    async fn create_table(
+        &self,
+        session: SessionId,
+        namespace: &Namespace,
+        schema: &NewTableSchema,
+    ) -> EngineResult<()> {
+        let mysql_session = self.get_session(session).await?;
+
+        sqlx::query(&Self::create_table_sql(namespace, schema))
+            .execute(&mysql_session.pool)
+            .await
+            .map_err(|e| EngineError::execution_error(e.to_string()))?;
+        Ok(())
+    }
+
+    async fn add_column(
+        &self,
+        session: SessionId,
+        namespace: &Namespace,
+        table: &str,
+        column: &NewColumnDef,
+    ) -> EngineResult<()> {
+        let mysql_session = self.get_session(session).await?;
+
+        let sql = format!(
+            "ALTER TABLE `{}`.`{}` ADD COLUMN {}",
+            namespace.database.replace("`", "``"),
+            table.replace("`", "``"),
+            Self::column_definition(column)
+        );
+        sqlx::query(&sql)
+            .execute(&mysql_session.pool)
+            .await
+            .map_err(|e| EngineError::execution_error(e.to_string()))?;
+        Self::forget_stale_statements(None, &mysql_session.pool).await;
+        Ok(())
+    }
+
+    /// Uses `CHANGE COLUMN` with the current column definition on servers
+    /// older than MySQL 8.0 / MariaDB 10.5.2
+    async fn rename_column(
+        &self,
+        session: SessionId,
+        namespace: &Namespace,
+        table: &str,
+        old_name: &str,
+        new_name: &str,
+    ) -> EngineResult<()> {
+        let mysql_session = self.get_session(session).await?;
+        let missing = || {
+            EngineError::execution_error(format!(
+                "Column '{}' does not exist in table '{}'",
+                old_name, table
+            ))
+        };
+
+        let sql = if mysql_session.supports_rename_column {
+            format!(
+                "ALTER TABLE `{}`.`{}` RENAME COLUMN `{}` TO `{}`",
+                namespace.database.replace("`", "``"),
+                table.replace("`", "``"),
+                old_name.replace("`", "``"),
+                new_name.replace("`", "``")
+            )
+        } else {
+            let schema = self.describe_table(session, namespace, table).await?;
+            let column = schema
+                .columns
+                .iter()
+                .find(|column| column.name.eq_ignore_ascii_case(old_name))
+                .ok_or_else(missing)?;
+            let extra: Option<String> = sqlx::query_scalar(
+                r#"
+                SELECT CAST(EXTRA AS CHAR)
+                FROM information_schema.COLUMNS
+                WHERE TABLE_SCHEMA = ? AND TABLE_NAME = ? AND COLUMN_NAME = ?
+                "#,
+            )
+            .bind(&namespace.database)
+            .bind(table)
+            .bind(&column.name)
+            .fetch_optional(&mysql_session.pool)
+            .await
+            .map_err(|e| EngineError::execution_error(e.to_string()))?;
+            Self::change_column_sql(
+                namespace,
+                table,
+                column,
+                extra.as_deref().unwrap_or_default(),
+                new_name,
+            )
+        };
+        sqlx::query(&sql)
+            .execute(&mysql_session.pool)
+            .await
+            .map_err(|e| {
+                // ER_BAD_FIELD_ERROR
+                let is_missing = e
+                    .as_database_error()
+                    .and_then(|db| db.try_downcast_ref::<sqlx::mysql::MySqlDatabaseError>())
+                    .is_some_and(|db| db.number() == 1054);
+                if is_missing {
+                    missing()
+                } else {
+                    EngineError::execution_error(e.to_string())
+                }
+            })?;
+        Self::forget_stale_statements(None, &mysql_session.pool).await;
+        Ok(())
+    }
+
+    async fn drop_column(
+        &self,
+        session: SessionId,
+        namespace: &Namespace,
+        table: &str,
+        column_name: &str,
+    ) -> EngineResult<()> {
+        let mysql_session = self.get_session(session).await?;
+
+        let sql = format!(
+            "ALTER TABLE `{}`.`{}` DROP COLUMN `{}`",
+            namespace.database.replace("`", "``"),
+            table.replace("`", "``"),
+            column_name.replace("`", "``")
+        );
+        sqlx::query(&sql)
+            .execute(&mysql_session.pool)
+            .await
+            .map_err(|e| {
+                // ER_CANT_DROP_FIELD_OR_KEY
+                let missing = e
+                    .as_database_error()
+                    .and_then(|db| db.try_downcast_ref::<sqlx::mysql::MySqlDatabaseError>())
+                    .is_some_and(|db| db.number() == 1091);
+                if missing {
+                    EngineError::execution_error(format!(
+                        "Column '{}' does not exist in table '{}'",
+                        column_name, table
+                    ))
+                } else {
+                    EngineError::execution_error(e.to_string())
+                }
+            })?;
+        Self::forget_stale_statements(None, &mysql_session.pool).await;
+        Ok(())
+    }
+
+    async fn estimate_plan(&self, session: SessionId, query: &str) -> EngineResult<PlanEstimate> {
+        let mysql_session = self.get_session(session).await?;
+
+        let sql = format!("EXPLAIN FORMAT=JSON {}", query.trim().trim_end_matches(';'));
+        let mut tx_guard = mysql_session.transaction_conn.lock().await;
+        let row = if let Some(ref mut conn) = *tx_guard {
+            sqlx::query(&sql).fetch_one(&mut **conn).await
+        } else {
+            sqlx::query(&sql).fetch_one(&mysql_session.pool).await
+        }
+        .map_err(|e| EngineError::execution_error(e.to_string()))?;
+        // The plan column is typed JSON on some server versions
+        let plan: String = row
+            .try_get_unchecked(0)
+            .map_err(|e| EngineError::execution_error(e.to_string()))?;
+
+        let plan: serde_json::Value = serde_json::from_str(&plan)
+            .map_err(|e| EngineError::execution_error(format!("Invalid plan: {}", e)))?;
+        Ok(Self::parse_plan_estimate(&plan))
+    }
+
     fn cancel_support(&self) -> CancelSupport {
         CancelSupport::Driver
     }
 
+    fn supports_streaming(&self) -> bool {
+        true
+    }
+
     // ==================== Transaction Methods ====================
 
     async fn begin_transaction(&self, session: SessionId) -> EngineResult<()> {
@@ -634,6 +2259,8 @@ impl DataEngine for MySqlDriver {
                 "Failed to acquire connection for transaction: {}", e
             )))?;
 
+        mysql_session.apply_session_variables(&mut conn).await?;
+
         sqlx::query("START TRANSACTION")
             .execute(&mut *conn)
             .await
@@ -683,6 +2310,15 @@ impl DataEngine for MySqlDriver {
         Ok(())
     }
 
+    async fn transaction_status(&self, session: SessionId) -> EngineResult<TransactionStatus> {
+        let mysql_session = self.get_session(session).await?;
+
+        Ok(TransactionStatus {
+            active: mysql_session.has_active_transaction(),
+            server_state: None,
+        })
+    }
+
     fn supports_transactions(&self) -> bool {
         true
     }
@@ -699,23 +2335,9 @@ impl DataEngine for MySqlDriver {
         let mysql_session = self.get_session(session).await?;
 
         // 1. Build Query String
-        // MySQL uses backticks for identifiers
-        let table_name = format!("`{}`.`{}`", 
-            namespace.database.replace("`", "``"), 
-            table.replace("`", "``")
-        );
-
         let mut keys: Vec<&String> = data.columns.keys().collect();
         keys.sort();
-
-        let sql = if keys.is_empty() {
-             // MySQL: INSERT INTO table () VALUES ()
-             format!("INSERT INTO {} () VALUES ()", table_name)
-        } else {
-            let cols_str = keys.iter().map(|k| format!("`{}`", k.replace("`", "``"))).collect::<Vec<_>>().join(", ");
-            let params_str = vec!["?"; keys.len()].join(", ");
-            format!("INSERT INTO {} ({}) VALUES ({})", table_name, cols_str, params_str)
-        };
+        let sql = Self::insert_sql(namespace, table, &keys);
 
         // 2. Prepare Query
         let mut query = sqlx::query(&sql);
@@ -741,6 +2363,101 @@ impl DataEngine for MySqlDriver {
         ))
     }
 
+    /// MySQL has no `INSERT ... RETURNING`, so the row is read back on the
+    /// same connection: by the primary key values in `data`, or by
+    /// `LAST_INSERT_ID()` for an auto-increment key. When neither applies
+    /// (no primary key, or a generated composite one) `rows` is empty.
+    async fn insert_row_returning(
+        &self,
+        session: SessionId,
+        namespace: &Namespace,
+        table: &str,
+        data: &RowData,
+    ) -> EngineResult<QueryResult> {
+        let primary_key = self
+            .describe_table(session, namespace, table)
+            .await?
+            .primary_key
+            .unwrap_or_default();
+        let mysql_session = self.get_session(session).await?;
+
+        let mut keys: Vec<&String> = data.columns.keys().collect();
+        keys.sort();
+        let sql = Self::insert_sql(namespace, table, &keys);
+
+        let mut query = sqlx::query(&sql);
+        for k in &keys {
+            let val = data.columns.get(*k).unwrap();
+            query = Self::bind_param(query, val);
+        }
+
+        let start = Instant::now();
+        let mut tx_guard = mysql_session.transaction_conn.lock().await;
+        let mut pooled: Option<PoolConnection<MySql>> = None;
+        let conn = match tx_guard.as_mut() {
+            Some(conn) => conn,
+            None => pooled.insert(
+                mysql_session
+                    .pool
+                    .acquire()
+                    .await
+                    .map_err(|e| EngineError::connection_failed(e.to_string()))?,
+            ),
+        };
+
+        let result = query
+            .execute(&mut **conn)
+            .await
+            .map_err(|e| EngineError::execution_error(e.to_string()))?;
+
+        let table_name = format!(
+            "`{}`.`{}`",
+            namespace.database.replace("`", "``"),
+            table.replace("`", "``")
+        );
+        let quote = |column: &String| format!("`{}`", column.replace("`", "``"));
+        let mysql_rows = if !primary_key.is_empty()
+            && primary_key.iter().all(|column| data.columns.contains_key(column))
+        {
+            let conditions: Vec<String> = primary_key
+                .iter()
+                .map(|column| format!("{} = ?", quote(column)))
+                .collect();
+            let sql = format!("SELECT * FROM {} WHERE {}", table_name, conditions.join(" AND "));
+            let mut select = sqlx::query(&sql);
+            for column in &primary_key {
+                select = Self::bind_param(select, &data.columns[column]);
+            }
+            select.fetch_all(&mut **conn).await
+        } else if primary_key.len() == 1 && result.last_insert_id() != 0 {
+            let sql = format!(
+                "SELECT * FROM {} WHERE {} = LAST_INSERT_ID()",
+                table_name,
+                quote(&primary_key[0])
+            );
+            sqlx::query(&sql).fetch_all(&mut **conn).await
+        } else {
+            Ok(Vec::new())
+        }
+        .map_err(|e| EngineError::execution_error(e.to_string()))?;
+
+        Ok(QueryResult {
+            columns: mysql_rows
+                .first()
+                .map(|row| Self::get_column_info(row.columns()))
+                .unwrap_or_default(),
+            rows: mysql_rows.iter().map(Self::convert_row).collect(),
+            affected_rows: Some(result.rows_affected()),
+            execution_time_ms: start.elapsed().as_micros() as f64 / 1000.0,
+            plan_estimate: None,
+            truncated: false,
+            estimated_memory_bytes: None,
+            truncated_values: Vec::new(),
+        })
+    }
+
+    /// Returns only the affected row count: `RETURNING` is not available
+    /// before MySQL 8.0.19 (and never for UPDATE), so keys are not reported.
     async fn update_row(
         &self,
         session: SessionId,
@@ -816,6 +2533,8 @@ impl DataEngine for MySqlDriver {
         ))
     }
 
+    /// Returns only the affected row count: `DELETE ... RETURNING` is
+    /// MariaDB-only and MySQL has no equivalent, so keys are not reported.
     async fn delete_row(
         &self,
         session: SessionId,
@@ -866,7 +2585,438 @@ impl DataEngine for MySqlDriver {
         ))
     }
 
+    /// The filter is sent as a prepared statement, so it cannot carry a
+    /// second statement.
+    async fn delete_rows_where(
+        &self,
+        session: SessionId,
+        namespace: &Namespace,
+        table: &str,
+        filter: &str,
+    ) -> EngineResult<QueryResult> {
+        let mysql_session = self.get_session(session).await?;
+
+        let table_name = format!(
+            "`{}`.`{}`",
+            namespace.database.replace("`", "``"),
+            table.replace("`", "``")
+        );
+
+        let filter = filter.trim();
+        let sql = if filter.is_empty() {
+            format!("DELETE FROM {}", table_name)
+        } else {
+            format!("DELETE FROM {} WHERE {}", table_name, filter)
+        };
+
+        let start = Instant::now();
+        let mut tx_guard = mysql_session.transaction_conn.lock().await;
+        let result = if let Some(ref mut conn) = *tx_guard {
+            sqlx::query(&sql).execute(&mut **conn).await
+        } else {
+            sqlx::query(&sql).execute(&mysql_session.pool).await
+        };
+
+        let result = result.map_err(Self::map_query_error)?;
+
+        Ok(QueryResult::with_affected_rows(
+            result.rows_affected(),
+            start.elapsed().as_micros() as f64 / 1000.0,
+        ))
+    }
+
+    /// `TRUNCATE` commits implicitly, so it runs on the pool rather than in
+    /// the active transaction. MySQL has no `CASCADE` and refuses to truncate
+    /// a table referenced by a foreign key, whatever `cascade` says.
+    async fn truncate_table(
+        &self,
+        session: SessionId,
+        namespace: &Namespace,
+        table: &str,
+        _cascade: bool,
+    ) -> EngineResult<QueryResult> {
+        let mysql_session = self.get_session(session).await?;
+
+        let sql = format!(
+            "TRUNCATE TABLE `{}`.`{}`",
+            namespace.database.replace("`", "``"),
+            table.replace("`", "``")
+        );
+
+        let start = Instant::now();
+        sqlx::query(&sql)
+            .execute(&mysql_session.pool)
+            .await
+            .map_err(Self::map_query_error)?;
+
+        let mut result = QueryResult::empty();
+        result.execution_time_ms = start.elapsed().as_micros() as f64 / 1000.0;
+        Ok(result)
+    }
+
     fn supports_mutations(&self) -> bool {
         true
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_config() -> ConnectionConfig {
+        ConnectionConfig {
+            driver: "mysql".to_string(),
+            host: "localhost".to_string(),
+            port: 3306,
+            username: "user".to_string(),
+            password: "pass".to_string(),
+            database: Some("testdb".to_string()),
+            ssl_mode: SslMode::Disabled,
+            ssl_ca_path: None,
+            environment: "development".to_string(),
+            read_only: false,
+            ssh_tunnel: None,
+            pool_idle_timeout_secs: 300,
+            pool_max_lifetime_secs: 1800,
+            use_http_api: false,
+            boost: None,
+            mongo_auth_source: None,
+            keepalive_query_secs: None,
+            label: None,
+            charset: None,
+            raw_connection_string: None,
+            retry_policy: None,
+            statement_cache_capacity: None,
+        }
+    }
+
+    #[test]
+    fn test_connection_string_ssl_modes() {
+        let mut config = test_config();
+        let conn_str = MySqlDriver::build_connection_string(&config);
+        assert!(conn_str.contains("ssl-mode=DISABLED"));
+
+        config.ssl_mode = SslMode::Required;
+        config.ssl_ca_path = Some("/ca.pem".to_string());
+        let conn_str = MySqlDriver::build_connection_string(&config);
+        assert!(conn_str.contains("ssl-mode=REQUIRED"));
+        assert!(!conn_str.contains("ssl-ca="));
+
+        config.ssl_mode = SslMode::VerifyIdentity;
+        let conn_str = MySqlDriver::build_connection_string(&config);
+        assert!(conn_str.contains("ssl-mode=VERIFY_IDENTITY"));
+        assert!(conn_str.contains("ssl-ca=/ca.pem"));
+    }
+
+    #[test]
+    fn connection_string_encodes_credentials() {
+        let mut config = test_config();
+        config.password = "p@ss:w/ord?".to_string();
+
+        let url = url::Url::parse(&MySqlDriver::build_connection_string(&config)).unwrap();
+        let password = percent_encoding::percent_decode_str(url.password().unwrap())
+            .decode_utf8()
+            .unwrap();
+        assert_eq!(url.username(), "user");
+        assert_eq!(password, "p@ss:w/ord?");
+        assert_eq!(url.host_str(), Some("localhost"));
+        assert_eq!(url.path(), "/testdb");
+        assert_eq!(url.query(), Some("ssl-mode=DISABLED&charset=utf8mb4"));
+    }
+
+    #[test]
+    fn connection_string_applies_charset() {
+        let mut config = test_config();
+        config.charset = Some("latin1".to_string());
+        assert!(MySqlDriver::build_connection_string(&config).ends_with("&charset=latin1"));
+
+        config.charset = Some("  ".to_string());
+        assert!(MySqlDriver::build_connection_string(&config).ends_with("&charset=utf8mb4"));
+    }
+
+    #[test]
+    fn detects_mariadb_versions() {
+        assert!(is_mariadb_version("10.11.6-MariaDB-log"));
+        assert!(is_mariadb_version("11.4.2-MariaDB"));
+        assert!(is_mariadb_version("5.5.5-10.6.12-MariaDB"));
+        assert!(!is_mariadb_version("8.0.36"));
+        assert!(!is_mariadb_version("5.7.44-log"));
+    }
+
+    #[test]
+    fn detects_servers_without_rename_column() {
+        assert!(supports_rename_column("8.0.36"));
+        assert!(supports_rename_column("9.1.0"));
+        assert!(supports_rename_column("10.11.6-MariaDB-log"));
+        assert!(supports_rename_column("5.5.5-10.5.2-MariaDB"));
+        assert!(!supports_rename_column("5.7.44-log"));
+        assert!(!supports_rename_column("10.4.32-MariaDB"));
+        assert!(!supports_rename_column("5.5.5-10.3.39-MariaDB"));
+    }
+
+    #[test]
+    fn renames_with_change_column_before_mysql_8() {
+        let column = TableColumn {
+            name: "title".to_string(),
+            data_type: "varchar(120)".to_string(),
+            nullable: false,
+            default_value: Some("it's new".to_string()),
+            is_primary_key: false,
+            comment: Some("Shown in lists".to_string()),
+            allowed_values: None,
+            charset: Some("utf8mb4".to_string()),
+        };
+        assert_eq!(
+            MySqlDriver::change_column_sql(&Namespace::new("app"), "posts", &column, "", "name"),
+            "ALTER TABLE `app`.`posts` CHANGE COLUMN `title` `name` varchar(120) \
+             CHARACTER SET utf8mb4 NOT NULL DEFAULT 'it''s new' COMMENT 'Shown in lists'"
+        );
+
+        let column = TableColumn {
+            name: "updated_at".to_string(),
+            data_type: "timestamp".to_string(),
+            nullable: true,
+            default_value: Some("CURRENT_TIMESTAMP".to_string()),
+            is_primary_key: false,
+            comment: None,
+            allowed_values: None,
+            charset: None,
+        };
+        assert_eq!(
+            MySqlDriver::change_column_sql(
+                &Namespace::new("app"),
+                "posts",
+                &column,
+                "on update CURRENT_TIMESTAMP",
+                "modified_at",
+            ),
+            "ALTER TABLE `app`.`posts` CHANGE COLUMN `updated_at` `modified_at` timestamp \
+             NULL DEFAULT CURRENT_TIMESTAMP on update CURRENT_TIMESTAMP"
+        );
+
+        let column = TableColumn {
+            name: "id".to_string(),
+            data_type: "int(11) unsigned".to_string(),
+            nullable: false,
+            default_value: None,
+            is_primary_key: true,
+            comment: None,
+            allowed_values: None,
+            charset: None,
+        };
+        assert_eq!(
+            MySqlDriver::change_column_sql(
+                &Namespace::new("app"),
+                "posts",
+                &column,
+                "auto_increment",
+                "post_id",
+            ),
+            "ALTER TABLE `app`.`posts` CHANGE COLUMN `id` `post_id` int(11) unsigned \
+             NOT NULL AUTO_INCREMENT"
+        );
+    }
+
+    #[test]
+    fn builds_routine_calls() {
+        let namespace = Namespace::new("app");
+        assert_eq!(
+            MySqlDriver::call_routine_sql(&namespace, "tax", RoutineType::Function, 1),
+            "SELECT `app`.`tax`(?)"
+        );
+        assert_eq!(
+            MySqlDriver::call_routine_sql(&namespace, "archive", RoutineType::Procedure, 2),
+            "CALL `app`.`archive`(?, ?)"
+        );
+    }
+
+    #[test]
+    fn builds_create_table_with_auto_increment() {
+        let schema = NewTableSchema {
+            table_name: "users".to_string(),
+            columns: vec![
+                NewColumnDef {
+                    name: "id".to_string(),
+                    data_type: "int unsigned".to_string(),
+                    nullable: false,
+                    default_value: None,
+                    is_primary_key: false,
+                    auto_increment: true,
+                },
+                NewColumnDef {
+                    name: "email".to_string(),
+                    data_type: "varchar(255)".to_string(),
+                    nullable: true,
+                    default_value: None,
+                    is_primary_key: false,
+                    auto_increment: false,
+                },
+            ],
+            primary_key: Some(vec!["id".to_string()]),
+            if_not_exists: false,
+        };
+        assert!(schema.validate().is_ok());
+        assert_eq!(
+            MySqlDriver::create_table_sql(&Namespace::new("app"), &schema),
+            "CREATE TABLE `app`.`users` (`id` int unsigned NOT NULL AUTO_INCREMENT, \
+             `email` varchar(255), PRIMARY KEY (`id`))"
+        );
+    }
+
+    #[test]
+    fn reads_plan_estimate_from_json_explain() {
+        let plan = serde_json::json!({
+            "query_block": {
+                "cost_info": { "query_cost": "12.50" },
+                "nested_loop": [
+                    { "table": { "table_name": "a", "rows_produced_per_join": 10 } },
+                    { "table": { "table_name": "b", "rows_produced_per_join": 42 } }
+                ]
+            }
+        });
+        let estimate = MySqlDriver::parse_plan_estimate(&plan);
+        assert_eq!(estimate.total_cost, Some(12.5));
+        assert_eq!(estimate.estimated_rows, Some(42.0));
+    }
+
+    #[test]
+    fn parses_enum_and_set_values() {
+        assert_eq!(
+            MySqlDriver::parse_enum_values("enum('small','medium','large')"),
+            Some(vec!["small".to_string(), "medium".to_string(), "large".to_string()])
+        );
+        assert_eq!(
+            MySqlDriver::parse_enum_values("SET('a,b', 'c')"),
+            Some(vec!["a,b".to_string(), "c".to_string()])
+        );
+        assert_eq!(
+            MySqlDriver::parse_enum_values(r"enum('it''s','back\\slash','','x\'y')"),
+            Some(vec![
+                "it's".to_string(),
+                "back\\slash".to_string(),
+                String::new(),
+                "x'y".to_string(),
+            ])
+        );
+        assert_eq!(
+            MySqlDriver::parse_enum_values("enum('it''s ok','no')"),
+            Some(vec!["it's ok".to_string(), "no".to_string()])
+        );
+        assert_eq!(MySqlDriver::parse_enum_values("varchar(255)"), None);
+        assert_eq!(MySqlDriver::parse_enum_values("enum('a',b)"), None);
+        assert_eq!(MySqlDriver::parse_enum_values("enum('a'"), None);
+    }
+
+    #[test]
+    fn parses_show_grants_output() {
+        let grants = vec![
+            "GRANT USAGE ON *.* TO `app`@`%`".to_string(),
+            "GRANT SELECT, INSERT, UPDATE (`status`) ON `shop`.* TO `app`@`%`".to_string(),
+            "GRANT SELECT ON `analytics`.`events` TO `app`@`%`".to_string(),
+            "GRANT `reporting`@`%` TO `app`@`%`".to_string(),
+        ];
+        let (report, has_global) = MySqlDriver::parse_grants(&grants);
+        assert!(!has_global);
+        assert!(report.can_select && report.can_insert && report.can_update);
+        assert!(!report.can_delete && !report.can_create && !report.can_drop);
+        assert!(!report.is_superuser);
+        assert_eq!(report.granted_databases, vec!["analytics", "shop"]);
+
+        let (report, has_global) = MySqlDriver::parse_grants(&[
+            "GRANT ALL PRIVILEGES ON *.* TO `root`@`localhost` WITH GRANT OPTION".to_string(),
+        ]);
+        assert!(has_global);
+        assert!(report.is_superuser && report.can_drop);
+    }
+
+    #[tokio::test]
+    #[ignore = "requires a MySQL server at QOREDB_TEST_MYSQL_URL"]
+    async fn returns_every_result_set_of_a_procedure() {
+        let url = std::env::var("QOREDB_TEST_MYSQL_URL").expect("QOREDB_TEST_MYSQL_URL");
+        let mut config = test_config();
+        config.raw_connection_string = Some(url);
+
+        let driver = MySqlDriver::new();
+        let session = driver.connect(&config).await.expect("connect");
+        let database = driver
+            .execute(session, "SELECT DATABASE()", QueryId::new())
+            .await
+            .expect("current database");
+        let Value::Text(database) = &database.rows[0].values[0] else {
+            panic!("expected a current database");
+        };
+        let namespace = Namespace::new(database);
+
+        driver
+            .execute_multi(session, "DROP PROCEDURE IF EXISTS qoredb_two_selects", QueryId::new())
+            .await
+            .expect("drop procedure");
+        // Procedures cannot be created through the prepared statement protocol
+        driver
+            .execute_multi(
+                session,
+                "CREATE PROCEDURE qoredb_two_selects(IN n INT) \
+                 BEGIN SELECT n AS first; SELECT n + 1 AS second, 'x' AS label; END",
+                QueryId::new(),
+            )
+            .await
+            .expect("create procedure");
+        let results = driver
+            .call_routine(
+                session,
+                &namespace,
+                "qoredb_two_selects",
+                RoutineType::Procedure,
+                &[Value::Int(41)],
+                QueryId::new(),
+            )
+            .await;
+        driver
+            .execute_multi(session, "DROP PROCEDURE qoredb_two_selects", QueryId::new())
+            .await
+            .expect("drop procedure");
+        driver.disconnect(session).await.expect("disconnect");
+
+        let results = results.expect("call procedure");
+        // Two result sets, then the status result of the CALL itself
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[0].columns[0].name, "first");
+        assert!(matches!(results[0].rows[0].values[0], Value::Int(41)));
+        assert_eq!(results[1].columns.len(), 2);
+        assert!(matches!(results[1].rows[0].values[0], Value::Int(42)));
+        assert!(results[2].rows.is_empty());
+    }
+
+    #[tokio::test]
+    #[ignore = "requires a MySQL server at QOREDB_TEST_MYSQL_URL"]
+    async fn lists_and_terminates_other_sessions() {
+        let url = std::env::var("QOREDB_TEST_MYSQL_URL").expect("QOREDB_TEST_MYSQL_URL");
+        let mut config = test_config();
+        config.raw_connection_string = Some(url);
+
+        let driver = MySqlDriver::new();
+        let admin = driver.connect(&config).await.expect("connect admin");
+        let victim = driver.connect(&config).await.expect("connect victim");
+        let id = driver
+            .execute(victim, "SELECT CONNECTION_ID()", QueryId::new())
+            .await
+            .expect("connection id");
+        let pid = match id.rows[0].values[0] {
+            Value::Int(pid) => pid,
+            ref other => panic!("unexpected connection id {:?}", other),
+        };
+
+        let sessions = driver.list_server_queries(admin).await.expect("list sessions");
+        assert!(sessions.iter().any(|s| s.pid == pid));
+
+        driver.cancel_backend(admin, pid, true).await.expect("kill connection");
+        let sessions = driver.list_server_queries(admin).await.expect("list sessions");
+        // The thread may linger briefly in the "Killed" state
+        assert!(!sessions
+            .iter()
+            .any(|s| s.pid == pid && s.state.as_deref() != Some("Killed")));
+
+        let _ = driver.disconnect(victim).await;
+        driver.disconnect(admin).await.expect("disconnect");
+    }
+}
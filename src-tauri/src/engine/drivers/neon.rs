@@ -0,0 +1,826 @@
+//! Neon Driver
+//!
+//! Talks to Neon's serverless PostgreSQL through its HTTP endpoint
+//! (`https://<endpoint host>/sql`), which only needs outbound HTTPS and works
+//! where WebSockets and the PostgreSQL port are unavailable.
+//!
+//! Every request is a separate round-trip with no server-side session, so
+//! transactions are emulated: statements run between `begin_transaction`
+//! and `commit` are queued client-side and sent as a single batch on commit,
+//! which Neon runs inside one transaction.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Instant;
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use tokio::sync::{Mutex, RwLock};
+
+use crate::engine::drivers::url_encode;
+use crate::engine::error::{EngineError, EngineResult};
+use crate::engine::traits::DataEngine;
+use crate::engine::types::{
+    Collection, CollectionType, ColumnInfo, ConnectionConfig, Namespace, QueryId, QueryResult,
+    Row as QRow, RowLockMode, SessionId, TableColumn, TableSchema, TransactionStatus, Value,
+};
+
+/// Database used when the connection has none set
+const DEFAULT_DATABASE: &str = "neondb";
+
+/// PostgreSQL type OIDs used to type the text values returned by the API
+mod oid {
+    pub const BOOL: u32 = 16;
+    pub const BYTEA: u32 = 17;
+    pub const INT8: u32 = 20;
+    pub const INT2: u32 = 21;
+    pub const INT4: u32 = 23;
+    pub const OID: u32 = 26;
+    pub const JSON: u32 = 114;
+    pub const FLOAT4: u32 = 700;
+    pub const FLOAT8: u32 = 701;
+    pub const NUMERIC: u32 = 1700;
+    pub const JSONB: u32 = 3802;
+}
+
+/// A session on the Neon HTTP endpoint
+struct NeonSession {
+    client: reqwest::Client,
+    url: String,
+    /// Sent with every request; selects the role and database
+    connection_string: String,
+    password: String,
+    database: String,
+    /// Statements queued by an open transaction, `None` outside one
+    pending: Mutex<Option<Vec<Statement>>>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct Statement {
+    query: String,
+    params: Vec<serde_json::Value>,
+}
+
+#[derive(Serialize)]
+struct BatchRequest<'a> {
+    queries: &'a [Statement],
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ApiResult {
+    #[serde(default)]
+    fields: Vec<ApiField>,
+    /// One array per row (`Neon-Array-Mode`)
+    #[serde(default)]
+    rows: Vec<Vec<serde_json::Value>>,
+    command: Option<String>,
+    row_count: Option<u64>,
+}
+
+#[derive(Deserialize)]
+struct ApiField {
+    name: String,
+    #[serde(rename = "dataTypeID", default)]
+    data_type_id: u32,
+}
+
+#[derive(Deserialize)]
+struct BatchResponse {
+    #[serde(default)]
+    results: Vec<ApiResult>,
+}
+
+#[derive(Deserialize)]
+struct ApiError {
+    message: String,
+    /// SQLSTATE of the failed statement
+    code: Option<String>,
+}
+
+impl NeonSession {
+    /// Runs a single statement immediately, bypassing any queued transaction
+    async fn query(&self, query: &str, params: Vec<serde_json::Value>) -> EngineResult<ApiResult> {
+        self.post(&Statement {
+            query: query.to_string(),
+            params,
+        })
+        .await
+    }
+
+    /// Runs the statements as one batch, in a single transaction
+    async fn batch(&self, statements: &[Statement]) -> EngineResult<Vec<ApiResult>> {
+        let response: BatchResponse = self
+            .post(&BatchRequest {
+                queries: statements,
+            })
+            .await?;
+        Ok(response.results)
+    }
+
+    async fn post<T: for<'de> Deserialize<'de>>(&self, body: &impl Serialize) -> EngineResult<T> {
+        let response = self
+            .client
+            .post(&self.url)
+            .bearer_auth(&self.password)
+            .header("Neon-Connection-String", &self.connection_string)
+            .header("Neon-Array-Mode", "true")
+            .header("Neon-Raw-Text-Output", "true")
+            .json(body)
+            .send()
+            .await
+            .map_err(|e| EngineError::connection_failed(e.to_string()))?;
+
+        let status = response.status();
+        if status == reqwest::StatusCode::UNAUTHORIZED || status == reqwest::StatusCode::FORBIDDEN {
+            return Err(EngineError::auth_failed("Neon rejected the credentials"));
+        }
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            return Err(match serde_json::from_str::<ApiError>(&body) {
+                Ok(error) => NeonDriver::map_api_error(error),
+                Err(_) => EngineError::connection_failed(format!(
+                    "Neon HTTP endpoint returned {}: {}",
+                    status, body
+                )),
+            });
+        }
+
+        response
+            .json::<T>()
+            .await
+            .map_err(|e| EngineError::internal(format!("Invalid Neon response: {}", e)))
+    }
+}
+
+/// Neon serverless PostgreSQL driver implementation
+pub struct NeonDriver {
+    sessions: RwLock<HashMap<SessionId, Arc<NeonSession>>>,
+}
+
+impl NeonDriver {
+    pub fn new() -> Self {
+        Self {
+            sessions: RwLock::new(HashMap::new()),
+        }
+    }
+
+    async fn get_session(&self, session: SessionId) -> EngineResult<Arc<NeonSession>> {
+        let sessions = self.sessions.read().await;
+        sessions
+            .get(&session)
+            .cloned()
+            .ok_or_else(|| EngineError::session_not_found(session.0.to_string()))
+    }
+
+    fn quote_ident(name: &str) -> String {
+        format!("\"{}\"", name.replace('"', "\"\""))
+    }
+
+    fn open_session(config: &ConnectionConfig) -> EngineResult<NeonSession> {
+        let host = config.host.trim();
+        if host.is_empty() {
+            return Err(EngineError::connection_failed(
+                "Neon needs the endpoint host (e.g. ep-example-123456.us-east-2.aws.neon.tech)",
+            ));
+        }
+        let database = config.database.as_deref().unwrap_or(DEFAULT_DATABASE);
+        let client = reqwest::Client::builder()
+            .timeout(std::time::Duration::from_secs(30))
+            .build()
+            .map_err(|e| EngineError::internal(e.to_string()))?;
+
+        Ok(NeonSession {
+            client,
+            url: format!("https://{}/sql", host),
+            connection_string: format!(
+                "postgresql://{}:{}@{}/{}?sslmode=require",
+                url_encode(&config.username),
+                url_encode(&config.password),
+                host,
+                url_encode(database)
+            ),
+            password: config.password.clone(),
+            database: database.to_string(),
+            pending: Mutex::new(None),
+        })
+    }
+
+    fn map_api_error(error: ApiError) -> EngineError {
+        let message = error.message;
+        match error.code.as_deref() {
+            Some("42601") => EngineError::syntax_error(message),
+            Some(code) => EngineError::from_server_code(code, message.clone())
+                .unwrap_or_else(|| EngineError::execution_error(message)),
+            None => EngineError::execution_error(message),
+        }
+    }
+
+    /// Encodes a bound parameter; the API sends every parameter as text
+    fn param_to_json(value: &Value) -> serde_json::Value {
+        match value {
+            Value::Null => serde_json::Value::Null,
+            Value::Bool(b) => serde_json::Value::Bool(*b),
+            Value::Int(i) => serde_json::Value::from(*i),
+            Value::Float(f) => serde_json::Value::from(*f),
+            Value::Text(s) => serde_json::Value::String(s.clone()),
+            Value::Timestamp(ts) => serde_json::Value::String(ts.to_string()),
+            Value::Uuid(uuid) => serde_json::Value::String(uuid.to_string()),
+            Value::Bytes(bytes) => {
+                let hex: String = bytes.iter().map(|b| format!("{:02x}", b)).collect();
+                serde_json::Value::String(format!("\\x{}", hex))
+            }
+            Value::Json(json) => serde_json::Value::String(json.to_string()),
+            Value::Array(values) => {
+                serde_json::Value::Array(values.iter().map(Self::param_to_json).collect())
+            }
+        }
+    }
+
+    /// Converts a value returned by the API according to its column type
+    fn convert_value(data_type_id: u32, raw: &serde_json::Value) -> Value {
+        let text = match raw {
+            serde_json::Value::Null => return Value::Null,
+            serde_json::Value::String(s) => s.clone(),
+            other => other.to_string(),
+        };
+        match data_type_id {
+            oid::BOOL => match text.as_str() {
+                "t" | "true" => Value::Bool(true),
+                "f" | "false" => Value::Bool(false),
+                _ => Value::Text(text),
+            },
+            oid::INT2 | oid::INT4 | oid::INT8 | oid::OID => text
+                .parse()
+                .map(Value::Int)
+                .unwrap_or(Value::Text(text)),
+            oid::FLOAT4 | oid::FLOAT8 | oid::NUMERIC => text
+                .parse()
+                .map(Value::Float)
+                .unwrap_or(Value::Text(text)),
+            oid::JSON | oid::JSONB => serde_json::from_str(&text)
+                .map(Value::Json)
+                .unwrap_or(Value::Text(text)),
+            oid::BYTEA => Self::decode_bytea(&text)
+                .map(Value::Bytes)
+                .unwrap_or(Value::Text(text)),
+            _ => Value::Text(text),
+        }
+    }
+
+    /// Decodes the `\x...` hex output format of `bytea`
+    fn decode_bytea(text: &str) -> Option<Vec<u8>> {
+        let hex = text.strip_prefix("\\x")?;
+        if hex.len() % 2 != 0 {
+            return None;
+        }
+        (0..hex.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok())
+            .collect()
+    }
+
+    /// Converts an API result into a `QueryResult`
+    fn convert_result(result: ApiResult, execution_time_ms: f64) -> QueryResult {
+        if result.fields.is_empty() {
+            return QueryResult::with_affected_rows(
+                result.row_count.unwrap_or(0),
+                execution_time_ms,
+            );
+        }
+
+        let columns = result
+            .fields
+            .iter()
+            .map(|field| {
+                let data_type = Self::type_name(field.data_type_id);
+                ColumnInfo {
+                    name: field.name.clone(),
+                    display_size: ColumnInfo::display_size_for(&data_type),
+                    data_type,
+                    nullable: true,
+                    ..Default::default()
+                }
+            })
+            .collect();
+
+        let rows = result
+            .rows
+            .iter()
+            .map(|row| QRow {
+                values: result
+                    .fields
+                    .iter()
+                    .zip(row)
+                    .map(|(field, raw)| Self::convert_value(field.data_type_id, raw))
+                    .collect(),
+            })
+            .collect();
+
+        // Statements with a result set still report a count, e.g. `UPDATE ... RETURNING`
+        let affected_rows = match result.command.as_deref() {
+            Some("SELECT") | None => None,
+            Some(_) => result.row_count,
+        };
+
+        QueryResult {
+            columns,
+            rows,
+            affected_rows,
+            execution_time_ms,
+            plan_estimate: None,
+            truncated: false,
+            estimated_memory_bytes: None,
+            truncated_values: Vec::new(),
+        }
+    }
+
+    fn type_name(data_type_id: u32) -> String {
+        match data_type_id {
+            oid::BOOL => "bool",
+            oid::BYTEA => "bytea",
+            oid::INT8 => "int8",
+            oid::INT2 => "int2",
+            oid::INT4 => "int4",
+            oid::OID => "oid",
+            oid::JSON => "json",
+            oid::FLOAT4 => "float4",
+            oid::FLOAT8 => "float8",
+            oid::NUMERIC => "numeric",
+            oid::JSONB => "jsonb",
+            _ => "text",
+        }
+        .to_string()
+    }
+
+    /// Runs a metadata query immediately and returns its rows as values
+    async fn query_values(
+        neon_session: &NeonSession,
+        query: &str,
+        params: Vec<serde_json::Value>,
+    ) -> EngineResult<Vec<Vec<Value>>> {
+        let result = Self::convert_result(neon_session.query(query, params).await?, 0.0);
+        Ok(result.rows.into_iter().map(|row| row.values).collect())
+    }
+
+    fn text(value: Option<Value>) -> Option<String> {
+        match value? {
+            Value::Null => None,
+            Value::Text(s) => Some(s),
+            Value::Int(i) => Some(i.to_string()),
+            Value::Float(f) => Some(f.to_string()),
+            Value::Bool(b) => Some(b.to_string()),
+            other => Some(format!("{:?}", other)),
+        }
+    }
+}
+
+impl Default for NeonDriver {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl DataEngine for NeonDriver {
+    fn driver_id(&self) -> &'static str {
+        "neon"
+    }
+
+    fn driver_name(&self) -> &'static str {
+        "Neon"
+    }
+
+    fn default_port(&self) -> u16 {
+        443
+    }
+
+    fn supports_schemas(&self) -> bool {
+        true
+    }
+
+    /// Namespaces are the schemas of the connected database only
+    fn supports_multiple_databases(&self) -> bool {
+        false
+    }
+
+    async fn test_connection(&self, config: &ConnectionConfig) -> EngineResult<()> {
+        let neon_session = Self::open_session(config)?;
+        neon_session.query("SELECT 1", Vec::new()).await?;
+        Ok(())
+    }
+
+    async fn connect(&self, config: &ConnectionConfig) -> EngineResult<SessionId> {
+        let neon_session = Self::open_session(config)?;
+        neon_session.query("SELECT 1", Vec::new()).await?;
+
+        let session_id = SessionId::new();
+        let mut sessions = self.sessions.write().await;
+        sessions.insert(session_id, Arc::new(neon_session));
+        Ok(session_id)
+    }
+
+    /// The endpoint keeps no server-side connection to close; queued
+    /// statements of an open transaction are dropped
+    async fn disconnect(&self, session: SessionId) -> EngineResult<()> {
+        let mut sessions = self.sessions.write().await;
+
+        if sessions.remove(&session).is_some() {
+            Ok(())
+        } else {
+            Err(EngineError::session_not_found(session.0.to_string()))
+        }
+    }
+
+    async fn rebind_session(&self, from: SessionId, to: SessionId) -> EngineResult<()> {
+        let mut sessions = self.sessions.write().await;
+        let session = sessions
+            .remove(&from)
+            .ok_or_else(|| EngineError::session_not_found(from.0.to_string()))?;
+        if let Some(stale) = sessions.insert(to, session) {
+            sessions.insert(from, stale);
+        }
+        Ok(())
+    }
+
+    async fn list_namespaces(&self, session: SessionId) -> EngineResult<Vec<Namespace>> {
+        let neon_session = self.get_session(session).await?;
+
+        let rows = Self::query_values(
+            &neon_session,
+            "SELECT schema_name FROM information_schema.schemata \
+             WHERE schema_name NOT IN ('pg_catalog', 'information_schema', 'pg_toast') \
+             ORDER BY schema_name",
+            Vec::new(),
+        )
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .filter_map(|row| Self::text(row.into_iter().next()))
+            .map(|schema| Namespace::with_schema(neon_session.database.clone(), schema))
+            .collect())
+    }
+
+    async fn list_collections(
+        &self,
+        session: SessionId,
+        namespace: &Namespace,
+    ) -> EngineResult<Vec<Collection>> {
+        let neon_session = self.get_session(session).await?;
+        let schema = namespace.schema.as_deref().unwrap_or("public");
+
+        let rows = Self::query_values(
+            &neon_session,
+            "SELECT table_name, table_type FROM information_schema.tables \
+             WHERE table_schema = $1 ORDER BY table_name",
+            vec![serde_json::Value::from(schema)],
+        )
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .filter_map(|row| {
+                let mut values = row.into_iter();
+                let name = Self::text(values.next())?;
+                let collection_type = match Self::text(values.next()).as_deref() {
+                    Some("VIEW") => CollectionType::View,
+                    _ => CollectionType::Table,
+                };
+                Some(Collection {
+                    namespace: namespace.clone(),
+                    name,
+                    collection_type,
+                })
+            })
+            .collect())
+    }
+
+    /// Inside a transaction, the statement is only queued and reports no
+    /// rows; it runs when the transaction is committed
+    async fn execute(
+        &self,
+        session: SessionId,
+        query: &str,
+        query_id: QueryId,
+    ) -> EngineResult<QueryResult> {
+        self.execute_with_params(session, query, &[], query_id)
+            .await
+    }
+
+    async fn execute_with_params(
+        &self,
+        session: SessionId,
+        query: &str,
+        params: &[Value],
+        query_id: QueryId,
+    ) -> EngineResult<QueryResult> {
+        let _ = query_id;
+        let neon_session = self.get_session(session).await?;
+        let params: Vec<serde_json::Value> = params.iter().map(Self::param_to_json).collect();
+
+        {
+            let mut pending = neon_session.pending.lock().await;
+            if let Some(queue) = pending.as_mut() {
+                queue.push(Statement {
+                    query: query.to_string(),
+                    params,
+                });
+                return Ok(QueryResult::empty());
+            }
+        }
+
+        let start = Instant::now();
+        let result = neon_session.query(query, params).await?;
+        Ok(Self::convert_result(
+            result,
+            start.elapsed().as_micros() as f64 / 1000.0,
+        ))
+    }
+
+    async fn describe_table(
+        &self,
+        session: SessionId,
+        namespace: &Namespace,
+        table: &str,
+    ) -> EngineResult<TableSchema> {
+        let neon_session = self.get_session(session).await?;
+        let schema = namespace.schema.as_deref().unwrap_or("public");
+        let params = || {
+            vec![
+                serde_json::Value::from(schema),
+                serde_json::Value::from(table),
+            ]
+        };
+
+        let rows = Self::query_values(
+            &neon_session,
+            r#"
+            SELECT c.column_name, c.data_type, c.is_nullable, c.column_default,
+                   EXISTS (
+                       SELECT 1
+                       FROM information_schema.table_constraints tc
+                       JOIN information_schema.key_column_usage k
+                         ON k.constraint_name = tc.constraint_name
+                        AND k.table_schema = tc.table_schema
+                        AND k.table_name = tc.table_name
+                       WHERE tc.constraint_type = 'PRIMARY KEY'
+                         AND tc.table_schema = c.table_schema
+                         AND tc.table_name = c.table_name
+                         AND k.column_name = c.column_name
+                   ),
+                   col_description(format('%I.%I', c.table_schema, c.table_name)::regclass,
+                                   c.ordinal_position::int)
+            FROM information_schema.columns c
+            WHERE c.table_schema = $1 AND c.table_name = $2
+            ORDER BY c.ordinal_position
+            "#,
+            params(),
+        )
+        .await?;
+
+        let mut pk_columns = Vec::new();
+        let mut columns = Vec::with_capacity(rows.len());
+        for row in rows {
+            let mut values = row.into_iter();
+            let name = Self::text(values.next()).unwrap_or_default();
+            let data_type = Self::text(values.next()).unwrap_or_default();
+            let nullable = Self::text(values.next()).as_deref() == Some("YES");
+            let default_value = Self::text(values.next());
+            let is_primary_key = matches!(values.next(), Some(Value::Bool(true)));
+            let comment = Self::text(values.next());
+            if is_primary_key {
+                pk_columns.push(name.clone());
+            }
+            columns.push(TableColumn {
+                name,
+                data_type,
+                nullable,
+                default_value,
+                is_primary_key,
+                comment,
+                allowed_values: None,
+                charset: None,
+            });
+        }
+
+        let mut table_row = Self::query_values(
+            &neon_session,
+            "SELECT c.reltuples::bigint, obj_description(c.oid, 'pg_class') \
+             FROM pg_class c JOIN pg_namespace n ON n.oid = c.relnamespace \
+             WHERE n.nspname = $1 AND c.relname = $2",
+            params(),
+        )
+        .await?
+        .into_iter()
+        .next()
+        .unwrap_or_default()
+        .into_iter();
+        let row_count_estimate = match table_row.next() {
+            // -1 means the table was never analyzed
+            Some(Value::Int(count)) if count >= 0 => Some(count as u64),
+            _ => None,
+        };
+        let comment = Self::text(table_row.next());
+
+        Ok(TableSchema {
+            columns,
+            primary_key: if pk_columns.is_empty() {
+                None
+            } else {
+                Some(pk_columns)
+            },
+            row_count_estimate,
+            comment,
+        })
+    }
+
+    async fn preview_table(
+        &self,
+        session: SessionId,
+        namespace: &Namespace,
+        table: &str,
+        limit: u32,
+        lock_rows: Option<RowLockMode>,
+    ) -> EngineResult<QueryResult> {
+        if lock_rows.is_some() {
+            return Err(EngineError::not_supported(
+                "Row locking is not available over the Neon HTTP endpoint",
+            ));
+        }
+
+        let neon_session = self.get_session(session).await?;
+        let query = format!(
+            "SELECT * FROM {}.{} LIMIT {}",
+            Self::quote_ident(namespace.schema.as_deref().unwrap_or("public")),
+            Self::quote_ident(table),
+            limit
+        );
+
+        let start = Instant::now();
+        let result = neon_session.query(&query, Vec::new()).await?;
+        Ok(Self::convert_result(
+            result,
+            start.elapsed().as_micros() as f64 / 1000.0,
+        ))
+    }
+
+    fn supports_ssh(&self) -> bool {
+        false
+    }
+
+    /// Starts queueing statements client-side
+    async fn begin_transaction(&self, session: SessionId) -> EngineResult<()> {
+        let neon_session = self.get_session(session).await?;
+        let mut pending = neon_session.pending.lock().await;
+        if pending.is_some() {
+            return Err(EngineError::transaction_error(
+                "A transaction is already active on this session",
+            ));
+        }
+        *pending = Some(Vec::new());
+        Ok(())
+    }
+
+    /// Sends the queued statements as one batch; if any fails, none is applied
+    async fn commit(&self, session: SessionId) -> EngineResult<()> {
+        let neon_session = self.get_session(session).await?;
+        let statements = neon_session
+            .pending
+            .lock()
+            .await
+            .take()
+            .ok_or_else(|| EngineError::transaction_error("No active transaction to commit"))?;
+
+        if statements.is_empty() {
+            return Ok(());
+        }
+        neon_session.batch(&statements).await.map(|_| ())
+    }
+
+    async fn rollback(&self, session: SessionId) -> EngineResult<()> {
+        let neon_session = self.get_session(session).await?;
+        let discarded = neon_session.pending.lock().await.take();
+        discarded
+            .map(|_| ())
+            .ok_or_else(|| EngineError::transaction_error("No active transaction to rollback"))
+    }
+
+    async fn transaction_status(&self, session: SessionId) -> EngineResult<TransactionStatus> {
+        let neon_session = self.get_session(session).await?;
+        let pending = neon_session.pending.lock().await;
+        Ok(TransactionStatus {
+            active: pending.is_some(),
+            server_state: None,
+        })
+    }
+
+    fn supports_transactions(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn converts_text_values_by_type_oid() {
+        let json = serde_json::Value::from;
+        assert!(matches!(
+            NeonDriver::convert_value(oid::INT4, &json("42")),
+            Value::Int(42)
+        ));
+        assert!(matches!(
+            NeonDriver::convert_value(oid::BOOL, &json("t")),
+            Value::Bool(true)
+        ));
+        assert!(matches!(
+            NeonDriver::convert_value(oid::NUMERIC, &json("1.5")),
+            Value::Float(f) if f == 1.5
+        ));
+        assert!(matches!(
+            NeonDriver::convert_value(oid::BYTEA, &json("\\x0aff")),
+            Value::Bytes(bytes) if bytes == vec![0x0a, 0xff]
+        ));
+        assert!(matches!(
+            NeonDriver::convert_value(oid::JSONB, &json(r#"{"a":1}"#)),
+            Value::Json(value) if value["a"] == 1
+        ));
+        assert!(matches!(
+            NeonDriver::convert_value(oid::INT4, &serde_json::Value::Null),
+            Value::Null
+        ));
+        assert!(matches!(
+            NeonDriver::convert_value(25, &json("hello")),
+            Value::Text(s) if s == "hello"
+        ));
+    }
+
+    #[test]
+    fn parses_array_mode_responses() {
+        let response = r#"{
+            "command": "SELECT",
+            "rowCount": 1,
+            "fields": [
+                {"name": "id", "dataTypeID": 23},
+                {"name": "name", "dataTypeID": 25}
+            ],
+            "rows": [["7", null]]
+        }"#;
+        let result: ApiResult = serde_json::from_str(response).unwrap();
+        let result = NeonDriver::convert_result(result, 1.0);
+
+        assert_eq!(result.columns[0].data_type, "int4");
+        assert!(matches!(result.rows[0].values[0], Value::Int(7)));
+        assert!(matches!(result.rows[0].values[1], Value::Null));
+        assert_eq!(result.affected_rows, None);
+    }
+
+    #[tokio::test]
+    async fn transactions_queue_statements_until_commit() {
+        let driver = NeonDriver::new();
+        let config = ConnectionConfig {
+            driver: "neon".to_string(),
+            host: "ep-example.us-east-2.aws.neon.tech".to_string(),
+            port: 443,
+            username: "app".to_string(),
+            password: "secret".to_string(),
+            database: None,
+            ssl_mode: Default::default(),
+            ssl_ca_path: None,
+            environment: "development".to_string(),
+            read_only: false,
+            ssh_tunnel: None,
+            pool_idle_timeout_secs: 0,
+            pool_max_lifetime_secs: 0,
+            use_http_api: false,
+            boost: None,
+            mongo_auth_source: None,
+            keepalive_query_secs: None,
+            label: None,
+            charset: None,
+            raw_connection_string: None,
+            retry_policy: None,
+            statement_cache_capacity: None,
+        };
+        let session = SessionId::new();
+        driver
+            .sessions
+            .write()
+            .await
+            .insert(session, Arc::new(NeonDriver::open_session(&config).unwrap()));
+
+        driver.begin_transaction(session).await.unwrap();
+        let result = driver
+            .execute(session, "UPDATE t SET a = 1", QueryId::new())
+            .await
+            .unwrap();
+        assert!(result.rows.is_empty());
+        assert!(driver.transaction_status(session).await.unwrap().active);
+
+        driver.rollback(session).await.unwrap();
+        assert!(!driver.transaction_status(session).await.unwrap().active);
+        assert!(driver.commit(session).await.is_err());
+    }
+}
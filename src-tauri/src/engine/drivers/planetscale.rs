@@ -0,0 +1,1075 @@
+//! PlanetScale Driver
+//!
+//! PlanetScale is MySQL-compatible, so regular connections reuse `MySqlDriver`.
+//!
+//! With `use_http_api`, queries go through PlanetScale's HTTP API instead,
+//! which only needs outbound HTTPS (port 443) and works on networks where
+//! the MySQL port is blocked. The API is stateless: every response carries
+//! an opaque session object (current database, open transaction, ...) that
+//! must be sent back with the next request.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Instant;
+
+use async_trait::async_trait;
+use base64::{engine::general_purpose::STANDARD, Engine};
+use serde::{Deserialize, Serialize};
+use tokio::sync::{Mutex, RwLock};
+
+use crate::engine::drivers::mysql::MySqlDriver;
+use crate::engine::error::{EngineError, EngineResult};
+use crate::engine::traits::DataEngine;
+use crate::engine::types::{
+    CancelSupport, Collection, CollectionType, ColumnInfo, ConnectionConfig, Namespace,
+    NewColumnDef, NewTableSchema, PrivilegeReport, QueryId, QueryResult, Row as QRow, RowData,
+    RowLockMode, ServerVariable, SessionId, TableColumn, TableSchema, TableSizeInfo,
+    TransactionStatus, Value, VariableScope,
+};
+
+/// HTTP API host used when the connection has no host set
+const DEFAULT_HTTP_HOST: &str = "aws.connect.psdb.cloud";
+
+/// Enables PlanetScale Boost for the reads of a connection
+const BOOST_STATEMENT: &str = "SET @@boost_cached_queries = true";
+
+/// Schemas hidden from the namespace list, as in `MySqlDriver`
+const SYSTEM_SCHEMAS: [&str; 4] = ["information_schema", "mysql", "performance_schema", "sys"];
+
+/// A session that talks to the PlanetScale HTTP API
+struct HttpSession {
+    client: reqwest::Client,
+    base_url: String,
+    username: String,
+    password: String,
+    /// Session state returned by the last request, `None` before the first one
+    state: Mutex<Option<serde_json::Value>>,
+    transaction_active: std::sync::atomic::AtomicBool,
+}
+
+#[derive(Serialize)]
+struct ExecuteRequest<'a> {
+    query: &'a str,
+    session: Option<&'a serde_json::Value>,
+}
+
+#[derive(Deserialize)]
+struct ApiResponse {
+    session: Option<serde_json::Value>,
+    result: Option<ApiResult>,
+    error: Option<ApiError>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ApiResult {
+    #[serde(default)]
+    fields: Vec<ApiField>,
+    #[serde(default)]
+    rows: Vec<ApiRow>,
+    /// 64-bit integers are encoded as JSON strings
+    rows_affected: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ApiField {
+    name: String,
+    /// Vitess type name (`INT64`, `VARCHAR`, ...); omitted for `NULL_TYPE`
+    #[serde(rename = "type", default)]
+    field_type: Option<String>,
+    /// Full column type (`varchar(255)`), when the API reports it
+    column_type: Option<String>,
+}
+
+/// A row as returned by the API: every value concatenated and base64
+/// encoded, with the byte length of each value (`-1` for NULL)
+#[derive(Deserialize)]
+struct ApiRow {
+    #[serde(default)]
+    lengths: Vec<String>,
+    values: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct ApiError {
+    message: String,
+    code: Option<String>,
+}
+
+impl HttpSession {
+    /// Runs a single statement and returns its raw result
+    async fn execute(&self, query: &str) -> EngineResult<ApiResult> {
+        let mut state = self.state.lock().await;
+        let response = self
+            .post(
+                "Execute",
+                &ExecuteRequest {
+                    query,
+                    session: state.as_ref(),
+                },
+            )
+            .await?;
+
+        if let Some(session) = response.session {
+            *state = Some(session);
+        }
+        if let Some(error) = response.error {
+            return Err(PlanetScaleDriver::map_api_error(error));
+        }
+        response
+            .result
+            .ok_or_else(|| EngineError::execution_error("PlanetScale returned no result"))
+    }
+
+    async fn post(&self, method: &str, body: &impl Serialize) -> EngineResult<ApiResponse> {
+        let response = self
+            .client
+            .post(format!(
+                "{}/psdb.v1alpha1.Database/{}",
+                self.base_url, method
+            ))
+            .basic_auth(&self.username, Some(&self.password))
+            .json(body)
+            .send()
+            .await
+            .map_err(|e| EngineError::connection_failed(e.to_string()))?;
+
+        let status = response.status();
+        if status == reqwest::StatusCode::UNAUTHORIZED {
+            return Err(EngineError::auth_failed(
+                "PlanetScale rejected the username or password",
+            ));
+        }
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            return Err(EngineError::connection_failed(format!(
+                "PlanetScale HTTP API returned {}: {}",
+                status, body
+            )));
+        }
+
+        response
+            .json::<ApiResponse>()
+            .await
+            .map_err(|e| EngineError::internal(format!("Invalid PlanetScale response: {}", e)))
+    }
+}
+
+/// PlanetScale driver implementation
+pub struct PlanetScaleDriver {
+    inner: MySqlDriver,
+    http_sessions: RwLock<HashMap<SessionId, Arc<HttpSession>>>,
+}
+
+impl PlanetScaleDriver {
+    pub fn new() -> Self {
+        Self {
+            inner: MySqlDriver::new(),
+            http_sessions: RwLock::new(HashMap::new()),
+        }
+    }
+
+    fn quote_ident(name: &str) -> String {
+        format!("`{}`", name.replace('`', "``"))
+    }
+
+    fn quote_literal(value: &str) -> String {
+        format!("'{}'", value.replace('\\', "\\\\").replace('\'', "''"))
+    }
+
+    /// Returns the HTTP session, or `None` for sessions using the MySQL protocol
+    async fn http_session(&self, session: SessionId) -> Option<Arc<HttpSession>> {
+        let sessions = self.http_sessions.read().await;
+        sessions.get(&session).cloned()
+    }
+
+    fn http_unsupported(feature: &str) -> EngineError {
+        EngineError::not_supported(format!(
+            "{} is not available over the PlanetScale HTTP API",
+            feature
+        ))
+    }
+
+    /// Opens a session on the HTTP API and selects the configured database
+    async fn open_http_session(config: &ConnectionConfig) -> EngineResult<HttpSession> {
+        let host = match config.host.trim() {
+            "" => DEFAULT_HTTP_HOST,
+            host => host,
+        };
+        let client = reqwest::Client::builder()
+            .timeout(std::time::Duration::from_secs(30))
+            .build()
+            .map_err(|e| EngineError::internal(e.to_string()))?;
+
+        let http_session = HttpSession {
+            client,
+            base_url: format!("https://{}", host),
+            username: config.username.clone(),
+            password: config.password.clone(),
+            state: Mutex::new(None),
+            transaction_active: std::sync::atomic::AtomicBool::new(false),
+        };
+
+        let response = http_session
+            .post("CreateSession", &serde_json::json!({}))
+            .await?;
+        if let Some(error) = response.error {
+            return Err(Self::map_api_error(error));
+        }
+        *http_session.state.lock().await = response.session;
+
+        if let Some(database) = config.database.as_deref() {
+            http_session
+                .execute(&format!("USE {}", Self::quote_ident(database)))
+                .await?;
+        }
+        if config.boost == Some(true) {
+            http_session.execute(BOOST_STATEMENT).await?;
+        }
+
+        Ok(http_session)
+    }
+
+    fn map_api_error(error: ApiError) -> EngineError {
+        let message = error.message;
+        match error.code.as_deref() {
+            Some("unauthenticated") | Some("permission_denied") => {
+                EngineError::auth_failed(message)
+            }
+            _ if message.contains("syntax error") => EngineError::syntax_error(message),
+            _ => EngineError::execution_error(message),
+        }
+    }
+
+    /// Converts an API result into a `QueryResult`
+    fn convert_result(result: ApiResult, execution_time_ms: f64) -> EngineResult<QueryResult> {
+        if result.fields.is_empty() {
+            let affected = result
+                .rows_affected
+                .as_deref()
+                .and_then(|n| n.parse().ok())
+                .unwrap_or(0);
+            return Ok(QueryResult::with_affected_rows(affected, execution_time_ms));
+        }
+
+        let columns = result
+            .fields
+            .iter()
+            .map(|field| {
+                let data_type = field
+                    .column_type
+                    .clone()
+                    .or_else(|| field.field_type.clone())
+                    .unwrap_or_else(|| "NULL".to_string());
+                ColumnInfo {
+                    name: field.name.clone(),
+                    display_size: ColumnInfo::display_size_for(&data_type),
+                    data_type,
+                    nullable: true,
+                    ..Default::default()
+                }
+            })
+            .collect();
+
+        let rows = result
+            .rows
+            .iter()
+            .map(|row| Self::decode_row(&result.fields, row))
+            .collect::<EngineResult<Vec<_>>>()?;
+
+        Ok(QueryResult {
+            columns,
+            rows,
+            affected_rows: None,
+            execution_time_ms,
+            plan_estimate: None,
+            truncated: false,
+            estimated_memory_bytes: None,
+            truncated_values: Vec::new(),
+        })
+    }
+
+    fn decode_row(fields: &[ApiField], row: &ApiRow) -> EngineResult<QRow> {
+        let invalid = || EngineError::internal("Invalid row in PlanetScale response");
+
+        let bytes = match row.values.as_deref() {
+            Some(values) => STANDARD.decode(values).map_err(|_| invalid())?,
+            None => Vec::new(),
+        };
+
+        let mut offset = 0usize;
+        let mut values = Vec::with_capacity(fields.len());
+        for (idx, field) in fields.iter().enumerate() {
+            let length: i64 = row
+                .lengths
+                .get(idx)
+                .and_then(|l| l.parse().ok())
+                .unwrap_or(-1);
+            if length < 0 {
+                values.push(Value::Null);
+                continue;
+            }
+
+            let end = offset + length as usize;
+            let raw = bytes.get(offset..end).ok_or_else(invalid)?;
+            offset = end;
+            values.push(Self::convert_value(field.field_type.as_deref(), raw));
+        }
+
+        Ok(QRow { values })
+    }
+
+    /// Converts a raw value according to its Vitess type
+    fn convert_value(field_type: Option<&str>, raw: &[u8]) -> Value {
+        let text = || String::from_utf8_lossy(raw).into_owned();
+        match field_type.unwrap_or("NULL_TYPE") {
+            "NULL_TYPE" => Value::Null,
+            "INT8" | "INT16" | "INT24" | "INT32" | "INT64" | "UINT8" | "UINT16" | "UINT24"
+            | "UINT32" | "YEAR" => text()
+                .parse()
+                .map(Value::Int)
+                .unwrap_or_else(|_| Value::Text(text())),
+            // Matches the MySQL driver, which maps BIGINT UNSIGNED to i64
+            "UINT64" => text()
+                .parse::<u64>()
+                .map(|u| Value::Int(u as i64))
+                .unwrap_or_else(|_| Value::Text(text())),
+            "FLOAT32" | "FLOAT64" | "DECIMAL" => text()
+                .parse()
+                .map(Value::Float)
+                .unwrap_or_else(|_| Value::Text(text())),
+            "JSON" => serde_json::from_slice(raw)
+                .map(Value::Json)
+                .unwrap_or_else(|_| Value::Text(text())),
+            "BLOB" | "BINARY" | "VARBINARY" | "BIT" | "GEOMETRY" => Value::Bytes(raw.to_vec()),
+            _ => Value::Text(text()),
+        }
+    }
+
+    /// Runs a query over HTTP and returns the rows as text columns
+    async fn http_query_strings(
+        http_session: &HttpSession,
+        query: &str,
+    ) -> EngineResult<Vec<Vec<Option<String>>>> {
+        let result = http_session.execute(query).await?;
+        let result = Self::convert_result(result, 0.0)?;
+        Ok(result
+            .rows
+            .into_iter()
+            .map(|row| {
+                row.values
+                    .into_iter()
+                    .map(|value| match value {
+                        Value::Null => None,
+                        Value::Text(s) => Some(s),
+                        Value::Int(i) => Some(i.to_string()),
+                        Value::Float(f) => Some(f.to_string()),
+                        other => Some(format!("{:?}", other)),
+                    })
+                    .collect()
+            })
+            .collect())
+    }
+}
+
+impl Default for PlanetScaleDriver {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl DataEngine for PlanetScaleDriver {
+    fn driver_id(&self) -> &'static str {
+        "planetscale"
+    }
+
+    fn driver_name(&self) -> &'static str {
+        "PlanetScale"
+    }
+
+    fn default_port(&self) -> u16 {
+        3306
+    }
+
+    async fn test_connection(&self, config: &ConnectionConfig) -> EngineResult<()> {
+        if config.use_http_api {
+            let http_session = Self::open_http_session(config).await?;
+            http_session.execute("SELECT 1").await?;
+            return Ok(());
+        }
+        self.inner.test_connection(config).await
+    }
+
+    async fn connect(&self, config: &ConnectionConfig) -> EngineResult<SessionId> {
+        if !config.use_http_api {
+            let init_statements = match config.boost {
+                Some(true) => vec![BOOST_STATEMENT.to_string()],
+                _ => Vec::new(),
+            };
+            return self.inner.connect_with_init(config, init_statements).await;
+        }
+
+        let http_session = Self::open_http_session(config).await?;
+        let session_id = SessionId::new();
+        let mut sessions = self.http_sessions.write().await;
+        sessions.insert(session_id, Arc::new(http_session));
+        Ok(session_id)
+    }
+
+    async fn disconnect(&self, session: SessionId) -> EngineResult<()> {
+        let removed = {
+            let mut sessions = self.http_sessions.write().await;
+            sessions.remove(&session)
+        };
+        match removed {
+            // The API keeps no server-side connection to close
+            Some(_) => Ok(()),
+            None => self.inner.disconnect(session).await,
+        }
+    }
+
+    async fn rebind_session(&self, from: SessionId, to: SessionId) -> EngineResult<()> {
+        let mut sessions = self.http_sessions.write().await;
+        match sessions.remove(&from) {
+            Some(http_session) => {
+                if let Some(stale) = sessions.insert(to, http_session) {
+                    sessions.insert(from, stale);
+                }
+                Ok(())
+            }
+            None => self.inner.rebind_session(from, to).await,
+        }
+    }
+
+    /// HTTP sessions hold no connection, so only MySQL sessions are pinged
+    async fn ping(&self, session: SessionId) -> EngineResult<()> {
+        if self.http_session(session).await.is_some() {
+            return Ok(());
+        }
+        self.inner.ping(session).await
+    }
+
+    async fn list_namespaces(&self, session: SessionId) -> EngineResult<Vec<Namespace>> {
+        let Some(http_session) = self.http_session(session).await else {
+            return self.inner.list_namespaces(session).await;
+        };
+
+        let rows = Self::http_query_strings(&http_session, "SHOW DATABASES").await?;
+        let mut namespaces: Vec<Namespace> = rows
+            .into_iter()
+            .filter_map(|row| row.into_iter().next().flatten())
+            .filter(|db| !SYSTEM_SCHEMAS.contains(&db.as_str()))
+            .map(Namespace::new)
+            .collect();
+        namespaces.sort_by(|a, b| a.database.cmp(&b.database));
+        Ok(namespaces)
+    }
+
+    async fn create_namespace(
+        &self,
+        session: SessionId,
+        namespace: &Namespace,
+    ) -> EngineResult<()> {
+        if self.http_session(session).await.is_some() {
+            return Err(Self::http_unsupported("Database creation"));
+        }
+        self.inner.create_namespace(session, namespace).await
+    }
+
+    async fn drop_namespace(
+        &self,
+        session: SessionId,
+        namespace: &Namespace,
+        cascade: bool,
+    ) -> EngineResult<()> {
+        if self.http_session(session).await.is_some() {
+            return Err(Self::http_unsupported("Database deletion"));
+        }
+        self.inner.drop_namespace(session, namespace, cascade).await
+    }
+
+    async fn create_table(
+        &self,
+        session: SessionId,
+        namespace: &Namespace,
+        schema: &NewTableSchema,
+    ) -> EngineResult<()> {
+        if self.http_session(session).await.is_some() {
+            return Err(Self::http_unsupported("Table creation"));
+        }
+        self.inner.create_table(session, namespace, schema).await
+    }
+
+    async fn add_column(
+        &self,
+        session: SessionId,
+        namespace: &Namespace,
+        table: &str,
+        column: &NewColumnDef,
+    ) -> EngineResult<()> {
+        if self.http_session(session).await.is_some() {
+            return Err(Self::http_unsupported("Column changes"));
+        }
+        self.inner
+            .add_column(session, namespace, table, column)
+            .await
+    }
+
+    async fn drop_column(
+        &self,
+        session: SessionId,
+        namespace: &Namespace,
+        table: &str,
+        column_name: &str,
+    ) -> EngineResult<()> {
+        if self.http_session(session).await.is_some() {
+            return Err(Self::http_unsupported("Column changes"));
+        }
+        self.inner
+            .drop_column(session, namespace, table, column_name)
+            .await
+    }
+
+    async fn rename_column(
+        &self,
+        session: SessionId,
+        namespace: &Namespace,
+        table: &str,
+        old_name: &str,
+        new_name: &str,
+    ) -> EngineResult<()> {
+        if self.http_session(session).await.is_some() {
+            return Err(Self::http_unsupported("Column changes"));
+        }
+        self.inner
+            .rename_column(session, namespace, table, old_name, new_name)
+            .await
+    }
+
+    async fn list_collections(
+        &self,
+        session: SessionId,
+        namespace: &Namespace,
+    ) -> EngineResult<Vec<Collection>> {
+        let Some(http_session) = self.http_session(session).await else {
+            return self.inner.list_collections(session, namespace).await;
+        };
+
+        let query = format!(
+            "SHOW FULL TABLES FROM {}",
+            Self::quote_ident(&namespace.database)
+        );
+        let rows = Self::http_query_strings(&http_session, &query).await?;
+        let mut collections: Vec<Collection> = rows
+            .into_iter()
+            .filter_map(|row| {
+                let mut values = row.into_iter();
+                let name = values.next().flatten()?;
+                let collection_type = match values.next().flatten().as_deref() {
+                    Some("VIEW") => CollectionType::View,
+                    _ => CollectionType::Table,
+                };
+                Some(Collection {
+                    namespace: namespace.clone(),
+                    name,
+                    collection_type,
+                })
+            })
+            .collect();
+        collections.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(collections)
+    }
+
+    /// The HTTP API has no bulk equivalent, so HTTP sessions list each
+    /// database in turn
+    async fn list_all_collections(
+        &self,
+        session: SessionId,
+    ) -> EngineResult<HashMap<Namespace, Vec<Collection>>> {
+        if self.http_session(session).await.is_none() {
+            return self.inner.list_all_collections(session).await;
+        }
+
+        let mut collections = HashMap::new();
+        for namespace in self.list_namespaces(session).await? {
+            let items = self.list_collections(session, &namespace).await?;
+            collections.insert(namespace, items);
+        }
+        Ok(collections)
+    }
+
+    async fn execute(
+        &self,
+        session: SessionId,
+        query: &str,
+        query_id: QueryId,
+    ) -> EngineResult<QueryResult> {
+        let Some(http_session) = self.http_session(session).await else {
+            return self.inner.execute(session, query, query_id).await;
+        };
+
+        let start = Instant::now();
+        let result = http_session.execute(query).await?;
+        Self::convert_result(result, start.elapsed().as_micros() as f64 / 1000.0)
+    }
+
+    async fn execute_multi(
+        &self,
+        session: SessionId,
+        query: &str,
+        query_id: QueryId,
+    ) -> EngineResult<Vec<QueryResult>> {
+        if self.http_session(session).await.is_some() {
+            return Err(Self::http_unsupported(
+                "Running multiple statements at once",
+            ));
+        }
+        self.inner.execute_multi(session, query, query_id).await
+    }
+
+    async fn execute_with_params(
+        &self,
+        session: SessionId,
+        query: &str,
+        params: &[Value],
+        query_id: QueryId,
+    ) -> EngineResult<QueryResult> {
+        if self.http_session(session).await.is_some() {
+            return Err(Self::http_unsupported("Binding query parameters"));
+        }
+        self.inner
+            .execute_with_params(session, query, params, query_id)
+            .await
+    }
+
+    async fn describe_table(
+        &self,
+        session: SessionId,
+        namespace: &Namespace,
+        table: &str,
+    ) -> EngineResult<TableSchema> {
+        let Some(http_session) = self.http_session(session).await else {
+            return self.inner.describe_table(session, namespace, table).await;
+        };
+
+        let query = format!(
+            "SELECT COLUMN_NAME, COLUMN_TYPE, IS_NULLABLE, COLUMN_DEFAULT, COLUMN_KEY, \
+             NULLIF(COLUMN_COMMENT, ''), CHARACTER_SET_NAME \
+             FROM information_schema.COLUMNS \
+             WHERE TABLE_SCHEMA = {} AND TABLE_NAME = {} \
+             ORDER BY ORDINAL_POSITION",
+            Self::quote_literal(&namespace.database),
+            Self::quote_literal(table)
+        );
+        let rows = Self::http_query_strings(&http_session, &query).await?;
+
+        let mut pk_columns: Vec<String> = Vec::new();
+        let mut columns = Vec::with_capacity(rows.len());
+        for row in rows {
+            let mut values = row.into_iter();
+            let name = values.next().flatten().unwrap_or_default();
+            let data_type = values.next().flatten().unwrap_or_default();
+            let nullable = values.next().flatten().as_deref() == Some("YES");
+            let default_value = values.next().flatten();
+            let is_primary_key = values.next().flatten().as_deref() == Some("PRI");
+            let comment = values.next().flatten();
+            let charset = values.next().flatten();
+            if is_primary_key {
+                pk_columns.push(name.clone());
+            }
+            columns.push(TableColumn {
+                allowed_values: MySqlDriver::parse_enum_values(&data_type),
+                name,
+                data_type,
+                nullable,
+                default_value,
+                is_primary_key,
+                comment,
+                charset,
+            });
+        }
+
+        let query = format!(
+            "SELECT TABLE_ROWS, \
+             CASE WHEN TABLE_TYPE = 'VIEW' THEN NULL ELSE NULLIF(TABLE_COMMENT, '') END \
+             FROM information_schema.TABLES \
+             WHERE TABLE_SCHEMA = {} AND TABLE_NAME = {}",
+            Self::quote_literal(&namespace.database),
+            Self::quote_literal(table)
+        );
+        let mut table_row = Self::http_query_strings(&http_session, &query)
+            .await?
+            .into_iter()
+            .next()
+            .unwrap_or_default()
+            .into_iter();
+        let row_count_estimate = table_row
+            .next()
+            .flatten()
+            .and_then(|count| count.parse().ok());
+        let comment = table_row.next().flatten();
+
+        Ok(TableSchema {
+            columns,
+            primary_key: if pk_columns.is_empty() {
+                None
+            } else {
+                Some(pk_columns)
+            },
+            row_count_estimate,
+            comment,
+        })
+    }
+
+    async fn preview_table(
+        &self,
+        session: SessionId,
+        namespace: &Namespace,
+        table: &str,
+        limit: u32,
+        lock_rows: Option<RowLockMode>,
+    ) -> EngineResult<QueryResult> {
+        if self.http_session(session).await.is_none() {
+            return self
+                .inner
+                .preview_table(session, namespace, table, limit, lock_rows)
+                .await;
+        }
+
+        let mut query = format!(
+            "SELECT * FROM {}.{} LIMIT {}",
+            Self::quote_ident(&namespace.database),
+            Self::quote_ident(table),
+            limit
+        );
+        if let Some(mode) = lock_rows {
+            query = self.lock_rows_query(session, &query, mode).await?;
+        }
+        self.execute(session, &query, QueryId::new()).await
+    }
+
+    async fn fetch_value(
+        &self,
+        session: SessionId,
+        namespace: &Namespace,
+        table: &str,
+        primary_key: &RowData,
+        column: &str,
+    ) -> EngineResult<Value> {
+        if self.http_session(session).await.is_some() {
+            return Err(Self::http_unsupported("Fetching single values"));
+        }
+        self.inner
+            .fetch_value(session, namespace, table, primary_key, column)
+            .await
+    }
+
+    async fn lock_rows_query(
+        &self,
+        session: SessionId,
+        query: &str,
+        mode: RowLockMode,
+    ) -> EngineResult<String> {
+        let Some(http_session) = self.http_session(session).await else {
+            return self.inner.lock_rows_query(session, query, mode).await;
+        };
+
+        if matches!(mode, RowLockMode::NoKeyUpdate | RowLockMode::KeyShare) {
+            return Err(EngineError::not_supported(format!(
+                "{} is not supported by MySQL",
+                mode.sql_clause()
+            )));
+        }
+        if !http_session
+            .transaction_active
+            .load(std::sync::atomic::Ordering::SeqCst)
+        {
+            return Err(EngineError::transaction_error(
+                "Row locking requires an active transaction",
+            ));
+        }
+        Ok(mode.wrap_query(query))
+    }
+
+    async fn get_table_sizes(
+        &self,
+        session: SessionId,
+        namespace: &Namespace,
+    ) -> EngineResult<Vec<TableSizeInfo>> {
+        if self.http_session(session).await.is_some() {
+            return Err(Self::http_unsupported("Table size statistics"));
+        }
+        self.inner.get_table_sizes(session, namespace).await
+    }
+
+    async fn check_privileges(&self, session: SessionId) -> EngineResult<PrivilegeReport> {
+        if self.http_session(session).await.is_some() {
+            return Err(Self::http_unsupported("Privilege checks"));
+        }
+        self.inner.check_privileges(session).await
+    }
+
+    async fn set_session_variable(
+        &self,
+        session: SessionId,
+        name: &str,
+        value: &str,
+        scope: VariableScope,
+    ) -> EngineResult<()> {
+        if self.http_session(session).await.is_some() {
+            return Err(Self::http_unsupported("Setting session variables"));
+        }
+        self.inner
+            .set_session_variable(session, name, value, scope)
+            .await
+    }
+
+    async fn list_server_variables(
+        &self,
+        session: SessionId,
+        scope: VariableScope,
+        filter: Option<&str>,
+    ) -> EngineResult<Vec<ServerVariable>> {
+        if self.http_session(session).await.is_some() {
+            return Err(Self::http_unsupported("Listing server variables"));
+        }
+        self.inner
+            .list_server_variables(session, scope, filter)
+            .await
+    }
+
+    async fn clear_statement_cache(&self, session: SessionId) -> EngineResult<()> {
+        if self.http_session(session).await.is_some() {
+            return Err(Self::http_unsupported("Prepared statement caching"));
+        }
+        self.inner.clear_statement_cache(session).await
+    }
+
+    async fn set_server_variable(
+        &self,
+        session: SessionId,
+        name: &str,
+        value: &str,
+        scope: VariableScope,
+    ) -> EngineResult<()> {
+        if self.http_session(session).await.is_some() {
+            return Err(Self::http_unsupported("Setting server variables"));
+        }
+        self.inner
+            .set_server_variable(session, name, value, scope)
+            .await
+    }
+
+    /// Only sessions using the MySQL protocol can be cancelled
+    async fn cancel(&self, session: SessionId, query_id: Option<QueryId>) -> EngineResult<()> {
+        if self.http_session(session).await.is_some() {
+            return Err(Self::http_unsupported("Query cancellation"));
+        }
+        self.inner.cancel(session, query_id).await
+    }
+
+    fn cancel_support(&self) -> CancelSupport {
+        self.inner.cancel_support()
+    }
+
+    async fn begin_transaction(&self, session: SessionId) -> EngineResult<()> {
+        let Some(http_session) = self.http_session(session).await else {
+            return self.inner.begin_transaction(session).await;
+        };
+
+        if http_session
+            .transaction_active
+            .load(std::sync::atomic::Ordering::SeqCst)
+        {
+            return Err(EngineError::transaction_error(
+                "A transaction is already active on this session",
+            ));
+        }
+        // The transaction lives in the session state echoed by the API
+        http_session.execute("BEGIN").await?;
+        http_session
+            .transaction_active
+            .store(true, std::sync::atomic::Ordering::SeqCst);
+        Ok(())
+    }
+
+    async fn commit(&self, session: SessionId) -> EngineResult<()> {
+        let Some(http_session) = self.http_session(session).await else {
+            return self.inner.commit(session).await;
+        };
+
+        if !http_session
+            .transaction_active
+            .swap(false, std::sync::atomic::Ordering::SeqCst)
+        {
+            return Err(EngineError::transaction_error(
+                "No active transaction to commit",
+            ));
+        }
+        http_session.execute("COMMIT").await.map(|_| ())
+    }
+
+    async fn rollback(&self, session: SessionId) -> EngineResult<()> {
+        let Some(http_session) = self.http_session(session).await else {
+            return self.inner.rollback(session).await;
+        };
+
+        if !http_session
+            .transaction_active
+            .swap(false, std::sync::atomic::Ordering::SeqCst)
+        {
+            return Err(EngineError::transaction_error(
+                "No active transaction to rollback",
+            ));
+        }
+        http_session.execute("ROLLBACK").await.map(|_| ())
+    }
+
+    async fn transaction_status(&self, session: SessionId) -> EngineResult<TransactionStatus> {
+        let Some(http_session) = self.http_session(session).await else {
+            return self.inner.transaction_status(session).await;
+        };
+
+        Ok(TransactionStatus {
+            active: http_session
+                .transaction_active
+                .load(std::sync::atomic::Ordering::SeqCst),
+            server_state: None,
+        })
+    }
+
+    fn supports_transactions(&self) -> bool {
+        true
+    }
+
+    /// Mutations bind parameters, which the HTTP API does not support
+    async fn insert_row(
+        &self,
+        session: SessionId,
+        namespace: &Namespace,
+        table: &str,
+        data: &RowData,
+    ) -> EngineResult<QueryResult> {
+        if self.http_session(session).await.is_some() {
+            return Err(Self::http_unsupported("Row editing"));
+        }
+        self.inner.insert_row(session, namespace, table, data).await
+    }
+
+    async fn insert_row_returning(
+        &self,
+        session: SessionId,
+        namespace: &Namespace,
+        table: &str,
+        data: &RowData,
+    ) -> EngineResult<QueryResult> {
+        if self.http_session(session).await.is_some() {
+            return Err(Self::http_unsupported("Row editing"));
+        }
+        self.inner
+            .insert_row_returning(session, namespace, table, data)
+            .await
+    }
+
+    async fn update_row(
+        &self,
+        session: SessionId,
+        namespace: &Namespace,
+        table: &str,
+        primary_key: &RowData,
+        data: &RowData,
+    ) -> EngineResult<QueryResult> {
+        if self.http_session(session).await.is_some() {
+            return Err(Self::http_unsupported("Row editing"));
+        }
+        self.inner
+            .update_row(session, namespace, table, primary_key, data)
+            .await
+    }
+
+    async fn delete_row(
+        &self,
+        session: SessionId,
+        namespace: &Namespace,
+        table: &str,
+        primary_key: &RowData,
+    ) -> EngineResult<QueryResult> {
+        if self.http_session(session).await.is_some() {
+            return Err(Self::http_unsupported("Row editing"));
+        }
+        self.inner
+            .delete_row(session, namespace, table, primary_key)
+            .await
+    }
+
+    async fn delete_rows_where(
+        &self,
+        session: SessionId,
+        namespace: &Namespace,
+        table: &str,
+        filter: &str,
+    ) -> EngineResult<QueryResult> {
+        if self.http_session(session).await.is_some() {
+            return Err(Self::http_unsupported("Row editing"));
+        }
+        self.inner
+            .delete_rows_where(session, namespace, table, filter)
+            .await
+    }
+
+    async fn truncate_table(
+        &self,
+        session: SessionId,
+        namespace: &Namespace,
+        table: &str,
+        cascade: bool,
+    ) -> EngineResult<QueryResult> {
+        if self.http_session(session).await.is_some() {
+            return Err(Self::http_unsupported("Truncate"));
+        }
+        self.inner
+            .truncate_table(session, namespace, table, cascade)
+            .await
+    }
+
+    fn supports_mutations(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decodes_http_api_rows() {
+        let fields = vec![
+            ApiField {
+                name: "id".to_string(),
+                field_type: Some("INT64".to_string()),
+                column_type: None,
+            },
+            ApiField {
+                name: "name".to_string(),
+                field_type: Some("VARCHAR".to_string()),
+                column_type: None,
+            },
+            ApiField {
+                name: "deleted_at".to_string(),
+                field_type: Some("DATETIME".to_string()),
+                column_type: None,
+            },
+        ];
+        let row = ApiRow {
+            lengths: vec!["2".to_string(), "5".to_string(), "-1".to_string()],
+            values: Some(STANDARD.encode("42alice")),
+        };
+
+        let decoded = PlanetScaleDriver::decode_row(&fields, &row).unwrap();
+        assert!(matches!(decoded.values[0], Value::Int(42)));
+        assert!(matches!(&decoded.values[1], Value::Text(s) if s == "alice"));
+        assert!(matches!(decoded.values[2], Value::Null));
+    }
+}
@@ -14,18 +14,78 @@ use std::sync::Arc;
 use std::time::Instant;
 
 use async_trait::async_trait;
+use futures::stream::BoxStream;
+use futures::StreamExt;
 use sqlx::pool::PoolConnection;
-use sqlx::postgres::{PgPool, PgPoolOptions, PgRow, Postgres};
-use sqlx::{Column, Row, TypeInfo};
+use sqlx::postgres::types::Oid;
+use sqlx::postgres::{
+    PgColumn, PgConnectOptions, PgConnection, PgPool, PgPoolOptions, PgRow, Postgres,
+};
+use sqlx::{Column, Executor, Row, TypeInfo};
 use tokio::sync::{Mutex, RwLock};
 
+use crate::engine::drivers::{clear_statement_caches, url_encode};
 use crate::engine::error::{EngineError, EngineResult};
+use crate::engine::sql_safety;
 use crate::engine::traits::DataEngine;
 use crate::engine::types::{
-    CancelSupport, Collection, CollectionType, ColumnInfo, ConnectionConfig, Namespace, QueryId,
-    QueryResult, Row as QRow, RowData, SessionId, TableColumn, TableSchema, Value,
+    CancelSupport, Collection, CollectionType, ColumnInfo, ConnectionConfig, Namespace,
+    NewColumnDef, NewTableSchema, PlanEstimate, PrivilegeReport, QueryId, QueryResult, Row as QRow,
+    Routine, RoutineType, RowData, RowLockMode, ServerQuery, ServerVariable, SessionId, SslMode,
+    TableColumn, TableSchema, TableSizeInfo, TransactionStatus, Value, VariableScope, WalStatus,
 };
 
+/// Size of the chunks sent to the server by `import_copy`
+const COPY_CHUNK_BYTES: usize = 64 * 1024;
+
+/// Data type reported for large object references (the `lo` extension's
+/// domain over `oid`)
+const LARGE_OBJECT_TYPE: &str = "lo";
+
+/// Variables `set_session_variable` may change
+const SETTABLE_VARIABLES: &[&str] = &[
+    "application_name",
+    "bytea_output",
+    "client_encoding",
+    "datestyle",
+    "default_transaction_isolation",
+    "default_transaction_read_only",
+    "extra_float_digits",
+    "idle_in_transaction_session_timeout",
+    "intervalstyle",
+    "lock_timeout",
+    "search_path",
+    "statement_timeout",
+    "timezone",
+    "work_mem",
+];
+
+/// Matches `text` against a SQL `LIKE` pattern (`%`, `_`), ignoring case
+///
+/// Backslash escapes are not supported.
+fn like_matches(pattern: &str, text: &str) -> bool {
+    let text: Vec<char> = text.to_lowercase().chars().collect();
+    // matches[j]: the pattern read so far matches the first j characters
+    let mut matches = vec![false; text.len() + 1];
+    matches[0] = true;
+    for p in pattern.to_lowercase().chars() {
+        let mut next = vec![false; text.len() + 1];
+        if p == '%' {
+            let mut seen = false;
+            for (slot, matched) in next.iter_mut().zip(&matches) {
+                seen |= *matched;
+                *slot = seen;
+            }
+        } else {
+            for (j, c) in text.iter().enumerate() {
+                next[j + 1] = matches[j] && (p == '_' || p == *c);
+            }
+        }
+        matches = next;
+    }
+    matches[text.len()]
+}
+
 /// Holds the connection state for a PostgreSQL session.
 ///
 /// A session always has a pool for regular operations.
@@ -39,6 +99,20 @@ pub struct PostgresSession {
     pub transaction_conn: Mutex<Option<PoolConnection<Postgres>>>,
     /// Active queries (query_id -> backend_pid)
     pub active_queries: Mutex<HashMap<QueryId, i32>>,
+    /// Session variables, re-applied to every pooled connection used for queries
+    pub session_variables: Mutex<HashMap<String, String>>,
+    /// Connection holding the session's advisory locks, if any
+    pub lock_conn: Mutex<Option<AdvisoryLockConn>>,
+}
+
+/// Connection holding a session's advisory locks
+///
+/// Session-level advisory locks belong to the backend that took them, so
+/// the connection stays out of the pool until every lock is released.
+pub struct AdvisoryLockConn {
+    conn: PoolConnection<Postgres>,
+    /// Acquisitions not released yet
+    held: usize,
 }
 
 impl PostgresSession {
@@ -47,7 +121,110 @@ impl PostgresSession {
             pool,
             transaction_conn: Mutex::new(None),
             active_queries: Mutex::new(HashMap::new()),
+            session_variables: Mutex::new(HashMap::new()),
+            lock_conn: Mutex::new(None),
+        }
+    }
+
+    /// Runs `statement`, which takes (`acquire`) or releases the advisory
+    /// lock `$1` and yields whether it did, on the lock connection
+    ///
+    /// With `lock_timeout_ms`, a wait for the lock that outlasts it counts
+    /// as not acquired.
+    async fn run_advisory_lock(
+        &self,
+        statement: &str,
+        key: i64,
+        acquire: bool,
+        lock_timeout_ms: Option<u64>,
+    ) -> EngineResult<bool> {
+        let mut guard = self.lock_conn.lock().await;
+        let mut lock_conn = match guard.take() {
+            Some(lock_conn) => lock_conn,
+            // Nothing to release without a lock connection
+            None if !acquire => return Ok(false),
+            None => AdvisoryLockConn {
+                conn: self
+                    .pool
+                    .acquire()
+                    .await
+                    .map_err(|e| EngineError::connection_failed(e.to_string()))?,
+                held: 0,
+            },
+        };
+
+        let conn = &mut *lock_conn.conn;
+        let result = async {
+            if let Some(timeout_ms) = lock_timeout_ms {
+                sqlx::query("SELECT set_config('lock_timeout', $1, false)")
+                    .bind(format!("{}ms", timeout_ms))
+                    .execute(&mut *conn)
+                    .await?;
+            }
+            let done = sqlx::query_scalar::<_, bool>(statement)
+                .bind(key)
+                .fetch_one(&mut *conn)
+                .await;
+            if lock_timeout_ms.is_some() {
+                sqlx::query("RESET lock_timeout").execute(&mut *conn).await?;
+            }
+            done
+        }
+        .await;
+
+        let result = result.or_else(|e| {
+            // lock_not_available: the wait outlasted lock_timeout
+            if e.as_database_error().and_then(|db| db.code()).as_deref() == Some("55P03") {
+                Ok(false)
+            } else {
+                Err(EngineError::execution_error(e.to_string()))
+            }
+        });
+        if matches!(result, Ok(true)) {
+            if acquire {
+                lock_conn.held += 1;
+            } else {
+                lock_conn.held = lock_conn.held.saturating_sub(1);
+            }
         }
+        // The connection goes back to the pool once no lock is left on it
+        if lock_conn.held > 0 {
+            *guard = Some(lock_conn);
+        }
+        result
+    }
+
+    /// Applies the session variables to a connection taken from the pool
+    async fn apply_session_variables(
+        &self,
+        conn: &mut PoolConnection<Postgres>,
+    ) -> EngineResult<()> {
+        let (names, values): (Vec<String>, Vec<String>) = {
+            let variables = self.session_variables.lock().await;
+            if variables.is_empty() {
+                return Ok(());
+            }
+            variables.iter().map(|(n, v)| (n.clone(), v.clone())).unzip()
+        };
+
+        sqlx::query(
+            "SELECT set_config(name, value, false) FROM unnest($1::text[], $2::text[]) AS v(name, value)",
+        )
+        .bind(&names)
+        .bind(&values)
+        .fetch_all(&mut **conn)
+        .await
+        .map_err(|e| EngineError::execution_error(e.to_string()))?;
+        Ok(())
+    }
+
+    /// Drops the prepared statements cached by the transaction connection
+    /// and the idle pooled ones
+    pub async fn clear_statement_cache(&self) -> EngineResult<()> {
+        let mut tx_guard = self.transaction_conn.lock().await;
+        clear_statement_caches(tx_guard.as_deref_mut(), &self.pool)
+            .await
+            .map_err(|e| EngineError::execution_error(e.to_string()))
     }
 
     /// Returns true if a transaction is currently active
@@ -79,15 +256,107 @@ impl PostgresDriver {
             .ok_or_else(|| EngineError::session_not_found(session.0.to_string()))
     }
 
+    /// Returns the pool of a session, for drivers built on top of this one
+    pub(crate) async fn session_pool(&self, session: SessionId) -> EngineResult<PgPool> {
+        Ok(self.get_session(session).await?.pool.clone())
+    }
+
+    /// Opens a throwaway pool on the connection string and runs `SELECT 1`
+    pub(crate) async fn test_connection_string(conn_str: &str) -> EngineResult<()> {
+        let pool = PgPoolOptions::new()
+            .max_connections(1)
+            .acquire_timeout(std::time::Duration::from_secs(10))
+            .connect(conn_str)
+            .await
+            .map_err(|e| {
+                let msg = e.to_string();
+                match e.as_database_error().and_then(|db| db.code()) {
+                    Some(code) if code.starts_with("28") => EngineError::auth_failed(msg),
+                    _ => EngineError::connection_failed(msg),
+                }
+            })?;
+
+        // Test with a simple query
+        sqlx::query("SELECT 1")
+            .execute(&pool)
+            .await
+            .map_err(|e| EngineError::execution_error(e.to_string()))?;
+
+        pool.close().await;
+        Ok(())
+    }
+
+    /// Opens a session pool on an already built connection string
+    ///
+    /// Pool recycling settings are taken from `config`.
+    pub(crate) async fn connect_with_string(
+        &self,
+        conn_str: &str,
+        config: &ConnectionConfig,
+    ) -> EngineResult<SessionId> {
+        let options = conn_str
+            .parse::<PgConnectOptions>()
+            .map_err(|e| EngineError::connection_failed(e.to_string()))?
+            .statement_cache_capacity(config.statement_cache_capacity());
+        let pool = PgPoolOptions::new()
+            .max_connections(5)
+            .acquire_timeout(std::time::Duration::from_secs(30))
+            .idle_timeout(config.pool_idle_timeout())
+            .max_lifetime(config.pool_max_lifetime())
+            .connect_with(options)
+            .await
+            .map_err(|e| EngineError::connection_failed(e.to_string()))?;
+
+        let session_id = SessionId::new();
+        let session = Arc::new(PostgresSession::new(pool));
+
+        let mut sessions = self.sessions.write().await;
+        sessions.insert(session_id, session);
+
+        Ok(session_id)
+    }
+
+    /// Schemes accepted for a raw connection string
+    pub(crate) const CONNECTION_SCHEMES: &'static [&'static str] =
+        &["postgres://", "postgresql://"];
+
+    /// Connection string of a config: its raw string if set, otherwise one
+    /// built from its fields
+    fn connection_string(config: &ConnectionConfig) -> EngineResult<String> {
+        Ok(match config.connection_string_override(Self::CONNECTION_SCHEMES)? {
+            Some(raw) => raw.to_string(),
+            None => Self::build_connection_string(config),
+        })
+    }
+
     /// Builds a connection string from config
     fn build_connection_string(config: &ConnectionConfig) -> String {
-        let ssl_mode = if config.ssl { "require" } else { "disable" };
+        let ssl_mode = match config.ssl_mode {
+            SslMode::Disabled => "disable",
+            SslMode::Preferred => "prefer",
+            SslMode::Required => "require",
+            SslMode::VerifyCa => "verify-ca",
+            SslMode::VerifyIdentity => "verify-full",
+        };
         let db = config.database.as_deref().unwrap_or("postgres");
 
-        format!(
+        let mut conn_str = format!(
             "postgres://{}:{}@{}:{}/{}?sslmode={}",
-            config.username, config.password, config.host, config.port, db, ssl_mode
-        )
+            url_encode(&config.username),
+            url_encode(&config.password),
+            config.host,
+            config.port,
+            url_encode(db),
+            ssl_mode
+        );
+
+        if config.ssl_mode.verifies_certificate() {
+            if let Some(ca_path) = config.ssl_ca_path.as_deref() {
+                conn_str.push_str(&format!("&sslrootcert={}", ca_path));
+            }
+        }
+
+        conn_str
     }
 
     /// Converts a SQLx row to our universal Row type
@@ -112,6 +381,8 @@ impl PostgresDriver {
             Value::Int(i) => query.bind(i),
             Value::Float(f) => query.bind(f),
             Value::Text(s) => query.bind(s),
+            Value::Timestamp(ts) => query.bind(ts),
+            Value::Uuid(uuid) => query.bind(uuid),
             Value::Bytes(b) => query.bind(b),
             Value::Json(j) => query.bind(j),
             // Fallback for arrays or other complex types not yet fully mapped
@@ -132,6 +403,10 @@ impl PostgresDriver {
         if let Ok(v) = row.try_get::<Option<i16>, _>(idx) {
             return v.map(|i| Value::Int(i as i64)).unwrap_or(Value::Null);
         }
+        // OIDs, including large object references (`lo` columns)
+        if let Ok(v) = row.try_get::<Option<Oid>, _>(idx) {
+            return v.map(|oid| Value::Int(oid.0 as i64)).unwrap_or(Value::Null);
+        }
         // Bool AFTER integers
         if let Ok(v) = row.try_get::<Option<bool>, _>(idx) {
             return v.map(Value::Bool).unwrap_or(Value::Null);
@@ -182,133 +457,735 @@ impl PostgresDriver {
             .map_err(|e| EngineError::execution_error(e.to_string()))
     }
 
-    /// Gets column info from a PgRow
-    fn get_column_info(row: &PgRow) -> Vec<ColumnInfo> {
-        row.columns()
-            .iter()
-            .map(|col| ColumnInfo {
-                name: col.name().to_string(),
-                data_type: col.type_info().name().to_string(),
-                nullable: true, // SQLx doesn't expose nullability easily at runtime
-            })
-            .collect()
-    }
-}
+    /// Runs statements in order on the transaction connection if one is
+    /// active, otherwise on a single pooled connection.
+    ///
+    /// The backend PID is registered under `query_id` for the whole run so
+    /// `cancel` can interrupt whichever statement is executing.
+    async fn run_statements(
+        &self,
+        session: SessionId,
+        statements: &[&str],
+        params: &[Value],
+        query_id: QueryId,
+    ) -> EngineResult<Vec<QueryResult>> {
+        let pg_session = self.get_session(session).await?;
 
-impl Default for PostgresDriver {
-    fn default() -> Self {
-        Self::new()
-    }
-}
+        let mut tx_guard = pg_session.transaction_conn.lock().await;
+        let mut pooled: Option<PoolConnection<Postgres>> = None;
+        let conn = match tx_guard.as_mut() {
+            Some(conn) => conn,
+            None => {
+                let conn = pooled.insert(
+                    pg_session
+                        .pool
+                        .acquire()
+                        .await
+                        .map_err(|e| EngineError::connection_failed(e.to_string()))?,
+                );
+                pg_session.apply_session_variables(conn).await?;
+                conn
+            }
+        };
 
-#[async_trait]
-impl DataEngine for PostgresDriver {
-    fn driver_id(&self) -> &'static str {
-        "postgres"
+        let backend_pid = Self::fetch_backend_pid(conn).await?;
+        {
+            let mut active = pg_session.active_queries.lock().await;
+            active.insert(query_id, backend_pid);
+        }
+
+        let mut results = Vec::with_capacity(statements.len());
+        let mut outcome = Ok(());
+        for statement in statements {
+            match Self::run_statement(conn, &pg_session.pool, statement, params).await {
+                Ok(result) => {
+                    if sql_safety::is_schema_change(statement) {
+                        Self::forget_stale_statements(Some(&mut **conn), &pg_session.pool).await;
+                    }
+                    results.push(result)
+                }
+                Err(e) => {
+                    outcome = Err(e);
+                    break;
+                }
+            }
+        }
+
+        let mut active = pg_session.active_queries.lock().await;
+        active.remove(&query_id);
+
+        outcome.map(|_| results)
     }
 
-    fn driver_name(&self) -> &'static str {
-        "PostgreSQL"
+    /// Clears the cached prepared statements after DDL ran on `conn`; a
+    /// failure is only logged since the DDL itself succeeded
+    async fn forget_stale_statements(conn: Option<&mut PgConnection>, pool: &PgPool) {
+        if let Err(e) = clear_statement_caches(conn, pool).await {
+            tracing::warn!(error = %e, "Failed to clear prepared statements after DDL");
+        }
     }
 
-    async fn test_connection(&self, config: &ConnectionConfig) -> EngineResult<()> {
-        let conn_str = Self::build_connection_string(config);
+    /// Runs a single statement on the given connection
+    async fn run_statement(
+        conn: &mut PoolConnection<Postgres>,
+        pool: &PgPool,
+        query: &str,
+        params: &[Value],
+    ) -> EngineResult<QueryResult> {
+        let start = Instant::now();
 
-        let pool = PgPoolOptions::new()
-            .max_connections(1)
-            .acquire_timeout(std::time::Duration::from_secs(10))
-            .connect(&conn_str)
-            .await
-            .map_err(|e| {
-                if e.to_string().contains("password authentication failed") {
-                    EngineError::auth_failed(e.to_string())
-                } else {
-                    EngineError::connection_failed(e.to_string())
-                }
-            })?;
+        let mut sql_query = sqlx::query(query);
+        for param in params {
+            sql_query = Self::bind_param(sql_query, param);
+        }
 
-        // Test with a simple query
-        sqlx::query("SELECT 1")
-            .execute(&pool)
-            .await
-            .map_err(|e| EngineError::execution_error(e.to_string()))?;
+        if Self::is_select(query) {
+            let pg_rows: Vec<PgRow> = sql_query
+                .fetch_all(&mut **conn)
+                .await
+                .map_err(Self::map_query_error)?;
 
-        pool.close().await;
-        Ok(())
-    }
+            let execution_time_ms = start.elapsed().as_micros() as f64 / 1000.0;
 
-    async fn connect(&self, config: &ConnectionConfig) -> EngineResult<SessionId> {
-        let conn_str = Self::build_connection_string(config);
+            if pg_rows.is_empty() {
+                // No row to read columns from; ask the server to describe the statement
+                let pg_columns = match (&mut **conn).describe(query).await {
+                    Ok(describe) => describe.columns().to_vec(),
+                    Err(_) => Vec::new(),
+                };
+                let mut columns = Self::get_column_info(&pg_columns);
+                Self::enrich_column_info(pool, &pg_columns, &mut columns).await;
 
-        let pool = PgPoolOptions::new()
-            .max_connections(5)
-            .acquire_timeout(std::time::Duration::from_secs(30))
-            .connect(&conn_str)
-            .await
-            .map_err(|e| EngineError::connection_failed(e.to_string()))?;
+                return Ok(QueryResult {
+                    columns,
+                    rows: Vec::new(),
+                    affected_rows: None,
+                    execution_time_ms,
+                    plan_estimate: None,
+                    truncated: false,
+                    estimated_memory_bytes: None,
+                    truncated_values: Vec::new(),
+                });
+            }
 
-        let session_id = SessionId::new();
-        let session = Arc::new(PostgresSession::new(pool));
+            let mut columns = Self::get_column_info(pg_rows[0].columns());
+            Self::enrich_column_info(pool, pg_rows[0].columns(), &mut columns).await;
+            let rows: Vec<QRow> = pg_rows.iter().map(Self::convert_row).collect();
+
+            Ok(QueryResult {
+                columns,
+                rows,
+                affected_rows: None,
+                execution_time_ms,
+                plan_estimate: None,
+                truncated: false,
+                estimated_memory_bytes: None,
+                truncated_values: Vec::new(),
+            })
+        } else {
+            let result = sql_query
+                .execute(&mut **conn)
+                .await
+                .map_err(Self::map_query_error)?;
 
-        let mut sessions = self.sessions.write().await;
-        sessions.insert(session_id, session);
+            let execution_time_ms = start.elapsed().as_micros() as f64 / 1000.0;
 
-        Ok(session_id)
+            Ok(QueryResult::with_affected_rows(
+                result.rows_affected(),
+                execution_time_ms,
+            ))
+        }
     }
 
-    async fn disconnect(&self, session: SessionId) -> EngineResult<()> {
-        let session = {
-            let mut sessions = self.sessions.write().await;
-            sessions
-                .remove(&session)
-                .ok_or_else(|| EngineError::session_not_found(session.0.to_string()))?
-        };
+    /// Streams the rows of a single statement in batches
+    ///
+    /// Statements that return no rows run through `run_statement` and are
+    /// reported as one batch.
+    async fn stream_statement(
+        conn: &mut PoolConnection<Postgres>,
+        pool: &PgPool,
+        query: &str,
+        batch_size: usize,
+        on_batch: &(dyn Fn(QueryResult) + Send + Sync),
+    ) -> EngineResult<u64> {
+        use futures::TryStreamExt;
+
+        if !Self::is_select(query) {
+            on_batch(Self::run_statement(conn, pool, query, &[]).await?);
+            return Ok(0);
+        }
+
+        let start = Instant::now();
+        let batch_size = batch_size.max(1);
+        let mut columns: Option<Vec<ColumnInfo>> = None;
+        let mut batch: Vec<QRow> = Vec::with_capacity(batch_size);
+        let mut total_rows = 0u64;
 
         {
-            let mut tx = session.transaction_conn.lock().await;
-            tx.take();
+            let mut stream = sqlx::query(query).fetch(&mut **conn);
+            while let Some(pg_row) = stream.try_next().await.map_err(Self::map_query_error)? {
+                if columns.is_none() {
+                    let mut info = Self::get_column_info(pg_row.columns());
+                    Self::enrich_column_info(pool, pg_row.columns(), &mut info).await;
+                    columns = Some(info);
+                }
+                batch.push(Self::convert_row(&pg_row));
+                total_rows += 1;
+
+                if batch.len() == batch_size {
+                    on_batch(QueryResult {
+                        columns: columns.clone().unwrap_or_default(),
+                        rows: std::mem::replace(&mut batch, Vec::with_capacity(batch_size)),
+                        affected_rows: None,
+                        execution_time_ms: start.elapsed().as_micros() as f64 / 1000.0,
+                        plan_estimate: None,
+                        truncated: false,
+                        estimated_memory_bytes: None,
+                        truncated_values: Vec::new(),
+                    });
+                }
+            }
         }
 
-        session.pool.close().await;
-        Ok(())
+        if total_rows == 0 || !batch.is_empty() {
+            let columns = match columns {
+                Some(columns) => columns,
+                None => {
+                    // No row to read columns from; ask the server to describe the statement
+                    let pg_columns = match (&mut **conn).describe(query).await {
+                        Ok(describe) => describe.columns().to_vec(),
+                        Err(_) => Vec::new(),
+                    };
+                    let mut columns = Self::get_column_info(&pg_columns);
+                    Self::enrich_column_info(pool, &pg_columns, &mut columns).await;
+                    columns
+                }
+            };
+            on_batch(QueryResult {
+                columns,
+                rows: batch,
+                affected_rows: None,
+                execution_time_ms: start.elapsed().as_micros() as f64 / 1000.0,
+                plan_estimate: None,
+                truncated: false,
+                estimated_memory_bytes: None,
+                truncated_values: Vec::new(),
+            });
+        }
+
+        Ok(total_rows)
     }
 
-    async fn list_namespaces(&self, session: SessionId) -> EngineResult<Vec<Namespace>> {
-        let pg_session = self.get_session(session).await?;
-        let pool = &pg_session.pool;
+    /// Returns true for statements that produce a result set
+    fn is_select(query: &str) -> bool {
+        let trimmed = sql_safety::strip_leading_comments(query).to_uppercase();
+        trimmed.starts_with("SELECT")
+            || trimmed.starts_with("WITH")
+            || trimmed.starts_with("SHOW")
+            || trimmed.starts_with("EXPLAIN")
+            // Procedures report their INOUT parameters as a row
+            || trimmed.starts_with("CALL")
+    }
 
-        // Get all schemas grouped by database
-        let rows: Vec<(String, String)> = sqlx::query_as(
-            r#"
-            SELECT current_database()::text as database, schema_name::text
-            FROM information_schema.schemata
-            WHERE schema_name NOT IN ('pg_catalog', 'information_schema', 'pg_toast')
-            ORDER BY schema_name
-            "#,
+    /// Builds a column definition for `CREATE TABLE` and `ADD COLUMN`
+    ///
+    /// Auto-increment columns become identity columns, unless their type is
+    /// already one of the `serial` pseudo-types.
+    fn column_definition(column: &NewColumnDef) -> String {
+        let data_type = column.data_type.trim();
+        let mut definition = format!("\"{}\" {}", column.name.replace("\"", "\"\""), data_type);
+        let is_serial = matches!(
+            data_type.to_ascii_lowercase().as_str(),
+            "smallserial" | "serial" | "bigserial"
+        );
+        if column.auto_increment && !is_serial {
+            definition.push_str(" GENERATED ALWAYS AS IDENTITY");
+        }
+        if !column.nullable {
+            definition.push_str(" NOT NULL");
+        }
+        if let Some(default_value) = &column.default_value {
+            definition.push_str(&format!(" DEFAULT {}", default_value));
+        }
+        definition
+    }
+
+    /// Builds the `CREATE TABLE` statement for `create_table`
+    fn create_table_sql(namespace: &Namespace, schema: &NewTableSchema) -> String {
+        let quote = |name: &str| format!("\"{}\"", name.replace("\"", "\"\""));
+        let table_name = match &namespace.schema {
+            Some(schema_name) => format!("{}.{}", quote(schema_name), quote(&schema.table_name)),
+            None => quote(&schema.table_name),
+        };
+
+        let mut definitions: Vec<String> =
+            schema.columns.iter().map(Self::column_definition).collect();
+
+        let primary_key = schema.primary_key_columns();
+        if !primary_key.is_empty() {
+            let columns: Vec<String> = primary_key.into_iter().map(quote).collect();
+            definitions.push(format!("PRIMARY KEY ({})", columns.join(", ")));
+        }
+
+        let if_not_exists = if schema.if_not_exists { "IF NOT EXISTS " } else { "" };
+        format!(
+            "CREATE TABLE {}{} ({})",
+            if_not_exists,
+            table_name,
+            definitions.join(", ")
         )
-        .fetch_all(pool)
-        .await
-        .map_err(|e| EngineError::execution_error(e.to_string()))?;
+    }
 
-        let namespaces = rows
-            .into_iter()
-            .map(|(db, schema)| Namespace::with_schema(db, schema))
-            .collect();
+    /// Reads the estimates of a plan's top node, e.g.
+    /// `Seq Scan on t  (cost=0.00..35.50 rows=2550 width=4)`
+    fn parse_plan_estimate(line: &str) -> Option<PlanEstimate> {
+        let estimates = &line[line.find("(cost=")? + 1..];
+        let estimate = |key: &str| {
+            let value = &estimates[estimates.find(key)? + key.len()..];
+            let end = value.find([' ', ')']).unwrap_or(value.len());
+            value[..end].parse::<f64>().ok()
+        };
+        Some(PlanEstimate {
+            total_cost: estimate(".."),
+            estimated_rows: estimate("rows="),
+        })
+    }
 
-        Ok(namespaces)
+    fn map_query_error(e: sqlx::Error) -> EngineError {
+        let msg = e.to_string();
+        let code = e.as_database_error().and_then(|db| db.code());
+        if let Some(error) = code.and_then(|code| EngineError::from_server_code(&code, &msg)) {
+            return error;
+        }
+        if msg.contains("syntax error") {
+            EngineError::syntax_error(msg)
+        } else {
+            EngineError::execution_error(msg)
+        }
     }
 
-    async fn list_collections(
-        &self,
-        session: SessionId,
-        namespace: &Namespace,
-    ) -> EngineResult<Vec<Collection>> {
-        let pg_session = self.get_session(session).await?;
-        let pool = &pg_session.pool;
+    /// Builds the parameterized `INSERT` for the given (sorted) columns
+    fn insert_sql(namespace: &Namespace, table: &str, keys: &[&String]) -> String {
+        let table_name = if let Some(schema) = &namespace.schema {
+            format!("\"{}\".\"{}\"", schema.replace("\"", "\"\""), table.replace("\"", "\"\""))
+        } else {
+            format!("\"{}\"", table.replace("\"", "\"\""))
+        };
 
-        let schema = namespace.schema.as_deref().unwrap_or("public");
+        if keys.is_empty() {
+            format!("INSERT INTO {} DEFAULT VALUES", table_name)
+        } else {
+            let cols_str = keys.iter().map(|k| format!("\"{}\"", k.replace("\"", "\"\""))).collect::<Vec<_>>().join(", ");
+            let params_str = (1..=keys.len()).map(|i| format!("${}", i)).collect::<Vec<_>>().join(", ");
+            format!("INSERT INTO {} ({}) VALUES ({})", table_name, cols_str, params_str)
+        }
+    }
 
-        let rows: Vec<(String, String)> = sqlx::query_as(
+    /// Builds the statement for `call_routine`: `SELECT` for a function,
+    /// `CALL` for a procedure
+    fn call_routine_sql(
+        namespace: &Namespace,
+        routine: &str,
+        routine_type: RoutineType,
+        arg_count: usize,
+    ) -> String {
+        let quote = |name: &str| format!("\"{}\"", name.replace('"', "\"\""));
+        let routine_name = match &namespace.schema {
+            Some(schema) => format!("{}.{}", quote(schema), quote(routine)),
+            None => quote(routine),
+        };
+        let params = (1..=arg_count).map(|i| format!("${}", i)).collect::<Vec<_>>().join(", ");
+        match routine_type {
+            RoutineType::Function => format!("SELECT {}({})", routine_name, params),
+            RoutineType::Procedure => format!("CALL {}({})", routine_name, params),
+        }
+    }
+
+    /// Builds the `COPY ... FROM STDIN` statement for `import_copy`
+    fn copy_in_sql(namespace: &Namespace, table: &str, columns: &[String]) -> String {
+        let quote = |name: &str| format!("\"{}\"", name.replace('"', "\"\""));
+        let table_name = match &namespace.schema {
+            Some(schema) => format!("{}.{}", quote(schema), quote(table)),
+            None => quote(table),
+        };
+        let columns = columns.iter().map(|c| quote(c)).collect::<Vec<_>>().join(", ");
+        format!("COPY {} ({}) FROM STDIN", table_name, columns)
+    }
+
+    /// Appends a row in the `COPY` text format: tab-separated fields, `\N`
+    /// for NULL, and backslashes and control characters escaped
+    fn write_copy_row(out: &mut String, row: &[Value]) {
+        for (i, value) in row.iter().enumerate() {
+            if i > 0 {
+                out.push('\t');
+            }
+            match value {
+                Value::Null => out.push_str("\\N"),
+                Value::Bool(b) => out.push(if *b { 't' } else { 'f' }),
+                Value::Int(i) => out.push_str(&i.to_string()),
+                Value::Float(f) => out.push_str(&f.to_string()),
+                Value::Text(s) => Self::push_copy_escaped(out, s),
+                Value::Timestamp(ts) => out.push_str(&ts.to_string()),
+                Value::Uuid(uuid) => out.push_str(&uuid.to_string()),
+                Value::Json(json) => Self::push_copy_escaped(out, &json.to_string()),
+                // bytea hex input `\x...`, whose backslash must itself be escaped
+                Value::Bytes(bytes) => {
+                    out.push_str("\\\\x");
+                    for byte in bytes {
+                        out.push_str(&format!("{:02x}", byte));
+                    }
+                }
+                Value::Array(values) => Self::push_copy_escaped(out, &Self::array_literal(values)),
+            }
+        }
+        out.push('\n');
+    }
+
+    fn push_copy_escaped(out: &mut String, text: &str) {
+        for c in text.chars() {
+            match c {
+                '\\' => out.push_str("\\\\"),
+                '\t' => out.push_str("\\t"),
+                '\n' => out.push_str("\\n"),
+                '\r' => out.push_str("\\r"),
+                _ => out.push(c),
+            }
+        }
+    }
+
+    /// Formats values as a PostgreSQL array literal (`{"a",NULL,"b"}`)
+    fn array_literal(values: &[Value]) -> String {
+        let elements: Vec<String> = values
+            .iter()
+            .map(|value| {
+                let text = match value {
+                    Value::Null => return "NULL".to_string(),
+                    Value::Array(inner) => return Self::array_literal(inner),
+                    Value::Bool(b) => b.to_string(),
+                    Value::Int(i) => i.to_string(),
+                    Value::Float(f) => f.to_string(),
+                    Value::Text(s) => s.clone(),
+                    Value::Timestamp(ts) => ts.to_string(),
+                    Value::Uuid(uuid) => uuid.to_string(),
+                    Value::Json(json) => json.to_string(),
+                    Value::Bytes(bytes) => {
+                        let hex: String = bytes.iter().map(|b| format!("{:02x}", b)).collect();
+                        format!("\\x{}", hex)
+                    }
+                };
+                format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""))
+            })
+            .collect();
+        format!("{{{}}}", elements.join(","))
+    }
+
+    /// Builds the `RETURNING` column list for the primary key columns
+    fn returning_columns(pk_keys: &[&String]) -> String {
+        pk_keys
+            .iter()
+            .map(|k| format!("\"{}\"", k.replace("\"", "\"\"")))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    /// Converts the rows returned by `RETURNING` into a mutation result
+    fn returning_result(pg_rows: &[PgRow], execution_time_ms: f64) -> QueryResult {
+        QueryResult {
+            columns: pg_rows
+                .first()
+                .map(|row| Self::get_column_info(row.columns()))
+                .unwrap_or_default(),
+            rows: pg_rows.iter().map(Self::convert_row).collect(),
+            affected_rows: Some(pg_rows.len() as u64),
+            execution_time_ms,
+            plan_estimate: None,
+            truncated: false,
+            estimated_memory_bytes: None,
+            truncated_values: Vec::new(),
+        }
+    }
+
+    /// Gets column info from result columns (of a row or a described statement)
+    fn get_column_info(pg_columns: &[PgColumn]) -> Vec<ColumnInfo> {
+        pg_columns
+            .iter()
+            .map(|col| {
+                let data_type = col.type_info().name().to_string();
+                ColumnInfo {
+                    name: col.name().to_string(),
+                    display_size: ColumnInfo::display_size_for(&data_type),
+                    data_type,
+                    nullable: true, // Refined from the catalog when the column maps to a table
+                    ..Default::default()
+                }
+            })
+            .collect()
+    }
+
+    /// Fills schema metadata for result columns that come straight from a table.
+    ///
+    /// Runs on the pool rather than the transaction connection so a catalog
+    /// error can never abort the user's transaction. Best effort: failures
+    /// leave the inferred metadata untouched.
+    async fn enrich_column_info(
+        pool: &PgPool,
+        pg_columns: &[PgColumn],
+        columns: &mut [ColumnInfo],
+    ) {
+        let origins: Vec<(usize, i64, i16)> = pg_columns
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, col)| {
+                Some((idx, col.relation_id()?.0 as i64, col.relation_attribute_no()?))
+            })
+            .collect();
+        if origins.is_empty() {
+            return;
+        }
+
+        let relation_ids: Vec<i64> = origins.iter().map(|(_, rel, _)| *rel).collect();
+        let attribute_nos: Vec<i16> = origins.iter().map(|(_, _, att)| *att).collect();
+
+        let metadata = sqlx::query(
+            r#"
+            SELECT a.attrelid::int8 AS relation_id,
+                   a.attnum,
+                   NOT a.attnotnull AS nullable,
+                   col_description(a.attrelid, a.attnum) AS comment,
+                   a.attgenerated <> '' AS is_generated,
+                   (a.attidentity <> ''
+                    OR COALESCE(pg_get_expr(d.adbin, d.adrelid) LIKE 'nextval(%', false)) AS is_auto_increment,
+                   CASE WHEN a.atttypid IN ('bpchar'::regtype, 'varchar'::regtype) AND a.atttypmod > 4
+                        THEN a.atttypmod - 4 END AS display_size,
+                   CASE WHEN t.typcategory = 'S' THEN pg_encoding_to_char(db.encoding) END AS character_set,
+                   t.typname = 'lo' AS is_large_object
+            FROM unnest($1::int8[], $2::int2[]) AS o(relation_id, attnum)
+            JOIN pg_attribute a ON a.attrelid = o.relation_id::oid AND a.attnum = o.attnum
+            JOIN pg_type t ON t.oid = a.atttypid
+            LEFT JOIN pg_attrdef d ON d.adrelid = a.attrelid AND d.adnum = a.attnum
+            JOIN pg_database db ON db.datname = current_database()
+            "#,
+        )
+        .bind(relation_ids)
+        .bind(attribute_nos)
+        .fetch_all(pool)
+        .await;
+
+        let Ok(metadata) = metadata else {
+            return;
+        };
+
+        for meta in &metadata {
+            let relation_id: i64 = meta.get("relation_id");
+            let attnum: i16 = meta.get("attnum");
+            for (idx, _, _) in origins
+                .iter()
+                .filter(|(_, rel, att)| *rel == relation_id && *att == attnum)
+            {
+                let column = &mut columns[*idx];
+                column.nullable = meta.get("nullable");
+                column.comment = meta.get("comment");
+                column.is_generated = meta.get("is_generated");
+                column.is_auto_increment = meta.get("is_auto_increment");
+                column.character_set = meta.get("character_set");
+                if let Some(size) = meta.get::<Option<i32>, _>("display_size") {
+                    column.display_size = u32::try_from(size).ok();
+                }
+                // Result columns report the `oid` base type of the `lo` domain
+                if meta.get::<bool, _>("is_large_object") {
+                    column.data_type = LARGE_OBJECT_TYPE.to_string();
+                }
+            }
+        }
+    }
+}
+
+impl Default for PostgresDriver {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl DataEngine for PostgresDriver {
+    fn driver_id(&self) -> &'static str {
+        "postgres"
+    }
+
+    fn driver_name(&self) -> &'static str {
+        "PostgreSQL"
+    }
+
+    fn default_port(&self) -> u16 {
+        5432
+    }
+
+    fn supports_schemas(&self) -> bool {
+        true
+    }
+
+    /// Namespaces are the schemas of the connected database only
+    fn supports_multiple_databases(&self) -> bool {
+        false
+    }
+
+    async fn test_connection(&self, config: &ConnectionConfig) -> EngineResult<()> {
+        Self::test_connection_string(&Self::connection_string(config)?).await
+    }
+
+    async fn connect(&self, config: &ConnectionConfig) -> EngineResult<SessionId> {
+        self.connect_with_string(&Self::connection_string(config)?, config)
+            .await
+    }
+
+    async fn disconnect(&self, session: SessionId) -> EngineResult<()> {
+        let session = {
+            let mut sessions = self.sessions.write().await;
+            sessions
+                .remove(&session)
+                .ok_or_else(|| EngineError::session_not_found(session.0.to_string()))?
+        };
+
+        {
+            let mut tx = session.transaction_conn.lock().await;
+            tx.take();
+        }
+        session.lock_conn.lock().await.take();
+
+        session.pool.close().await;
+        Ok(())
+    }
+
+    async fn rebind_session(&self, from: SessionId, to: SessionId) -> EngineResult<()> {
+        let mut sessions = self.sessions.write().await;
+        let session = sessions
+            .remove(&from)
+            .ok_or_else(|| EngineError::session_not_found(from.0.to_string()))?;
+        if let Some(stale) = sessions.insert(to, session) {
+            sessions.insert(from, stale);
+        }
+        Ok(())
+    }
+
+    async fn ping(&self, session: SessionId) -> EngineResult<()> {
+        let session = self.get_session(session).await?;
+        sqlx::query("SELECT 1")
+            .execute(&session.pool)
+            .await
+            .map_err(|e| EngineError::connection_failed(e.to_string()))?;
+        Ok(())
+    }
+
+    async fn list_namespaces(&self, session: SessionId) -> EngineResult<Vec<Namespace>> {
+        let pg_session = self.get_session(session).await?;
+        let pool = &pg_session.pool;
+
+        // Get all schemas grouped by database
+        let rows: Vec<(String, String)> = sqlx::query_as(
+            r#"
+            SELECT current_database()::text as database, schema_name::text
+            FROM information_schema.schemata
+            WHERE schema_name NOT IN ('pg_catalog', 'information_schema', 'pg_toast')
+            ORDER BY schema_name
+            "#,
+        )
+        .fetch_all(pool)
+        .await
+        .map_err(|e| EngineError::execution_error(e.to_string()))?;
+
+        let namespaces = rows
+            .into_iter()
+            .map(|(db, schema)| Namespace::with_schema(db, schema))
+            .collect();
+
+        Ok(namespaces)
+    }
+
+    /// Schemas are created in the session's transaction when one is active.
+    /// `CREATE DATABASE` cannot run inside a transaction block, so databases
+    /// are always created on a pooled connection.
+    async fn create_namespace(
+        &self,
+        session: SessionId,
+        namespace: &Namespace,
+    ) -> EngineResult<()> {
+        let pg_session = self.get_session(session).await?;
+
+        let Some(schema) = namespace.schema.as_deref() else {
+            let sql = format!(
+                "CREATE DATABASE \"{}\"",
+                namespace.database.replace("\"", "\"\"")
+            );
+            sqlx::query(&sql)
+                .execute(&pg_session.pool)
+                .await
+                .map_err(|e| EngineError::execution_error(e.to_string()))?;
+            return Ok(());
+        };
+
+        let sql = format!(
+            "CREATE SCHEMA IF NOT EXISTS \"{}\"",
+            schema.replace("\"", "\"\"")
+        );
+        let mut tx_guard = pg_session.transaction_conn.lock().await;
+        let result = if let Some(ref mut conn) = *tx_guard {
+            sqlx::query(&sql).execute(&mut **conn).await
+        } else {
+            sqlx::query(&sql).execute(&pg_session.pool).await
+        };
+        result.map_err(|e| EngineError::execution_error(e.to_string()))?;
+        Ok(())
+    }
+
+    /// Same transaction handling as `create_namespace`. `cascade` only
+    /// applies to schemas; the session's own database cannot be dropped.
+    async fn drop_namespace(
+        &self,
+        session: SessionId,
+        namespace: &Namespace,
+        cascade: bool,
+    ) -> EngineResult<()> {
+        let pg_session = self.get_session(session).await?;
+
+        let Some(schema) = namespace.schema.as_deref() else {
+            let sql = format!(
+                "DROP DATABASE \"{}\"",
+                namespace.database.replace("\"", "\"\"")
+            );
+            sqlx::query(&sql)
+                .execute(&pg_session.pool)
+                .await
+                .map_err(|e| EngineError::execution_error(e.to_string()))?;
+            return Ok(());
+        };
+
+        let sql = format!(
+            "DROP SCHEMA \"{}\"{}",
+            schema.replace("\"", "\"\""),
+            if cascade { " CASCADE" } else { "" }
+        );
+        let mut tx_guard = pg_session.transaction_conn.lock().await;
+        let result = if let Some(ref mut conn) = *tx_guard {
+            sqlx::query(&sql).execute(&mut **conn).await
+        } else {
+            sqlx::query(&sql).execute(&pg_session.pool).await
+        };
+        result.map_err(|e| EngineError::execution_error(e.to_string()))?;
+        Self::forget_stale_statements(tx_guard.as_deref_mut(), &pg_session.pool).await;
+        Ok(())
+    }
+
+    async fn list_collections(
+        &self,
+        session: SessionId,
+        namespace: &Namespace,
+    ) -> EngineResult<Vec<Collection>> {
+        let pg_session = self.get_session(session).await?;
+        let pool = &pg_session.pool;
+
+        let schema = namespace.schema.as_deref().unwrap_or("public");
+
+        let rows: Vec<(String, String)> = sqlx::query_as(
             r#"
             SELECT table_name::text, table_type::text
             FROM information_schema.tables
@@ -339,194 +1216,307 @@ impl DataEngine for PostgresDriver {
         Ok(collections)
     }
 
-    async fn execute(
+    /// Aggregates and window functions (`prokind` `a` and `w`) are skipped.
+    async fn list_routines(
         &self,
         session: SessionId,
-        query: &str,
-        query_id: QueryId,
-    ) -> EngineResult<QueryResult> {
+        namespace: &Namespace,
+    ) -> EngineResult<Vec<Routine>> {
         let pg_session = self.get_session(session).await?;
-        let start = Instant::now();
-
-        // Determine if this is a SELECT-like query
-        let trimmed = query.trim().to_uppercase();
-        let is_select = trimmed.starts_with("SELECT")
-            || trimmed.starts_with("WITH")
-            || trimmed.starts_with("SHOW")
-            || trimmed.starts_with("EXPLAIN");
-
-        let mut tx_guard = pg_session.transaction_conn.lock().await;
-        let result = if let Some(ref mut conn) = *tx_guard {
-            let backend_pid = Self::fetch_backend_pid(conn).await?;
-            {
-                let mut active = pg_session.active_queries.lock().await;
-                active.insert(query_id, backend_pid);
-            }
-
-            let result = if is_select {
-                let pg_rows: Vec<PgRow> = sqlx::query(query)
-                    .fetch_all(&mut **conn)
-                    .await
-                    .map_err(|e| {
-                        let msg = e.to_string();
-                        if msg.contains("syntax error") {
-                            EngineError::syntax_error(msg)
-                        } else {
-                            EngineError::execution_error(msg)
-                        }
-                    })?;
-
-                let execution_time_ms = start.elapsed().as_micros() as f64 / 1000.0;
-
-                if pg_rows.is_empty() {
-                    Ok(QueryResult {
-                        columns: Vec::new(),
-                        rows: Vec::new(),
-                        affected_rows: None,
-                        execution_time_ms,
-                    })
-                } else {
-                    let columns = Self::get_column_info(&pg_rows[0]);
-                    let rows: Vec<QRow> = pg_rows.iter().map(Self::convert_row).collect();
-
-                    Ok(QueryResult {
-                        columns,
-                        rows,
-                        affected_rows: None,
-                        execution_time_ms,
-                    })
-                }
-            } else {
-                let result = sqlx::query(query)
-                    .execute(&mut **conn)
-                    .await
-                    .map_err(|e| {
-                        let msg = e.to_string();
-                        if msg.contains("syntax error") {
-                            EngineError::syntax_error(msg)
-                        } else {
-                            EngineError::execution_error(msg)
-                        }
-                    })?;
-
-                let execution_time_ms = start.elapsed().as_micros() as f64 / 1000.0;
-
-                Ok(QueryResult::with_affected_rows(
-                    result.rows_affected(),
-                    execution_time_ms,
-                ))
-            };
+        let schema = namespace.schema.as_deref().unwrap_or("public");
 
-            let mut active = pg_session.active_queries.lock().await;
-            active.remove(&query_id);
-            result
-        } else {
-            let mut conn = pg_session
-                .pool
-                .acquire()
-                .await
-                .map_err(|e| EngineError::connection_failed(e.to_string()))?;
-            let backend_pid = Self::fetch_backend_pid(&mut conn).await?;
-            {
-                let mut active = pg_session.active_queries.lock().await;
-                active.insert(query_id, backend_pid);
-            }
+        let rows: Vec<(String, String, Option<String>, String)> = sqlx::query_as(
+            r#"
+            SELECT
+                p.proname::text,
+                p.prokind::text,
+                CASE WHEN p.prokind = 'p' THEN NULL ELSE pg_get_function_result(p.oid) END,
+                pg_get_function_arguments(p.oid)
+            FROM pg_proc p
+            JOIN pg_namespace n ON n.oid = p.pronamespace
+            WHERE n.nspname = $1 AND p.prokind IN ('f', 'p')
+            ORDER BY p.proname, 4
+            "#,
+        )
+        .bind(schema)
+        .fetch_all(&pg_session.pool)
+        .await
+        .map_err(|e| EngineError::execution_error(e.to_string()))?;
 
-            let result = if is_select {
-                let pg_rows: Vec<PgRow> = sqlx::query(query)
-                    .fetch_all(&mut *conn)
-                    .await
-                    .map_err(|e| {
-                        let msg = e.to_string();
-                        if msg.contains("syntax error") {
-                            EngineError::syntax_error(msg)
-                        } else {
-                            EngineError::execution_error(msg)
-                        }
-                    })?;
-
-                let execution_time_ms = start.elapsed().as_micros() as f64 / 1000.0;
-
-                if pg_rows.is_empty() {
-                    Ok(QueryResult {
-                        columns: Vec::new(),
-                        rows: Vec::new(),
-                        affected_rows: None,
-                        execution_time_ms,
-                    })
+        Ok(rows
+            .into_iter()
+            .map(|(name, kind, return_type, arguments)| Routine {
+                namespace: namespace.clone(),
+                name,
+                routine_type: if kind == "p" {
+                    RoutineType::Procedure
                 } else {
-                    let columns = Self::get_column_info(&pg_rows[0]);
-                    let rows: Vec<QRow> = pg_rows.iter().map(Self::convert_row).collect();
-
-                    Ok(QueryResult {
-                        columns,
-                        rows,
-                        affected_rows: None,
-                        execution_time_ms,
-                    })
-                }
-            } else {
-                let result = sqlx::query(query)
-                    .execute(&mut *conn)
-                    .await
-                    .map_err(|e| {
-                        let msg = e.to_string();
-                        if msg.contains("syntax error") {
-                            EngineError::syntax_error(msg)
-                        } else {
-                            EngineError::execution_error(msg)
-                        }
-                    })?;
-
-                let execution_time_ms = start.elapsed().as_micros() as f64 / 1000.0;
-
-                Ok(QueryResult::with_affected_rows(
-                    result.rows_affected(),
-                    execution_time_ms,
-                ))
-            };
-
-            let mut active = pg_session.active_queries.lock().await;
-            active.remove(&query_id);
-            result
-        };
-
-        result
+                    RoutineType::Function
+                },
+                return_type,
+                arguments,
+            })
+            .collect())
     }
 
-    async fn describe_table(
+    /// Loads every schema's tables in one round-trip. Schemas without tables
+    /// are kept (with an empty list) through the `LEFT JOIN`.
+    async fn list_all_collections(
         &self,
         session: SessionId,
-        namespace: &Namespace,
-        table: &str,
-    ) -> EngineResult<TableSchema> {
+    ) -> EngineResult<HashMap<Namespace, Vec<Collection>>> {
         let pg_session = self.get_session(session).await?;
         let pool = &pg_session.pool;
 
-        let schema = namespace.schema.as_deref().unwrap_or("public");
-
-        // Get column info
-        let column_rows: Vec<(String, String, String, Option<String>)> = sqlx::query_as(
+        let rows: Vec<(String, String, Option<String>, Option<String>)> = sqlx::query_as(
             r#"
-            SELECT 
-                column_name::text,
-                data_type::text,
-                is_nullable::text,
-                column_default::text
-            FROM information_schema.columns
-            WHERE table_schema = $1 AND table_name = $2
-            ORDER BY ordinal_position
+            SELECT current_database()::text, s.schema_name::text,
+                   t.table_name::text, t.table_type::text
+            FROM information_schema.schemata s
+            LEFT JOIN information_schema.tables t ON t.table_schema = s.schema_name
+            WHERE s.schema_name NOT IN ('pg_catalog', 'information_schema', 'pg_toast')
+            ORDER BY s.schema_name, t.table_name
             "#,
         )
-        .bind(schema)
-        .bind(table)
         .fetch_all(pool)
         .await
         .map_err(|e| EngineError::execution_error(e.to_string()))?;
 
-        // Get primary key columns
-        let pk_rows: Vec<(String,)> = sqlx::query_as(
-            r#"
+        let mut collections: HashMap<Namespace, Vec<Collection>> = HashMap::new();
+        for (db, schema, name, table_type) in rows {
+            let namespace = Namespace::with_schema(db, schema);
+            let entry = collections.entry(namespace.clone()).or_default();
+            let Some(name) = name else {
+                continue;
+            };
+            let collection_type = match table_type.as_deref() {
+                Some("VIEW") => CollectionType::View,
+                _ => CollectionType::Table,
+            };
+            entry.push(Collection {
+                namespace,
+                name,
+                collection_type,
+            });
+        }
+
+        Ok(collections)
+    }
+
+    async fn execute(
+        &self,
+        session: SessionId,
+        query: &str,
+        query_id: QueryId,
+    ) -> EngineResult<QueryResult> {
+        self.execute_with_params(session, query, &[], query_id).await
+    }
+
+    async fn execute_with_params(
+        &self,
+        session: SessionId,
+        query: &str,
+        params: &[Value],
+        query_id: QueryId,
+    ) -> EngineResult<QueryResult> {
+        let mut results = self
+            .run_statements(session, &[query], params, query_id)
+            .await?;
+        Ok(results.pop().unwrap_or_else(QueryResult::empty))
+    }
+
+    async fn call_routine(
+        &self,
+        session: SessionId,
+        namespace: &Namespace,
+        routine: &str,
+        routine_type: RoutineType,
+        args: &[Value],
+        query_id: QueryId,
+    ) -> EngineResult<Vec<QueryResult>> {
+        let sql = Self::call_routine_sql(namespace, routine, routine_type, args.len());
+        Ok(vec![self.execute_with_params(session, &sql, args, query_id).await?])
+    }
+
+    /// Reads rows through a cursor on the transaction connection if one is
+    /// active, otherwise on a pooled connection.
+    async fn execute_stream(
+        &self,
+        session: SessionId,
+        query: &str,
+        query_id: QueryId,
+        batch_size: usize,
+        on_batch: &(dyn Fn(QueryResult) + Send + Sync),
+    ) -> EngineResult<u64> {
+        let pg_session = self.get_session(session).await?;
+
+        let mut tx_guard = pg_session.transaction_conn.lock().await;
+        let mut pooled: Option<PoolConnection<Postgres>> = None;
+        let conn = match tx_guard.as_mut() {
+            Some(conn) => conn,
+            None => {
+                let conn = pooled.insert(
+                    pg_session
+                        .pool
+                        .acquire()
+                        .await
+                        .map_err(|e| EngineError::connection_failed(e.to_string()))?,
+                );
+                pg_session.apply_session_variables(conn).await?;
+                conn
+            }
+        };
+
+        let backend_pid = Self::fetch_backend_pid(conn).await?;
+        {
+            let mut active = pg_session.active_queries.lock().await;
+            active.insert(query_id, backend_pid);
+        }
+
+        let result =
+            Self::stream_statement(conn, &pg_session.pool, query, batch_size, on_batch).await;
+
+        let mut active = pg_session.active_queries.lock().await;
+        active.remove(&query_id);
+        result
+    }
+
+    /// Streams the rows with `COPY ... FROM STDIN` in the text format,
+    /// inside the session's transaction when one is active
+    async fn import_copy(
+        &self,
+        session: SessionId,
+        namespace: &Namespace,
+        table: &str,
+        columns: &[String],
+        mut rows: BoxStream<'_, EngineResult<Vec<Value>>>,
+        on_progress: &(dyn Fn(u64) + Send + Sync),
+    ) -> EngineResult<u64> {
+        let pg_session = self.get_session(session).await?;
+
+        let mut tx_guard = pg_session.transaction_conn.lock().await;
+        let mut pooled: Option<PoolConnection<Postgres>> = None;
+        let conn = match tx_guard.as_mut() {
+            Some(conn) => conn,
+            None => {
+                let conn = pooled.insert(
+                    pg_session
+                        .pool
+                        .acquire()
+                        .await
+                        .map_err(|e| EngineError::connection_failed(e.to_string()))?,
+                );
+                pg_session.apply_session_variables(conn).await?;
+                conn
+            }
+        };
+
+        let mut copy = conn
+            .copy_in_raw(&Self::copy_in_sql(namespace, table, columns))
+            .await
+            .map_err(|e| EngineError::execution_error(e.to_string()))?;
+
+        let mut buffer = String::with_capacity(COPY_CHUNK_BYTES);
+        let mut sent: u64 = 0;
+        let mut buffered: u64 = 0;
+        loop {
+            let row = match rows.next().await {
+                Some(Ok(row)) => row,
+                Some(Err(e)) => {
+                    let _ = copy.abort(e.to_string()).await;
+                    return Err(e);
+                }
+                None => break,
+            };
+            if row.len() != columns.len() {
+                let error = format!(
+                    "Row {} has {} values, expected {}",
+                    sent + buffered + 1,
+                    row.len(),
+                    columns.len()
+                );
+                let _ = copy.abort(error.clone()).await;
+                return Err(EngineError::execution_error(error));
+            }
+
+            Self::write_copy_row(&mut buffer, &row);
+            buffered += 1;
+            if buffer.len() >= COPY_CHUNK_BYTES {
+                copy.send(buffer.as_bytes())
+                    .await
+                    .map_err(|e| EngineError::execution_error(e.to_string()))?;
+                buffer.clear();
+                sent += buffered;
+                buffered = 0;
+                on_progress(sent);
+            }
+        }
+
+        if !buffer.is_empty() {
+            copy.send(buffer.as_bytes())
+                .await
+                .map_err(|e| EngineError::execution_error(e.to_string()))?;
+            sent += buffered;
+            on_progress(sent);
+        }
+
+        copy.finish()
+            .await
+            .map_err(|e| EngineError::execution_error(e.to_string()))
+    }
+
+    /// Splits the script on statement boundaries and runs each statement in
+    /// order on a single connection, so session state (e.g. `SET`) carries over.
+    async fn execute_multi(
+        &self,
+        session: SessionId,
+        query: &str,
+        query_id: QueryId,
+    ) -> EngineResult<Vec<QueryResult>> {
+        let statements = sql_safety::split_statements(self.driver_id(), query)
+            .map_err(EngineError::syntax_error)?;
+        let statements: Vec<&str> = statements.iter().map(String::as_str).collect();
+        self.run_statements(session, &statements, &[], query_id).await
+    }
+
+    async fn describe_table(
+        &self,
+        session: SessionId,
+        namespace: &Namespace,
+        table: &str,
+    ) -> EngineResult<TableSchema> {
+        let pg_session = self.get_session(session).await?;
+        let pool = &pg_session.pool;
+
+        let schema = namespace.schema.as_deref().unwrap_or("public");
+
+        // Get column info
+        let column_rows: Vec<(String, String, String, Option<String>, Option<String>)> =
+            sqlx::query_as(
+                r#"
+                SELECT 
+                    column_name::text,
+                    CASE WHEN domain_name = 'lo' THEN 'lo' ELSE data_type::text END,
+                    is_nullable::text,
+                    column_default::text,
+                    col_description(
+                        format('%I.%I', table_schema, table_name)::regclass,
+                        ordinal_position::int
+                    )
+                FROM information_schema.columns
+                WHERE table_schema = $1 AND table_name = $2
+                ORDER BY ordinal_position
+                "#,
+            )
+            .bind(schema)
+            .bind(table)
+            .fetch_all(pool)
+            .await
+            .map_err(|e| EngineError::execution_error(e.to_string()))?;
+
+        // Get primary key columns
+        let pk_rows: Vec<(String,)> = sqlx::query_as(
+            r#"
             SELECT a.attname::text
             FROM pg_index i
             JOIN pg_attribute a ON a.attrelid = i.indrelid AND a.attnum = ANY(i.indkey)
@@ -549,19 +1539,22 @@ impl DataEngine for PostgresDriver {
         // Build columns vec
         let columns: Vec<TableColumn> = column_rows
             .into_iter()
-            .map(|(name, data_type, is_nullable, default_value)| TableColumn {
+            .map(|(name, data_type, is_nullable, default_value, comment)| TableColumn {
                 is_primary_key: pk_columns.contains(&name),
                 name,
                 data_type,
                 nullable: is_nullable == "YES",
                 default_value,
+                comment,
+                allowed_values: None,
+                charset: None,
             })
             .collect();
 
-        // Get row count estimate
-        let count_row: Option<(i64,)> = sqlx::query_as(
+        // Get row count estimate and table comment
+        let count_row: Option<(i64, Option<String>)> = sqlx::query_as(
             r#"
-            SELECT reltuples::bigint
+            SELECT reltuples::bigint, obj_description(c.oid, 'pg_class')
             FROM pg_class c
             JOIN pg_namespace n ON n.oid = c.relnamespace
             WHERE n.nspname = $1 AND c.relname = $2
@@ -573,31 +1566,487 @@ impl DataEngine for PostgresDriver {
         .await
         .map_err(|e| EngineError::execution_error(e.to_string()))?;
 
-        let row_count_estimate = count_row.map(|(c,)| c as u64);
+        let (row_count_estimate, comment) = match count_row {
+            Some((count, comment)) => (Some(count as u64), comment),
+            None => (None, None),
+        };
 
         Ok(TableSchema {
             columns,
             primary_key: if pk_columns.is_empty() { None } else { Some(pk_columns) },
             row_count_estimate,
+            comment,
         })
     }
 
+    async fn describe_namespace(
+        &self,
+        session: SessionId,
+        namespace: &Namespace,
+    ) -> EngineResult<HashMap<String, TableSchema>> {
+        let pg_session = self.get_session(session).await?;
+        let pool = &pg_session.pool;
+
+        let schema = namespace.schema.as_deref().unwrap_or("public");
+
+        let column_rows: Vec<(String, String, String, String, Option<String>, Option<String>)> =
+            sqlx::query_as(
+                r#"
+                SELECT
+                    table_name::text,
+                    column_name::text,
+                    CASE WHEN domain_name = 'lo' THEN 'lo' ELSE data_type::text END,
+                    is_nullable::text,
+                    column_default::text,
+                    col_description(
+                        format('%I.%I', table_schema, table_name)::regclass,
+                        ordinal_position::int
+                    )
+                FROM information_schema.columns
+                WHERE table_schema = $1
+                ORDER BY table_name, ordinal_position
+                "#,
+            )
+            .bind(schema)
+            .fetch_all(pool)
+            .await
+            .map_err(|e| EngineError::execution_error(e.to_string()))?;
+
+        let pk_rows: Vec<(String, String)> = sqlx::query_as(
+            r#"
+            SELECT c.relname::text, a.attname::text
+            FROM pg_index i
+            JOIN pg_attribute a ON a.attrelid = i.indrelid AND a.attnum = ANY(i.indkey)
+            JOIN pg_class c ON c.oid = i.indrelid
+            JOIN pg_namespace n ON n.oid = c.relnamespace
+            WHERE i.indisprimary
+              AND n.nspname = $1
+            ORDER BY c.relname, array_position(i.indkey, a.attnum)
+            "#,
+        )
+        .bind(schema)
+        .fetch_all(pool)
+        .await
+        .map_err(|e| EngineError::execution_error(e.to_string()))?;
+
+        let count_rows: Vec<(String, i64, Option<String>)> = sqlx::query_as(
+            r#"
+            SELECT c.relname::text, reltuples::bigint, obj_description(c.oid, 'pg_class')
+            FROM pg_class c
+            JOIN pg_namespace n ON n.oid = c.relnamespace
+            WHERE n.nspname = $1 AND c.relkind IN ('r', 'v', 'm', 'p', 'f')
+            "#,
+        )
+        .bind(schema)
+        .fetch_all(pool)
+        .await
+        .map_err(|e| EngineError::execution_error(e.to_string()))?;
+
+        let mut pk_columns: HashMap<String, Vec<String>> = HashMap::new();
+        for (table, column) in pk_rows {
+            pk_columns.entry(table).or_default().push(column);
+        }
+        let table_info: HashMap<String, (i64, Option<String>)> = count_rows
+            .into_iter()
+            .map(|(table, count, comment)| (table, (count, comment)))
+            .collect();
+
+        let mut schemas: HashMap<String, TableSchema> = HashMap::new();
+        for (table, name, data_type, is_nullable, default_value, comment) in column_rows {
+            let table_pk = pk_columns.get(&table);
+            let is_primary_key = table_pk.is_some_and(|pk| pk.contains(&name));
+            let entry = schemas.entry(table.clone()).or_insert_with(|| TableSchema {
+                columns: Vec::new(),
+                primary_key: table_pk.cloned(),
+                // reltuples is -1 for tables that were never analyzed
+                row_count_estimate: table_info
+                    .get(&table)
+                    .and_then(|(count, _)| u64::try_from(*count).ok()),
+                comment: table_info
+                    .get(&table)
+                    .and_then(|(_, comment)| comment.clone()),
+            });
+            entry.columns.push(TableColumn {
+                name,
+                data_type,
+                nullable: is_nullable == "YES",
+                default_value,
+                is_primary_key,
+                comment,
+                allowed_values: None,
+                charset: None,
+            });
+        }
+
+        Ok(schemas)
+    }
+
     async fn preview_table(
         &self,
         session: SessionId,
         namespace: &Namespace,
         table: &str,
         limit: u32,
+        lock_rows: Option<RowLockMode>,
     ) -> EngineResult<QueryResult> {
         let schema = namespace.schema.as_deref().unwrap_or("public");
         // Use quoted identifiers to handle special characters
-        let query = format!(
+        let mut query = format!(
             "SELECT * FROM \"{}\".\"{}\" LIMIT {}",
             schema, table, limit
         );
+        if let Some(mode) = lock_rows {
+            query = self.lock_rows_query(session, &query, mode).await?;
+        }
         self.execute(session, &query, QueryId::new()).await
     }
 
+    async fn fetch_value(
+        &self,
+        session: SessionId,
+        namespace: &Namespace,
+        table: &str,
+        primary_key: &RowData,
+        column: &str,
+    ) -> EngineResult<Value> {
+        let pg_session = self.get_session(session).await?;
+
+        if primary_key.columns.is_empty() {
+            return Err(EngineError::execution_error("Primary key required to fetch a value".to_string()));
+        }
+
+        let quote = |name: &str| format!("\"{}\"", name.replace("\"", "\"\""));
+        let table_name = match &namespace.schema {
+            Some(schema) => format!("{}.{}", quote(schema), quote(table)),
+            None => quote(table),
+        };
+
+        let mut pk_keys: Vec<&String> = primary_key.columns.keys().collect();
+        pk_keys.sort();
+        let where_clauses: Vec<String> = pk_keys
+            .iter()
+            .enumerate()
+            .map(|(i, k)| format!("{}=${}", quote(k), i + 1))
+            .collect();
+        let sql = format!(
+            "SELECT {} FROM {} WHERE {} LIMIT 1",
+            quote(column),
+            table_name,
+            where_clauses.join(" AND ")
+        );
+
+        let mut query = sqlx::query(&sql);
+        for k in &pk_keys {
+            query = Self::bind_param(query, &primary_key.columns[*k]);
+        }
+
+        let mut tx_guard = pg_session.transaction_conn.lock().await;
+        let row = if let Some(ref mut conn) = *tx_guard {
+            query.fetch_optional(&mut **conn).await
+        } else {
+            query.fetch_optional(&pg_session.pool).await
+        };
+
+        match row.map_err(|e| EngineError::execution_error(e.to_string()))? {
+            Some(row) => Ok(Self::extract_value(&row, 0)),
+            None => Err(EngineError::execution_error("Row not found".to_string())),
+        }
+    }
+
+    async fn read_large_object(&self, session: SessionId, oid: u32) -> EngineResult<Vec<u8>> {
+        let pg_session = self.get_session(session).await?;
+        let query = sqlx::query_scalar::<_, Vec<u8>>("SELECT lo_get($1)").bind(Oid(oid));
+
+        let mut tx_guard = pg_session.transaction_conn.lock().await;
+        let data = if let Some(ref mut conn) = *tx_guard {
+            query.fetch_one(&mut **conn).await
+        } else {
+            query.fetch_one(&pg_session.pool).await
+        };
+        data.map_err(Self::map_query_error)
+    }
+
+    async fn lock_rows_query(
+        &self,
+        session: SessionId,
+        query: &str,
+        mode: RowLockMode,
+    ) -> EngineResult<String> {
+        let pg_session = self.get_session(session).await?;
+        if pg_session.transaction_conn.lock().await.is_none() {
+            return Err(EngineError::transaction_error(
+                "Row locking requires an active transaction",
+            ));
+        }
+        Ok(mode.wrap_query(query))
+    }
+
+    async fn get_table_sizes(
+        &self,
+        session: SessionId,
+        namespace: &Namespace,
+    ) -> EngineResult<Vec<TableSizeInfo>> {
+        let pg_session = self.get_session(session).await?;
+        let pool = &pg_session.pool;
+
+        let schema = namespace.schema.as_deref().unwrap_or("public");
+
+        let rows: Vec<(String, i64, i64, i64, i64, Option<i64>)> = sqlx::query_as(
+            r#"
+            SELECT c.relname::text,
+                   COALESCE(s.n_live_tup, GREATEST(c.reltuples, 0)::int8)::int8,
+                   pg_total_relation_size(c.oid)::int8 AS total_bytes,
+                   pg_relation_size(c.oid)::int8,
+                   pg_indexes_size(c.oid)::int8,
+                   CASE WHEN c.reltoastrelid <> 0
+                        THEN pg_total_relation_size(c.reltoastrelid)::int8 END
+            FROM pg_class c
+            JOIN pg_namespace n ON n.oid = c.relnamespace
+            LEFT JOIN pg_stat_user_tables s ON s.relid = c.oid
+            WHERE n.nspname = $1 AND c.relkind IN ('r', 'm')
+            ORDER BY total_bytes DESC, c.relname
+            "#,
+        )
+        .bind(schema)
+        .fetch_all(pool)
+        .await
+        .map_err(|e| EngineError::execution_error(e.to_string()))?;
+
+        Ok(rows
+            .into_iter()
+            .map(
+                |(table_name, row_count, total_bytes, table_bytes, index_bytes, toast_bytes)| {
+                    TableSizeInfo {
+                        table_name,
+                        row_count,
+                        total_bytes: total_bytes.max(0) as u64,
+                        table_bytes: table_bytes.max(0) as u64,
+                        index_bytes: index_bytes.max(0) as u64,
+                        toast_bytes: toast_bytes.map(|bytes| bytes.max(0) as u64),
+                    }
+                },
+            )
+            .collect())
+    }
+
+    /// Combines role attributes, table grants and database CONNECT privileges
+    ///
+    /// Table privileges are granted if the user holds them on at least one
+    /// table outside the system schemas; owned tables count through their
+    /// default ACL. `can_drop` means the user owns at least one relation.
+    async fn check_privileges(&self, session: SessionId) -> EngineResult<PrivilegeReport> {
+        let pg_session = self.get_session(session).await?;
+        let pool = &pg_session.pool;
+
+        let (is_superuser, can_create, can_drop, privileges, granted_databases): (
+            bool,
+            bool,
+            bool,
+            Vec<String>,
+            Vec<String>,
+        ) = sqlx::query_as(
+            r#"
+            SELECT r.rolsuper,
+                   COALESCE(has_schema_privilege(current_schema(), 'CREATE'), false),
+                   EXISTS (
+                       SELECT 1
+                       FROM pg_class c
+                       JOIN pg_namespace n ON n.oid = c.relnamespace
+                       WHERE c.relkind IN ('r', 'p', 'v', 'm')
+                         AND n.nspname NOT IN ('pg_catalog', 'information_schema')
+                         AND n.nspname NOT LIKE 'pg_toast%'
+                         AND pg_has_role(c.relowner, 'USAGE')
+                   ),
+                   ARRAY(
+                       SELECT DISTINCT privilege_type::text
+                       FROM information_schema.role_table_grants
+                       WHERE table_schema NOT IN ('pg_catalog', 'information_schema')
+                   ),
+                   ARRAY(
+                       SELECT datname::text
+                       FROM pg_database
+                       WHERE datallowconn
+                         AND NOT datistemplate
+                         AND has_database_privilege(datname, 'CONNECT')
+                       ORDER BY datname
+                   )
+            FROM pg_roles r
+            WHERE r.rolname = current_user
+            "#,
+        )
+        .fetch_one(pool)
+        .await
+        .map_err(|e| EngineError::execution_error(e.to_string()))?;
+
+        let has = |privilege: &str| is_superuser || privileges.iter().any(|p| p == privilege);
+
+        Ok(PrivilegeReport {
+            can_select: has("SELECT"),
+            can_insert: has("INSERT"),
+            can_update: has("UPDATE"),
+            can_delete: has("DELETE"),
+            can_create: is_superuser || can_create,
+            can_drop: is_superuser || can_drop,
+            is_superuser,
+            granted_databases,
+        })
+    }
+
+    async fn get_wal_status(&self, session: SessionId) -> EngineResult<WalStatus> {
+        let pg_session = self.get_session(session).await?;
+
+        // On a standby the current position is the last replayed record; the
+        // delay is zero once everything received has been replayed.
+        let row: (bool, Option<String>, Option<String>, Option<String>, Option<f64>) =
+            sqlx::query_as(
+                    r#"
+                    SELECT
+                        pg_is_in_recovery(),
+                        (CASE WHEN pg_is_in_recovery()
+                            THEN pg_last_wal_replay_lsn()
+                            ELSE pg_current_wal_lsn()
+                        END)::text,
+                        (SELECT checkpoint_lsn::text FROM pg_control_checkpoint()),
+                        NULLIF(current_setting('recovery_target_lsn', true), ''),
+                        CASE WHEN pg_is_in_recovery() THEN
+                            CASE WHEN pg_last_wal_receive_lsn() = pg_last_wal_replay_lsn() THEN 0
+                            ELSE EXTRACT(EPOCH FROM now() - pg_last_xact_replay_timestamp())::float8
+                            END
+                        END
+                    "#,
+                )
+                .fetch_one(&pg_session.pool)
+                .await
+                .map_err(|e| EngineError::execution_error(e.to_string()))?;
+
+        Ok(WalStatus {
+            current_lsn: row.1.unwrap_or_default(),
+            last_checkpoint_lsn: row.2.unwrap_or_default(),
+            recovery_target_lsn: row.3,
+            is_in_recovery: row.0,
+            standby_delay: row.4,
+        })
+    }
+
+    async fn force_checkpoint(&self, session: SessionId) -> EngineResult<()> {
+        let pg_session = self.get_session(session).await?;
+
+        sqlx::query("CHECKPOINT")
+            .execute(&pg_session.pool)
+            .await
+            .map_err(|e| EngineError::execution_error(e.to_string()))?;
+        Ok(())
+    }
+
+    async fn is_read_replica(&self, session: SessionId) -> EngineResult<bool> {
+        let pg_session = self.get_session(session).await?;
+
+        let (in_recovery,): (bool,) = sqlx::query_as("SELECT pg_is_in_recovery()")
+            .fetch_one(&pg_session.pool)
+            .await
+            .map_err(|e| EngineError::execution_error(e.to_string()))?;
+        Ok(in_recovery)
+    }
+
+    /// Uses `set_config()` so the value is a bound parameter
+    ///
+    /// `Local` needs an active transaction. `Session` values are also
+    /// remembered and re-applied to pooled connections. There is no global
+    /// scope; that takes `ALTER SYSTEM` or `ALTER DATABASE`.
+    async fn set_session_variable(
+        &self,
+        session: SessionId,
+        name: &str,
+        value: &str,
+        scope: VariableScope,
+    ) -> EngineResult<()> {
+        let name = name.trim().to_ascii_lowercase();
+        if !SETTABLE_VARIABLES.contains(&name.as_str()) {
+            return Err(EngineError::not_supported(format!(
+                "Variable '{}' cannot be set from QoreDB",
+                name
+            )));
+        }
+        if scope == VariableScope::Global {
+            return Err(EngineError::not_supported(
+                "PostgreSQL has no global SET; use ALTER SYSTEM or ALTER DATABASE",
+            ));
+        }
+
+        let pg_session = self.get_session(session).await?;
+        let is_local = scope == VariableScope::Local;
+
+        let mut tx = pg_session.transaction_conn.lock().await;
+        match tx.as_mut() {
+            Some(conn) => {
+                sqlx::query("SELECT set_config($1, $2, $3)")
+                    .bind(&name)
+                    .bind(value)
+                    .bind(is_local)
+                    .fetch_one(&mut **conn)
+                    .await
+                    .map_err(|e| EngineError::execution_error(e.to_string()))?;
+            }
+            None if is_local => {
+                return Err(EngineError::transaction_error(
+                    "SET LOCAL requires an active transaction",
+                ));
+            }
+            None => {
+                // Validate the value now rather than on the next query
+                sqlx::query("SELECT set_config($1, $2, false)")
+                    .bind(&name)
+                    .bind(value)
+                    .fetch_one(&pg_session.pool)
+                    .await
+                    .map_err(|e| EngineError::execution_error(e.to_string()))?;
+            }
+        }
+
+        if !is_local {
+            let mut variables = pg_session.session_variables.lock().await;
+            variables.insert(name, value.to_string());
+        }
+        Ok(())
+    }
+
+    /// Runs `SHOW ALL` as seen by the session. `SHOW` takes no pattern, so
+    /// `filter` is applied to the names afterwards.
+    async fn list_server_variables(
+        &self,
+        session: SessionId,
+        scope: VariableScope,
+        filter: Option<&str>,
+    ) -> EngineResult<Vec<ServerVariable>> {
+        if scope == VariableScope::Global {
+            return Err(EngineError::not_supported(
+                "PostgreSQL has no global variables; use the session scope",
+            ));
+        }
+
+        let pg_session = self.get_session(session).await?;
+        let mut tx = pg_session.transaction_conn.lock().await;
+        let rows: Vec<(String, String, String)> = match tx.as_mut() {
+            Some(conn) => sqlx::query_as("SHOW ALL").fetch_all(&mut **conn).await,
+            None => {
+                let mut conn = pg_session
+                    .pool
+                    .acquire()
+                    .await
+                    .map_err(|e| EngineError::connection_failed(e.to_string()))?;
+                pg_session.apply_session_variables(&mut conn).await?;
+                sqlx::query_as("SHOW ALL").fetch_all(&mut *conn).await
+            }
+        }
+        .map_err(|e| EngineError::execution_error(e.to_string()))?;
+
+        Ok(rows
+            .into_iter()
+            .filter(|(name, _, _)| filter.is_none_or(|filter| like_matches(filter, name)))
+            .map(|(name, value, _description)| ServerVariable { name, value, scope })
+            .collect())
+    }
+
     async fn cancel(&self, session: SessionId, query_id: Option<QueryId>) -> EngineResult<()> {
         let pg_session = self.get_session(session).await?;
 
@@ -613,31 +2062,398 @@ impl DataEngine for PostgresDriver {
             }
         };
 
-        if backend_pids.is_empty() {
-            return Err(EngineError::execution_error("No active queries to cancel"));
-        }
+        if backend_pids.is_empty() {
+            return Err(EngineError::execution_error("No active queries to cancel"));
+        }
+
+        let mut conn = pg_session
+            .pool
+            .acquire()
+            .await
+            .map_err(|e| EngineError::connection_failed(e.to_string()))?;
+
+        for pid in backend_pids {
+            let _ = sqlx::query("SELECT pg_cancel_backend($1)")
+                .bind(pid)
+                .execute(&mut *conn)
+                .await
+                .map_err(|e| EngineError::execution_error(e.to_string()))?;
+        }
+
+        Ok(())
+    }
+
+    async fn list_server_queries(&self, session: SessionId) -> EngineResult<Vec<ServerQuery>> {
+        let pg_session = self.get_session(session).await?;
+
+        // Other roles' queries show as NULL without pg_read_all_stats
+        let rows = sqlx::query(
+            r#"
+            SELECT pid, usename::text AS username, datname::text AS database,
+                   host(client_addr) AS client_addr, state, query,
+                   (EXTRACT(EPOCH FROM (now() - query_start)) * 1000)::float8 AS duration_ms
+            FROM pg_stat_activity
+            WHERE backend_type = 'client backend' AND pid <> pg_backend_pid()
+            ORDER BY query_start NULLS LAST
+            "#,
+        )
+        .fetch_all(&pg_session.pool)
+        .await
+        .map_err(|e| EngineError::execution_error(e.to_string()))?;
+
+        rows.iter()
+            .map(|row| {
+                Ok(ServerQuery {
+                    pid: row.try_get::<i32, _>("pid")? as i64,
+                    username: row.try_get("username")?,
+                    database: row.try_get("database")?,
+                    client_addr: row.try_get("client_addr")?,
+                    state: row.try_get("state")?,
+                    query: row.try_get("query")?,
+                    duration_ms: row.try_get("duration_ms")?,
+                })
+            })
+            .collect::<Result<_, sqlx::Error>>()
+            .map_err(|e| EngineError::execution_error(e.to_string()))
+    }
+
+    async fn clear_statement_cache(&self, session: SessionId) -> EngineResult<()> {
+        self.get_session(session).await?.clear_statement_cache().await
+    }
+
+    async fn acquire_advisory_lock(
+        &self,
+        session: SessionId,
+        key: i64,
+        shared: bool,
+    ) -> EngineResult<bool> {
+        let function = if shared {
+            "pg_try_advisory_lock_shared"
+        } else {
+            "pg_try_advisory_lock"
+        };
+        self.get_session(session)
+            .await?
+            .run_advisory_lock(&format!("SELECT {}($1)", function), key, true, None)
+            .await
+    }
+
+    /// Uses `lock_timeout`, which also bounds waits for advisory locks
+    async fn acquire_advisory_lock_wait(
+        &self,
+        session: SessionId,
+        key: i64,
+        shared: bool,
+        timeout_ms: u64,
+    ) -> EngineResult<bool> {
+        let function = if shared {
+            "pg_advisory_lock_shared"
+        } else {
+            "pg_advisory_lock"
+        };
+        // The function returns void
+        self.get_session(session)
+            .await?
+            .run_advisory_lock(
+                &format!("SELECT true FROM {}($1)", function),
+                key,
+                true,
+                Some(timeout_ms),
+            )
+            .await
+    }
+
+    async fn release_advisory_lock(
+        &self,
+        session: SessionId,
+        key: i64,
+        shared: bool,
+    ) -> EngineResult<bool> {
+        let function = if shared {
+            "pg_advisory_unlock_shared"
+        } else {
+            "pg_advisory_unlock"
+        };
+        self.get_session(session)
+            .await?
+            .run_advisory_lock(&format!("SELECT {}($1)", function), key, false, None)
+            .await
+    }
+
+    async fn cancel_backend(
+        &self,
+        session: SessionId,
+        pid: i64,
+        terminate: bool,
+    ) -> EngineResult<()> {
+        let pg_session = self.get_session(session).await?;
+        let pid = i32::try_from(pid)
+            .map_err(|_| EngineError::execution_error(format!("Invalid backend PID {}", pid)))?;
+
+        let sql = if terminate {
+            "SELECT pg_terminate_backend($1)"
+        } else {
+            "SELECT pg_cancel_backend($1)"
+        };
+        let signalled: bool = sqlx::query_scalar(sql)
+            .bind(pid)
+            .fetch_one(&pg_session.pool)
+            .await
+            .map_err(|e| EngineError::execution_error(e.to_string()))?;
+
+        if signalled {
+            Ok(())
+        } else {
+            Err(EngineError::execution_error(format!(
+                "No server process with PID {} could be signalled",
+                pid
+            )))
+        }
+    }
+
+    async fn send_notify(
+        &self,
+        session: SessionId,
+        channel: &str,
+        payload: Option<&str>,
+    ) -> EngineResult<()> {
+        let pg_session = self.get_session(session).await?;
+
+        // Inside a transaction the notification is only delivered on commit
+        let mut tx_guard = pg_session.transaction_conn.lock().await;
+        let query = sqlx::query("SELECT pg_notify($1, $2)")
+            .bind(channel)
+            .bind(payload.unwrap_or(""));
+        let result = match tx_guard.as_mut() {
+            Some(conn) => query.execute(&mut **conn).await,
+            None => query.execute(&pg_session.pool).await,
+        };
+        result.map_err(|e| EngineError::execution_error(e.to_string()))?;
+        Ok(())
+    }
+
+    async fn explain_analyze(
+        &self,
+        session: SessionId,
+        query: &str,
+        query_id: QueryId,
+        on_line: &(dyn for<'a> Fn(&'a str) + Send + Sync),
+    ) -> EngineResult<Vec<String>> {
+        let pg_session = self.get_session(session).await?;
+
+        // Always use a fresh pool connection so a long analysis never
+        // holds the transaction connection.
+        let mut conn = pg_session
+            .pool
+            .acquire()
+            .await
+            .map_err(|e| EngineError::connection_failed(e.to_string()))?;
+        let backend_pid = Self::fetch_backend_pid(&mut conn).await?;
+        {
+            let mut active = pg_session.active_queries.lock().await;
+            active.insert(query_id, backend_pid);
+        }
+
+        let sql = format!(
+            "EXPLAIN (ANALYZE, FORMAT TEXT, BUFFERS ON) {}",
+            query.trim().trim_end_matches(';')
+        );
+
+        let result = async {
+            use futures::TryStreamExt;
+
+            // TEXT format returns one row per plan line.
+            let mut lines = Vec::new();
+            let mut stream = sqlx::query_scalar::<_, String>(&sql).fetch(&mut *conn);
+            while let Some(line) = stream.try_next().await.map_err(|e| {
+                let msg = e.to_string();
+                if msg.contains("canceling statement due to user request") {
+                    EngineError::Cancelled
+                } else if msg.contains("syntax error") {
+                    EngineError::syntax_error(msg)
+                } else {
+                    EngineError::execution_error(msg)
+                }
+            })? {
+                on_line(&line);
+                lines.push(line);
+            }
+            Ok(lines)
+        }
+        .await;
+
+        let mut active = pg_session.active_queries.lock().await;
+        active.remove(&query_id);
+        result
+    }
+
+    /// Runs in the session's transaction when one is active
+    async fn create_table(
+        &self,
+        session: SessionId,
+        namespace: &Namespace,
+        schema: &NewTableSchema,
+    ) -> EngineResult<()> {
+        let pg_session = self.get_session(session).await?;
+
+        let sql = Self::create_table_sql(namespace, schema);
+        let mut tx_guard = pg_session.transaction_conn.lock().await;
+        let result = if let Some(ref mut conn) = *tx_guard {
+            sqlx::query(&sql).execute(&mut **conn).await
+        } else {
+            sqlx::query(&sql).execute(&pg_session.pool).await
+        };
+        result.map_err(|e| EngineError::execution_error(e.to_string()))?;
+        Ok(())
+    }
+
+    /// Runs in the session's transaction when one is active
+    async fn add_column(
+        &self,
+        session: SessionId,
+        namespace: &Namespace,
+        table: &str,
+        column: &NewColumnDef,
+    ) -> EngineResult<()> {
+        let pg_session = self.get_session(session).await?;
+
+        let table_name = if let Some(schema) = &namespace.schema {
+            format!("\"{}\".\"{}\"", schema.replace("\"", "\"\""), table.replace("\"", "\"\""))
+        } else {
+            format!("\"{}\"", table.replace("\"", "\"\""))
+        };
+        let sql = format!(
+            "ALTER TABLE {} ADD COLUMN {}",
+            table_name,
+            Self::column_definition(column)
+        );
+
+        let mut tx_guard = pg_session.transaction_conn.lock().await;
+        let result = if let Some(ref mut conn) = *tx_guard {
+            sqlx::query(&sql).execute(&mut **conn).await
+        } else {
+            sqlx::query(&sql).execute(&pg_session.pool).await
+        };
+        result.map_err(|e| EngineError::execution_error(e.to_string()))?;
+        Self::forget_stale_statements(tx_guard.as_deref_mut(), &pg_session.pool).await;
+        Ok(())
+    }
+
+    /// Runs in the session's transaction when one is active
+    async fn drop_column(
+        &self,
+        session: SessionId,
+        namespace: &Namespace,
+        table: &str,
+        column_name: &str,
+    ) -> EngineResult<()> {
+        let pg_session = self.get_session(session).await?;
+
+        let table_name = if let Some(schema) = &namespace.schema {
+            format!("\"{}\".\"{}\"", schema.replace("\"", "\"\""), table.replace("\"", "\"\""))
+        } else {
+            format!("\"{}\"", table.replace("\"", "\"\""))
+        };
+        let sql = format!(
+            "ALTER TABLE {} DROP COLUMN \"{}\"",
+            table_name,
+            column_name.replace("\"", "\"\"")
+        );
+
+        let mut tx_guard = pg_session.transaction_conn.lock().await;
+        let result = if let Some(ref mut conn) = *tx_guard {
+            sqlx::query(&sql).execute(&mut **conn).await
+        } else {
+            sqlx::query(&sql).execute(&pg_session.pool).await
+        };
+        result.map_err(|e| {
+            // undefined_column
+            let code = e.as_database_error().and_then(|db| db.code());
+            if code.as_deref() == Some("42703") {
+                EngineError::execution_error(format!(
+                    "Column '{}' does not exist in table '{}'",
+                    column_name, table
+                ))
+            } else {
+                EngineError::execution_error(e.to_string())
+            }
+        })?;
+        Self::forget_stale_statements(tx_guard.as_deref_mut(), &pg_session.pool).await;
+        Ok(())
+    }
+
+    async fn rename_column(
+        &self,
+        session: SessionId,
+        namespace: &Namespace,
+        table: &str,
+        old_name: &str,
+        new_name: &str,
+    ) -> EngineResult<()> {
+        let pg_session = self.get_session(session).await?;
+
+        let table_name = if let Some(schema) = &namespace.schema {
+            format!("\"{}\".\"{}\"", schema.replace("\"", "\"\""), table.replace("\"", "\"\""))
+        } else {
+            format!("\"{}\"", table.replace("\"", "\"\""))
+        };
+        let sql = format!(
+            "ALTER TABLE {} RENAME COLUMN \"{}\" TO \"{}\"",
+            table_name,
+            old_name.replace("\"", "\"\""),
+            new_name.replace("\"", "\"\"")
+        );
+
+        let mut tx_guard = pg_session.transaction_conn.lock().await;
+        let result = if let Some(ref mut conn) = *tx_guard {
+            sqlx::query(&sql).execute(&mut **conn).await
+        } else {
+            sqlx::query(&sql).execute(&pg_session.pool).await
+        };
+        result.map_err(|e| {
+            // undefined_column
+            let code = e.as_database_error().and_then(|db| db.code());
+            if code.as_deref() == Some("42703") {
+                EngineError::execution_error(format!(
+                    "Column '{}' does not exist in table '{}'",
+                    old_name, table
+                ))
+            } else {
+                EngineError::execution_error(e.to_string())
+            }
+        })?;
+        Self::forget_stale_statements(tx_guard.as_deref_mut(), &pg_session.pool).await;
+        Ok(())
+    }
 
-        let mut conn = pg_session
-            .pool
-            .acquire()
-            .await
-            .map_err(|e| EngineError::connection_failed(e.to_string()))?;
+    async fn estimate_plan(&self, session: SessionId, query: &str) -> EngineResult<PlanEstimate> {
+        let pg_session = self.get_session(session).await?;
 
-        for pid in backend_pids {
-            let _ = sqlx::query("SELECT pg_cancel_backend($1)")
-                .bind(pid)
-                .execute(&mut *conn)
-                .await
-                .map_err(|e| EngineError::execution_error(e.to_string()))?;
+        // TEXT format: the first row describes the top plan node
+        let sql = format!(
+            "EXPLAIN (FORMAT TEXT) {}",
+            query.trim().trim_end_matches(';')
+        );
+        let mut tx_guard = pg_session.transaction_conn.lock().await;
+        let line = if let Some(ref mut conn) = *tx_guard {
+            sqlx::query_scalar::<_, String>(&sql).fetch_one(&mut **conn).await
+        } else {
+            sqlx::query_scalar::<_, String>(&sql).fetch_one(&pg_session.pool).await
         }
+        .map_err(|e| EngineError::execution_error(e.to_string()))?;
 
-        Ok(())
+        Self::parse_plan_estimate(&line)
+            .ok_or_else(|| EngineError::execution_error(format!("Unexpected plan line: {}", line)))
     }
 
     fn cancel_support(&self) -> CancelSupport {
         CancelSupport::Driver
     }
 
+    fn supports_streaming(&self) -> bool {
+        true
+    }
+
     // ==================== Transaction Methods ====================
 
     async fn begin_transaction(&self, session: SessionId) -> EngineResult<()> {
@@ -657,6 +2473,8 @@ impl DataEngine for PostgresDriver {
                 "Failed to acquire connection for transaction: {}", e
             )))?;
 
+        pg_session.apply_session_variables(&mut conn).await?;
+
         // Execute BEGIN on the dedicated connection
         sqlx::query("BEGIN")
             .execute(&mut *conn)
@@ -715,6 +2533,39 @@ impl DataEngine for PostgresDriver {
         Ok(())
     }
 
+    async fn transaction_status(&self, session: SessionId) -> EngineResult<TransactionStatus> {
+        let pg_session = self.get_session(session).await?;
+
+        let backend_pid = {
+            let mut tx = pg_session.transaction_conn.lock().await;
+            match tx.as_mut() {
+                Some(conn) => Self::fetch_backend_pid(conn).await?,
+                None => {
+                    return Ok(TransactionStatus {
+                        active: false,
+                        server_state: None,
+                    });
+                }
+            }
+        };
+
+        // Ask the server how it sees the dedicated connection
+        // ("idle in transaction", "idle in transaction (aborted)", ...)
+        let server_state: Option<String> = sqlx::query_scalar(
+            "SELECT state::text FROM pg_stat_activity WHERE pid = $1",
+        )
+        .bind(backend_pid)
+        .fetch_optional(&pg_session.pool)
+        .await
+        .map_err(|e| EngineError::execution_error(e.to_string()))?
+        .flatten();
+
+        Ok(TransactionStatus {
+            active: true,
+            server_state,
+        })
+    }
+
     fn supports_transactions(&self) -> bool {
         true
     }
@@ -731,22 +2582,9 @@ impl DataEngine for PostgresDriver {
         let pg_session = self.get_session(session).await?;
 
         // 1. Build Query String
-        let table_name = if let Some(schema) = &namespace.schema {
-            format!("\"{}\".\"{}\"", schema.replace("\"", "\"\""), table.replace("\"", "\"\""))
-        } else {
-            format!("\"{}\"", table.replace("\"", "\"\""))
-        };
-
         let mut keys: Vec<&String> = data.columns.keys().collect();
         keys.sort();
-
-        let sql = if keys.is_empty() {
-            format!("INSERT INTO {} DEFAULT VALUES", table_name)
-        } else {
-            let cols_str = keys.iter().map(|k| format!("\"{}\"", k.replace("\"", "\"\""))).collect::<Vec<_>>().join(", ");
-            let params_str = (1..=keys.len()).map(|i| format!("${}", i)).collect::<Vec<_>>().join(", ");
-            format!("INSERT INTO {} ({}) VALUES ({})", table_name, cols_str, params_str)
-        };
+        let sql = Self::insert_sql(namespace, table, &keys);
 
         // 2. Prepare Query
         let mut query = sqlx::query(&sql);
@@ -772,6 +2610,40 @@ impl DataEngine for PostgresDriver {
         ))
     }
 
+    async fn insert_row_returning(
+        &self,
+        session: SessionId,
+        namespace: &Namespace,
+        table: &str,
+        data: &RowData,
+    ) -> EngineResult<QueryResult> {
+        let pg_session = self.get_session(session).await?;
+
+        let mut keys: Vec<&String> = data.columns.keys().collect();
+        keys.sort();
+        let sql = format!("{} RETURNING *", Self::insert_sql(namespace, table, &keys));
+
+        let mut query = sqlx::query(&sql);
+        for k in &keys {
+            let val = data.columns.get(*k).unwrap();
+            query = Self::bind_param(query, val);
+        }
+
+        let start = Instant::now();
+        let mut tx_guard = pg_session.transaction_conn.lock().await;
+        let pg_rows = if let Some(ref mut conn) = *tx_guard {
+            query.fetch_all(&mut **conn).await
+        } else {
+            query.fetch_all(&pg_session.pool).await
+        };
+        let pg_rows = pg_rows.map_err(|e| EngineError::execution_error(e.to_string()))?;
+
+        Ok(Self::returning_result(
+            &pg_rows,
+            start.elapsed().as_micros() as f64 / 1000.0,
+        ))
+    }
+
     async fn update_row(
         &self,
         session: SessionId,
@@ -818,10 +2690,11 @@ impl DataEngine for PostgresDriver {
         }
 
         let sql = format!(
-            "UPDATE {} SET {} WHERE {}", 
-            table_name, 
-            set_clauses.join(", "), 
-            where_clauses.join(" AND ")
+            "UPDATE {} SET {} WHERE {} RETURNING {}",
+            table_name,
+            set_clauses.join(", "),
+            where_clauses.join(" AND "),
+            Self::returning_columns(&pk_keys)
         );
 
         let mut query = sqlx::query(&sql);
@@ -841,15 +2714,15 @@ impl DataEngine for PostgresDriver {
         let start = Instant::now();
         let mut tx_guard = pg_session.transaction_conn.lock().await;
         let result = if let Some(ref mut conn) = *tx_guard {
-             query.execute(&mut **conn).await
+             query.fetch_all(&mut **conn).await
         } else {
-             query.execute(&pg_session.pool).await
+             query.fetch_all(&pg_session.pool).await
         };
 
-        let result = result.map_err(|e| EngineError::execution_error(e.to_string()))?;
-        
-        Ok(QueryResult::with_affected_rows(
-            result.rows_affected(),
+        let pg_rows = result.map_err(|e| EngineError::execution_error(e.to_string()))?;
+
+        Ok(Self::returning_result(
+            &pg_rows,
             start.elapsed().as_micros() as f64 / 1000.0,
         ))
     }
@@ -878,13 +2751,16 @@ impl DataEngine for PostgresDriver {
 
         // DELETE FROM table WHERE pk1=$1
         let mut where_clauses = Vec::new();
-        let mut i = 1;
-        for k in &pk_keys {
+        for (i, k) in (1..).zip(&pk_keys) {
             where_clauses.push(format!("\"{}\"=${}", k.replace("\"", "\"\""), i));
-            i += 1;
         }
 
-        let sql = format!("DELETE FROM {} WHERE {}", table_name, where_clauses.join(" AND "));
+        let sql = format!(
+            "DELETE FROM {} WHERE {} RETURNING {}",
+            table_name,
+            where_clauses.join(" AND "),
+            Self::returning_columns(&pk_keys)
+        );
 
         let mut query = sqlx::query(&sql);
         for k in &pk_keys {
@@ -895,46 +2771,531 @@ impl DataEngine for PostgresDriver {
         let start = Instant::now();
         let mut tx_guard = pg_session.transaction_conn.lock().await;
         let result = if let Some(ref mut conn) = *tx_guard {
-             query.execute(&mut **conn).await
+             query.fetch_all(&mut **conn).await
         } else {
-             query.execute(&pg_session.pool).await
+             query.fetch_all(&pg_session.pool).await
         };
 
-        let result = result.map_err(|e| EngineError::execution_error(e.to_string()))?;
-        
+        let pg_rows = result.map_err(|e| EngineError::execution_error(e.to_string()))?;
+
+        Ok(Self::returning_result(
+            &pg_rows,
+            start.elapsed().as_micros() as f64 / 1000.0,
+        ))
+    }
+
+    /// The filter is sent as a prepared statement, so it cannot carry a
+    /// second statement.
+    async fn delete_rows_where(
+        &self,
+        session: SessionId,
+        namespace: &Namespace,
+        table: &str,
+        filter: &str,
+    ) -> EngineResult<QueryResult> {
+        let pg_session = self.get_session(session).await?;
+
+        let table_name = if let Some(schema) = &namespace.schema {
+            format!("\"{}\".\"{}\"", schema.replace("\"", "\"\""), table.replace("\"", "\"\""))
+        } else {
+            format!("\"{}\"", table.replace("\"", "\"\""))
+        };
+
+        let filter = filter.trim();
+        let sql = if filter.is_empty() {
+            format!("DELETE FROM {}", table_name)
+        } else {
+            format!("DELETE FROM {} WHERE {}", table_name, filter)
+        };
+
+        let start = Instant::now();
+        let mut tx_guard = pg_session.transaction_conn.lock().await;
+        let result = if let Some(ref mut conn) = *tx_guard {
+            sqlx::query(&sql).execute(&mut **conn).await
+        } else {
+            sqlx::query(&sql).execute(&pg_session.pool).await
+        };
+
+        let result = result.map_err(Self::map_query_error)?;
+
         Ok(QueryResult::with_affected_rows(
             result.rows_affected(),
             start.elapsed().as_micros() as f64 / 1000.0,
         ))
     }
 
+    /// `TRUNCATE` takes an ACCESS EXCLUSIVE lock on the table, blocking
+    /// even reads until it completes, or until the active transaction ends
+    /// when run inside one. The server reports no row count.
+    async fn truncate_table(
+        &self,
+        session: SessionId,
+        namespace: &Namespace,
+        table: &str,
+        cascade: bool,
+    ) -> EngineResult<QueryResult> {
+        let pg_session = self.get_session(session).await?;
+
+        let table_name = if let Some(schema) = &namespace.schema {
+            format!("\"{}\".\"{}\"", schema.replace("\"", "\"\""), table.replace("\"", "\"\""))
+        } else {
+            format!("\"{}\"", table.replace("\"", "\"\""))
+        };
+        let cascade = if cascade { " CASCADE" } else { "" };
+        let sql = format!("TRUNCATE TABLE {}{}", table_name, cascade);
+
+        let start = Instant::now();
+        let mut tx_guard = pg_session.transaction_conn.lock().await;
+        let result = if let Some(ref mut conn) = *tx_guard {
+            sqlx::query(&sql).execute(&mut **conn).await
+        } else {
+            sqlx::query(&sql).execute(&pg_session.pool).await
+        };
+        result.map_err(Self::map_query_error)?;
+
+        let mut result = QueryResult::empty();
+        result.execution_time_ms = start.elapsed().as_micros() as f64 / 1000.0;
+        Ok(result)
+    }
+
     fn supports_mutations(&self) -> bool {
         true
     }
+
+    fn supports_returning_keys(&self) -> bool {
+        true
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::engine::types::{coerce_value, LogicalType};
 
-    #[test]
-    fn test_connection_string_building() {
-        let config = ConnectionConfig {
+    fn test_config() -> ConnectionConfig {
+        ConnectionConfig {
             driver: "postgres".to_string(),
             host: "localhost".to_string(),
             port: 5432,
             username: "user".to_string(),
             password: "pass".to_string(),
             database: Some("testdb".to_string()),
-            ssl: false,
+            ssl_mode: SslMode::Disabled,
+            ssl_ca_path: None,
             environment: "development".to_string(),
             read_only: false,
             ssh_tunnel: None,
+            pool_idle_timeout_secs: 300,
+            pool_max_lifetime_secs: 1800,
+            use_http_api: false,
+            boost: None,
+            mongo_auth_source: None,
+            keepalive_query_secs: None,
+            label: None,
+            charset: None,
+            raw_connection_string: None,
+            retry_policy: None,
+            statement_cache_capacity: None,
+        }
+    }
+
+    #[test]
+    fn encodes_rows_in_copy_text_format() {
+        let mut out = String::new();
+        PostgresDriver::write_copy_row(
+            &mut out,
+            &[
+                Value::Int(1),
+                Value::Null,
+                Value::Text("tab\there\nback\\slash".to_string()),
+                Value::Bytes(vec![0xde, 0xad]),
+                Value::Array(vec![Value::Text("a\"b".to_string()), Value::Null]),
+            ],
+        );
+        assert_eq!(
+            out,
+            "1\t\\N\ttab\\there\\nback\\\\slash\t\\\\xdead\t{\"a\\\\\"b\",NULL}\n"
+        );
+
+        let namespace = Namespace::with_schema("db", "public");
+        assert_eq!(
+            PostgresDriver::copy_in_sql(&namespace, "my\"table", &["id".to_string()]),
+            "COPY \"public\".\"my\"\"table\" (\"id\") FROM STDIN"
+        );
+    }
+
+    #[test]
+    fn like_patterns_match_variable_names() {
+        assert!(like_matches("%timeout", "statement_timeout"));
+        assert!(like_matches("STATEMENT%", "statement_timeout"));
+        assert!(like_matches("work_me_", "work_mem"));
+        assert!(like_matches("%", ""));
+        assert!(!like_matches("work_me_", "work_me"));
+        assert!(!like_matches("timeout", "statement_timeout"));
+    }
+
+    #[test]
+    fn builds_create_table_with_identity_and_composite_key() {
+        let column = |name: &str, data_type: &str| NewColumnDef {
+            name: name.to_string(),
+            data_type: data_type.to_string(),
+            nullable: false,
+            default_value: None,
+            is_primary_key: true,
+            auto_increment: false,
         };
+        let mut id = column("id", "bigint");
+        id.auto_increment = true;
+        let mut status = column("status", "text");
+        status.is_primary_key = false;
+        status.default_value = Some("'draft'".to_string());
+
+        let schema = NewTableSchema {
+            table_name: "orders".to_string(),
+            columns: vec![id, column("tenant", "int"), status],
+            primary_key: None,
+            if_not_exists: true,
+        };
+        assert!(schema.validate().is_ok());
+        assert_eq!(
+            PostgresDriver::create_table_sql(&Namespace::with_schema("shop", "public"), &schema),
+            "CREATE TABLE IF NOT EXISTS \"public\".\"orders\" (\"id\" bigint GENERATED ALWAYS AS IDENTITY NOT NULL, \
+             \"tenant\" int NOT NULL, \"status\" text NOT NULL DEFAULT 'draft', PRIMARY KEY (\"id\", \"tenant\"))"
+        );
+    }
+
+    #[test]
+    fn builds_routine_calls() {
+        let namespace = Namespace::with_schema("shop", "billing");
+        assert_eq!(
+            PostgresDriver::call_routine_sql(&namespace, "total", RoutineType::Function, 2),
+            "SELECT \"billing\".\"total\"($1, $2)"
+        );
+        assert_eq!(
+            PostgresDriver::call_routine_sql(&namespace, "close\"day", RoutineType::Procedure, 0),
+            "CALL \"billing\".\"close\"\"day\"()"
+        );
+    }
+
+    #[test]
+    fn parses_top_plan_node_estimates() {
+        let estimate =
+            PostgresDriver::parse_plan_estimate("Hash Join  (cost=1.09..36.85 rows=2550 width=44)")
+                .unwrap();
+        assert_eq!(estimate.total_cost, Some(36.85));
+        assert_eq!(estimate.estimated_rows, Some(2550.0));
+        assert!(PostgresDriver::parse_plan_estimate("Planning Time: 0.1 ms").is_none());
+    }
+
+    #[test]
+    fn test_connection_string_building() {
+        let config = test_config();
 
         let conn_str = PostgresDriver::build_connection_string(&config);
         assert!(conn_str.contains("localhost:5432"));
         assert!(conn_str.contains("testdb"));
         assert!(conn_str.contains("sslmode=disable"));
     }
+
+    #[test]
+    fn connection_string_encodes_credentials() {
+        let config = ConnectionConfig {
+            username: "app@corp".to_string(),
+            password: "p@ss:w/ord?".to_string(),
+            database: Some("my db".to_string()),
+            ..test_config()
+        };
+
+        let url = url::Url::parse(&PostgresDriver::build_connection_string(&config)).unwrap();
+        let decode = |s: &str| {
+            percent_encoding::percent_decode_str(s)
+                .decode_utf8()
+                .unwrap()
+                .to_string()
+        };
+        assert_eq!(decode(url.username()), "app@corp");
+        assert_eq!(decode(url.password().unwrap()), "p@ss:w/ord?");
+        assert_eq!(url.host_str(), Some("localhost"));
+        assert_eq!(decode(url.path()), "/my db");
+        assert_eq!(url.query(), Some("sslmode=disable"));
+    }
+
+    #[tokio::test]
+    #[ignore = "requires a PostgreSQL server at QOREDB_TEST_POSTGRES_URL"]
+    async fn empty_select_still_returns_columns() {
+        let url = std::env::var("QOREDB_TEST_POSTGRES_URL").expect("QOREDB_TEST_POSTGRES_URL");
+        let config = test_config();
+
+        let driver = PostgresDriver::new();
+        let session = driver.connect_with_string(&url, &config).await.expect("connect");
+
+        // Temporary tables are per connection, so keep everything in one transaction
+        driver.begin_transaction(session).await.expect("begin");
+        driver
+            .execute(
+                session,
+                "CREATE TEMP TABLE users (id int PRIMARY KEY, name text)",
+                QueryId::new(),
+            )
+            .await
+            .expect("create table");
+
+        let result = driver
+            .execute(session, "SELECT id, name FROM users WHERE 1=0", QueryId::new())
+            .await
+            .expect("select");
+        driver.rollback(session).await.expect("rollback");
+        driver.disconnect(session).await.expect("disconnect");
+
+        assert!(result.rows.is_empty());
+        let names: Vec<&str> = result.columns.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, vec!["id", "name"]);
+        assert!(!result.columns[0].nullable);
+    }
+
+    #[tokio::test]
+    #[ignore = "requires a PostgreSQL server at QOREDB_TEST_POSTGRES_URL"]
+    async fn non_finite_floats_serialize() {
+        let url = std::env::var("QOREDB_TEST_POSTGRES_URL").expect("QOREDB_TEST_POSTGRES_URL");
+        let config = test_config();
+
+        let driver = PostgresDriver::new();
+        let session = driver.connect_with_string(&url, &config).await.expect("connect");
+        let result = driver
+            .execute(
+                session,
+                "SELECT 'NaN'::float8, 'Infinity'::float8, '-Infinity'::float4",
+                QueryId::new(),
+            )
+            .await
+            .expect("select");
+        driver.disconnect(session).await.expect("disconnect");
+
+        let json = serde_json::to_value(&result).expect("result should serialize");
+        assert_eq!(
+            json["rows"][0]["values"],
+            serde_json::json!(["NaN", "Infinity", "-Infinity"])
+        );
+    }
+
+    #[tokio::test]
+    #[ignore = "requires a PostgreSQL server at QOREDB_TEST_POSTGRES_URL"]
+    async fn dropping_a_missing_column_reports_it() {
+        let url = std::env::var("QOREDB_TEST_POSTGRES_URL").expect("QOREDB_TEST_POSTGRES_URL");
+        let config = test_config();
+
+        let driver = PostgresDriver::new();
+        let session = driver.connect_with_string(&url, &config).await.expect("connect");
+        let namespace = Namespace::new("postgres");
+
+        driver.begin_transaction(session).await.expect("begin");
+        driver
+            .execute(session, "CREATE TEMP TABLE users (id int)", QueryId::new())
+            .await
+            .expect("create table");
+        let column = NewColumnDef {
+            name: "email".to_string(),
+            data_type: "text".to_string(),
+            nullable: true,
+            default_value: None,
+            is_primary_key: false,
+            auto_increment: false,
+        };
+        driver
+            .add_column(session, &namespace, "users", &column)
+            .await
+            .expect("add column");
+        let error = driver
+            .drop_column(session, &namespace, "users", "missing")
+            .await
+            .expect_err("missing column");
+        driver.rollback(session).await.expect("rollback");
+        driver.disconnect(session).await.expect("disconnect");
+
+        assert!(error
+            .to_string()
+            .contains("Column 'missing' does not exist in table 'users'"));
+    }
+
+    #[tokio::test]
+    #[ignore = "requires a PostgreSQL server at QOREDB_TEST_POSTGRES_URL"]
+    async fn updates_rows_keyed_by_uuid() {
+        let url = std::env::var("QOREDB_TEST_POSTGRES_URL").expect("QOREDB_TEST_POSTGRES_URL");
+        let config = test_config();
+
+        let driver = PostgresDriver::new();
+        let session = driver.connect_with_string(&url, &config).await.expect("connect");
+        let namespace = Namespace::new("postgres");
+        let id = "5f0c6a3e-8d1b-4c2a-9e7f-0a1b2c3d4e5f";
+
+        driver.begin_transaction(session).await.expect("begin");
+        driver
+            .execute(
+                session,
+                "CREATE TEMP TABLE devices (id uuid PRIMARY KEY, name text)",
+                QueryId::new(),
+            )
+            .await
+            .expect("create table");
+        driver
+            .execute(
+                session,
+                &format!("INSERT INTO devices VALUES ('{id}', 'old')"),
+                QueryId::new(),
+            )
+            .await
+            .expect("insert");
+
+        // The grid sends the key as text, coerced by the mutation commands
+        let mut primary_key = RowData::new();
+        primary_key.columns.insert(
+            "id".to_string(),
+            coerce_value(&Value::Text(id.to_string()), LogicalType::from_data_type("uuid")),
+        );
+        let mut data = RowData::new();
+        data.columns
+            .insert("name".to_string(), Value::Text("new".to_string()));
+        let updated = driver
+            .update_row(session, &namespace, "devices", &primary_key, &data)
+            .await
+            .expect("update");
+        let result = driver
+            .execute(session, "SELECT name FROM devices", QueryId::new())
+            .await
+            .expect("select");
+        driver.rollback(session).await.expect("rollback");
+        driver.disconnect(session).await.expect("disconnect");
+
+        assert_eq!(updated.affected_rows, Some(1));
+        assert!(matches!(&result.rows[0].values[0], Value::Text(name) if name == "new"));
+    }
+
+    #[tokio::test]
+    #[ignore = "requires a PostgreSQL server at QOREDB_TEST_POSTGRES_URL"]
+    async fn advisory_locks_are_held_until_released() {
+        const KEY: i64 = 4_242_001;
+
+        let url = std::env::var("QOREDB_TEST_POSTGRES_URL").expect("QOREDB_TEST_POSTGRES_URL");
+        let config = test_config();
+
+        let driver = PostgresDriver::new();
+        let owner = driver.connect_with_string(&url, &config).await.expect("connect");
+        let other = driver.connect_with_string(&url, &config).await.expect("connect");
+
+        assert!(driver.acquire_advisory_lock(owner, KEY, false).await.unwrap());
+        assert!(!driver.acquire_advisory_lock(other, KEY, false).await.unwrap());
+        assert!(!driver.acquire_advisory_lock(other, KEY, true).await.unwrap());
+        assert!(!driver
+            .acquire_advisory_lock_wait(other, KEY, false, 50)
+            .await
+            .unwrap());
+        assert!(!driver.release_advisory_lock(other, KEY, false).await.unwrap());
+
+        assert!(driver.release_advisory_lock(owner, KEY, false).await.unwrap());
+        assert!(driver
+            .acquire_advisory_lock_wait(other, KEY, true, 1000)
+            .await
+            .unwrap());
+        assert!(driver.acquire_advisory_lock(owner, KEY, true).await.unwrap());
+        assert!(driver.release_advisory_lock(other, KEY, true).await.unwrap());
+        assert!(driver.release_advisory_lock(owner, KEY, true).await.unwrap());
+
+        driver.disconnect(owner).await.expect("disconnect");
+        driver.disconnect(other).await.expect("disconnect");
+    }
+
+    #[tokio::test]
+    #[ignore = "requires a PostgreSQL server at QOREDB_TEST_POSTGRES_URL"]
+    async fn bulk_collection_listing_matches_sequential() {
+        const SCHEMAS: usize = 25;
+
+        let url = std::env::var("QOREDB_TEST_POSTGRES_URL").expect("QOREDB_TEST_POSTGRES_URL");
+        let config = test_config();
+
+        let driver = PostgresDriver::new();
+        let session = driver.connect_with_string(&url, &config).await.expect("connect");
+
+        // Both listings read from the pool, so the schemas must be committed.
+        // Dropping first clears what an interrupted run left behind.
+        for i in 0..SCHEMAS {
+            let sql = format!(
+                "DROP SCHEMA IF EXISTS qoredb_bench_{i} CASCADE; \
+                 CREATE SCHEMA qoredb_bench_{i}; \
+                 CREATE TABLE qoredb_bench_{i}.items (id int); \
+                 CREATE VIEW qoredb_bench_{i}.items_view AS SELECT id FROM qoredb_bench_{i}.items"
+            );
+            driver
+                .execute_multi(session, &sql, QueryId::new())
+                .await
+                .expect("create schema");
+        }
+
+        let start = Instant::now();
+        let mut sequential = HashMap::new();
+        for namespace in driver.list_namespaces(session).await.expect("namespaces") {
+            let collections = driver
+                .list_collections(session, &namespace)
+                .await
+                .expect("collections");
+            sequential.insert(namespace, collections);
+        }
+        let sequential_elapsed = start.elapsed();
+
+        let start = Instant::now();
+        let bulk = driver.list_all_collections(session).await.expect("bulk");
+        let bulk_elapsed = start.elapsed();
+
+        for i in 0..SCHEMAS {
+            let sql = format!("DROP SCHEMA qoredb_bench_{i} CASCADE");
+            driver
+                .execute(session, &sql, QueryId::new())
+                .await
+                .expect("drop schema");
+        }
+        driver.disconnect(session).await.expect("disconnect");
+
+        println!(
+            "{} namespaces: sequential {:?}, bulk {:?}",
+            bulk.len(),
+            sequential_elapsed,
+            bulk_elapsed
+        );
+        let names = |all: &HashMap<Namespace, Vec<Collection>>| {
+            all.iter()
+                .map(|(namespace, collections)| {
+                    let names: Vec<String> = collections.iter().map(|c| c.name.clone()).collect();
+                    (namespace.clone(), names)
+                })
+                .collect::<HashMap<_, _>>()
+        };
+        assert!(bulk.len() > SCHEMAS);
+        assert_eq!(names(&bulk), names(&sequential));
+    }
+
+    #[tokio::test]
+    #[ignore = "requires a PostgreSQL server at QOREDB_TEST_POSTGRES_URL"]
+    async fn reads_large_objects_by_oid() {
+        let url = std::env::var("QOREDB_TEST_POSTGRES_URL").expect("QOREDB_TEST_POSTGRES_URL");
+        let config = test_config();
+
+        let driver = PostgresDriver::new();
+        let session = driver.connect_with_string(&url, &config).await.expect("connect");
+
+        driver.begin_transaction(session).await.expect("begin");
+        let result = driver
+            .execute(session, "SELECT lo_from_bytea(0, '\\x0102ff'::bytea)", QueryId::new())
+            .await
+            .expect("create large object");
+        let Value::Int(oid) = result.rows[0].values[0] else {
+            panic!("expected the OID as an integer");
+        };
+        let data = driver
+            .read_large_object(session, oid as u32)
+            .await
+            .expect("read large object");
+        driver.rollback(session).await.expect("rollback");
+        driver.disconnect(session).await.expect("disconnect");
+
+        assert_eq!(data, vec![0x01, 0x02, 0xff]);
+    }
 }
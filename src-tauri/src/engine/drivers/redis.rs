@@ -0,0 +1,615 @@
+//! Redis Driver
+//!
+//! Implements a read-focused subset of the DataEngine trait for Redis.
+//!
+//! ## Mapping
+//!
+//! - Namespaces are the numbered logical databases (`0` to `15`).
+//! - Collections are key prefixes, grouped on the first `:` delimiter.
+//!   A collection `user` covers the key `user` and every `user:*` key.
+//! - `execute` only runs a whitelist of read commands.
+
+use std::collections::{BTreeSet, HashMap};
+use std::sync::Arc;
+use std::time::Instant;
+
+use async_trait::async_trait;
+use redis::aio::MultiplexedConnection;
+use tokio::sync::{Mutex, RwLock};
+
+use crate::engine::drivers::url_encode;
+use crate::engine::error::{EngineError, EngineResult};
+use crate::engine::traits::DataEngine;
+use crate::engine::types::{
+    Collection, CollectionType, ColumnInfo, ConnectionConfig, Namespace, QueryId, QueryResult,
+    Row as QRow, RowLockMode, SessionId, TableColumn, TableSchema, Value,
+};
+
+/// Number of logical databases exposed by a default Redis server
+const DATABASE_COUNT: u32 = 16;
+
+/// Delimiter used to group keys into collections
+const KEY_DELIMITER: char = ':';
+
+/// Upper bound on keys visited by a single SCAN walk
+const MAX_SCANNED_KEYS: usize = 10_000;
+
+/// COUNT hint passed to SCAN
+const SCAN_BATCH_SIZE: u32 = 500;
+
+/// Read-only commands accepted by `execute`
+const ALLOWED_COMMANDS: &[&str] = &[
+    "GET", "MGET", "STRLEN", "GETRANGE", "EXISTS", "TYPE", "TTL", "PTTL", "HGET", "HMGET",
+    "HGETALL", "HKEYS", "HVALS", "HLEN", "HEXISTS", "LRANGE", "LLEN", "LINDEX", "SMEMBERS",
+    "SCARD", "SISMEMBER", "SRANDMEMBER", "ZRANGE", "ZREVRANGE", "ZRANGEBYSCORE", "ZCARD",
+    "ZSCORE", "ZRANK", "XRANGE", "XREVRANGE", "XLEN", "SCAN", "HSCAN", "SSCAN", "ZSCAN",
+    "DBSIZE", "INFO", "PING",
+];
+
+/// Holds the connection state for a Redis session.
+///
+/// Redis selects the logical database per connection, so one
+/// multiplexed connection is opened lazily for each database used.
+pub struct RedisSession {
+    config: ConnectionConfig,
+    default_db: u32,
+    connections: Mutex<HashMap<u32, MultiplexedConnection>>,
+}
+
+impl RedisSession {
+    async fn connection(&self, db: u32) -> EngineResult<MultiplexedConnection> {
+        let mut connections = self.connections.lock().await;
+        if let Some(conn) = connections.get(&db) {
+            return Ok(conn.clone());
+        }
+
+        let conn = RedisDriver::open_connection(&self.config, db).await?;
+        connections.insert(db, conn.clone());
+        Ok(conn)
+    }
+}
+
+/// Redis driver implementation
+pub struct RedisDriver {
+    sessions: Arc<RwLock<HashMap<SessionId, Arc<RedisSession>>>>,
+}
+
+impl RedisDriver {
+    pub fn new() -> Self {
+        Self {
+            sessions: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+
+    async fn get_session(&self, session: SessionId) -> EngineResult<Arc<RedisSession>> {
+        let sessions = self.sessions.read().await;
+        sessions
+            .get(&session)
+            .cloned()
+            .ok_or_else(|| EngineError::session_not_found(session.0.to_string()))
+    }
+
+    /// Builds a connection string from config
+    fn build_connection_string(config: &ConnectionConfig, db: u32) -> String {
+        let scheme = if config.ssl_mode.is_required() { "rediss" } else { "redis" };
+
+        if config.password.is_empty() {
+            format!("{}://{}:{}/{}", scheme, config.host, config.port, db)
+        } else {
+            format!(
+                "{}://{}:{}@{}:{}/{}",
+                scheme,
+                url_encode(&config.username),
+                url_encode(&config.password),
+                config.host,
+                config.port,
+                db
+            )
+        }
+    }
+
+    async fn open_connection(
+        config: &ConnectionConfig,
+        db: u32,
+    ) -> EngineResult<MultiplexedConnection> {
+        let client = redis::Client::open(Self::build_connection_string(config, db))
+            .map_err(|e| EngineError::connection_failed(e.to_string()))?;
+
+        client
+            .get_multiplexed_async_connection()
+            .await
+            .map_err(|e| {
+                if e.kind() == redis::ErrorKind::AuthenticationFailed {
+                    EngineError::auth_failed(e.to_string())
+                } else {
+                    EngineError::connection_failed(e.to_string())
+                }
+            })
+    }
+
+    /// Parses a namespace database name ("0".."15") into a database index
+    fn parse_db(namespace: &Namespace) -> EngineResult<u32> {
+        namespace
+            .database
+            .parse::<u32>()
+            .ok()
+            .filter(|db| *db < DATABASE_COUNT)
+            .ok_or_else(|| {
+                EngineError::execution_error(format!(
+                    "Invalid Redis database: {}",
+                    namespace.database
+                ))
+            })
+    }
+
+    /// Returns the collection (prefix) a key belongs to
+    fn key_prefix(key: &str) -> &str {
+        key.split(KEY_DELIMITER).next().unwrap_or(key)
+    }
+
+    /// Escapes glob metacharacters for use in a SCAN MATCH pattern
+    fn escape_pattern(value: &str) -> String {
+        let mut escaped = String::with_capacity(value.len());
+        for c in value.chars() {
+            if matches!(c, '*' | '?' | '[' | ']' | '\\') {
+                escaped.push('\\');
+            }
+            escaped.push(c);
+        }
+        escaped
+    }
+
+    /// Walks the keyspace with SCAN, stopping after `limit` keys
+    async fn scan_keys(
+        conn: &mut MultiplexedConnection,
+        pattern: &str,
+        limit: usize,
+    ) -> EngineResult<Vec<String>> {
+        let mut keys = Vec::new();
+        let mut cursor: u64 = 0;
+
+        loop {
+            let (next, batch): (u64, Vec<String>) = redis::cmd("SCAN")
+                .arg(cursor)
+                .arg("MATCH")
+                .arg(pattern)
+                .arg("COUNT")
+                .arg(SCAN_BATCH_SIZE)
+                .query_async(conn)
+                .await
+                .map_err(|e| EngineError::execution_error(e.to_string()))?;
+
+            keys.extend(batch);
+            cursor = next;
+
+            if cursor == 0 || keys.len() >= limit {
+                break;
+            }
+        }
+
+        keys.truncate(limit);
+        Ok(keys)
+    }
+
+    /// Splits a command line into arguments, honoring single and double quotes
+    fn tokenize(command: &str) -> EngineResult<Vec<String>> {
+        let mut args = Vec::new();
+        let mut current = String::new();
+        let mut quote: Option<char> = None;
+        let mut in_token = false;
+
+        for c in command.trim().chars() {
+            match quote {
+                Some(q) if c == q => quote = None,
+                Some(_) => current.push(c),
+                None if c == '"' || c == '\'' => {
+                    quote = Some(c);
+                    in_token = true;
+                }
+                None if c.is_whitespace() => {
+                    if in_token {
+                        args.push(std::mem::take(&mut current));
+                        in_token = false;
+                    }
+                }
+                None => {
+                    current.push(c);
+                    in_token = true;
+                }
+            }
+        }
+
+        if quote.is_some() {
+            return Err(EngineError::syntax_error("Unterminated quoted argument"));
+        }
+        if in_token {
+            args.push(current);
+        }
+
+        Ok(args)
+    }
+
+    /// Converts a Redis reply to our universal Value type
+    fn redis_to_value(value: &redis::Value) -> Value {
+        match value {
+            redis::Value::Nil => Value::Null,
+            redis::Value::Int(i) => Value::Int(*i),
+            redis::Value::Double(f) => Value::Float(*f),
+            redis::Value::Boolean(b) => Value::Bool(*b),
+            redis::Value::Okay => Value::Text("OK".to_string()),
+            redis::Value::SimpleString(s) => Value::Text(s.clone()),
+            redis::Value::VerbatimString { text, .. } => Value::Text(text.clone()),
+            redis::Value::BulkString(bytes) => match String::from_utf8(bytes.clone()) {
+                Ok(s) => Value::Text(s),
+                Err(_) => Value::Bytes(bytes.clone()),
+            },
+            redis::Value::Array(items) | redis::Value::Set(items) => {
+                Value::Array(items.iter().map(Self::redis_to_value).collect())
+            }
+            redis::Value::Map(pairs) => Value::Array(
+                pairs
+                    .iter()
+                    .map(|(k, v)| Value::Array(vec![Self::redis_to_value(k), Self::redis_to_value(v)]))
+                    .collect(),
+            ),
+            other => Value::Text(format!("{:?}", other)),
+        }
+    }
+
+    fn text_column(name: &str) -> ColumnInfo {
+        ColumnInfo {
+            name: name.to_string(),
+            data_type: "string".to_string(),
+            nullable: true,
+            ..Default::default()
+        }
+    }
+
+    /// Shapes a command reply into rows
+    ///
+    /// Hash replies become field/value rows, other collections one row
+    /// per element, and scalars a single row.
+    fn reply_to_result(command: &str, reply: &redis::Value) -> (Vec<ColumnInfo>, Vec<QRow>) {
+        match reply {
+            redis::Value::Map(pairs) => (
+                vec![Self::text_column("field"), Self::text_column("value")],
+                pairs
+                    .iter()
+                    .map(|(k, v)| QRow {
+                        values: vec![Self::redis_to_value(k), Self::redis_to_value(v)],
+                    })
+                    .collect(),
+            ),
+            redis::Value::Array(items) if command == "HGETALL" => (
+                vec![Self::text_column("field"), Self::text_column("value")],
+                items
+                    .chunks(2)
+                    .map(|pair| QRow {
+                        values: pair.iter().map(Self::redis_to_value).collect(),
+                    })
+                    .collect(),
+            ),
+            redis::Value::Array(items) | redis::Value::Set(items) => (
+                vec![Self::text_column("value")],
+                items
+                    .iter()
+                    .map(|item| QRow {
+                        values: vec![Self::redis_to_value(item)],
+                    })
+                    .collect(),
+            ),
+            other => (
+                vec![Self::text_column("value")],
+                vec![QRow {
+                    values: vec![Self::redis_to_value(other)],
+                }],
+            ),
+        }
+    }
+}
+
+impl Default for RedisDriver {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl DataEngine for RedisDriver {
+    fn driver_id(&self) -> &'static str {
+        "redis"
+    }
+
+    fn driver_name(&self) -> &'static str {
+        "Redis"
+    }
+
+    fn default_port(&self) -> u16 {
+        6379
+    }
+
+    async fn test_connection(&self, config: &ConnectionConfig) -> EngineResult<()> {
+        let mut conn = Self::open_connection(config, 0).await?;
+
+        redis::cmd("PING")
+            .query_async::<String>(&mut conn)
+            .await
+            .map_err(|e| EngineError::connection_failed(e.to_string()))?;
+
+        Ok(())
+    }
+
+    async fn connect(&self, config: &ConnectionConfig) -> EngineResult<SessionId> {
+        let default_db = config
+            .database
+            .as_deref()
+            .and_then(|db| db.parse::<u32>().ok())
+            .unwrap_or(0);
+
+        let mut conn = Self::open_connection(config, default_db).await?;
+
+        redis::cmd("PING")
+            .query_async::<String>(&mut conn)
+            .await
+            .map_err(|e| EngineError::connection_failed(e.to_string()))?;
+
+        let session = RedisSession {
+            config: config.clone(),
+            default_db,
+            connections: Mutex::new(HashMap::from([(default_db, conn)])),
+        };
+
+        let session_id = SessionId::new();
+        let mut sessions = self.sessions.write().await;
+        sessions.insert(session_id, Arc::new(session));
+
+        Ok(session_id)
+    }
+
+    async fn disconnect(&self, session: SessionId) -> EngineResult<()> {
+        let mut sessions = self.sessions.write().await;
+
+        if sessions.remove(&session).is_some() {
+            Ok(())
+        } else {
+            Err(EngineError::session_not_found(session.0.to_string()))
+        }
+    }
+
+    async fn rebind_session(&self, from: SessionId, to: SessionId) -> EngineResult<()> {
+        let mut sessions = self.sessions.write().await;
+        let session = sessions
+            .remove(&from)
+            .ok_or_else(|| EngineError::session_not_found(from.0.to_string()))?;
+        if let Some(stale) = sessions.insert(to, session) {
+            sessions.insert(from, stale);
+        }
+        Ok(())
+    }
+
+    async fn ping(&self, session: SessionId) -> EngineResult<()> {
+        let redis_session = self.get_session(session).await?;
+        let mut conn = redis_session.connection(redis_session.default_db).await?;
+        redis::cmd("PING")
+            .query_async::<String>(&mut conn)
+            .await
+            .map_err(|e| EngineError::connection_failed(e.to_string()))?;
+        Ok(())
+    }
+
+    async fn list_namespaces(&self, session: SessionId) -> EngineResult<Vec<Namespace>> {
+        self.get_session(session).await?;
+
+        Ok((0..DATABASE_COUNT)
+            .map(|db| Namespace::new(db.to_string()))
+            .collect())
+    }
+
+    async fn list_collections(
+        &self,
+        session: SessionId,
+        namespace: &Namespace,
+    ) -> EngineResult<Vec<Collection>> {
+        let redis_session = self.get_session(session).await?;
+        let db = Self::parse_db(namespace)?;
+        let mut conn = redis_session.connection(db).await?;
+
+        let keys = Self::scan_keys(&mut conn, "*", MAX_SCANNED_KEYS).await?;
+
+        let prefixes: BTreeSet<String> = keys
+            .iter()
+            .map(|key| Self::key_prefix(key).to_string())
+            .collect();
+
+        Ok(prefixes
+            .into_iter()
+            .map(|name| Collection {
+                namespace: namespace.clone(),
+                name,
+                collection_type: CollectionType::Collection,
+            })
+            .collect())
+    }
+
+    async fn execute(
+        &self,
+        session: SessionId,
+        query: &str,
+        _query_id: QueryId,
+    ) -> EngineResult<QueryResult> {
+        let redis_session = self.get_session(session).await?;
+        let start = Instant::now();
+
+        let args = Self::tokenize(query)?;
+        let (name, rest) = args
+            .split_first()
+            .ok_or_else(|| EngineError::syntax_error("Empty command"))?;
+        let command = name.to_ascii_uppercase();
+
+        if !ALLOWED_COMMANDS.contains(&command.as_str()) {
+            return Err(EngineError::not_supported(format!(
+                "Redis command '{}' is not allowed. Only read commands are supported.",
+                command
+            )));
+        }
+
+        let mut conn = redis_session.connection(redis_session.default_db).await?;
+
+        let mut cmd = redis::cmd(&command);
+        for arg in rest {
+            cmd.arg(arg);
+        }
+
+        let reply: redis::Value = cmd
+            .query_async(&mut conn)
+            .await
+            .map_err(|e| EngineError::execution_error(e.to_string()))?;
+
+        let (columns, rows) = Self::reply_to_result(&command, &reply);
+
+        Ok(QueryResult {
+            columns,
+            rows,
+            affected_rows: None,
+            execution_time_ms: start.elapsed().as_micros() as f64 / 1000.0,
+            plan_estimate: None,
+            truncated: false,
+            estimated_memory_bytes: None,
+            truncated_values: Vec::new(),
+        })
+    }
+
+    async fn describe_table(
+        &self,
+        session: SessionId,
+        namespace: &Namespace,
+        _table: &str,
+    ) -> EngineResult<TableSchema> {
+        self.get_session(session).await?;
+        Self::parse_db(namespace)?;
+
+        let column = |name: &str, data_type: &str, nullable: bool| TableColumn {
+            name: name.to_string(),
+            data_type: data_type.to_string(),
+            nullable,
+            default_value: None,
+            is_primary_key: name == "key",
+            comment: None,
+            allowed_values: None,
+            charset: None,
+        };
+
+        Ok(TableSchema {
+            columns: vec![
+                column("key", "string", false),
+                column("type", "string", false),
+                column("ttl", "integer", true),
+            ],
+            primary_key: Some(vec!["key".to_string()]),
+            row_count_estimate: None,
+            comment: None,
+        })
+    }
+
+    async fn preview_table(
+        &self,
+        session: SessionId,
+        namespace: &Namespace,
+        table: &str,
+        limit: u32,
+        lock_rows: Option<RowLockMode>,
+    ) -> EngineResult<QueryResult> {
+        if lock_rows.is_some() {
+            return Err(EngineError::not_supported(
+                "Row locking is not supported by Redis",
+            ));
+        }
+
+        let redis_session = self.get_session(session).await?;
+        let db = Self::parse_db(namespace)?;
+        let mut conn = redis_session.connection(db).await?;
+        let start = Instant::now();
+
+        // A collection covers the bare key as well as every `prefix:*` key.
+        let mut keys = Vec::new();
+        let exists: bool = redis::cmd("EXISTS")
+            .arg(table)
+            .query_async(&mut conn)
+            .await
+            .map_err(|e| EngineError::execution_error(e.to_string()))?;
+        if exists {
+            keys.push(table.to_string());
+        }
+
+        let pattern = format!("{}{}*", Self::escape_pattern(table), KEY_DELIMITER);
+        let remaining = (limit as usize).saturating_sub(keys.len());
+        keys.extend(Self::scan_keys(&mut conn, &pattern, remaining).await?);
+        keys.sort();
+
+        let mut pipe = redis::pipe();
+        for key in &keys {
+            pipe.cmd("TYPE").arg(key).cmd("TTL").arg(key);
+        }
+        let details: Vec<(String, i64)> = if keys.is_empty() {
+            Vec::new()
+        } else {
+            pipe.query_async(&mut conn)
+                .await
+                .map_err(|e| EngineError::execution_error(e.to_string()))?
+        };
+
+        let rows = keys
+            .into_iter()
+            .zip(details)
+            .map(|(key, (key_type, ttl))| QRow {
+                values: vec![
+                    Value::Text(key),
+                    Value::Text(key_type),
+                    // -1 means the key has no expiry
+                    if ttl < 0 { Value::Null } else { Value::Int(ttl) },
+                ],
+            })
+            .collect();
+
+        Ok(QueryResult {
+            columns: vec![
+                Self::text_column("key"),
+                Self::text_column("type"),
+                ColumnInfo {
+                    name: "ttl".to_string(),
+                    data_type: "integer".to_string(),
+                    nullable: true,
+                    ..Default::default()
+                },
+            ],
+            rows,
+            affected_rows: None,
+            execution_time_ms: start.elapsed().as_micros() as f64 / 1000.0,
+            plan_estimate: None,
+            truncated: false,
+            estimated_memory_bytes: None,
+            truncated_values: Vec::new(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tokenizes_quoted_arguments() {
+        let args = RedisDriver::tokenize(r#"HGET "user:1 profile" 'display name'"#)
+            .expect("should tokenize");
+        assert_eq!(args, vec!["HGET", "user:1 profile", "display name"]);
+
+        assert!(RedisDriver::tokenize("GET \"unterminated").is_err());
+    }
+
+    #[test]
+    fn groups_keys_on_first_delimiter() {
+        assert_eq!(RedisDriver::key_prefix("user:1:profile"), "user");
+        assert_eq!(RedisDriver::key_prefix("counter"), "counter");
+        assert_eq!(RedisDriver::escape_pattern("a*b?"), "a\\*b\\?");
+    }
+}
@@ -4,8 +4,104 @@
 //! to provide consistent error handling across the application.
 
 use serde::{Deserialize, Serialize};
+use std::fmt;
 use thiserror::Error;
 
+/// Stable identifier of an error kind, for the frontend
+///
+/// Serialized in snake_case (`auth_failed`, `constraint_violation`, ...), so
+/// callers can react to a kind of error without matching on its message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EngineErrorCode {
+    ConnectionFailed,
+    AuthFailed,
+    SessionNotFound,
+    DriverNotFound,
+    ExecutionError,
+    SyntaxError,
+    TransactionError,
+    NotSupported,
+    Timeout,
+    SshError,
+    SslError,
+    ConstraintViolation,
+    PermissionDenied,
+    QueryCancelled,
+    /// The driver cannot cancel running queries (`CancelSupport::None`)
+    CancelNotSupported,
+    Deadlock,
+    SerializationFailure,
+    LockTimeout,
+    Internal,
+}
+
+impl EngineErrorCode {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::ConnectionFailed => "connection_failed",
+            Self::AuthFailed => "auth_failed",
+            Self::SessionNotFound => "session_not_found",
+            Self::DriverNotFound => "driver_not_found",
+            Self::ExecutionError => "execution_error",
+            Self::SyntaxError => "syntax_error",
+            Self::TransactionError => "transaction_error",
+            Self::NotSupported => "not_supported",
+            Self::Timeout => "timeout",
+            Self::SshError => "ssh_error",
+            Self::SslError => "ssl_error",
+            Self::ConstraintViolation => "constraint_violation",
+            Self::PermissionDenied => "permission_denied",
+            Self::QueryCancelled => "query_cancelled",
+            Self::CancelNotSupported => "cancel_not_supported",
+            Self::Deadlock => "deadlock",
+            Self::SerializationFailure => "serialization_failure",
+            Self::LockTimeout => "lock_timeout",
+            Self::Internal => "internal",
+        }
+    }
+}
+
+impl fmt::Display for EngineErrorCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Way a transaction lost a conflict with another one
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TransactionConflictKind {
+    Deadlock,
+    /// Concurrent update under `REPEATABLE READ` or `SERIALIZABLE`
+    SerializationFailure,
+    LockTimeout,
+}
+
+impl TransactionConflictKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Deadlock => "deadlock",
+            Self::SerializationFailure => "serialization_failure",
+            Self::LockTimeout => "lock_timeout",
+        }
+    }
+
+    fn description(self) -> &'static str {
+        match self {
+            Self::Deadlock => "Deadlock detected",
+            Self::SerializationFailure => "Serialization failure",
+            Self::LockTimeout => "Lock wait timeout",
+        }
+    }
+}
+
+impl fmt::Display for TransactionConflictKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
 /// Unified error type for all data engine operations
 #[derive(Debug, Error, Serialize, Deserialize)]
 pub enum EngineError {
@@ -47,6 +143,22 @@ pub enum EngineError {
 
     #[error("Transaction error: {message}")]
     TransactionError { message: String },
+
+    /// The transaction was rolled back, or its statement aborted, because
+    /// of a concurrent transaction
+    #[error("{}: {message}", kind.description())]
+    TransactionConflict {
+        kind: TransactionConflictKind,
+        /// Whether running the transaction again is likely to succeed
+        retry_recommended: bool,
+        message: String,
+    },
+
+    #[error("Constraint violation: {message}")]
+    ConstraintViolation { message: String },
+
+    #[error("Permission denied: {message}")]
+    PermissionDenied { message: String },
 }
 
 impl EngineError {
@@ -85,7 +197,189 @@ impl EngineError {
     pub fn transaction_error(msg: impl Into<String>) -> Self {
         Self::TransactionError { message: msg.into() }
     }
+
+    /// Retrying at once tends to deadlock again, so no retry is recommended
+    pub fn deadlock(msg: impl Into<String>) -> Self {
+        Self::TransactionConflict {
+            kind: TransactionConflictKind::Deadlock,
+            retry_recommended: false,
+            message: msg.into(),
+        }
+    }
+
+    /// The conflicting transaction has finished by the time this one is
+    /// told, so retrying is recommended
+    pub fn serialization_failure(msg: impl Into<String>) -> Self {
+        Self::TransactionConflict {
+            kind: TransactionConflictKind::SerializationFailure,
+            retry_recommended: true,
+            message: msg.into(),
+        }
+    }
+
+    pub fn lock_timeout(msg: impl Into<String>) -> Self {
+        Self::TransactionConflict {
+            kind: TransactionConflictKind::LockTimeout,
+            retry_recommended: false,
+            message: msg.into(),
+        }
+    }
+
+    pub fn constraint_violation(msg: impl Into<String>) -> Self {
+        Self::ConstraintViolation { message: msg.into() }
+    }
+
+    pub fn permission_denied(msg: impl Into<String>) -> Self {
+        Self::PermissionDenied { message: msg.into() }
+    }
+
+    /// Recognizes a lock conflict from the server's error code
+    ///
+    /// `code` is the SQLSTATE on PostgreSQL and the error number on MySQL.
+    pub fn from_lock_conflict(code: &str, msg: impl Into<String>) -> Option<Self> {
+        match code {
+            "40P01" | "1213" => Some(Self::deadlock(msg)),
+            "40001" => Some(Self::serialization_failure(msg)),
+            "55P03" | "1205" => Some(Self::lock_timeout(msg)),
+            _ => None,
+        }
+    }
+
+    /// Kind of transaction conflict this error reports, if any
+    pub fn conflict_kind(&self) -> Option<TransactionConflictKind> {
+        match self {
+            Self::TransactionConflict { kind, .. } => Some(*kind),
+            _ => None,
+        }
+    }
+
+    /// Recognizes an error from the server's error code
+    ///
+    /// `code` is the SQLSTATE on PostgreSQL and the error number on MySQL.
+    /// Covers lock conflicts, authentication and permission failures and
+    /// constraint violations; other codes return `None`.
+    pub fn from_server_code(code: &str, msg: impl Into<String>) -> Option<Self> {
+        let msg = msg.into();
+        match code {
+            "28P01" | "28000" | "1045" => Some(Self::auth_failed(msg)),
+            "42501" | "1044" | "1142" | "1143" | "1227" => Some(Self::permission_denied(msg)),
+            "1048" | "1062" | "1216" | "1217" | "1451" | "1452" | "3819" => {
+                Some(Self::constraint_violation(msg))
+            }
+            // SQLSTATE class 23: integrity constraint violation
+            _ if code.len() == 5 && code.starts_with("23") => {
+                Some(Self::constraint_violation(msg))
+            }
+            _ => Self::from_lock_conflict(code, msg),
+        }
+    }
+
+    /// Kind of the error, see `EngineErrorCode`
+    pub fn code(&self) -> EngineErrorCode {
+        match self {
+            Self::ConnectionFailed { .. } => EngineErrorCode::ConnectionFailed,
+            Self::AuthenticationFailed { .. } => EngineErrorCode::AuthFailed,
+            Self::SyntaxError { .. } => EngineErrorCode::SyntaxError,
+            Self::ExecutionError { .. } => EngineErrorCode::ExecutionError,
+            Self::Timeout { .. } => EngineErrorCode::Timeout,
+            Self::DriverNotFound { .. } => EngineErrorCode::DriverNotFound,
+            Self::SessionNotFound { .. } => EngineErrorCode::SessionNotFound,
+            Self::Cancelled => EngineErrorCode::QueryCancelled,
+            Self::SslError { .. } => EngineErrorCode::SslError,
+            Self::SshError { .. } => EngineErrorCode::SshError,
+            Self::Internal { .. } => EngineErrorCode::Internal,
+            Self::NotSupported { .. } => EngineErrorCode::NotSupported,
+            Self::TransactionError { .. } => EngineErrorCode::TransactionError,
+            Self::TransactionConflict { kind, .. } => match kind {
+                TransactionConflictKind::Deadlock => EngineErrorCode::Deadlock,
+                TransactionConflictKind::SerializationFailure => {
+                    EngineErrorCode::SerializationFailure
+                }
+                TransactionConflictKind::LockTimeout => EngineErrorCode::LockTimeout,
+            },
+            Self::ConstraintViolation { .. } => EngineErrorCode::ConstraintViolation,
+            Self::PermissionDenied { .. } => EngineErrorCode::PermissionDenied,
+        }
+    }
 }
 
 /// Result type alias for engine operations
 pub type EngineResult<T> = Result<T, EngineError>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classifies_lock_conflicts_by_code() {
+        let cases = [
+            ("40P01", TransactionConflictKind::Deadlock),
+            ("1213", TransactionConflictKind::Deadlock),
+            ("40001", TransactionConflictKind::SerializationFailure),
+            ("55P03", TransactionConflictKind::LockTimeout),
+            ("1205", TransactionConflictKind::LockTimeout),
+        ];
+        for (code, expected) in cases {
+            let error = EngineError::from_lock_conflict(code, "conflict").unwrap();
+            assert_eq!(error.conflict_kind(), Some(expected), "{}", code);
+            assert_eq!(error.code().as_str(), expected.as_str(), "{}", code);
+        }
+        assert!(EngineError::from_lock_conflict("23505", "conflict").is_none());
+    }
+
+    #[test]
+    fn recommends_retry_only_for_serialization_failures() {
+        let retry = |error: EngineError| match error {
+            EngineError::TransactionConflict {
+                retry_recommended, ..
+            } => retry_recommended,
+            _ => panic!("not a transaction conflict"),
+        };
+        assert!(retry(EngineError::serialization_failure("conflict")));
+        assert!(!retry(EngineError::deadlock("conflict")));
+        assert!(!retry(EngineError::lock_timeout("conflict")));
+        assert_eq!(
+            EngineError::serialization_failure("could not serialize access").to_string(),
+            "Serialization failure: could not serialize access"
+        );
+    }
+
+    #[test]
+    fn classifies_server_errors_by_code() {
+        let cases = [
+            ("28P01", EngineErrorCode::AuthFailed),
+            ("1045", EngineErrorCode::AuthFailed),
+            ("42501", EngineErrorCode::PermissionDenied),
+            ("1142", EngineErrorCode::PermissionDenied),
+            ("23505", EngineErrorCode::ConstraintViolation),
+            ("23503", EngineErrorCode::ConstraintViolation),
+            ("1062", EngineErrorCode::ConstraintViolation),
+            ("40P01", EngineErrorCode::Deadlock),
+        ];
+        for (code, expected) in cases {
+            let error = EngineError::from_server_code(code, "error").unwrap();
+            assert_eq!(error.code(), expected, "{}", code);
+        }
+        assert!(EngineError::from_server_code("42601", "error").is_none());
+    }
+
+    #[test]
+    fn codes_serialize_in_snake_case() {
+        assert_eq!(
+            serde_json::to_string(&EngineErrorCode::AuthFailed).unwrap(),
+            "\"auth_failed\""
+        );
+        assert_eq!(EngineError::Cancelled.code().to_string(), "query_cancelled");
+        assert_eq!(
+            serde_json::to_string(&EngineErrorCode::CancelNotSupported).unwrap(),
+            format!("\"{}\"", EngineErrorCode::CancelNotSupported)
+        );
+    }
+
+    #[test]
+    fn other_codes_are_not_lock_conflicts() {
+        for code in ["42601", "23505", "1062", ""] {
+            assert!(EngineError::from_lock_conflict(code, "error").is_none());
+        }
+    }
+}
@@ -0,0 +1,342 @@
+//! Result Export
+//!
+//! Writers that turn a `QueryResult` into files for downstream tooling.
+//! Rows are converted and written in fixed-size batches so memory stays
+//! bounded by the batch, not by the size of the export.
+
+use std::collections::HashMap;
+use std::io::Write;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+use arrow_array::builder::{
+    BinaryBuilder, BooleanBuilder, Float64Builder, Int64Builder, StringBuilder,
+};
+use arrow_array::{ArrayRef, RecordBatch};
+use arrow_schema::{DataType, Field, Schema};
+use parquet::arrow::ArrowWriter;
+use parquet::basic::Compression;
+use parquet::file::properties::WriterProperties;
+
+use crate::engine::error::{EngineError, EngineResult};
+use crate::engine::types::{ColumnInfo, QueryResult, Row, Value};
+
+/// Rows converted per Arrow record batch
+pub const PARQUET_BATCH_SIZE: usize = 8192;
+
+/// Cancellation flags for running exports, keyed by export ID
+#[derive(Default)]
+pub struct ExportManager {
+    running: Mutex<HashMap<String, Arc<AtomicBool>>>,
+}
+
+impl ExportManager {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers an export and returns the flag it should poll
+    pub fn register(&self, export_id: &str) -> Arc<AtomicBool> {
+        let flag = Arc::new(AtomicBool::new(false));
+        let mut running = self.running.lock().unwrap_or_else(|e| e.into_inner());
+        running.insert(export_id.to_string(), Arc::clone(&flag));
+        flag
+    }
+
+    /// Requests cancellation; returns false if the export is not running
+    pub fn cancel(&self, export_id: &str) -> bool {
+        let running = self.running.lock().unwrap_or_else(|e| e.into_inner());
+        match running.get(export_id) {
+            Some(flag) => {
+                flag.store(true, Ordering::SeqCst);
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn finish(&self, export_id: &str) {
+        let mut running = self.running.lock().unwrap_or_else(|e| e.into_inner());
+        running.remove(export_id);
+    }
+}
+
+/// Writes a query result as a Snappy-compressed Parquet file
+///
+/// The Arrow schema is inferred per column from the values (falling back to
+/// the column's type name when every value is NULL). Columns mixing integers
+/// and floats become `Float64`; any other mix is written as text.
+pub fn to_parquet<W: Write + Send>(
+    result: &QueryResult,
+    writer: W,
+    batch_size: usize,
+    cancelled: &AtomicBool,
+) -> EngineResult<()> {
+    let column_types: Vec<DataType> = result
+        .columns
+        .iter()
+        .enumerate()
+        .map(|(idx, column)| infer_arrow_type(column, &result.rows, idx))
+        .collect();
+
+    let fields: Vec<Field> = result
+        .columns
+        .iter()
+        .zip(&column_types)
+        .enumerate()
+        .map(|(idx, (column, data_type))| {
+            let has_null = result
+                .rows
+                .iter()
+                .any(|row| matches!(cell(row, idx), Value::Null));
+            Field::new(&column.name, data_type.clone(), column.nullable || has_null)
+        })
+        .collect();
+    let schema = Arc::new(Schema::new(fields));
+
+    let props = WriterProperties::builder()
+        .set_compression(Compression::SNAPPY)
+        .build();
+    let mut arrow_writer = ArrowWriter::try_new(writer, Arc::clone(&schema), Some(props))
+        .map_err(|e| EngineError::internal(e.to_string()))?;
+
+    for chunk in result.rows.chunks(batch_size.max(1)) {
+        if cancelled.load(Ordering::SeqCst) {
+            return Err(EngineError::Cancelled);
+        }
+
+        let arrays: Vec<ArrayRef> = column_types
+            .iter()
+            .enumerate()
+            .map(|(idx, data_type)| build_array(data_type, chunk, idx))
+            .collect();
+        let batch = RecordBatch::try_new(Arc::clone(&schema), arrays)
+            .map_err(|e| EngineError::internal(e.to_string()))?;
+
+        arrow_writer
+            .write(&batch)
+            .map_err(|e| EngineError::internal(e.to_string()))?;
+    }
+
+    arrow_writer
+        .close()
+        .map_err(|e| EngineError::internal(e.to_string()))?;
+    Ok(())
+}
+
+fn cell(row: &Row, idx: usize) -> &Value {
+    row.values.get(idx).unwrap_or(&Value::Null)
+}
+
+fn infer_arrow_type(column: &ColumnInfo, rows: &[Row], idx: usize) -> DataType {
+    let mut inferred: Option<DataType> = None;
+
+    for row in rows {
+        let value_type = match cell(row, idx) {
+            Value::Null => continue,
+            Value::Bool(_) => DataType::Boolean,
+            Value::Int(_) => DataType::Int64,
+            Value::Float(_) => DataType::Float64,
+            Value::Bytes(_) => DataType::Binary,
+            Value::Text(_)
+            | Value::Timestamp(_)
+            | Value::Uuid(_)
+            | Value::Json(_)
+            | Value::Array(_) => DataType::Utf8,
+        };
+
+        inferred = Some(match inferred {
+            None => value_type,
+            Some(current) if current == value_type => current,
+            Some(DataType::Int64) if value_type == DataType::Float64 => DataType::Float64,
+            Some(DataType::Float64) if value_type == DataType::Int64 => DataType::Float64,
+            Some(_) => return DataType::Utf8,
+        });
+    }
+
+    inferred.unwrap_or_else(|| arrow_type_for_name(&column.data_type))
+}
+
+/// Maps a database type name to an Arrow type, for all-NULL columns
+fn arrow_type_for_name(data_type: &str) -> DataType {
+    let upper = data_type.to_uppercase();
+    match upper.trim_end_matches(" UNSIGNED") {
+        "BOOL" | "BOOLEAN" => DataType::Boolean,
+        "TINYINT" | "SMALLINT" | "MEDIUMINT" | "INT" | "INTEGER" | "BIGINT" | "INT2"
+        | "INT4" | "INT8" => DataType::Int64,
+        "FLOAT" | "FLOAT4" | "FLOAT8" | "REAL" | "DOUBLE" => DataType::Float64,
+        "BYTEA" | "BLOB" | "TINYBLOB" | "MEDIUMBLOB" | "LONGBLOB" | "BINARY" | "VARBINARY" => {
+            DataType::Binary
+        }
+        _ => DataType::Utf8,
+    }
+}
+
+fn build_array(data_type: &DataType, rows: &[Row], idx: usize) -> ArrayRef {
+    match data_type {
+        DataType::Boolean => {
+            let mut builder = BooleanBuilder::with_capacity(rows.len());
+            for row in rows {
+                builder.append_option(match cell(row, idx) {
+                    Value::Bool(b) => Some(*b),
+                    _ => None,
+                });
+            }
+            Arc::new(builder.finish())
+        }
+        DataType::Int64 => {
+            let mut builder = Int64Builder::with_capacity(rows.len());
+            for row in rows {
+                builder.append_option(match cell(row, idx) {
+                    Value::Int(i) => Some(*i),
+                    _ => None,
+                });
+            }
+            Arc::new(builder.finish())
+        }
+        DataType::Float64 => {
+            let mut builder = Float64Builder::with_capacity(rows.len());
+            for row in rows {
+                builder.append_option(match cell(row, idx) {
+                    Value::Float(f) => Some(*f),
+                    Value::Int(i) => Some(*i as f64),
+                    _ => None,
+                });
+            }
+            Arc::new(builder.finish())
+        }
+        DataType::Binary => {
+            let mut builder = BinaryBuilder::new();
+            for row in rows {
+                match cell(row, idx) {
+                    Value::Bytes(bytes) => builder.append_value(bytes),
+                    _ => builder.append_null(),
+                }
+            }
+            Arc::new(builder.finish())
+        }
+        _ => {
+            let mut builder = StringBuilder::new();
+            for row in rows {
+                builder.append_option(value_to_text(cell(row, idx)));
+            }
+            Arc::new(builder.finish())
+        }
+    }
+}
+
+fn value_to_text(value: &Value) -> Option<String> {
+    match value {
+        Value::Null => None,
+        Value::Bool(b) => Some(b.to_string()),
+        Value::Int(i) => Some(i.to_string()),
+        Value::Float(f) => Some(f.to_string()),
+        Value::Text(s) => Some(s.clone()),
+        Value::Timestamp(ts) => Some(ts.to_string()),
+        Value::Uuid(uuid) => Some(uuid.to_string()),
+        Value::Json(json) => Some(json.to_string()),
+        Value::Bytes(_) | Value::Array(_) => serde_json::to_string(value).ok(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow_array::{Array, Float64Array, Int64Array, StringArray};
+    use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+
+    fn column(name: &str, data_type: &str) -> ColumnInfo {
+        ColumnInfo {
+            name: name.to_string(),
+            data_type: data_type.to_string(),
+            nullable: true,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn parquet_round_trip_preserves_values_and_nulls() {
+        let result = QueryResult {
+            columns: vec![
+                column("id", "INT8"),
+                column("score", "NUMERIC"),
+                column("name", "TEXT"),
+                column("missing", "INT4"),
+            ],
+            rows: (0..5)
+                .map(|i| Row {
+                    values: vec![
+                        Value::Int(i),
+                        if i % 2 == 0 {
+                            Value::Int(i)
+                        } else {
+                            Value::Float(i as f64 + 0.5)
+                        },
+                        if i == 3 {
+                            Value::Null
+                        } else {
+                            Value::Text(format!("row {}", i))
+                        },
+                        Value::Null,
+                    ],
+                })
+                .collect(),
+            affected_rows: None,
+            execution_time_ms: 0.0,
+            plan_estimate: None,
+            truncated: false,
+            estimated_memory_bytes: None,
+            truncated_values: Vec::new(),
+        };
+
+        let path = std::env::temp_dir()
+            .join(format!("qoredb-export-{}.parquet", uuid::Uuid::new_v4()));
+        let file = std::fs::File::create(&path).expect("create file");
+        to_parquet(&result, file, 2, &AtomicBool::new(false)).expect("write parquet");
+
+        let reader = ParquetRecordBatchReaderBuilder::try_new(
+            std::fs::File::open(&path).expect("open file"),
+        )
+        .expect("read metadata")
+        .build()
+        .expect("build reader");
+        let batches: Vec<RecordBatch> = reader.map(|batch| batch.expect("batch")).collect();
+        std::fs::remove_file(&path).ok();
+
+        let schema = batches[0].schema();
+        assert_eq!(schema.field(0).data_type(), &DataType::Int64);
+        assert_eq!(schema.field(1).data_type(), &DataType::Float64);
+        assert_eq!(schema.field(2).data_type(), &DataType::Utf8);
+        assert_eq!(schema.field(3).data_type(), &DataType::Int64);
+        assert_eq!(batches.len(), 1);
+
+        let batch = &batches[0];
+        assert_eq!(batch.num_rows(), 5);
+        let ids = batch.column(0).as_any().downcast_ref::<Int64Array>().unwrap();
+        assert_eq!(ids.value(4), 4);
+        let scores = batch.column(1).as_any().downcast_ref::<Float64Array>().unwrap();
+        assert_eq!(scores.value(1), 1.5);
+        assert_eq!(scores.value(2), 2.0);
+        let names = batch.column(2).as_any().downcast_ref::<StringArray>().unwrap();
+        assert_eq!(names.value(2), "row 2");
+        assert!(names.is_null(3));
+        assert_eq!(batch.column(3).null_count(), 5);
+    }
+
+    #[test]
+    fn cancelled_export_stops_before_writing() {
+        let result = QueryResult {
+            columns: vec![column("id", "INT8")],
+            rows: vec![Row { values: vec![Value::Int(1)] }],
+            affected_rows: None,
+            execution_time_ms: 0.0,
+            plan_estimate: None,
+            truncated: false,
+            estimated_memory_bytes: None,
+            truncated_values: Vec::new(),
+        };
+
+        let outcome = to_parquet(&result, Vec::new(), 10, &AtomicBool::new(true));
+        assert!(matches!(outcome, Err(EngineError::Cancelled)));
+    }
+}
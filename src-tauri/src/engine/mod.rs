@@ -3,18 +3,26 @@
 
 pub mod drivers;
 pub mod error;
+pub mod export;
+pub mod named_params;
+pub mod plugin;
+pub mod query_history;
 pub mod query_manager;
 pub mod registry;
 pub mod sql_safety;
 pub mod session_manager;
+pub mod snippets;
 pub mod ssh_tunnel;
 pub mod traits;
 pub mod types;
 
 pub use error::EngineError;
+pub use query_history::QueryHistory;
 pub use query_manager::QueryManager;
-pub use registry::DriverRegistry;
-pub use session_manager::SessionManager;
+pub use registry::{DriverInfo, DriverRegistry};
+pub use session_manager::{
+    ColumnTypes, RetryAttempt, SessionCounters, SessionDisplay, SessionManager,
+};
 pub use traits::DataEngine;
 pub use types::*;
 
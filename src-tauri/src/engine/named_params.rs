@@ -0,0 +1,211 @@
+//! Named query parameters
+//!
+//! Rewrites `:name` placeholders into the driver's positional placeholders
+//! (`$1` for PostgreSQL-compatible drivers, `?` otherwise) and orders the
+//! parameter values to match.
+//!
+//! Placeholders inside string literals, quoted identifiers and comments are
+//! left alone, as are PostgreSQL `::` casts and MySQL `:=` assignments.
+
+use std::collections::HashMap;
+
+use crate::engine::types::Value;
+
+/// Returns true if the driver uses numbered `$n` placeholders
+fn uses_numbered_placeholders(driver_id: &str) -> bool {
+    driver_id.eq_ignore_ascii_case("postgres") || driver_id.eq_ignore_ascii_case("cockroachdb")
+}
+
+fn is_name_start(c: char) -> bool {
+    c.is_ascii_alphabetic() || c == '_'
+}
+
+fn is_name_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_'
+}
+
+/// Rewrites `:name` placeholders and returns the positional parameter list
+///
+/// With numbered placeholders a name used several times maps to a single
+/// `$n`; with `?` placeholders its value is repeated for every occurrence.
+/// Fails if a placeholder has no value in `params`; unused values are ignored.
+pub fn rewrite_named_params(
+    driver_id: &str,
+    sql: &str,
+    params: &HashMap<String, Value>,
+) -> Result<(String, Vec<Value>), String> {
+    let numbered = uses_numbered_placeholders(driver_id);
+    let chars: Vec<char> = sql.chars().collect();
+
+    let mut rewritten = String::with_capacity(sql.len());
+    let mut values: Vec<Value> = Vec::new();
+    let mut positions: HashMap<String, usize> = HashMap::new();
+
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        let next = chars.get(i + 1).copied();
+
+        let skip_to = match c {
+            '\'' | '"' | '`' => Some(skip_quoted(&chars, i, c)),
+            '-' if next == Some('-') => Some(skip_until(&chars, i, "\n")),
+            '#' if !numbered => Some(skip_until(&chars, i, "\n")),
+            '/' if next == Some('*') => Some(skip_until(&chars, i, "*/")),
+            '$' if numbered => skip_dollar_quoted(&chars, i),
+            // `::` cast: copy both colons so the second is not read as a placeholder
+            ':' if next == Some(':') => Some(i + 2),
+            _ => None,
+        };
+        if let Some(end) = skip_to {
+            rewritten.extend(&chars[i..end]);
+            i = end;
+            continue;
+        }
+
+        if c == ':' && next.is_some_and(is_name_start) {
+            let start = i + 1;
+            let mut end = start;
+            while end < chars.len() && is_name_char(chars[end]) {
+                end += 1;
+            }
+            let name: String = chars[start..end].iter().collect();
+            let value = params
+                .get(&name)
+                .ok_or_else(|| format!("Missing value for parameter :{}", name))?;
+
+            if numbered {
+                let position = match positions.get(&name) {
+                    Some(position) => *position,
+                    None => {
+                        values.push(value.clone());
+                        positions.insert(name, values.len());
+                        values.len()
+                    }
+                };
+                rewritten.push_str(&format!("${}", position));
+            } else {
+                values.push(value.clone());
+                rewritten.push('?');
+            }
+            i = end;
+            continue;
+        }
+
+        rewritten.push(c);
+        i += 1;
+    }
+
+    Ok((rewritten, values))
+}
+
+/// Returns the index after a quoted section starting at `start`
+///
+/// Handles doubled quotes and backslash escapes; an unterminated section
+/// runs to the end of the query.
+fn skip_quoted(chars: &[char], start: usize, quote: char) -> usize {
+    let mut i = start + 1;
+    while i < chars.len() {
+        if chars[i] == '\\' && quote == '\'' {
+            i += 2;
+            continue;
+        }
+        if chars[i] == quote {
+            if chars.get(i + 1) == Some(&quote) {
+                i += 2;
+                continue;
+            }
+            return i + 1;
+        }
+        i += 1;
+    }
+    chars.len()
+}
+
+/// Returns the index after the first `terminator` found after `start`
+fn skip_until(chars: &[char], start: usize, terminator: &str) -> usize {
+    let terminator: Vec<char> = terminator.chars().collect();
+    let mut i = start + 2;
+    while i + terminator.len() <= chars.len() {
+        if chars[i..i + terminator.len()] == terminator[..] {
+            return i + terminator.len();
+        }
+        i += 1;
+    }
+    chars.len()
+}
+
+/// Skips a PostgreSQL dollar-quoted string (`$$...$$`, `$tag$...$tag$`)
+///
+/// Returns `None` when `$` does not open one (e.g. a `$1` placeholder).
+fn skip_dollar_quoted(chars: &[char], start: usize) -> Option<usize> {
+    let mut tag_end = start + 1;
+    while tag_end < chars.len() && is_name_char(chars[tag_end]) {
+        tag_end += 1;
+    }
+    if chars.get(tag_end) != Some(&'$')
+        || chars.get(start + 1).is_some_and(|c| c.is_ascii_digit())
+    {
+        return None;
+    }
+
+    let tag = &chars[start..=tag_end];
+    let mut i = tag_end + 1;
+    while i + tag.len() <= chars.len() {
+        if &chars[i..i + tag.len()] == tag {
+            return Some(i + tag.len());
+        }
+        i += 1;
+    }
+    Some(chars.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn params() -> HashMap<String, Value> {
+        HashMap::from([
+            ("user_id".to_string(), Value::Int(7)),
+            ("status".to_string(), Value::Text("active".to_string())),
+        ])
+    }
+
+    #[test]
+    fn rewrites_to_numbered_placeholders() {
+        let (sql, values) = rewrite_named_params(
+            "postgres",
+            "SELECT id::text, ':skip' FROM t WHERE id = :user_id AND status = :status OR owner = :user_id",
+            &params(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            sql,
+            "SELECT id::text, ':skip' FROM t WHERE id = $1 AND status = $2 OR owner = $1"
+        );
+        assert_eq!(values.len(), 2);
+        assert!(matches!(values[0], Value::Int(7)));
+    }
+
+    #[test]
+    fn rewrites_to_question_marks() {
+        let (sql, values) = rewrite_named_params(
+            "mysql",
+            "SELECT @n := 1, `a:b` FROM t -- :ignored\nWHERE id = :user_id OR owner = :user_id",
+            &params(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            sql,
+            "SELECT @n := 1, `a:b` FROM t -- :ignored\nWHERE id = ? OR owner = ?"
+        );
+        assert_eq!(values.len(), 2);
+    }
+
+    #[test]
+    fn rejects_missing_parameters() {
+        let error = rewrite_named_params("postgres", "SELECT :missing", &params()).unwrap_err();
+        assert!(error.contains(":missing"));
+    }
+}
@@ -0,0 +1,53 @@
+//! Driver Plugin ABI
+//!
+//! A driver plugin is a dynamic library (`.so`, `.dylib`, `.dll`) exporting
+//! two symbols, loaded by `DriverRegistry::load_plugin`:
+//!
+//! - `qoredb_plugin_abi_version() -> u32`, which must return
+//!   `QOREDB_PLUGIN_ABI_VERSION`;
+//! - `create_data_engine() -> Box<dyn DataEngine>`, which builds the driver.
+//!
+//! `Box<dyn DataEngine>` has no stable layout, so plugins must be built with
+//! the same compiler and QoreDB version as the application. The ABI version
+//! only catches changes to the `DataEngine` trait itself.
+//!
+//! Plugins should export both symbols with `declare_driver_plugin!`.
+
+use crate::engine::traits::DataEngine;
+
+/// Bumped whenever `DataEngine` or the plugin symbols change
+pub const QOREDB_PLUGIN_ABI_VERSION: u32 = 15;
+
+/// Name of the symbol returning the plugin's ABI version
+pub const ABI_VERSION_SYMBOL: &[u8] = b"qoredb_plugin_abi_version";
+
+/// Name of the symbol creating the driver
+pub const CREATE_ENGINE_SYMBOL: &[u8] = b"create_data_engine";
+
+/// Signature of `qoredb_plugin_abi_version`
+pub type AbiVersionFn = unsafe extern "C" fn() -> u32;
+
+/// Signature of `create_data_engine`
+#[allow(improper_ctypes_definitions)]
+pub type CreateDataEngineFn = unsafe extern "C" fn() -> Box<dyn DataEngine>;
+
+/// Exports the plugin symbols for a driver constructor
+///
+/// ```ignore
+/// qoredb::declare_driver_plugin!(MyDriver::new);
+/// ```
+#[macro_export]
+macro_rules! declare_driver_plugin {
+    ($constructor:path) => {
+        #[no_mangle]
+        pub extern "C" fn qoredb_plugin_abi_version() -> u32 {
+            $crate::engine::plugin::QOREDB_PLUGIN_ABI_VERSION
+        }
+
+        #[no_mangle]
+        #[allow(improper_ctypes_definitions)]
+        pub extern "C" fn create_data_engine() -> Box<dyn $crate::engine::traits::DataEngine> {
+            Box::new($constructor())
+        }
+    };
+}
@@ -0,0 +1,157 @@
+//! Query History
+//!
+//! Keeps recent execution times of successful queries, grouped by query
+//! fingerprint, to suggest timeouts that fit how long a query usually takes.
+//! History is in-memory only and shared by all sessions.
+
+use std::collections::{HashMap, VecDeque};
+
+use tokio::sync::RwLock;
+
+/// Execution times kept per fingerprint (oldest are dropped first)
+const MAX_SAMPLES: usize = 100;
+
+/// Samples needed before a timeout is suggested
+const MIN_SAMPLES: usize = 3;
+
+pub struct QueryHistory {
+    samples: RwLock<HashMap<String, VecDeque<f64>>>,
+}
+
+impl QueryHistory {
+    pub fn new() -> Self {
+        Self {
+            samples: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Records the execution time of a successful query
+    pub async fn record(&self, query_fingerprint: String, execution_time_ms: f64) {
+        let mut samples = self.samples.write().await;
+        let entry = samples.entry(query_fingerprint).or_default();
+        if entry.len() == MAX_SAMPLES {
+            entry.pop_front();
+        }
+        entry.push_back(execution_time_ms);
+    }
+
+    /// Number of recorded executions for a fingerprint
+    pub async fn sample_count(&self, query_fingerprint: &str) -> u32 {
+        let samples = self.samples.read().await;
+        samples
+            .get(query_fingerprint)
+            .map(|entry| entry.len() as u32)
+            .unwrap_or(0)
+    }
+
+    /// Suggests a timeout in ms: p95 execution time × 1.5, rounded up to the
+    /// next second. `None` until enough executions were recorded.
+    pub async fn suggest_timeout(&self, query_fingerprint: &str) -> Option<u64> {
+        let samples = self.samples.read().await;
+        let entry = samples.get(query_fingerprint)?;
+        if entry.len() < MIN_SAMPLES {
+            return None;
+        }
+
+        let mut sorted: Vec<f64> = entry.iter().copied().collect();
+        sorted.sort_by(|a, b| a.total_cmp(b));
+        // Nearest-rank percentile
+        let rank = (sorted.len() as f64 * 0.95).ceil() as usize;
+        let p95 = sorted[rank.saturating_sub(1)];
+
+        let seconds = (p95 * 1.5 / 1000.0).ceil().max(1.0);
+        Some(seconds as u64 * 1000)
+    }
+}
+
+impl Default for QueryHistory {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Normalizes a query so executions with different literal values share a
+/// fingerprint: string and number literals become `?`, whitespace is
+/// collapsed and the text is lowercased.
+pub fn fingerprint(query: &str) -> String {
+    let mut normalized = String::with_capacity(query.len());
+    let mut chars = query.trim().trim_end_matches(';').chars().peekable();
+    let mut previous: Option<char> = None;
+
+    while let Some(c) = chars.next() {
+        if c == '\'' {
+            while let Some(c) = chars.next() {
+                match c {
+                    '\\' => {
+                        chars.next();
+                    }
+                    '\'' if chars.peek() == Some(&'\'') => {
+                        chars.next();
+                    }
+                    '\'' => break,
+                    _ => {}
+                }
+            }
+            normalized.push('?');
+            previous = Some('?');
+            continue;
+        }
+
+        let in_identifier = previous.is_some_and(|p| p.is_alphanumeric() || p == '_' || p == '$');
+        if c.is_ascii_digit() && !in_identifier {
+            while chars
+                .peek()
+                .is_some_and(|next| next.is_ascii_alphanumeric() || *next == '.')
+            {
+                chars.next();
+            }
+            normalized.push('?');
+            previous = Some('?');
+            continue;
+        }
+
+        if c.is_whitespace() {
+            if previous.is_some_and(|p| p != ' ') {
+                normalized.push(' ');
+                previous = Some(' ');
+            }
+            continue;
+        }
+
+        normalized.extend(c.to_lowercase());
+        previous = Some(c);
+    }
+
+    normalized.trim_end().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fingerprints_ignore_literals_and_whitespace() {
+        assert_eq!(
+            fingerprint("SELECT *\n  FROM  t2 WHERE id = 42 AND name = 'O''Brien';"),
+            "select * from t2 where id = ? and name = ?"
+        );
+        assert_eq!(
+            fingerprint("select * from t2 where id = 7 and name = 'x'"),
+            fingerprint("SELECT * FROM t2 WHERE id = 1.5 AND name = ''")
+        );
+    }
+
+    #[tokio::test]
+    async fn suggests_timeout_from_p95() {
+        let history = QueryHistory::new();
+        let key = "select ?".to_string();
+        assert_eq!(history.suggest_timeout(&key).await, None);
+
+        for ms in [100.0, 200.0, 2400.0] {
+            history.record(key.clone(), ms).await;
+        }
+        // p95 = 2400ms, × 1.5 = 3600ms, rounded up to 4s
+        assert_eq!(history.suggest_timeout(&key).await, Some(4000));
+        assert_eq!(history.sample_count(&key).await, 3);
+    }
+}
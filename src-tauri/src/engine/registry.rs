@@ -3,50 +3,170 @@
 //! Central registry for all available database drivers.
 //! Provides plugin-like architecture for adding new drivers.
 
-use std::collections::HashMap;
-use std::sync::Arc;
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use std::sync::{Arc, Mutex, RwLock};
 
+use serde::Serialize;
+
+use crate::engine::error::{EngineError, EngineResult};
+use crate::engine::plugin::{
+    AbiVersionFn, CreateDataEngineFn, ABI_VERSION_SYMBOL, CREATE_ENGINE_SYMBOL,
+    QOREDB_PLUGIN_ABI_VERSION,
+};
 use crate::engine::traits::DataEngine;
+use crate::engine::types::DriverCapabilities;
+
+/// Summary of a registered driver, used to adapt the connection form
+#[derive(Debug, Clone, Serialize)]
+pub struct DriverInfo {
+    pub driver_id: String,
+    pub driver_name: String,
+    /// False for drivers loaded from a plugin
+    pub is_builtin: bool,
+    pub default_port: u16,
+    /// Whether namespaces are schemas (PostgreSQL) rather than databases
+    pub supports_schemas: bool,
+    pub supports_multiple_databases: bool,
+    pub requires_database: bool,
+    pub capabilities: DriverCapabilities,
+}
 
 /// Registry that holds all available database drivers
 pub struct DriverRegistry {
-    drivers: HashMap<String, Arc<dyn DataEngine>>,
+    drivers: RwLock<HashMap<String, Arc<dyn DataEngine>>>,
+    /// Alternative IDs resolving to a registered driver (alias -> driver ID)
+    aliases: RwLock<HashMap<String, String>>,
+    /// IDs of drivers loaded through `load_plugin`
+    plugin_ids: RwLock<HashSet<String>>,
+    /// Plugin libraries are never unloaded: their code backs the drivers above
+    libraries: Mutex<Vec<libloading::Library>>,
 }
 
 impl DriverRegistry {
     /// Creates a new empty registry
     pub fn new() -> Self {
         Self {
-            drivers: HashMap::new(),
+            drivers: RwLock::new(HashMap::new()),
+            aliases: RwLock::new(HashMap::new()),
+            plugin_ids: RwLock::new(HashSet::new()),
+            libraries: Mutex::new(Vec::new()),
         }
     }
 
     /// Registers a new driver
     ///
     /// The driver's `driver_id()` is used as the key.
-    pub fn register(&mut self, driver: Arc<dyn DataEngine>) {
+    pub fn register(&self, driver: Arc<dyn DataEngine>) {
         let id = driver.driver_id().to_string();
-        self.drivers.insert(id, driver);
+        self.drivers.write().unwrap().insert(id, driver);
     }
 
-    /// Gets a driver by its ID
+    /// Registers `alias` as another ID for the driver `driver_id`
+    ///
+    /// Aliases are only used by `get`; registered drivers take precedence.
+    pub fn register_alias(&self, alias: &str, driver_id: &str) {
+        self.aliases
+            .write()
+            .unwrap()
+            .insert(alias.to_string(), driver_id.to_string());
+    }
+
+    /// Loads a driver plugin from a dynamic library and registers its driver
+    ///
+    /// See `engine::plugin` for the symbols the library must export. Plugins
+    /// cannot replace an already registered driver.
+    pub fn load_plugin(&self, path: &Path) -> EngineResult<()> {
+        let load_error =
+            |e: libloading::Error| EngineError::internal(format!("Failed to load plugin: {}", e));
+
+        // SAFETY: loading runs the library's initializers; plugins are
+        // trusted code chosen by the user.
+        let library = unsafe { libloading::Library::new(path) }.map_err(load_error)?;
+
+        // SAFETY: the symbol types match the ABI documented in `engine::plugin`
+        let version = unsafe {
+            let abi_version = library
+                .get::<AbiVersionFn>(ABI_VERSION_SYMBOL)
+                .map_err(load_error)?;
+            abi_version()
+        };
+        if version != QOREDB_PLUGIN_ABI_VERSION {
+            return Err(EngineError::internal(format!(
+                "Plugin ABI version {} does not match QoreDB ({})",
+                version, QOREDB_PLUGIN_ABI_VERSION
+            )));
+        }
+
+        // SAFETY: same as above; the ABI version has been checked
+        let driver: Arc<dyn DataEngine> = unsafe {
+            let create = library
+                .get::<CreateDataEngineFn>(CREATE_ENGINE_SYMBOL)
+                .map_err(load_error)?;
+            Arc::from(create())
+        };
+
+        let id = driver.driver_id().to_string();
+        {
+            let mut drivers = self.drivers.write().unwrap();
+            if drivers.contains_key(&id) {
+                return Err(EngineError::internal(format!(
+                    "A driver with ID '{}' is already registered",
+                    id
+                )));
+            }
+            drivers.insert(id.clone(), driver);
+        }
+        self.plugin_ids.write().unwrap().insert(id);
+        self.libraries.lock().unwrap().push(library);
+        Ok(())
+    }
+
+    /// Gets a driver by its ID or one of its aliases
     pub fn get(&self, driver_id: &str) -> Option<Arc<dyn DataEngine>> {
-        self.drivers.get(driver_id).cloned()
+        let drivers = self.drivers.read().unwrap();
+        if let Some(driver) = drivers.get(driver_id) {
+            return Some(Arc::clone(driver));
+        }
+        let aliases = self.aliases.read().unwrap();
+        aliases.get(driver_id).and_then(|id| drivers.get(id)).cloned()
     }
 
     /// Lists all registered driver IDs
-    pub fn list(&self) -> Vec<&str> {
-        self.drivers.keys().map(|s| s.as_str()).collect()
+    pub fn list(&self) -> Vec<String> {
+        self.drivers.read().unwrap().keys().cloned().collect()
+    }
+
+    /// Describes all registered drivers, sorted by ID
+    pub fn list_drivers_info(&self) -> Vec<DriverInfo> {
+        let drivers = self.drivers.read().unwrap();
+        let plugin_ids = self.plugin_ids.read().unwrap();
+
+        let mut infos: Vec<DriverInfo> = drivers
+            .iter()
+            .map(|(id, driver)| DriverInfo {
+                driver_id: id.clone(),
+                driver_name: driver.driver_name().to_string(),
+                is_builtin: !plugin_ids.contains(id),
+                default_port: driver.default_port(),
+                supports_schemas: driver.supports_schemas(),
+                supports_multiple_databases: driver.supports_multiple_databases(),
+                requires_database: driver.requires_database(),
+                capabilities: driver.capabilities(),
+            })
+            .collect();
+        infos.sort_by(|a, b| a.driver_id.cmp(&b.driver_id));
+        infos
     }
 
     /// Returns the number of registered drivers
     pub fn len(&self) -> usize {
-        self.drivers.len()
+        self.drivers.read().unwrap().len()
     }
 
     /// Returns true if no drivers are registered
     pub fn is_empty(&self) -> bool {
-        self.drivers.is_empty()
+        self.drivers.read().unwrap().is_empty()
     }
 }
 
@@ -58,5 +178,39 @@ impl Default for DriverRegistry {
 
 #[cfg(test)]
 mod tests {
-    // Tests will be added when we have mock drivers
+    use super::*;
+    use crate::engine::drivers::mysql::MySqlDriver;
+    use crate::engine::drivers::postgres::PostgresDriver;
+
+    #[test]
+    fn describes_registered_drivers() {
+        let registry = DriverRegistry::new();
+        registry.register(Arc::new(PostgresDriver::new()));
+        registry.register(Arc::new(MySqlDriver::new()));
+
+        let infos = registry.list_drivers_info();
+        let ids: Vec<&str> = infos.iter().map(|i| i.driver_id.as_str()).collect();
+        assert_eq!(ids, ["mysql", "postgres"]);
+
+        let (mysql, postgres) = (&infos[0], &infos[1]);
+        assert_eq!(mysql.default_port, 3306);
+        assert!(!mysql.supports_schemas);
+        assert!(mysql.supports_multiple_databases);
+        assert_eq!(postgres.default_port, 5432);
+        assert!(postgres.supports_schemas);
+        assert!(postgres.is_builtin);
+        assert!(postgres.capabilities.transactions);
+    }
+
+    #[test]
+    fn resolves_driver_aliases() {
+        let registry = DriverRegistry::new();
+        registry.register(Arc::new(MySqlDriver::new()));
+        registry.register_alias("mariadb", "mysql");
+
+        let driver = registry.get("mariadb").expect("alias should resolve");
+        assert_eq!(driver.driver_id(), "mysql");
+        assert!(registry.get("mssql").is_none());
+        assert_eq!(registry.list(), ["mysql"]);
+    }
 }
@@ -4,24 +4,348 @@
 //! This is the SINGLE SOURCE OF TRUTH for all connection state.
 
 use std::collections::HashMap;
+use std::future::Future;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
+use std::time::Instant;
 
-use tokio::sync::RwLock;
-use tokio::time::{timeout, Duration};
-use tracing::instrument;
+use futures::future::{AbortHandle, Abortable};
+use tokio::sync::{Mutex, RwLock};
+use tokio::task::JoinHandle;
+use tokio::time::{timeout, Duration, MissedTickBehavior};
+use tracing::{field, instrument};
+use zeroize::Zeroize;
 
 use crate::engine::error::{EngineError, EngineResult};
 use crate::engine::ssh_tunnel::SshTunnel;
 use crate::engine::traits::DataEngine;
-use crate::engine::types::{ConnectionConfig, SessionId};
+use crate::engine::types::{
+    ConnectionConfig, LogicalType, Namespace, RetryPolicy, SessionId, SessionStats, SshAuth,
+    VariableScope,
+};
 use crate::engine::DriverRegistry;
+use crate::policy::SafetyPolicy;
+use crate::vault::credentials::Environment;
 
 /// Active session with its connection pool and optional tunnel
 pub struct ActiveSession {
     pub driver_id: String,
     pub config: ConnectionConfig,
+    /// Generated `user@host:db` name, used when the config has no label
     pub display_name: String,
     pub tunnel: Option<SshTunnel>,
+    /// Session-scoped variables set through `set_session_variable`
+    pub variables: HashMap<String, String>,
+    /// The server is a standby; the session is then forced read-only
+    pub read_replica: bool,
+    /// Project of the saved connection the session was opened from
+    pub source_project_id: Option<String>,
+    /// Saved connection the session was opened from, whose credentials
+    /// `reconnect_session` reads again from the vault
+    pub source_connection_id: Option<String>,
+    /// Background task pinging the session (`keepalive_query_secs`)
+    pub keepalive: Option<JoinHandle<()>>,
+    /// Counters of the queries run through `execute_query`
+    pub stats: Arc<SessionCounters>,
+    /// Column types described for row mutations, keyed by table
+    pub column_types: HashMap<(Namespace, String), (Instant, ColumnTypes)>,
+}
+
+/// Logical type of each column of a table, keyed by column name
+pub type ColumnTypes = Arc<HashMap<String, LogicalType>>;
+
+/// Query counters of a session
+///
+/// Updated with relaxed atomics after every query so the hot path never
+/// waits on a lock; readers take a `SessionStats` snapshot. Timestamps are
+/// kept as Unix milliseconds, with 0 meaning "never".
+#[derive(Debug)]
+pub struct SessionCounters {
+    total_queries: AtomicU64,
+    failed_queries: AtomicU64,
+    total_rows_affected: AtomicU64,
+    total_rows_fetched: AtomicU64,
+    execution_time_us: AtomicU64,
+    connected_at_ms: AtomicU64,
+    last_query_at_ms: AtomicU64,
+}
+
+/// Orders environments by how strictly they are guarded
+fn strictness(environment: &Environment) -> u8 {
+    match environment {
+        Environment::Local => 0,
+        Environment::Development => 1,
+        Environment::Staging => 2,
+        Environment::Production => 3,
+    }
+}
+
+fn now_ms() -> u64 {
+    u64::try_from(chrono::Utc::now().timestamp_millis()).unwrap_or(0)
+}
+
+fn rfc3339_from_ms(ms: u64) -> Option<String> {
+    let ms = i64::try_from(ms).ok().filter(|ms| *ms > 0)?;
+    chrono::DateTime::from_timestamp_millis(ms).map(|at| at.to_rfc3339())
+}
+
+impl Default for SessionCounters {
+    fn default() -> Self {
+        Self {
+            total_queries: AtomicU64::new(0),
+            failed_queries: AtomicU64::new(0),
+            total_rows_affected: AtomicU64::new(0),
+            total_rows_fetched: AtomicU64::new(0),
+            execution_time_us: AtomicU64::new(0),
+            connected_at_ms: AtomicU64::new(now_ms()),
+            last_query_at_ms: AtomicU64::new(0),
+        }
+    }
+}
+
+impl SessionCounters {
+    /// Records a query that returned `rows_fetched` rows and reported
+    /// `rows_affected` changed rows
+    pub fn record_success(&self, rows_fetched: u64, rows_affected: u64, elapsed: Duration) {
+        self.total_rows_fetched.fetch_add(rows_fetched, Ordering::Relaxed);
+        self.total_rows_affected.fetch_add(rows_affected, Ordering::Relaxed);
+        self.record_query(elapsed);
+    }
+
+    /// Records a query that failed or timed out
+    pub fn record_error(&self, elapsed: Duration) {
+        self.failed_queries.fetch_add(1, Ordering::Relaxed);
+        self.record_query(elapsed);
+    }
+
+    fn record_query(&self, elapsed: Duration) {
+        let micros = u64::try_from(elapsed.as_micros()).unwrap_or(u64::MAX);
+        self.total_queries.fetch_add(1, Ordering::Relaxed);
+        self.execution_time_us.fetch_add(micros, Ordering::Relaxed);
+        self.last_query_at_ms.store(now_ms(), Ordering::Relaxed);
+    }
+
+    /// Starts the counters over, as after a fresh connect
+    pub fn reset(&self) {
+        self.total_queries.store(0, Ordering::Relaxed);
+        self.failed_queries.store(0, Ordering::Relaxed);
+        self.total_rows_affected.store(0, Ordering::Relaxed);
+        self.total_rows_fetched.store(0, Ordering::Relaxed);
+        self.execution_time_us.store(0, Ordering::Relaxed);
+        self.last_query_at_ms.store(0, Ordering::Relaxed);
+        self.connected_at_ms.store(now_ms(), Ordering::Relaxed);
+    }
+
+    pub fn snapshot(&self) -> SessionStats {
+        SessionStats {
+            total_queries: self.total_queries.load(Ordering::Relaxed),
+            failed_queries: self.failed_queries.load(Ordering::Relaxed),
+            total_rows_affected: self.total_rows_affected.load(Ordering::Relaxed),
+            total_rows_fetched: self.total_rows_fetched.load(Ordering::Relaxed),
+            total_execution_time_ms: self.execution_time_us.load(Ordering::Relaxed) as f64
+                / 1000.0,
+            connected_at: rfc3339_from_ms(self.connected_at_ms.load(Ordering::Relaxed))
+                .unwrap_or_default(),
+            last_query_at: rfc3339_from_ms(self.last_query_at_ms.load(Ordering::Relaxed)),
+        }
+    }
+}
+
+impl ActiveSession {
+    /// Name shown for the session: its label, or the generated display name
+    pub fn name(&self) -> &str {
+        self.config.label.as_deref().unwrap_or(&self.display_name)
+    }
+}
+
+impl Drop for ActiveSession {
+    /// Stops the keepalive task and clears the credentials kept in the
+    /// config once the session is gone
+    fn drop(&mut self) {
+        if let Some(keepalive) = self.keepalive.take() {
+            keepalive.abort();
+        }
+        self.config.password.zeroize();
+        if let Some(raw) = self.config.raw_connection_string.as_mut() {
+            raw.zeroize();
+        }
+        if let Some(ssh) = self.config.ssh_tunnel.as_mut() {
+            match &mut ssh.auth {
+                SshAuth::Password { password } => password.zeroize(),
+                SshAuth::Key { passphrase, .. } => {
+                    if let Some(passphrase) = passphrase.as_mut() {
+                        passphrase.zeroize();
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Driver session that is connected but not yet stored in the session map
+///
+/// Dropping it before `release` (the connect future is cancelled, times out,
+/// or panics) disconnects the driver session so its pool does not leak.
+struct PendingSession {
+    driver: Arc<dyn DataEngine>,
+    session_id: SessionId,
+    released: bool,
+}
+
+impl PendingSession {
+    fn new(driver: Arc<dyn DataEngine>, session_id: SessionId) -> Self {
+        Self {
+            driver,
+            session_id,
+            released: false,
+        }
+    }
+
+    /// Hands the session over to its owner; it is no longer disconnected on drop
+    fn release(mut self) -> SessionId {
+        self.released = true;
+        self.session_id
+    }
+}
+
+impl Drop for PendingSession {
+    fn drop(&mut self) {
+        if self.released {
+            return;
+        }
+        let Ok(runtime) = tokio::runtime::Handle::try_current() else {
+            tracing::warn!(
+                session_id = %self.session_id.0,
+                "No runtime to disconnect orphaned session"
+            );
+            return;
+        };
+        let driver = Arc::clone(&self.driver);
+        let session_id = self.session_id;
+        runtime.spawn(async move {
+            if let Err(e) = driver.disconnect(session_id).await {
+                tracing::warn!(
+                    session_id = %session_id.0,
+                    error = %e,
+                    "Failed to disconnect orphaned session"
+                );
+            }
+        });
+    }
+}
+
+/// Connect attempt that failed with a transient error and is about to be
+/// retried
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryAttempt {
+    /// The failed attempt, starting at 1
+    pub attempt: u32,
+    /// Wait before the next attempt
+    pub next_delay_ms: u64,
+}
+
+/// Runs `connect` until it succeeds, fails with anything other than
+/// `ConnectionFailed`, or `policy` runs out of attempts
+///
+/// Without a policy, `connect` runs once. `on_retry` is called before each
+/// wait.
+async fn with_retry<T, F, Fut>(
+    policy: Option<&RetryPolicy>,
+    on_retry: &(dyn Fn(RetryAttempt) + Send + Sync),
+    mut connect: F,
+) -> EngineResult<T>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = EngineResult<T>>,
+{
+    let max_attempts = policy.map_or(1, |policy| policy.max_attempts.max(1));
+    let mut attempt = 0;
+    loop {
+        match connect().await {
+            Err(e @ EngineError::ConnectionFailed { .. }) if attempt + 1 < max_attempts => {
+                let delay_ms = policy.map_or(0, |policy| policy.delay_ms(attempt));
+                attempt += 1;
+                tracing::warn!(
+                    error = %e,
+                    attempt,
+                    delay_ms,
+                    "Connection failed, retrying"
+                );
+                on_retry(RetryAttempt {
+                    attempt,
+                    next_delay_ms: delay_ms,
+                });
+                tokio::time::sleep(Duration::from_millis(delay_ms)).await;
+            }
+            result => return result,
+        }
+    }
+}
+
+/// Pings a session every `interval` so NATs and firewalls keep its idle
+/// connections open
+///
+/// A failed ping is only logged: it usually means the connection is dead.
+fn spawn_keepalive(
+    driver: Arc<dyn DataEngine>,
+    session_id: SessionId,
+    interval: Duration,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+        ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        // The first tick completes immediately, right after connecting
+        ticker.tick().await;
+        loop {
+            ticker.tick().await;
+            if let Err(e) = driver.ping(session_id).await {
+                tracing::warn!(
+                    session_id = %session_id.0,
+                    error = %e,
+                    "Keepalive ping failed, the connection may be dead"
+                );
+            }
+        }
+    })
+}
+
+/// How a new session is shown in the UI and in logs
+#[derive(Debug, Clone, Default)]
+pub struct SessionDisplay {
+    /// Hide the username and host (`SafetyPolicy::mask_prod_connection_details`)
+    pub mask_details: bool,
+    /// Saved connection name, used as the display name when masking
+    pub name: Option<String>,
+    /// Shown instead of the host when masking without a name
+    pub host_alias: Option<String>,
+}
+
+impl SessionDisplay {
+    /// Host as it may appear in logs
+    fn log_host<'a>(&'a self, config: &'a ConnectionConfig) -> &'a str {
+        if self.mask_details {
+            self.host_alias.as_deref().unwrap_or("***")
+        } else {
+            &config.host
+        }
+    }
+
+    fn display_name(&self, config: &ConnectionConfig, tunneled: bool) -> String {
+        let ssh_suffix = if tunneled { " (SSH)" } else { "" };
+        if self.mask_details {
+            if let Some(name) = self.name.as_deref() {
+                return format!("{}{}", name, ssh_suffix);
+            }
+        }
+        let username = if self.mask_details { "***" } else { config.username.as_str() };
+        format!(
+            "{}@{}:{}{}",
+            username,
+            self.log_host(config),
+            config.database.as_deref().unwrap_or("default"),
+            ssh_suffix
+        )
+    }
 }
 
 /// Manages all active database sessions
@@ -29,21 +353,30 @@ pub struct ActiveSession {
 pub struct SessionManager {
     registry: Arc<DriverRegistry>,
     sessions: RwLock<HashMap<SessionId, ActiveSession>>,
+    /// In-flight connect attempts, keyed by client-supplied attempt ID
+    pending_connects: Mutex<HashMap<String, AbortHandle>>,
 }
 
 impl SessionManager {
     const CONNECT_TIMEOUT_MS: u64 = 15000;
     const TEST_TIMEOUT_MS: u64 = 10000;
+    /// How long described column types are reused before describing the table again
+    const COLUMN_TYPES_TTL: Duration = Duration::from_secs(60);
+
     pub fn new(registry: Arc<DriverRegistry>) -> Self {
         Self {
             registry,
             sessions: RwLock::new(HashMap::new()),
+            pending_connects: Mutex::new(HashMap::new()),
         }
     }
 
     /// Tests a connection without persisting it
+    ///
+    /// Transient failures are retried per the config's `retry_policy`,
+    /// reporting each retry to `on_retry`.
     #[instrument(
-        skip(self, config),
+        skip(self, config, on_retry),
         fields(
             driver = %config.driver,
             host = %config.host,
@@ -52,7 +385,11 @@ impl SessionManager {
             ssh = config.ssh_tunnel.is_some()
         )
     )]
-    pub async fn test_connection(&self, config: &ConnectionConfig) -> EngineResult<()> {
+    pub async fn test_connection(
+        &self,
+        config: &ConnectionConfig,
+        on_retry: &(dyn Fn(RetryAttempt) + Send + Sync),
+    ) -> EngineResult<()> {
         let driver = self
             .registry
             .get(&config.driver)
@@ -66,10 +403,16 @@ impl SessionManager {
                 tunneled_config.host = "127.0.0.1".to_string();
                 tunneled_config.port = tunnel.local_port();
                 // Tunnel will be dropped after test, closing the connection
-                return driver.test_connection(&tunneled_config).await;
+                return with_retry(config.retry_policy.as_ref(), on_retry, || {
+                    driver.test_connection(&tunneled_config)
+                })
+                .await;
             }
 
-            driver.test_connection(config).await
+            with_retry(config.retry_policy.as_ref(), on_retry, || {
+                driver.test_connection(config)
+            })
+            .await
         };
 
         match timeout(Duration::from_millis(Self::TEST_TIMEOUT_MS), test_future).await {
@@ -81,17 +424,29 @@ impl SessionManager {
     }
 
     /// Establishes a new connection and returns its session ID
+    ///
+    /// With an `attempt_id`, the attempt can be aborted via `cancel_connect`.
+    /// Transient failures are retried per the config's `retry_policy`,
+    /// reporting each retry to `on_retry`.
     #[instrument(
-        skip(self, config),
+        skip(self, config, display, on_retry),
         fields(
             driver = %config.driver,
-            host = %config.host,
+            host = field::Empty,
             port = config.port,
             database = ?config.database,
             ssh = config.ssh_tunnel.is_some()
         )
     )]
-    pub async fn connect(&self, config: ConnectionConfig) -> EngineResult<SessionId> {
+    pub async fn connect(
+        &self,
+        config: ConnectionConfig,
+        attempt_id: Option<String>,
+        display: SessionDisplay,
+        on_retry: &(dyn Fn(RetryAttempt) + Send + Sync),
+    ) -> EngineResult<SessionId> {
+        tracing::Span::current().record("host", display.log_host(&config));
+
         let driver = self
             .registry
             .get(&config.driver)
@@ -109,37 +464,276 @@ impl SessionManager {
                 (config.clone(), None)
             };
 
-            let session_id = driver.connect(&effective_config).await?;
+            let session_id = with_retry(config.retry_policy.as_ref(), on_retry, || {
+                driver.connect(&effective_config)
+            })
+            .await?;
+            let pending = PendingSession::new(Arc::clone(&driver), session_id);
 
-            let display_name = format!(
-                "{}@{}:{}{}",
-                config.username,
-                config.host,
-                config.database.as_deref().unwrap_or("default"),
-                if tunnel.is_some() { " (SSH)" } else { "" }
-            );
+            let read_replica = match driver.is_read_replica(pending.session_id).await {
+                Ok(read_replica) => read_replica,
+                Err(e) => {
+                    tracing::warn!(error = %e, "Failed to check read replica status");
+                    false
+                }
+            };
+
+            let display_name = display.display_name(&config, tunnel.is_some());
+            let keepalive = config.keepalive_interval().map(|interval| {
+                spawn_keepalive(Arc::clone(&driver), pending.session_id, interval)
+            });
 
             let session = ActiveSession {
                 driver_id: config.driver.clone(),
-                config,
+                config: ConnectionConfig {
+                    read_only: config.read_only || read_replica,
+                    ..config
+                },
                 display_name,
                 tunnel,
+                variables: HashMap::new(),
+                read_replica,
+                source_project_id: None,
+                source_connection_id: None,
+                keepalive,
+                stats: Arc::default(),
+                column_types: HashMap::new(),
             };
 
             let mut sessions = self.sessions.write().await;
-            sessions.insert(session_id, session);
+            sessions.insert(pending.session_id, session);
 
-            Ok(session_id)
+            Ok(pending.release())
         };
 
-        match timeout(Duration::from_millis(Self::CONNECT_TIMEOUT_MS), connect_future).await {
-            Ok(result) => result,
+        let (abort_handle, abort_registration) = AbortHandle::new_pair();
+        if let Some(ref attempt_id) = attempt_id {
+            let mut pending = self.pending_connects.lock().await;
+            // Never replace the handle of an attempt that is still running
+            if pending.contains_key(attempt_id) {
+                return Err(EngineError::internal(format!(
+                    "Connect attempt {} is already in progress",
+                    attempt_id
+                )));
+            }
+            pending.insert(attempt_id.clone(), abort_handle);
+        }
+
+        // Aborting drops the future, which also drops (and kills) any SSH tunnel
+        let result = timeout(
+            Duration::from_millis(Self::CONNECT_TIMEOUT_MS),
+            Abortable::new(connect_future, abort_registration),
+        )
+        .await;
+
+        if let Some(ref attempt_id) = attempt_id {
+            let mut pending = self.pending_connects.lock().await;
+            pending.remove(attempt_id);
+        }
+
+        match result {
+            Ok(Ok(result)) => result,
+            Ok(Err(_aborted)) => Err(EngineError::Cancelled),
             Err(_) => Err(EngineError::Timeout {
                 timeout_ms: Self::CONNECT_TIMEOUT_MS,
             }),
         }
     }
 
+    /// Aborts an in-flight connect attempt; returns false if none is pending
+    pub async fn cancel_connect(&self, attempt_id: &str) -> bool {
+        let pending = self.pending_connects.lock().await;
+        match pending.get(attempt_id) {
+            Some(handle) => {
+                handle.abort();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Records the saved connection a session was opened from
+    pub async fn set_source_connection(
+        &self,
+        session_id: SessionId,
+        project_id: &str,
+        connection_id: &str,
+    ) -> EngineResult<()> {
+        let mut sessions = self.sessions.write().await;
+        let session = sessions
+            .get_mut(&session_id)
+            .ok_or_else(|| EngineError::session_not_found(session_id.0.to_string()))?;
+        session.source_project_id = Some(project_id.to_string());
+        session.source_connection_id = Some(connection_id.to_string());
+        Ok(())
+    }
+
+    /// Returns the `(project_id, connection_id)` of the session's saved connection
+    pub async fn source_connection(
+        &self,
+        session_id: SessionId,
+    ) -> EngineResult<Option<(String, String)>> {
+        let sessions = self.sessions.read().await;
+        let session = sessions
+            .get(&session_id)
+            .ok_or_else(|| EngineError::session_not_found(session_id.0.to_string()))?;
+
+        Ok(session
+            .source_project_id
+            .clone()
+            .zip(session.source_connection_id.clone()))
+    }
+
+    /// Replaces the driver connection of a session, keeping its ID
+    ///
+    /// With a `password`, it replaces the one stored in the session config.
+    /// The new connection is opened before the stale one is closed, so a
+    /// failed attempt leaves the session as it was. An existing SSH tunnel is
+    /// reused, and session variables are set again on the new connection.
+    #[instrument(skip(self, password), fields(session_id = %session_id.0))]
+    pub async fn reconnect(
+        &self,
+        session_id: SessionId,
+        password: Option<String>,
+    ) -> EngineResult<()> {
+        let (driver_id, mut config, tunnel_port, variables) = {
+            let sessions = self.sessions.read().await;
+            let session = sessions
+                .get(&session_id)
+                .ok_or_else(|| EngineError::session_not_found(session_id.0.to_string()))?;
+            (
+                session.driver_id.clone(),
+                session.config.clone(),
+                session.tunnel.as_ref().map(|tunnel| tunnel.local_port()),
+                session.variables.clone(),
+            )
+        };
+
+        let driver = self
+            .registry
+            .get(&driver_id)
+            .ok_or_else(|| EngineError::driver_not_found(&driver_id))?;
+
+        if let Some(password) = password {
+            config.password.zeroize();
+            config.password = password;
+        }
+        let mut effective_config = config.clone();
+        if let Some(port) = tunnel_port {
+            effective_config.host = "127.0.0.1".to_string();
+            effective_config.port = port;
+        }
+
+        let pending = match timeout(
+            Duration::from_millis(Self::CONNECT_TIMEOUT_MS),
+            driver.connect(&effective_config),
+        )
+        .await
+        {
+            Ok(result) => PendingSession::new(Arc::clone(&driver), result?),
+            Err(_) => {
+                return Err(EngineError::Timeout {
+                    timeout_ms: Self::CONNECT_TIMEOUT_MS,
+                })
+            }
+        };
+        effective_config.password.zeroize();
+
+        // On failure `pending` is dropped, closing only the fresh connection
+        driver.rebind_session(pending.session_id, session_id).await?;
+        let stale = pending.release();
+        if let Err(e) = driver.disconnect(stale).await {
+            tracing::warn!(error = %e, "Failed to disconnect stale session");
+        }
+
+        for (name, value) in &variables {
+            if let Err(e) = driver
+                .set_session_variable(session_id, name, value, VariableScope::Session)
+                .await
+            {
+                tracing::warn!(variable = %name, error = %e, "Failed to restore session variable");
+            }
+        }
+
+        let mut sessions = self.sessions.write().await;
+        if let Some(session) = sessions.get_mut(&session_id) {
+            session.config.password.zeroize();
+            session.config.password = std::mem::take(&mut config.password);
+            session.stats.reset();
+            session.column_types.clear();
+        }
+        Ok(())
+    }
+
+    /// Checks the SSH tunnel of a session: its process is running and its
+    /// local port accepts connections. Sessions without a tunnel are healthy.
+    pub async fn tunnel_healthy(&self, session_id: SessionId) -> EngineResult<bool> {
+        let sessions = self.sessions.read().await;
+        let session = sessions
+            .get(&session_id)
+            .ok_or_else(|| EngineError::session_not_found(session_id.0.to_string()))?;
+
+        match session.tunnel.as_ref() {
+            Some(tunnel) => Ok(tunnel.is_healthy().await),
+            None => Ok(true),
+        }
+    }
+
+    /// Returns true if the session's tunnel is down and the session opted
+    /// into `SshTunnelConfig::auto_restart`
+    pub async fn should_restart_tunnel(&self, session_id: SessionId) -> bool {
+        let sessions = self.sessions.read().await;
+        let Some(session) = sessions.get(&session_id) else {
+            return false;
+        };
+        let auto_restart = session
+            .config
+            .ssh_tunnel
+            .as_ref()
+            .is_some_and(|ssh| ssh.auto_restart);
+
+        match session.tunnel.as_ref() {
+            Some(tunnel) if auto_restart => !tunnel.is_healthy().await,
+            _ => false,
+        }
+    }
+
+    /// Re-opens the SSH tunnel of a session and reconnects the driver through it
+    ///
+    /// The stale tunnel is closed once the new one is up; see `reconnect` for
+    /// how the driver connection is replaced.
+    #[instrument(skip(self), fields(session_id = %session_id.0))]
+    pub async fn restart_tunnel(&self, session_id: SessionId) -> EngineResult<()> {
+        let (ssh_config, host, port) = {
+            let sessions = self.sessions.read().await;
+            let session = sessions
+                .get(&session_id)
+                .ok_or_else(|| EngineError::session_not_found(session_id.0.to_string()))?;
+            let ssh_config = session.config.ssh_tunnel.clone().ok_or_else(|| {
+                EngineError::SshError {
+                    message: "Session has no SSH tunnel".to_string(),
+                }
+            })?;
+            (ssh_config, session.config.host.clone(), session.config.port)
+        };
+
+        let tunnel = SshTunnel::open(&ssh_config, &host, port).await?;
+        let stale = {
+            let mut sessions = self.sessions.write().await;
+            let session = sessions
+                .get_mut(&session_id)
+                .ok_or_else(|| EngineError::session_not_found(session_id.0.to_string()))?;
+            session.tunnel.replace(tunnel)
+        };
+        if let Some(mut stale) = stale {
+            if let Err(e) = stale.close().await {
+                tracing::warn!(error = %e, "Failed to close stale SSH tunnel");
+            }
+        }
+
+        self.reconnect(session_id, None).await
+    }
+
     /// Disconnects a session
     #[instrument(skip(self), fields(session_id = %session_id.0))]
     pub async fn disconnect(&self, session_id: SessionId) -> EngineResult<()> {
@@ -178,19 +772,56 @@ impl SessionManager {
             .ok_or_else(|| EngineError::driver_not_found(&session.driver_id))
     }
 
-    /// Lists all active sessions
-    pub async fn list_sessions(&self) -> Vec<(SessionId, String)> {
+    /// Lists all active sessions as `(id, name, label)`
+    pub async fn list_sessions(&self) -> Vec<(SessionId, String, Option<String>, SessionStats)> {
         let sessions = self.sessions.read().await;
         sessions
             .iter()
-            .map(|(id, session)| (*id, session.display_name.clone()))
+            .map(|(id, session)| {
+                (
+                    *id,
+                    session.name().to_string(),
+                    session.config.label.clone(),
+                    session.stats.snapshot(),
+                )
+            })
             .collect()
     }
 
     /// Gets session info
     pub async fn get_session_info(&self, session_id: SessionId) -> Option<String> {
         let sessions = self.sessions.read().await;
-        sessions.get(&session_id).map(|s| s.display_name.clone())
+        sessions.get(&session_id).map(|s| s.name().to_string())
+    }
+
+    /// Sets the session's custom label; `None` restores the generated name
+    pub async fn rename_session(
+        &self,
+        session_id: SessionId,
+        label: Option<String>,
+    ) -> EngineResult<()> {
+        let mut sessions = self.sessions.write().await;
+        let session = sessions
+            .get_mut(&session_id)
+            .ok_or_else(|| EngineError::session_not_found(session_id.0.to_string()))?;
+        session.config.label = label;
+        Ok(())
+    }
+
+    /// Returns the query counters of a session
+    ///
+    /// Counters live with the session, so they start over once it is
+    /// disconnected, and are reset when it reconnects.
+    pub async fn session_stats(
+        &self,
+        session_id: SessionId,
+    ) -> EngineResult<Arc<SessionCounters>> {
+        let sessions = self.sessions.read().await;
+        let session = sessions
+            .get(&session_id)
+            .ok_or_else(|| EngineError::session_not_found(session_id.0.to_string()))?;
+
+        Ok(Arc::clone(&session.stats))
     }
 
     /// Checks if the session is read-only
@@ -203,6 +834,45 @@ impl SessionManager {
         Ok(session.config.read_only)
     }
 
+    /// Checks if the session's server was detected as a read replica
+    pub async fn is_read_replica(&self, session_id: SessionId) -> EngineResult<bool> {
+        let sessions = self.sessions.read().await;
+        let session = sessions
+            .get(&session_id)
+            .ok_or_else(|| EngineError::session_not_found(session_id.0.to_string()))?;
+
+        Ok(session.read_replica)
+    }
+
+    /// Asks the server again whether it is a read replica and updates the session
+    ///
+    /// A replica is made read-only. A standby promoted since then stays
+    /// read-only until the session is reconnected.
+    pub async fn refresh_replica_status(&self, session_id: SessionId) -> EngineResult<bool> {
+        let driver = self.get_driver(session_id).await?;
+        let read_replica = driver.is_read_replica(session_id).await?;
+
+        let mut sessions = self.sessions.write().await;
+        let session = sessions
+            .get_mut(&session_id)
+            .ok_or_else(|| EngineError::session_not_found(session_id.0.to_string()))?;
+        session.read_replica = read_replica;
+        if read_replica {
+            session.config.read_only = true;
+        }
+        Ok(read_replica)
+    }
+
+    /// Gets the database host the session connects to (before any SSH tunneling)
+    pub async fn get_host(&self, session_id: SessionId) -> EngineResult<String> {
+        let sessions = self.sessions.read().await;
+        let session = sessions
+            .get(&session_id)
+            .ok_or_else(|| EngineError::session_not_found(session_id.0.to_string()))?;
+
+        Ok(session.config.host.clone())
+    }
+
     /// Checks if the session is a production environment
     pub async fn is_production(&self, session_id: SessionId) -> EngineResult<bool> {
         let sessions = self.sessions.read().await;
@@ -210,7 +880,157 @@ impl SessionManager {
             .get(&session_id)
             .ok_or_else(|| EngineError::session_not_found(session_id.0.to_string()))?;
 
-        Ok(session.config.environment == "production")
+        Ok(Environment::parse(&session.config.environment) == Some(Environment::Production))
+    }
+
+    /// Checks if the session is classified as staging
+    pub async fn is_staging(&self, session_id: SessionId) -> EngineResult<bool> {
+        let sessions = self.sessions.read().await;
+        let session = sessions
+            .get(&session_id)
+            .ok_or_else(|| EngineError::session_not_found(session_id.0.to_string()))?;
+
+        Ok(Environment::parse(&session.config.environment) == Some(Environment::Staging))
+    }
+
+    /// Reclassifies a session's environment without reconnecting
+    ///
+    /// The safety guards follow the new environment from the next query on;
+    /// the display name computed at connect time is kept. Leaving production
+    /// or staging turns their guards off, so it needs `acknowledged` when
+    /// the policy requires confirmation there. Downgrades are audited.
+    pub async fn set_environment(
+        &self,
+        session_id: SessionId,
+        environment: Environment,
+        policy: &SafetyPolicy,
+        acknowledged: bool,
+    ) -> EngineResult<()> {
+        let mut sessions = self.sessions.write().await;
+        let session = sessions
+            .get_mut(&session_id)
+            .ok_or_else(|| EngineError::session_not_found(session_id.0.to_string()))?;
+
+        let previous = Environment::parse(&session.config.environment).unwrap_or_default();
+        let downgrade = strictness(&environment) < strictness(&previous);
+        let require_confirmation = match previous {
+            Environment::Production => policy.prod_require_confirmation,
+            Environment::Staging => policy.staging_require_confirmation,
+            _ => false,
+        };
+        if downgrade && require_confirmation && !acknowledged {
+            return Err(EngineError::permission_denied(format!(
+                "Leaving {} requires confirmation",
+                previous.as_str()
+            )));
+        }
+
+        session.config.environment = environment.as_str().to_string();
+        if downgrade {
+            tracing::info!(
+                target: "qoredb::audit",
+                session_id = %session_id.0,
+                from = previous.as_str(),
+                to = environment.as_str(),
+                "Session environment downgraded"
+            );
+        }
+        Ok(())
+    }
+
+    /// Column types of a table cached by `cache_column_types`, unless they
+    /// are older than `COLUMN_TYPES_TTL`
+    pub async fn cached_column_types(
+        &self,
+        session_id: SessionId,
+        namespace: &Namespace,
+        table: &str,
+    ) -> Option<ColumnTypes> {
+        let sessions = self.sessions.read().await;
+        let (described_at, types) = sessions
+            .get(&session_id)?
+            .column_types
+            .get(&(namespace.clone(), table.to_string()))?;
+        (described_at.elapsed() < Self::COLUMN_TYPES_TTL).then(|| Arc::clone(types))
+    }
+
+    pub async fn cache_column_types(
+        &self,
+        session_id: SessionId,
+        namespace: &Namespace,
+        table: &str,
+        types: ColumnTypes,
+    ) {
+        let mut sessions = self.sessions.write().await;
+        if let Some(session) = sessions.get_mut(&session_id) {
+            session
+                .column_types
+                .insert((namespace.clone(), table.to_string()), (Instant::now(), types));
+        }
+    }
+
+    /// Drops the cached column types of a session, after a schema change
+    pub async fn forget_column_types(&self, session_id: SessionId) {
+        let mut sessions = self.sessions.write().await;
+        if let Some(session) = sessions.get_mut(&session_id) {
+            session.column_types.clear();
+        }
+    }
+
+    /// Checks if writes on the session go through the protected host guards
+    ///
+    /// Local sessions are never protected, whatever their host.
+    pub async fn is_protected_host(
+        &self,
+        session_id: SessionId,
+        policy: &SafetyPolicy,
+    ) -> EngineResult<bool> {
+        let sessions = self.sessions.read().await;
+        let session = sessions
+            .get(&session_id)
+            .ok_or_else(|| EngineError::session_not_found(session_id.0.to_string()))?;
+
+        Ok(policy.is_protected_connection(&session.config.environment, &session.config.host))
+    }
+
+    /// Sets a server variable through the session's driver
+    ///
+    /// Session-scoped values are cached on the session; transaction-local
+    /// and global ones are not, since they do not follow the session.
+    pub async fn set_session_variable(
+        &self,
+        session_id: SessionId,
+        name: &str,
+        value: &str,
+        scope: VariableScope,
+    ) -> EngineResult<()> {
+        let driver = self.get_driver(session_id).await?;
+        driver
+            .set_session_variable(session_id, name, value, scope)
+            .await?;
+
+        if scope == VariableScope::Session {
+            let mut sessions = self.sessions.write().await;
+            if let Some(session) = sessions.get_mut(&session_id) {
+                session
+                    .variables
+                    .insert(name.to_ascii_lowercase(), value.to_string());
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the session-scoped variables set on a session
+    pub async fn get_session_variables(
+        &self,
+        session_id: SessionId,
+    ) -> EngineResult<HashMap<String, String>> {
+        let sessions = self.sessions.read().await;
+        let session = sessions
+            .get(&session_id)
+            .ok_or_else(|| EngineError::session_not_found(session_id.0.to_string()))?;
+
+        Ok(session.variables.clone())
     }
 
     /// Checks if a session exists
@@ -219,3 +1039,524 @@ impl SessionManager {
         sessions.contains_key(&session_id)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use async_trait::async_trait;
+
+    use super::*;
+    use crate::engine::types::{
+        Collection, Namespace, QueryId, QueryResult, RowLockMode, SslMode, TableSchema,
+    };
+
+    /// Driver that connects instantly and counts disconnects
+    #[derive(Default)]
+    struct MockDriver {
+        connects: AtomicUsize,
+        disconnects: AtomicUsize,
+        pings: AtomicUsize,
+        read_replica: bool,
+        /// Connects left to fail with `ConnectionFailed`
+        connect_failures: AtomicUsize,
+        /// Fails every connect with `AuthenticationFailed`
+        rejects_auth: bool,
+        /// Leaves `rebind_session` to the trait default (`NotSupported`)
+        no_rebind: bool,
+        disconnected: std::sync::Mutex<Vec<SessionId>>,
+    }
+
+    #[async_trait]
+    impl DataEngine for MockDriver {
+        fn driver_id(&self) -> &'static str {
+            "mock"
+        }
+
+        fn driver_name(&self) -> &'static str {
+            "Mock"
+        }
+
+        async fn test_connection(&self, _config: &ConnectionConfig) -> EngineResult<()> {
+            Ok(())
+        }
+
+        async fn connect(&self, _config: &ConnectionConfig) -> EngineResult<SessionId> {
+            self.connects.fetch_add(1, Ordering::SeqCst);
+            if self.rejects_auth {
+                return Err(EngineError::auth_failed("mock"));
+            }
+            let failures = self.connect_failures.load(Ordering::SeqCst);
+            if failures > 0 {
+                self.connect_failures.store(failures - 1, Ordering::SeqCst);
+                return Err(EngineError::connection_failed("mock"));
+            }
+            Ok(SessionId::new())
+        }
+
+        async fn disconnect(&self, session: SessionId) -> EngineResult<()> {
+            self.disconnects.fetch_add(1, Ordering::SeqCst);
+            self.disconnected.lock().unwrap().push(session);
+            Ok(())
+        }
+
+        async fn rebind_session(&self, _from: SessionId, _to: SessionId) -> EngineResult<()> {
+            if self.no_rebind {
+                return Err(EngineError::not_supported("mock"));
+            }
+            Ok(())
+        }
+
+        async fn ping(&self, _session: SessionId) -> EngineResult<()> {
+            self.pings.fetch_add(1, Ordering::SeqCst);
+            Ok(())
+        }
+
+        async fn list_namespaces(&self, _session: SessionId) -> EngineResult<Vec<Namespace>> {
+            Ok(Vec::new())
+        }
+
+        async fn is_read_replica(&self, _session: SessionId) -> EngineResult<bool> {
+            Ok(self.read_replica)
+        }
+
+        async fn list_collections(
+            &self,
+            _session: SessionId,
+            _namespace: &Namespace,
+        ) -> EngineResult<Vec<Collection>> {
+            Ok(Vec::new())
+        }
+
+        async fn execute(
+            &self,
+            _session: SessionId,
+            _query: &str,
+            _query_id: QueryId,
+        ) -> EngineResult<QueryResult> {
+            Err(EngineError::not_supported("mock"))
+        }
+
+        async fn describe_table(
+            &self,
+            _session: SessionId,
+            _namespace: &Namespace,
+            _table: &str,
+        ) -> EngineResult<TableSchema> {
+            Err(EngineError::not_supported("mock"))
+        }
+
+        async fn preview_table(
+            &self,
+            _session: SessionId,
+            _namespace: &Namespace,
+            _table: &str,
+            _limit: u32,
+            _lock_rows: Option<RowLockMode>,
+        ) -> EngineResult<QueryResult> {
+            Err(EngineError::not_supported("mock"))
+        }
+    }
+
+    fn mock_config() -> ConnectionConfig {
+        ConnectionConfig {
+            driver: "mock".to_string(),
+            host: "localhost".to_string(),
+            port: 0,
+            username: "app".to_string(),
+            password: "secret".to_string(),
+            database: None,
+            ssl_mode: SslMode::Disabled,
+            ssl_ca_path: None,
+            environment: "development".to_string(),
+            read_only: false,
+            ssh_tunnel: None,
+            pool_idle_timeout_secs: 0,
+            pool_max_lifetime_secs: 0,
+            use_http_api: false,
+            boost: None,
+            mongo_auth_source: None,
+            keepalive_query_secs: None,
+            label: None,
+            charset: None,
+            raw_connection_string: None,
+            retry_policy: None,
+            statement_cache_capacity: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn disconnects_when_connect_fails_after_driver_connect() {
+        let driver = Arc::new(MockDriver::default());
+        let registry = Arc::new(DriverRegistry::new());
+        registry.register(driver.clone());
+        let manager = Arc::new(SessionManager::new(registry));
+
+        // Holding the session map stalls connect right after driver.connect
+        let sessions = manager.sessions.write().await;
+        let connect = tokio::spawn({
+            let manager = Arc::clone(&manager);
+            async move {
+                manager
+                    .connect(
+                        mock_config(),
+                        Some("attempt".to_string()),
+                        SessionDisplay::default(),
+                        &|_| {},
+                    )
+                    .await
+            }
+        });
+        while driver.connects.load(Ordering::SeqCst) == 0 {
+            tokio::task::yield_now().await;
+        }
+
+        assert!(manager.cancel_connect("attempt").await);
+        assert!(matches!(
+            connect.await.unwrap(),
+            Err(EngineError::Cancelled)
+        ));
+        drop(sessions);
+
+        for _ in 0..100 {
+            if driver.disconnects.load(Ordering::SeqCst) > 0 {
+                break;
+            }
+            tokio::task::yield_now().await;
+        }
+        assert_eq!(driver.disconnects.load(Ordering::SeqCst), 1);
+        assert!(manager.list_sessions().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn retries_transient_connect_failures_with_backoff() {
+        let driver = Arc::new(MockDriver {
+            connect_failures: AtomicUsize::new(2),
+            ..MockDriver::default()
+        });
+        let registry = Arc::new(DriverRegistry::new());
+        registry.register(driver.clone());
+        let manager = SessionManager::new(registry);
+        let config = ConnectionConfig {
+            retry_policy: Some(RetryPolicy {
+                max_attempts: 3,
+                initial_delay_ms: 1,
+            }),
+            ..mock_config()
+        };
+
+        let retries = std::sync::Mutex::new(Vec::new());
+        let session_id = manager
+            .connect(config, None, SessionDisplay::default(), &|retry| {
+                retries.lock().unwrap().push(retry)
+            })
+            .await
+            .unwrap();
+
+        assert!(manager.session_exists(session_id).await);
+        assert_eq!(driver.connects.load(Ordering::SeqCst), 3);
+        assert_eq!(
+            *retries.lock().unwrap(),
+            vec![
+                RetryAttempt {
+                    attempt: 1,
+                    next_delay_ms: 1
+                },
+                RetryAttempt {
+                    attempt: 2,
+                    next_delay_ms: 2
+                },
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn does_not_retry_authentication_failures() {
+        let driver = Arc::new(MockDriver {
+            rejects_auth: true,
+            ..MockDriver::default()
+        });
+        let registry = Arc::new(DriverRegistry::new());
+        registry.register(driver.clone());
+        let manager = SessionManager::new(registry);
+        let config = ConnectionConfig {
+            retry_policy: Some(RetryPolicy::default()),
+            ..mock_config()
+        };
+
+        let result = manager
+            .connect(config, None, SessionDisplay::default(), &|_| {
+                panic!("authentication failures must not be retried")
+            })
+            .await;
+
+        assert!(matches!(result, Err(EngineError::AuthenticationFailed { .. })));
+        assert_eq!(driver.connects.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn keeps_session_connected_after_successful_connect() {
+        let driver = Arc::new(MockDriver::default());
+        let registry = Arc::new(DriverRegistry::new());
+        registry.register(driver.clone());
+        let manager = SessionManager::new(registry);
+
+        let session_id = manager
+            .connect(mock_config(), None, SessionDisplay::default(), &|_| {})
+            .await
+            .unwrap();
+        tokio::task::yield_now().await;
+
+        assert!(manager.session_exists(session_id).await);
+        assert_eq!(driver.disconnects.load(Ordering::SeqCst), 0);
+    }
+
+    #[tokio::test]
+    async fn resets_query_stats_on_reconnect_and_disconnect() {
+        let registry = Arc::new(DriverRegistry::new());
+        registry.register(Arc::new(MockDriver::default()));
+        let manager = SessionManager::new(registry);
+
+        let session_id = manager
+            .connect(mock_config(), None, SessionDisplay::default(), &|_| {})
+            .await
+            .unwrap();
+        let stats = manager.session_stats(session_id).await.unwrap();
+        stats.record_success(3, 0, Duration::from_millis(2));
+        stats.record_success(0, 4, Duration::from_millis(1));
+        stats.record_error(Duration::from_micros(500));
+
+        let snapshot = manager.session_stats(session_id).await.unwrap().snapshot();
+        assert_eq!(snapshot.total_queries, 3);
+        assert_eq!(snapshot.failed_queries, 1);
+        assert_eq!(snapshot.total_rows_fetched, 3);
+        assert_eq!(snapshot.total_rows_affected, 4);
+        assert_eq!(snapshot.total_execution_time_ms, 3.5);
+        assert!(snapshot.last_query_at.is_some());
+
+        manager.reconnect(session_id, None).await.unwrap();
+        let snapshot = manager.session_stats(session_id).await.unwrap().snapshot();
+        assert_eq!(snapshot.total_queries, 0);
+        assert_eq!(snapshot.last_query_at, None);
+        assert!(!snapshot.connected_at.is_empty());
+
+        manager.disconnect(session_id).await.unwrap();
+        assert!(matches!(
+            manager.session_stats(session_id).await,
+            Err(EngineError::SessionNotFound { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn forgets_cached_column_types_on_reconnect() {
+        let registry = Arc::new(DriverRegistry::new());
+        registry.register(Arc::new(MockDriver::default()));
+        let manager = SessionManager::new(registry);
+        let session_id = manager
+            .connect(mock_config(), None, SessionDisplay::default(), &|_| {})
+            .await
+            .unwrap();
+        let namespace = Namespace::new("app");
+
+        let types: ColumnTypes = Arc::new(HashMap::from([("id".to_string(), LogicalType::Uuid)]));
+        manager
+            .cache_column_types(session_id, &namespace, "users", types)
+            .await;
+        let cached = manager
+            .cached_column_types(session_id, &namespace, "users")
+            .await
+            .unwrap();
+        assert_eq!(cached.get("id"), Some(&LogicalType::Uuid));
+        assert!(manager
+            .cached_column_types(session_id, &namespace, "orders")
+            .await
+            .is_none());
+
+        manager.reconnect(session_id, None).await.unwrap();
+        assert!(manager
+            .cached_column_types(session_id, &namespace, "users")
+            .await
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn makes_read_replica_sessions_read_only() {
+        let registry = Arc::new(DriverRegistry::new());
+        registry.register(Arc::new(MockDriver {
+            read_replica: true,
+            ..MockDriver::default()
+        }));
+        let manager = SessionManager::new(registry);
+
+        let session_id = manager
+            .connect(mock_config(), None, SessionDisplay::default(), &|_| {})
+            .await
+            .unwrap();
+
+        assert!(manager.is_read_replica(session_id).await.unwrap());
+        assert!(manager.is_read_only(session_id).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn reconnect_keeps_session_id_and_updates_password() {
+        let driver = Arc::new(MockDriver::default());
+        let registry = Arc::new(DriverRegistry::new());
+        registry.register(driver.clone());
+        let manager = SessionManager::new(registry);
+
+        let session_id = manager
+            .connect(mock_config(), None, SessionDisplay::default(), &|_| {})
+            .await
+            .unwrap();
+        manager
+            .reconnect(session_id, Some("rotated".to_string()))
+            .await
+            .unwrap();
+
+        assert!(manager.session_exists(session_id).await);
+        assert_eq!(driver.connects.load(Ordering::SeqCst), 2);
+        assert_eq!(driver.disconnects.load(Ordering::SeqCst), 1);
+        let sessions = manager.sessions.read().await;
+        assert_eq!(sessions[&session_id].config.password, "rotated");
+    }
+
+    #[tokio::test]
+    async fn failed_reconnect_keeps_the_live_session() {
+        let driver = Arc::new(MockDriver {
+            no_rebind: true,
+            ..Default::default()
+        });
+        let registry = Arc::new(DriverRegistry::new());
+        registry.register(driver.clone());
+        let manager = SessionManager::new(registry);
+
+        let session_id = manager
+            .connect(mock_config(), None, SessionDisplay::default(), &|_| {})
+            .await
+            .unwrap();
+        let err = manager.reconnect(session_id, None).await.unwrap_err();
+        assert!(matches!(err, EngineError::NotSupported { .. }));
+
+        // The fresh connection is closed in the background
+        timeout(Duration::from_secs(5), async {
+            while driver.disconnects.load(Ordering::SeqCst) == 0 {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .expect("fresh connection closed");
+
+        assert!(manager.session_exists(session_id).await);
+        assert!(!driver.disconnected.lock().unwrap().contains(&session_id));
+    }
+
+    #[tokio::test]
+    async fn leaving_production_needs_acknowledgment() {
+        let registry = Arc::new(DriverRegistry::new());
+        registry.register(Arc::new(MockDriver::default()));
+        let manager = SessionManager::new(registry);
+        let mut policy = SafetyPolicy::load();
+        policy.prod_require_confirmation = true;
+
+        let config = ConnectionConfig {
+            environment: "production".to_string(),
+            ..mock_config()
+        };
+        let session_id = manager
+            .connect(config, None, SessionDisplay::default(), &|_| {})
+            .await
+            .unwrap();
+
+        let err = manager
+            .set_environment(session_id, Environment::Development, &policy, false)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, EngineError::PermissionDenied { .. }));
+        assert!(manager.is_production(session_id).await.unwrap());
+
+        manager
+            .set_environment(session_id, Environment::Development, &policy, true)
+            .await
+            .unwrap();
+        assert!(!manager.is_production(session_id).await.unwrap());
+
+        // Raising the environment never needs confirmation
+        manager
+            .set_environment(session_id, Environment::Production, &policy, false)
+            .await
+            .unwrap();
+        assert!(manager.is_production(session_id).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn keepalive_pings_until_disconnect() {
+        let driver = Arc::new(MockDriver::default());
+        let registry = Arc::new(DriverRegistry::new());
+        registry.register(driver.clone());
+        let manager = SessionManager::new(registry);
+
+        let config = ConnectionConfig {
+            keepalive_query_secs: Some(1),
+            ..mock_config()
+        };
+        let session_id = manager
+            .connect(config, None, SessionDisplay::default(), &|_| {})
+            .await
+            .unwrap();
+
+        timeout(Duration::from_secs(5), async {
+            while driver.pings.load(Ordering::SeqCst) == 0 {
+                tokio::time::sleep(Duration::from_millis(50)).await;
+            }
+        })
+        .await
+        .expect("keepalive ping");
+
+        manager.disconnect(session_id).await.unwrap();
+        let pings = driver.pings.load(Ordering::SeqCst);
+        tokio::time::sleep(Duration::from_millis(1500)).await;
+        assert_eq!(driver.pings.load(Ordering::SeqCst), pings);
+    }
+
+    #[tokio::test]
+    async fn keepalive_is_opt_in() {
+        let driver = Arc::new(MockDriver::default());
+        let registry = Arc::new(DriverRegistry::new());
+        registry.register(driver.clone());
+        let manager = SessionManager::new(registry);
+
+        let session_id = manager
+            .connect(mock_config(), None, SessionDisplay::default(), &|_| {})
+            .await
+            .unwrap();
+
+        let sessions = manager.sessions.read().await;
+        assert!(sessions[&session_id].keepalive.is_none());
+    }
+
+    #[tokio::test]
+    async fn label_replaces_generated_name_until_cleared() {
+        let registry = Arc::new(DriverRegistry::new());
+        registry.register(Arc::new(MockDriver::default()));
+        let manager = SessionManager::new(registry);
+
+        let config = ConnectionConfig {
+            label: Some("Prod Orders DB".to_string()),
+            ..mock_config()
+        };
+        let session_id = manager
+            .connect(config, None, SessionDisplay::default(), &|_| {})
+            .await
+            .unwrap();
+        assert_eq!(
+            manager.get_session_info(session_id).await.as_deref(),
+            Some("Prod Orders DB")
+        );
+
+        manager.rename_session(session_id, None).await.unwrap();
+        assert_eq!(
+            manager.get_session_info(session_id).await.as_deref(),
+            Some("app@localhost:default")
+        );
+    }
+}
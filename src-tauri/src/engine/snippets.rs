@@ -0,0 +1,283 @@
+//! Query Snippets
+//!
+//! Named, reusable queries. Personal snippets are persisted to a JSON file in
+//! the per-user config directory; other stores, such as the project snippets
+//! kept in the vault, plug in as a `SnippetBackend`. Snippets are plain text;
+//! running one goes through the regular query commands and their safety
+//! guards.
+
+use std::fs;
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::engine::error::{EngineError, EngineResult};
+
+/// Placeholder replaced by the target table when a snippet is rendered
+pub const TABLE_NAME_PLACEHOLDER: &str = "{{table_name}}";
+
+/// A saved query
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Snippet {
+    /// Generated on first save when left empty
+    #[serde(default)]
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Driver the snippet applies to (e.g. "postgres"); `None` matches all
+    #[serde(default, alias = "driver")]
+    pub driver_id: Option<String>,
+    /// SQL statement or MongoDB query, possibly with `{{table_name}}`
+    #[serde(alias = "body")]
+    pub query: String,
+    /// RFC 3339 creation time, set on first save
+    #[serde(default)]
+    pub created_at: String,
+    #[serde(default)]
+    pub tags: Vec<String>,
+}
+
+impl Snippet {
+    /// Returns true if the snippet can run on the given driver
+    pub fn matches_driver(&self, driver_id: &str) -> bool {
+        self.driver_id
+            .as_deref()
+            .is_none_or(|driver| driver == driver_id)
+    }
+
+    /// Returns the query with `{{table_name}}` replaced by `table_name`
+    ///
+    /// The table name is inserted as given, so it must already be quoted
+    /// where the dialect requires it.
+    pub fn render(&self, table_name: &str) -> String {
+        self.query.replace(TABLE_NAME_PLACEHOLDER, table_name)
+    }
+
+    fn validate(&self) -> EngineResult<()> {
+        if self.name.trim().is_empty() {
+            return Err(EngineError::internal("Snippet name cannot be empty"));
+        }
+        if self.query.trim().is_empty() {
+            return Err(EngineError::internal("Snippet query cannot be empty"));
+        }
+        Ok(())
+    }
+}
+
+/// Where a `SnippetStore` keeps its snippets
+pub trait SnippetBackend: Send + Sync {
+    fn load(&self) -> EngineResult<Vec<Snippet>>;
+
+    /// Inserts or replaces a snippet, matched by ID
+    fn put(&self, snippet: &Snippet) -> EngineResult<()>;
+
+    /// Removes a snippet; returns false if it did not exist
+    fn remove(&self, id: &str) -> EngineResult<bool>;
+}
+
+/// Snippet storage over a file or another `SnippetBackend`
+pub struct SnippetStore {
+    backend: Box<dyn SnippetBackend>,
+}
+
+impl SnippetStore {
+    /// Opens the personal store at the default per-user location
+    pub fn new() -> Self {
+        Self::with_path(crate::policy::config_dir().join("snippets.json"))
+    }
+
+    pub fn with_path(path: PathBuf) -> Self {
+        Self::with_backend(Box::new(SnippetFile { path }))
+    }
+
+    pub fn with_backend(backend: Box<dyn SnippetBackend>) -> Self {
+        Self { backend }
+    }
+
+    /// Lists snippets sorted by name, optionally only those for a driver
+    pub fn list(&self, driver_id: Option<&str>) -> EngineResult<Vec<Snippet>> {
+        let mut snippets = self.backend.load()?;
+        if let Some(driver_id) = driver_id {
+            snippets.retain(|snippet| snippet.matches_driver(driver_id));
+        }
+        snippets.sort_by_key(|snippet| snippet.name.to_lowercase());
+        Ok(snippets)
+    }
+
+    /// Gets a snippet by ID
+    pub fn get(&self, id: &str) -> EngineResult<Snippet> {
+        self.find(id)?
+            .ok_or_else(|| EngineError::internal(format!("Snippet not found: {}", id)))
+    }
+
+    /// Inserts or replaces a snippet (matched by ID) and returns it
+    ///
+    /// New snippets get an ID when theirs is empty, and a creation time;
+    /// replaced ones keep their creation time.
+    pub fn save(&self, mut snippet: Snippet) -> EngineResult<Snippet> {
+        snippet.validate()?;
+        if snippet.id.is_empty() {
+            snippet.id = Uuid::new_v4().to_string();
+        }
+        snippet.created_at = match self.find(&snippet.id)? {
+            Some(existing) => existing.created_at,
+            None => chrono::Utc::now().to_rfc3339(),
+        };
+
+        self.backend.put(&snippet)?;
+        Ok(snippet)
+    }
+
+    /// Replaces an existing snippet; fails if there is none with its ID
+    pub fn update(&self, snippet: Snippet) -> EngineResult<Snippet> {
+        self.get(&snippet.id)?;
+        self.save(snippet)
+    }
+
+    /// Deletes a snippet; returns false if it did not exist
+    pub fn delete(&self, id: &str) -> EngineResult<bool> {
+        self.backend.remove(id)
+    }
+
+    fn find(&self, id: &str) -> EngineResult<Option<Snippet>> {
+        Ok(self
+            .backend
+            .load()?
+            .into_iter()
+            .find(|snippet| snippet.id == id))
+    }
+}
+
+impl Default for SnippetStore {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Snippets kept together in one JSON file
+struct SnippetFile {
+    path: PathBuf,
+}
+
+impl SnippetFile {
+    fn store(&self, snippets: &[Snippet]) -> EngineResult<()> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent).map_err(|e| {
+                EngineError::internal(format!("Failed to create config directory: {}", e))
+            })?;
+        }
+        let json = serde_json::to_string_pretty(snippets)
+            .map_err(|e| EngineError::internal(format!("Serialization error: {}", e)))?;
+        fs::write(&self.path, json)
+            .map_err(|e| EngineError::internal(format!("Failed to write snippets: {}", e)))
+    }
+}
+
+impl SnippetBackend for SnippetFile {
+    fn load(&self) -> EngineResult<Vec<Snippet>> {
+        let raw = match fs::read_to_string(&self.path) {
+            Ok(raw) => raw,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => {
+                return Err(EngineError::internal(format!(
+                    "Failed to read snippets: {}",
+                    e
+                )))
+            }
+        };
+        serde_json::from_str(&raw)
+            .map_err(|e| EngineError::internal(format!("Invalid snippets file: {}", e)))
+    }
+
+    fn put(&self, snippet: &Snippet) -> EngineResult<()> {
+        let mut snippets = self.load()?;
+        match snippets.iter_mut().find(|existing| existing.id == snippet.id) {
+            Some(existing) => *existing = snippet.clone(),
+            None => snippets.push(snippet.clone()),
+        }
+        self.store(&snippets)
+    }
+
+    fn remove(&self, id: &str) -> EngineResult<bool> {
+        let mut snippets = self.load()?;
+        let before = snippets.len();
+        snippets.retain(|snippet| snippet.id != id);
+        if snippets.len() == before {
+            return Ok(false);
+        }
+        self.store(&snippets)?;
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snippet(name: &str, driver_id: Option<&str>) -> Snippet {
+        Snippet {
+            id: String::new(),
+            name: name.to_string(),
+            description: None,
+            driver_id: driver_id.map(str::to_string),
+            query: "SELECT 1".to_string(),
+            created_at: String::new(),
+            tags: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn saves_filters_and_deletes_snippets() {
+        let path = std::env::temp_dir().join(format!("qoredb-snippets-{}.json", Uuid::new_v4()));
+        let store = SnippetStore::with_path(path.clone());
+
+        let locks = store.save(snippet("Locks", Some("postgres"))).unwrap();
+        store.save(snippet("Active sessions", None)).unwrap();
+        store.save(snippet("Process list", Some("mysql"))).unwrap();
+        assert!(!locks.id.is_empty());
+        assert!(!locks.created_at.is_empty());
+
+        let names = |snippets: Vec<Snippet>| -> Vec<String> {
+            snippets.into_iter().map(|s| s.name).collect()
+        };
+        assert_eq!(
+            names(store.list(Some("postgres")).unwrap()),
+            vec!["Active sessions", "Locks"]
+        );
+        assert_eq!(store.list(None).unwrap().len(), 3);
+
+        let renamed = store
+            .update(Snippet {
+                name: "Blocking locks".to_string(),
+                created_at: String::new(),
+                ..locks.clone()
+            })
+            .unwrap();
+        assert_eq!(renamed.id, locks.id);
+        assert_eq!(renamed.created_at, locks.created_at);
+        assert_eq!(store.get(&locks.id).unwrap().name, "Blocking locks");
+        assert_eq!(store.list(None).unwrap().len(), 3);
+        assert!(store.update(snippet("Unsaved", None)).is_err());
+
+        assert!(store.delete(&locks.id).unwrap());
+        assert!(!store.delete(&locks.id).unwrap());
+        assert_eq!(names(store.list(Some("postgres")).unwrap()), vec!["Active sessions"]);
+
+        fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn reads_snippets_saved_with_driver_and_body() {
+        let legacy: Snippet = serde_json::from_str(
+            r#"{"id":"s1","name":"Row count","driver":"postgres","body":"SELECT count(*) FROM {{table_name}}"}"#,
+        )
+        .unwrap();
+
+        assert_eq!(legacy.driver_id.as_deref(), Some("postgres"));
+        assert_eq!(legacy.render("public.users"), "SELECT count(*) FROM public.users");
+        assert!(legacy.matches_driver("postgres"));
+        assert!(!legacy.matches_driver("mysql"));
+    }
+}
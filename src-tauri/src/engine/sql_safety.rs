@@ -4,6 +4,7 @@ use sqlparser::{
     ast::{Query, Select, SetExpr, Statement},
     dialect::{Dialect, GenericDialect, MySqlDialect, PostgreSqlDialect},
     parser::Parser,
+    tokenizer::{Token, Tokenizer},
 };
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -39,10 +40,154 @@ pub fn analyze_sql(driver_id: &str, sql: &str) -> Result<SqlSafetyAnalysis, Stri
     Ok(analysis)
 }
 
+/// Classifies a filtered delete (`DataEngine::delete_rows_where`).
+///
+/// Always a mutation; without a filter every row is deleted, which is as
+/// dangerous as `TRUNCATE`.
+pub fn analyze_delete_where(filter: Option<&str>) -> SqlSafetyAnalysis {
+    SqlSafetyAnalysis {
+        is_mutation: true,
+        is_dangerous: filter.is_none_or(|filter| filter.trim().is_empty()),
+    }
+}
+
+/// Returns `sql` without its leading comments and whitespace.
+pub fn strip_leading_comments(sql: &str) -> &str {
+    let mut rest = sql.trim_start();
+    loop {
+        if let Some(line) = rest.strip_prefix("--") {
+            rest = line.split_once('\n').map_or("", |(_, after)| after).trim_start();
+        } else if let Some(block) = rest.strip_prefix("/*") {
+            rest = block.split_once("*/").map_or("", |(_, after)| after).trim_start();
+        } else {
+            return rest;
+        }
+    }
+}
+
+/// Returns true if a statement changes the schema (`CREATE`, `ALTER`,
+/// `DROP` or `RENAME`), which can leave cached prepared statements stale.
+pub fn is_schema_change(sql: &str) -> bool {
+    let keyword = strip_leading_comments(sql)
+        .split(|c: char| !c.is_ascii_alphabetic())
+        .next()
+        .unwrap_or("");
+    ["CREATE", "ALTER", "DROP", "RENAME"]
+        .iter()
+        .any(|ddl| keyword.eq_ignore_ascii_case(ddl))
+}
+
+/// Prepends `comment` to a statement as a `/* ... */` block comment.
+///
+/// `/*` and `*/` inside the comment are broken up, so it can neither end
+/// early nor nest (PostgreSQL) and leak text into the statement. The space
+/// after `/*` keeps MySQL from reading `/*!` as an executable comment.
+pub fn prepend_comment(sql: &str, comment: &str) -> String {
+    let comment = comment.replace("*/", "* /").replace("/*", "/ *");
+    format!("/* {} */ {}", comment, sql)
+}
+
+/// Adds a `LIMIT` to a single read query that has none of its own.
+///
+/// Queries with a recursive CTE are wrapped as `SELECT * FROM (...) LIMIT n`;
+/// others get the `LIMIT` appended on its own line, after any trailing
+/// comment. Returns `None` when the query already limits its rows or can't
+/// take a cap safely: locking reads, `SELECT ... INTO`, several statements,
+/// or anything other than a query.
+pub fn cap_rows(driver_id: &str, sql: &str, limit: u64) -> Option<String> {
+    let dialect = dialect_for_driver(driver_id);
+    let statements = Parser::parse_sql(&*dialect, sql.trim()).ok()?;
+    let [Statement::Query(query)] = statements.as_slice() else {
+        return None;
+    };
+    if query.limit_clause.is_some()
+        || query.fetch.is_some()
+        || !query.locks.is_empty()
+        || query.for_clause.is_some()
+        || !query.pipe_operators.is_empty()
+        || query_is_mutation(query)
+    {
+        return None;
+    }
+
+    // Drops the trailing semicolon the parser accepts
+    let statement = match split_statements(driver_id, sql).ok()?.as_slice() {
+        [statement] => statement.clone(),
+        _ => return None,
+    };
+    if query.with.as_ref().is_some_and(|with| with.recursive) {
+        Some(format!(
+            "SELECT * FROM (\n{}\n) AS qoredb_capped LIMIT {}",
+            statement, limit
+        ))
+    } else {
+        Some(format!("{}\nLIMIT {}", statement, limit))
+    }
+}
+
+/// Splits a script into individual statements at top-level `;` boundaries.
+///
+/// Uses the SQL tokenizer, so semicolons inside string literals, quoted
+/// identifiers, comments and dollar-quoted bodies are not separators.
+/// Empty and comment-only statements are dropped.
+pub fn split_statements(driver_id: &str, sql: &str) -> Result<Vec<String>, String> {
+    let dialect = dialect_for_driver(driver_id);
+    let tokens = Tokenizer::new(&*dialect, sql)
+        .tokenize_with_location()
+        .map_err(|err| err.to_string())?;
+
+    // Semicolon locations (1-based line/column, as reported by the tokenizer)
+    let separators: Vec<(u64, u64)> = tokens
+        .iter()
+        .filter(|token| token.token == Token::SemiColon)
+        .map(|token| (token.span.start.line, token.span.start.column))
+        .collect();
+
+    let mut statements = Vec::new();
+    let mut next_separator = separators.iter().peekable();
+    let mut chunk_start = 0;
+    let (mut line, mut column) = (1u64, 1u64);
+
+    for (offset, ch) in sql.char_indices() {
+        if next_separator.peek() == Some(&&(line, column)) {
+            next_separator.next();
+            push_statement(&*dialect, &sql[chunk_start..offset], &mut statements);
+            chunk_start = offset + ch.len_utf8();
+        }
+        if ch == '\n' {
+            line += 1;
+            column = 1;
+        } else {
+            column += 1;
+        }
+    }
+    push_statement(&*dialect, &sql[chunk_start..], &mut statements);
+
+    Ok(statements)
+}
+
+fn push_statement(dialect: &dyn Dialect, chunk: &str, statements: &mut Vec<String>) {
+    let has_content = Tokenizer::new(dialect, chunk)
+        .tokenize()
+        .map(|tokens| {
+            tokens
+                .iter()
+                .any(|token| !matches!(token, Token::Whitespace(_)))
+        })
+        .unwrap_or(true);
+
+    if has_content {
+        statements.push(chunk.trim().to_string());
+    }
+}
+
 fn dialect_for_driver(driver_id: &str) -> Box<dyn Dialect> {
-    if driver_id.eq_ignore_ascii_case("postgres") {
+    if driver_id.eq_ignore_ascii_case("postgres") || driver_id.eq_ignore_ascii_case("cockroachdb") {
         Box::new(PostgreSqlDialect {})
-    } else if driver_id.eq_ignore_ascii_case("mysql") {
+    } else if driver_id.eq_ignore_ascii_case("mysql")
+        || driver_id.eq_ignore_ascii_case("mariadb")
+        || driver_id.eq_ignore_ascii_case("planetscale")
+    {
         Box::new(MySqlDialect {})
     } else {
         Box::new(GenericDialect {})
@@ -143,6 +288,24 @@ fn select_has_into(select: &Select) -> bool {
 mod tests {
     use super::*;
 
+    #[test]
+    fn split_statements_respects_literals_and_comments() {
+        let statements = split_statements(
+            "postgres",
+            "SELECT 'a;b'; -- trailing; comment\nCREATE FUNCTION f() RETURNS int AS $$ SELECT 1; $$ LANGUAGE sql;\n;",
+        )
+        .expect("should tokenize");
+
+        assert_eq!(
+            statements,
+            vec![
+                "SELECT 'a;b'".to_string(),
+                "-- trailing; comment\nCREATE FUNCTION f() RETURNS int AS $$ SELECT 1; $$ LANGUAGE sql"
+                    .to_string(),
+            ]
+        );
+    }
+
     #[test]
     fn postgres_cte_select_is_read_only() {
         let analysis = analyze_sql(
@@ -185,6 +348,24 @@ mod tests {
         assert!(analysis.is_dangerous);
     }
 
+    #[test]
+    fn mariadb_delete_returning_without_where_is_dangerous() {
+        let analysis = analyze_sql("mariadb", "DELETE FROM t RETURNING *")
+            .expect("should parse");
+
+        assert!(analysis.is_mutation);
+        assert!(analysis.is_dangerous);
+    }
+
+    #[test]
+    fn mariadb_insert_returning_is_mutation() {
+        let analysis = analyze_sql("mariadb", "INSERT INTO t (a) VALUES (1) RETURNING id")
+            .expect("should parse");
+
+        assert!(analysis.is_mutation);
+        assert!(!analysis.is_dangerous);
+    }
+
     #[test]
     fn select_into_is_mutation() {
         let analysis = analyze_sql(
@@ -207,6 +388,77 @@ mod tests {
         assert!(analysis.is_dangerous);
     }
 
+    #[test]
+    fn delete_without_filter_is_dangerous() {
+        assert!(analyze_delete_where(None).is_dangerous);
+        assert!(analyze_delete_where(Some("  ")).is_dangerous);
+        assert!(!analyze_delete_where(Some("age > 30")).is_dangerous);
+    }
+
+    #[test]
+    fn detects_schema_changes() {
+        assert!(is_schema_change("ALTER TABLE users ADD COLUMN age int"));
+        assert!(is_schema_change("-- migrate\n/* v2 */ drop index idx"));
+        assert!(is_schema_change("RENAME TABLE a TO b"));
+        assert!(!is_schema_change("SELECT * FROM created"));
+        assert!(!is_schema_change("UPDATE users SET dropped = true"));
+    }
+
+    #[test]
+    fn prepended_comment_cannot_close_early() {
+        let sql = prepend_comment("SELECT 1", "tab */ DROP TABLE users; /*");
+        assert_eq!(sql, "/* tab * / DROP TABLE users; / * */ SELECT 1");
+        assert_eq!(strip_leading_comments(&sql), "SELECT 1");
+
+        let analysis = analyze_sql("postgres", &sql).expect("should parse");
+        assert!(!analysis.is_mutation);
+    }
+
+    #[test]
+    fn cap_rows_appends_limit_to_plain_queries() {
+        let capped = cap_rows(
+            "postgres",
+            "WITH cte AS (SELECT * FROM users) SELECT * FROM cte; -- all users",
+            101,
+        )
+        .expect("should cap");
+
+        assert_eq!(
+            capped,
+            "WITH cte AS (SELECT * FROM users) SELECT * FROM cte\nLIMIT 101"
+        );
+        assert!(analyze_sql("postgres", &capped).is_ok());
+    }
+
+    #[test]
+    fn cap_rows_wraps_recursive_ctes() {
+        let sql = "WITH RECURSIVE n(i) AS (SELECT 1 UNION ALL SELECT i + 1 FROM n) \
+                   SELECT i FROM n -- unbounded";
+        for driver in ["postgres", "mysql"] {
+            let capped = cap_rows(driver, sql, 11).expect("should cap");
+
+            assert!(capped.starts_with("SELECT * FROM (\nWITH RECURSIVE"));
+            assert!(capped.ends_with("\n) AS qoredb_capped LIMIT 11"));
+            assert!(analyze_sql(driver, &capped).is_ok());
+        }
+    }
+
+    #[test]
+    fn cap_rows_leaves_limited_and_non_query_statements() {
+        assert_eq!(cap_rows("postgres", "SELECT * FROM users LIMIT 5", 11), None);
+        assert_eq!(
+            cap_rows("postgres", "SELECT * FROM users FETCH FIRST 5 ROWS ONLY", 11),
+            None
+        );
+        assert_eq!(cap_rows("mysql", "SELECT * FROM users FOR UPDATE", 11), None);
+        assert_eq!(
+            cap_rows("postgres", "SELECT * INTO archive FROM users", 11),
+            None
+        );
+        assert_eq!(cap_rows("postgres", "DELETE FROM users", 11), None);
+        assert_eq!(cap_rows("postgres", "SELECT 1; SELECT 2", 11), None);
+    }
+
     #[test]
     fn mysql_show_tables_is_read_only() {
         let analysis = analyze_sql("mysql", "SHOW TABLES")
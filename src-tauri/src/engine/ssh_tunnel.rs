@@ -4,20 +4,28 @@
 //! Uses the native OpenSSH client for maximum compatibility.
 
 use std::process::Stdio;
+use std::time::Duration;
 use std::{fs, path::PathBuf};
 
 use async_trait::async_trait;
-use tokio::io::AsyncReadExt;
-use tokio::process::{Child, Command};
+use tokio::io::{AsyncBufReadExt, BufReader, Lines};
+use tokio::process::{Child, ChildStderr, Command};
 use tokio::sync::Mutex;
 
 use crate::engine::error::{EngineError, EngineResult};
-use crate::engine::types::{SshAuth, SshHostKeyPolicy, SshTunnelConfig};
+use crate::engine::types::{
+    SshAuth, SshHostKeyPolicy, SshTunnelConfig, DEFAULT_SSH_STARTUP_TIMEOUT_MS,
+};
+
+/// Time allowed to connect to the local port when checking tunnel health
+const HEALTH_PROBE_TIMEOUT_MS: u64 = 1_000;
 
 /// Handle for an active SSH tunnel.
 #[async_trait]
 pub trait SshTunnelHandle: Send {
     fn local_port(&self) -> u16;
+    /// Returns false once the tunnel process has exited
+    fn is_alive(&mut self) -> bool;
     async fn close(&mut self) -> EngineResult<()>;
 }
 
@@ -66,6 +74,19 @@ impl SshTunnel {
         format!("127.0.0.1:{}", self.local_port())
     }
 
+    /// Checks that the tunnel process is still running and its local port
+    /// still accepts connections
+    pub async fn is_healthy(&self) -> bool {
+        if !self.handle.lock().await.is_alive() {
+            return false;
+        }
+        let probe = tokio::net::TcpStream::connect(("127.0.0.1", self.local_port));
+        matches!(
+            tokio::time::timeout(Duration::from_millis(HEALTH_PROBE_TIMEOUT_MS), probe).await,
+            Ok(Ok(_))
+        )
+    }
+
     /// Closes the tunnel
     pub async fn close(&mut self) -> EngineResult<()> {
         let mut handle = self.handle.lock().await;
@@ -159,64 +180,82 @@ impl SshTunnelBackend for OpenSshBackend {
                 message: format!("Failed to spawn SSH process: {}. Is OpenSSH installed?", e),
             })?;
 
-        // Wait until ssh is actually listening on the local port, or fail with stderr.
-        let startup_deadline = tokio::time::Instant::now()
-            + tokio::time::Duration::from_millis(Self::STARTUP_TIMEOUT_MS);
+        // Wait until ssh is actually listening on the local port. stderr is read
+        // concurrently so auth and host key failures show up even on timeout.
+        let mut stderr_lines = process
+            .stderr
+            .take()
+            .map(|stderr| BufReader::new(stderr).lines());
+        let mut stderr_buf: Vec<String> = Vec::new();
+
+        let (startup_timeout, poll_interval) = startup_timing(config);
+        let startup_deadline = tokio::time::sleep(startup_timeout);
+        tokio::pin!(startup_deadline);
+        let mut poll = tokio::time::interval(poll_interval);
 
         loop {
-            // If the process exited early, surface stderr.
-            if let Some(status) = process
-                .try_wait()
-                .map_err(|e| EngineError::SshError {
-                    message: format!("Failed to check SSH process status: {}", e),
-                })?
-            {
-                let stderr = match process.stderr.take() {
-                    Some(mut s) => {
-                        let mut buf = Vec::new();
-                        let _ = s.read_to_end(&mut buf).await;
-                        String::from_utf8_lossy(&buf).trim().to_string()
+            tokio::select! {
+                line = next_stderr_line(&mut stderr_lines), if stderr_lines.is_some() => {
+                    match line {
+                        Some(line) => stderr_buf.push(line),
+                        None => stderr_lines = None,
                     }
-                    None => String::new(),
-                };
-
-                return Err(EngineError::SshError {
-                    message: format!(
-                        "SSH tunnel process exited (status: {}). {}",
-                        status,
-                        if stderr.is_empty() {
-                            "No stderr output was captured.".to_string()
-                        } else {
-                            format!("stderr: {}", stderr)
-                        }
-                    ),
-                });
-            }
+                }
+                status = process.wait() => {
+                    let status = status.map_err(|e| EngineError::SshError {
+                        message: format!("Failed to check SSH process status: {}", e),
+                    })?;
+
+                    // The pipe closes with the process; collect whatever is left.
+                    let _ = tokio::time::timeout(
+                        tokio::time::Duration::from_millis(Self::STDERR_DRAIN_TIMEOUT_MS),
+                        async {
+                            while let Some(line) = next_stderr_line(&mut stderr_lines).await {
+                                stderr_buf.push(line);
+                            }
+                        },
+                    )
+                    .await;
 
-            // Port is open?
-            match tokio::net::TcpStream::connect(("127.0.0.1", local_port)).await {
-                Ok(stream) => {
-                    drop(stream);
-                    break;
+                    return Err(EngineError::SshError {
+                        message: format!(
+                            "SSH tunnel process exited (status: {}). {}",
+                            status,
+                            format_stderr(&stderr_buf)
+                                .unwrap_or_else(|| "No stderr output was captured.".to_string())
+                        ),
+                    });
                 }
-                Err(_) => {
-                    if tokio::time::Instant::now() >= startup_deadline {
-                        return Err(EngineError::SshError {
-                            message: format!(
+                _ = &mut startup_deadline => {
+                    return Err(EngineError::SshError {
+                        message: match format_stderr(&stderr_buf) {
+                            Some(stderr) => format!(
+                                "SSH tunnel did not become ready within {}ms. {}",
+                                startup_timeout.as_millis(),
+                                stderr
+                            ),
+                            None => format!(
                                 "SSH tunnel did not become ready within {}ms. Ensure host key is trusted and OpenSSH supports StrictHostKeyChecking=accept-new.",
-                                Self::STARTUP_TIMEOUT_MS
+                                startup_timeout.as_millis()
                             ),
-                        });
+                        },
+                    });
+                }
+                _ = poll.tick() => {
+                    // Port is open?
+                    if let Ok(stream) =
+                        tokio::net::TcpStream::connect(("127.0.0.1", local_port)).await
+                    {
+                        drop(stream);
+                        break;
                     }
-
-                    tokio::time::sleep(tokio::time::Duration::from_millis(
-                        Self::STARTUP_POLL_INTERVAL_MS,
-                    ))
-                    .await;
                 }
             }
         }
 
+        // Hand the pipe back to the child so later writes from ssh do not fail
+        process.stderr = stderr_lines.map(|lines| lines.into_inner().into_inner());
+
         Ok(Box::new(OpenSshTunnel {
             local_port,
             process: Some(process),
@@ -225,8 +264,44 @@ impl SshTunnelBackend for OpenSshBackend {
 }
 
 impl OpenSshBackend {
-    const STARTUP_TIMEOUT_MS: u64 = 5_000;
-    const STARTUP_POLL_INTERVAL_MS: u64 = 50;
+    const STDERR_DRAIN_TIMEOUT_MS: u64 = 500;
+    /// Lower bound of the probe interval, so a 0 cannot busy-loop
+    const MIN_POLL_INTERVAL_MS: u64 = 10;
+}
+
+/// Startup timeout and port probe interval of a tunnel; a zero timeout
+/// falls back to the default
+fn startup_timing(config: &SshTunnelConfig) -> (Duration, Duration) {
+    let timeout_ms = match config.startup_timeout_ms {
+        0 => DEFAULT_SSH_STARTUP_TIMEOUT_MS,
+        ms => ms,
+    };
+    let poll_ms = config
+        .startup_poll_interval_ms
+        .max(OpenSshBackend::MIN_POLL_INTERVAL_MS);
+    (Duration::from_millis(timeout_ms), Duration::from_millis(poll_ms))
+}
+
+/// Reads the next stderr line, or `None` once the pipe is closed or unreadable
+async fn next_stderr_line(lines: &mut Option<Lines<BufReader<ChildStderr>>>) -> Option<String> {
+    match lines {
+        Some(lines) => lines.next_line().await.ok().flatten(),
+        None => None,
+    }
+}
+
+/// Formats buffered stderr lines for an error message, skipping blank lines
+fn format_stderr(lines: &[String]) -> Option<String> {
+    let lines: Vec<&str> = lines
+        .iter()
+        .map(|line| line.trim())
+        .filter(|line| !line.is_empty())
+        .collect();
+    if lines.is_empty() {
+        None
+    } else {
+        Some(format!("stderr: {}", lines.join("\n")))
+    }
 }
 
 #[async_trait]
@@ -235,6 +310,13 @@ impl SshTunnelHandle for OpenSshTunnel {
         self.local_port
     }
 
+    fn is_alive(&mut self) -> bool {
+        match self.process.as_mut() {
+            Some(process) => matches!(process.try_wait(), Ok(None)),
+            None => false,
+        }
+    }
+
     async fn close(&mut self) -> EngineResult<()> {
         if let Some(mut process) = self.process.take() {
             process.kill().await.map_err(|e| EngineError::SshError {
@@ -326,25 +408,12 @@ fn build_ssh_command(
     Ok(cmd)
 }
 
-fn default_known_hosts_path() -> String {
+pub(crate) fn default_known_hosts_path() -> String {
     // Per-user, app-owned file.
     // Windows: %APPDATA%\QoreDB\ssh\known_hosts
     // Others:  $HOME/.qoredb/ssh/known_hosts
-    if cfg!(windows) {
-        let appdata = std::env::var_os("APPDATA").unwrap_or_else(|| std::env::var_os("USERPROFILE").unwrap_or_default());
-        let mut path = PathBuf::from(appdata);
-        path.push("QoreDB");
-        path.push("ssh");
-        path.push("known_hosts");
-        path.to_string_lossy().to_string()
-    } else {
-        let home = std::env::var_os("HOME").unwrap_or_default();
-        let mut path = PathBuf::from(home);
-        path.push(".qoredb");
-        path.push("ssh");
-        path.push("known_hosts");
-        path.to_string_lossy().to_string()
-    }
+    let path = crate::policy::config_dir().join("ssh").join("known_hosts");
+    path.to_string_lossy().to_string()
 }
 
 fn null_device_path() -> &'static str {
@@ -389,6 +458,9 @@ mod tests {
             connect_timeout_secs: 7,
             keepalive_interval_secs: 11,
             keepalive_count_max: 2,
+            startup_timeout_ms: 5_000,
+            startup_poll_interval_ms: 50,
+            auto_restart: false,
         };
 
         let cmd = build_ssh_command(&cfg, "/tmp/qoredb_known_hosts", 50000, "postgres", 5432)
@@ -404,6 +476,56 @@ mod tests {
         assert!(args.iter().any(|a| a == "127.0.0.1:50000:postgres:5432"));
     }
 
+    #[test]
+    fn startup_deadline_uses_configured_timeout() {
+        let mut cfg: SshTunnelConfig = serde_json::from_value(serde_json::json!({
+            "host": "ssh.example.com",
+            "port": 22,
+            "username": "user",
+            "auth": { "Key": { "private_key_path": "id_ed25519", "passphrase": null } },
+            "host_key_policy": "strict",
+            "known_hosts_path": null,
+            "proxy_jump": null,
+            "connect_timeout_secs": 10,
+            "keepalive_interval_secs": 30,
+            "keepalive_count_max": 3,
+        }))
+        .unwrap();
+        assert_eq!(
+            startup_timing(&cfg),
+            (Duration::from_millis(5_000), Duration::from_millis(50))
+        );
+
+        cfg.startup_timeout_ms = 30_000;
+        cfg.startup_poll_interval_ms = 250;
+        assert_eq!(
+            startup_timing(&cfg),
+            (Duration::from_millis(30_000), Duration::from_millis(250))
+        );
+
+        cfg.startup_timeout_ms = 0;
+        cfg.startup_poll_interval_ms = 0;
+        assert_eq!(
+            startup_timing(&cfg),
+            (Duration::from_millis(5_000), Duration::from_millis(10))
+        );
+    }
+
+    #[test]
+    fn formats_buffered_stderr() {
+        assert_eq!(format_stderr(&[]), None);
+        assert_eq!(format_stderr(&["  ".to_string()]), None);
+        assert_eq!(
+            format_stderr(&[
+                "user@ssh.example.com: Permission denied (publickey).".to_string(),
+                String::new(),
+                "Host key verification failed.".to_string(),
+            ])
+            .as_deref(),
+            Some("stderr: user@ssh.example.com: Permission denied (publickey).\nHost key verification failed.")
+        );
+    }
+
     #[test]
     fn rejects_key_passphrase_for_openssh_backend() {
         let cfg = SshTunnelConfig {
@@ -420,6 +542,9 @@ mod tests {
             connect_timeout_secs: 10,
             keepalive_interval_secs: 30,
             keepalive_count_max: 3,
+            startup_timeout_ms: 5_000,
+            startup_poll_interval_ms: 50,
+            auto_restart: false,
         };
 
         let err = build_ssh_command(&cfg, "/tmp/qoredb_known_hosts", 50000, "postgres", 5432)
@@ -4,12 +4,17 @@
 //! It provides a unified interface for connecting, querying, and managing
 //! database sessions across SQL and NoSQL engines.
 
+use std::collections::HashMap;
+
 use async_trait::async_trait;
+use futures::stream::BoxStream;
 
 use crate::engine::error::EngineResult;
 use crate::engine::types::{
-    CancelSupport, Collection, ConnectionConfig, DriverCapabilities, Namespace, QueryId,
-    QueryResult, RowData, SessionId, TableSchema,
+    CancelSupport, Collection, ConnectionConfig, DriverCapabilities, Namespace, NewColumnDef,
+    NewTableSchema, PlanEstimate, PrivilegeReport, QueryId, QueryResult, RowData, RowLockMode,
+    Routine, RoutineType, ServerQuery, ServerVariable, SessionId, TableSchema, TableSizeInfo,
+    TransactionStatus, Value, VariableScope, WalStatus,
 };
 
 /// Core trait that all database drivers must implement
@@ -25,6 +30,15 @@ pub trait DataEngine: Send + Sync {
     /// Returns a human-readable name for this driver
     fn driver_name(&self) -> &'static str;
 
+    /// Returns the SQL dialect spoken by a session's server
+    ///
+    /// Defaults to `driver_id()`; drivers serving several server flavors
+    /// (MySQL / MariaDB) report the one detected at connect time.
+    async fn dialect_id(&self, session: SessionId) -> &'static str {
+        let _ = session;
+        self.driver_id()
+    }
+
     /// Tests the connection without establishing a persistent session
     ///
     /// Use this to validate credentials before saving a connection.
@@ -38,9 +52,127 @@ pub trait DataEngine: Send + Sync {
     /// Closes a session and releases associated resources
     async fn disconnect(&self, session: SessionId) -> EngineResult<()>;
 
+    /// Moves the connection of session `from` to the ID `to`
+    ///
+    /// Lets a session be reconnected without changing its ID: a fresh session
+    /// is connected and takes over the ID of the stale one. If `to` is still
+    /// connected, the two connections swap IDs, so the stale one can be
+    /// disconnected as `from` afterwards.
+    async fn rebind_session(&self, from: SessionId, to: SessionId) -> EngineResult<()> {
+        let _ = (from, to);
+        Err(crate::engine::error::EngineError::not_supported(
+            "Session reconnects are not supported by this driver"
+        ))
+    }
+
+    /// Sends a cheap round-trip (`SELECT 1`, `PING`) on the session
+    ///
+    /// Used by connection keepalives. Drivers without long-lived connections
+    /// have nothing to keep alive and succeed without contacting the server.
+    async fn ping(&self, session: SessionId) -> EngineResult<()> {
+        let _ = session;
+        Ok(())
+    }
+
     /// Lists all namespaces (databases/schemas) accessible in this session
     async fn list_namespaces(&self, session: SessionId) -> EngineResult<Vec<Namespace>>;
 
+    /// Creates a namespace
+    ///
+    /// A namespace with a `schema` creates that schema (if missing); without
+    /// one, it creates the database. MongoDB creates databases implicitly, so
+    /// there `schema` names the first collection to create.
+    async fn create_namespace(
+        &self,
+        session: SessionId,
+        namespace: &Namespace,
+    ) -> EngineResult<()> {
+        let _ = (session, namespace);
+        Err(crate::engine::error::EngineError::not_supported(
+            "Namespace creation is not supported by this driver"
+        ))
+    }
+
+    /// Drops a namespace: the schema if one is given, otherwise the database
+    ///
+    /// With `cascade`, a schema is dropped along with the objects it
+    /// contains; without it, dropping a non-empty schema fails.
+    async fn drop_namespace(
+        &self,
+        session: SessionId,
+        namespace: &Namespace,
+        cascade: bool,
+    ) -> EngineResult<()> {
+        let _ = (session, namespace, cascade);
+        Err(crate::engine::error::EngineError::not_supported(
+            "Namespace deletion is not supported by this driver"
+        ))
+    }
+
+    /// Creates a table (a collection for MongoDB) from a column definition
+    ///
+    /// Callers should check the definition with `NewTableSchema::validate`
+    /// first; drivers build the DDL from it as-is.
+    async fn create_table(
+        &self,
+        session: SessionId,
+        namespace: &Namespace,
+        schema: &NewTableSchema,
+    ) -> EngineResult<()> {
+        let _ = (session, namespace, schema);
+        Err(crate::engine::error::EngineError::not_supported(
+            "Table creation is not supported by this driver"
+        ))
+    }
+
+    /// Adds a column to an existing table
+    ///
+    /// Callers should check the definition with `NewColumnDef::validate`
+    /// first. Schemaless drivers keep this default.
+    async fn add_column(
+        &self,
+        session: SessionId,
+        namespace: &Namespace,
+        table: &str,
+        column: &NewColumnDef,
+    ) -> EngineResult<()> {
+        let _ = (session, namespace, table, column);
+        Err(crate::engine::error::EngineError::not_supported(
+            "Column changes are not supported by this driver"
+        ))
+    }
+
+    /// Drops a column from a table
+    ///
+    /// Fails with an error naming the column when the table has no such column.
+    async fn drop_column(
+        &self,
+        session: SessionId,
+        namespace: &Namespace,
+        table: &str,
+        column_name: &str,
+    ) -> EngineResult<()> {
+        let _ = (session, namespace, table, column_name);
+        Err(crate::engine::error::EngineError::not_supported(
+            "Column changes are not supported by this driver"
+        ))
+    }
+
+    /// Renames a column of a table, keeping its type and constraints
+    async fn rename_column(
+        &self,
+        session: SessionId,
+        namespace: &Namespace,
+        table: &str,
+        old_name: &str,
+        new_name: &str,
+    ) -> EngineResult<()> {
+        let _ = (session, namespace, table, old_name, new_name);
+        Err(crate::engine::error::EngineError::not_supported(
+            "Column changes are not supported by this driver"
+        ))
+    }
+
     /// Lists all collections (tables/views/collections) in a namespace
     async fn list_collections(
         &self,
@@ -48,6 +180,34 @@ pub trait DataEngine: Send + Sync {
         namespace: &Namespace,
     ) -> EngineResult<Vec<Collection>>;
 
+    /// Lists the collections of every namespace, keyed by namespace
+    ///
+    /// The default implementation calls `list_collections` once per namespace;
+    /// drivers should override it with a single query where possible.
+    async fn list_all_collections(
+        &self,
+        session: SessionId,
+    ) -> EngineResult<HashMap<Namespace, Vec<Collection>>> {
+        let mut collections = HashMap::new();
+        for namespace in self.list_namespaces(session).await? {
+            let items = self.list_collections(session, &namespace).await?;
+            collections.insert(namespace, items);
+        }
+        Ok(collections)
+    }
+
+    /// Lists the stored functions and procedures of a namespace
+    async fn list_routines(
+        &self,
+        session: SessionId,
+        namespace: &Namespace,
+    ) -> EngineResult<Vec<Routine>> {
+        let _ = (session, namespace);
+        Err(crate::engine::error::EngineError::not_supported(
+            "Listing routines is not supported by this driver"
+        ))
+    }
+
     /// Executes a query and returns the result
     ///
     /// For SQL engines: executes SQL statements
@@ -59,6 +219,100 @@ pub trait DataEngine: Send + Sync {
         query_id: QueryId,
     ) -> EngineResult<QueryResult>;
 
+    /// Executes a script that may contain several statements
+    ///
+    /// Returns one result per statement (or result set), in order, and stops
+    /// at the first failing statement. Engines without multi-statement support
+    /// run the query as a single statement.
+    async fn execute_multi(
+        &self,
+        session: SessionId,
+        query: &str,
+        query_id: QueryId,
+    ) -> EngineResult<Vec<QueryResult>> {
+        Ok(vec![self.execute(session, query, query_id).await?])
+    }
+
+    /// Executes a single statement with positional parameters bound in order
+    ///
+    /// Placeholders use the driver's native syntax (`$1` for PostgreSQL,
+    /// `?` for MySQL); see `named_params` to rewrite `:name` placeholders.
+    async fn execute_with_params(
+        &self,
+        session: SessionId,
+        query: &str,
+        params: &[Value],
+        query_id: QueryId,
+    ) -> EngineResult<QueryResult> {
+        let _ = (session, query, params, query_id);
+        Err(crate::engine::error::EngineError::not_supported(
+            "Query parameters are not supported by this driver"
+        ))
+    }
+
+    /// Calls a stored function or procedure with positional arguments
+    ///
+    /// Returns the rows, output parameters or function result produced by
+    /// the call, one `QueryResult` per result set as for `execute_multi`:
+    /// a MySQL procedure may return several.
+    async fn call_routine(
+        &self,
+        session: SessionId,
+        namespace: &Namespace,
+        routine: &str,
+        routine_type: RoutineType,
+        args: &[Value],
+        query_id: QueryId,
+    ) -> EngineResult<Vec<QueryResult>> {
+        let _ = (session, namespace, routine, routine_type, args, query_id);
+        Err(crate::engine::error::EngineError::not_supported(
+            "Calling routines is not supported by this driver"
+        ))
+    }
+
+    /// Executes a query, reporting its rows in batches of at most `batch_size`
+    ///
+    /// Every batch carries the result columns; a statement without rows is
+    /// reported as a single batch with its affected row count. Returns the
+    /// number of rows delivered. The default implementation buffers the whole
+    /// result with `execute` and reports it as one batch; drivers overriding
+    /// `supports_streaming` read rows from a cursor instead.
+    async fn execute_stream(
+        &self,
+        session: SessionId,
+        query: &str,
+        query_id: QueryId,
+        batch_size: usize,
+        on_batch: &(dyn Fn(QueryResult) + Send + Sync),
+    ) -> EngineResult<u64> {
+        let _ = batch_size;
+        let result = self.execute(session, query, query_id).await?;
+        let rows = result.rows.len() as u64;
+        on_batch(result);
+        Ok(rows)
+    }
+
+    /// Bulk-loads rows into a table with the server's bulk protocol
+    /// (`COPY ... FROM STDIN` on PostgreSQL)
+    ///
+    /// Each row holds one value per entry of `columns`. `on_progress` is
+    /// called with the number of rows sent so far. Returns the number of
+    /// rows imported; nothing is imported if any row fails.
+    async fn import_copy(
+        &self,
+        session: SessionId,
+        namespace: &Namespace,
+        table: &str,
+        columns: &[String],
+        rows: BoxStream<'_, EngineResult<Vec<Value>>>,
+        on_progress: &(dyn Fn(u64) + Send + Sync),
+    ) -> EngineResult<u64> {
+        let _ = (session, namespace, table, columns, rows, on_progress);
+        Err(crate::engine::error::EngineError::not_supported(
+            "Bulk import is not supported by this driver"
+        ))
+    }
+
     /// Returns the schema of a table/collection
     ///
     /// Includes column types, nullability, default values, and primary key info.
@@ -69,15 +323,187 @@ pub trait DataEngine: Send + Sync {
         table: &str,
     ) -> EngineResult<TableSchema>;
 
+    /// Returns the schema of every table/collection in a namespace, keyed by name
+    ///
+    /// The default implementation calls `describe_table` once per collection;
+    /// drivers should override it with batched queries where possible.
+    async fn describe_namespace(
+        &self,
+        session: SessionId,
+        namespace: &Namespace,
+    ) -> EngineResult<HashMap<String, TableSchema>> {
+        let mut schemas = HashMap::new();
+        for collection in self.list_collections(session, namespace).await? {
+            let schema = self
+                .describe_table(session, namespace, &collection.name)
+                .await?;
+            schemas.insert(collection.name, schema);
+        }
+        Ok(schemas)
+    }
+
     /// Returns a preview of the table data (first N rows)
+    ///
+    /// With `lock_rows`, the previewed rows are locked for the rest of the
+    /// active transaction (see `lock_rows_query`).
     async fn preview_table(
         &self,
         session: SessionId,
         namespace: &Namespace,
         table: &str,
         limit: u32,
+        lock_rows: Option<RowLockMode>,
     ) -> EngineResult<QueryResult>;
 
+    /// Reads one column of the row identified by primary key
+    ///
+    /// Lets the UI load a value in full after `execute_query` truncated it.
+    async fn fetch_value(
+        &self,
+        session: SessionId,
+        namespace: &Namespace,
+        table: &str,
+        primary_key: &RowData,
+        column: &str,
+    ) -> EngineResult<Value> {
+        let _ = (session, namespace, table, primary_key, column);
+        Err(crate::engine::error::EngineError::not_supported(
+            "Fetching single values is not supported by this driver"
+        ))
+    }
+
+    /// Reads the content of a PostgreSQL large object
+    ///
+    /// Columns holding large object references are reported with the `lo`
+    /// data type and carry the object's OID as an integer.
+    async fn read_large_object(&self, session: SessionId, oid: u32) -> EngineResult<Vec<u8>> {
+        let _ = (session, oid);
+        Err(crate::engine::error::EngineError::not_supported(
+            "Large objects are not supported by this driver"
+        ))
+    }
+
+    /// Rewrites a SELECT so the rows it returns are locked
+    ///
+    /// Locks only last until the end of a transaction, so this fails with
+    /// `EngineError::TransactionError` when no transaction is active.
+    /// MongoDB has no row locks: a transaction with majority read concern is
+    /// the closest equivalent, so document drivers keep this default.
+    async fn lock_rows_query(
+        &self,
+        session: SessionId,
+        query: &str,
+        mode: RowLockMode,
+    ) -> EngineResult<String> {
+        let _ = (session, query, mode);
+        Err(crate::engine::error::EngineError::not_supported(
+            "Row locking is not supported by this driver"
+        ))
+    }
+
+    /// Returns disk usage statistics for every table in the namespace
+    async fn get_table_sizes(
+        &self,
+        session: SessionId,
+        namespace: &Namespace,
+    ) -> EngineResult<Vec<TableSizeInfo>> {
+        let _ = (session, namespace);
+        Err(crate::engine::error::EngineError::not_supported(
+            "Table size statistics are not supported by this driver"
+        ))
+    }
+
+    /// Reports the privileges of the session's user
+    ///
+    /// Meant to be called right after connecting, so the UI can disable
+    /// actions the user is not allowed to perform.
+    async fn check_privileges(&self, session: SessionId) -> EngineResult<PrivilegeReport> {
+        let _ = session;
+        Err(crate::engine::error::EngineError::not_supported(
+            "Privilege checks are not supported by this driver"
+        ))
+    }
+
+    /// Reports the WAL and checkpoint position of the server
+    ///
+    /// Lets DBAs check how far a point-in-time recovery or a standby has
+    /// progressed.
+    async fn get_wal_status(&self, session: SessionId) -> EngineResult<WalStatus> {
+        let _ = session;
+        Err(crate::engine::error::EngineError::not_supported(
+            "WAL inspection is not supported by this driver"
+        ))
+    }
+
+    /// Forces an immediate checkpoint (`CHECKPOINT`)
+    ///
+    /// Usually requires superuser privileges.
+    async fn force_checkpoint(&self, session: SessionId) -> EngineResult<()> {
+        let _ = session;
+        Err(crate::engine::error::EngineError::not_supported(
+            "Checkpoints are not supported by this driver"
+        ))
+    }
+
+    /// Checks whether the server is a read-only standby (a read replica)
+    ///
+    /// Checked right after connecting so that writes to a replica are
+    /// blocked before the server rejects them.
+    async fn is_read_replica(&self, session: SessionId) -> EngineResult<bool> {
+        let _ = session;
+        Ok(false)
+    }
+
+    /// Sets a server variable for the session (`SET statement_timeout = ...`)
+    ///
+    /// Drivers only accept an allowlist of variable names and always send the
+    /// value as a bound parameter, so this cannot be used to inject SQL.
+    async fn set_session_variable(
+        &self,
+        session: SessionId,
+        name: &str,
+        value: &str,
+        scope: VariableScope,
+    ) -> EngineResult<()> {
+        let _ = (session, name, value, scope);
+        Err(crate::engine::error::EngineError::not_supported(
+            "Session variables are not supported by this driver"
+        ))
+    }
+
+    /// Lists server variables visible in `scope`
+    ///
+    /// `filter` is a `LIKE` pattern (`%` and `_` wildcards) on the name.
+    async fn list_server_variables(
+        &self,
+        session: SessionId,
+        scope: VariableScope,
+        filter: Option<&str>,
+    ) -> EngineResult<Vec<ServerVariable>> {
+        let _ = (session, scope, filter);
+        Err(crate::engine::error::EngineError::not_supported(
+            "Server variable listing is not supported by this driver"
+        ))
+    }
+
+    /// Sets any server variable that is not on the driver's blocklist
+    ///
+    /// Unlike `set_session_variable`, names are not limited to an allowlist;
+    /// variables touching the filesystem, logging or server security are
+    /// refused. The value is still sent as a bound parameter.
+    async fn set_server_variable(
+        &self,
+        session: SessionId,
+        name: &str,
+        value: &str,
+        scope: VariableScope,
+    ) -> EngineResult<()> {
+        let _ = (session, name, value, scope);
+        Err(crate::engine::error::EngineError::not_supported(
+            "Setting server variables is not supported by this driver"
+        ))
+    }
+
     /// Cancels a running query for the given session
     async fn cancel(&self, session: SessionId, query_id: Option<QueryId>) -> EngineResult<()> {
         let _ = (session, query_id);
@@ -86,6 +512,133 @@ pub trait DataEngine: Send + Sync {
         ))
     }
 
+    /// Lists the client sessions on the server and their current queries
+    ///
+    /// Lets users find a query started by another client or a previous app
+    /// session, whose `QueryId` is unknown, or a session to terminate.
+    async fn list_server_queries(&self, session: SessionId) -> EngineResult<Vec<ServerQuery>> {
+        let _ = session;
+        Err(crate::engine::error::EngineError::not_supported(
+            "Listing server queries is not supported by this driver"
+        ))
+    }
+
+    /// Sends a notification on `channel` to the sessions listening to it
+    /// (PostgreSQL `NOTIFY`)
+    async fn send_notify(
+        &self,
+        session: SessionId,
+        channel: &str,
+        payload: Option<&str>,
+    ) -> EngineResult<()> {
+        let _ = (session, channel, payload);
+        Err(crate::engine::error::EngineError::not_supported(
+            "Notifications are not supported by this driver"
+        ))
+    }
+
+    /// Cancels the query of a server process by its ID
+    ///
+    /// With `terminate`, the whole backend is disconnected instead, rolling
+    /// back its open transaction.
+    async fn cancel_backend(
+        &self,
+        session: SessionId,
+        pid: i64,
+        terminate: bool,
+    ) -> EngineResult<()> {
+        let _ = (session, pid, terminate);
+        Err(crate::engine::error::EngineError::not_supported(
+            "Cancelling server processes is not supported by this driver"
+        ))
+    }
+
+    /// Drops the prepared statements cached by the session's connections
+    ///
+    /// Drivers clear them on their own after schema-changing DDL; this is
+    /// for DDL run by other clients.
+    async fn clear_statement_cache(&self, session: SessionId) -> EngineResult<()> {
+        let _ = session;
+        Err(crate::engine::error::EngineError::not_supported(
+            "Prepared statement caching is not supported by this driver"
+        ))
+    }
+
+    /// Tries to take the advisory lock `key` without waiting, returning
+    /// whether it was acquired
+    ///
+    /// The lock is held by the session until released or disconnected; a
+    /// lock taken twice must be released twice. `shared` locks only conflict
+    /// with exclusive ones.
+    async fn acquire_advisory_lock(
+        &self,
+        session: SessionId,
+        key: i64,
+        shared: bool,
+    ) -> EngineResult<bool> {
+        let _ = (session, key, shared);
+        Err(crate::engine::error::EngineError::not_supported(
+            "Advisory locks are not supported by this driver"
+        ))
+    }
+
+    /// Waits for the advisory lock `key`, returning false when it was not
+    /// acquired within `timeout_ms` (0 waits indefinitely)
+    async fn acquire_advisory_lock_wait(
+        &self,
+        session: SessionId,
+        key: i64,
+        shared: bool,
+        timeout_ms: u64,
+    ) -> EngineResult<bool> {
+        let _ = (session, key, shared, timeout_ms);
+        Err(crate::engine::error::EngineError::not_supported(
+            "Advisory locks are not supported by this driver"
+        ))
+    }
+
+    /// Releases the advisory lock `key`, returning false when the session
+    /// did not hold it
+    async fn release_advisory_lock(
+        &self,
+        session: SessionId,
+        key: i64,
+        shared: bool,
+    ) -> EngineResult<bool> {
+        let _ = (session, key, shared);
+        Err(crate::engine::error::EngineError::not_supported(
+            "Advisory locks are not supported by this driver"
+        ))
+    }
+
+    /// Runs `EXPLAIN ANALYZE` for a query, reporting each plan line as it arrives.
+    ///
+    /// Runs on a dedicated connection (never the transaction connection) and
+    /// registers `query_id` so the analysis can be stopped through `cancel()`.
+    /// Returns the full plan once the analysis completes.
+    async fn explain_analyze(
+        &self,
+        session: SessionId,
+        query: &str,
+        query_id: QueryId,
+        on_line: &(dyn for<'a> Fn(&'a str) + Send + Sync),
+    ) -> EngineResult<Vec<String>> {
+        let _ = (session, query, query_id, on_line);
+        Err(crate::engine::error::EngineError::not_supported(
+            "EXPLAIN ANALYZE streaming is not supported by this driver"
+        ))
+    }
+
+    /// Returns the planner's top-level cost and row estimates for a query
+    ///
+    /// Runs a plain `EXPLAIN`, so the query itself is not executed.
+    async fn estimate_plan(&self, session: SessionId, query: &str) -> EngineResult<PlanEstimate> {
+        let _ = (session, query);
+        Err(crate::engine::error::EngineError::not_supported(
+            "Plan estimates are not supported by this driver"
+        ))
+    }
+
     /// Reports cancellation support level for this driver.
     fn cancel_support(&self) -> CancelSupport {
         CancelSupport::None
@@ -96,13 +649,40 @@ pub trait DataEngine: Send + Sync {
         true
     }
 
+    /// Reports whether `execute_stream` reads rows incrementally.
+    fn supports_streaming(&self) -> bool {
+        false
+    }
+
+    /// Default server port, pre-filled in the connection form (0 if none).
+    fn default_port(&self) -> u16 {
+        0
+    }
+
+    /// Reports whether namespaces are schemas inside a database.
+    fn supports_schemas(&self) -> bool {
+        false
+    }
+
+    /// Reports whether one session can browse several databases.
+    fn supports_multiple_databases(&self) -> bool {
+        true
+    }
+
+    /// Reports whether a database name is needed to connect.
+    fn requires_database(&self) -> bool {
+        false
+    }
+
     /// Aggregated driver capabilities.
     fn capabilities(&self) -> DriverCapabilities {
         DriverCapabilities {
             transactions: self.supports_transactions(),
             mutations: self.supports_mutations(),
+            returning_keys: self.supports_returning_keys(),
             cancel: self.cancel_support(),
             supports_ssh: self.supports_ssh(),
+            streaming: self.supports_streaming(),
         }
     }
 
@@ -143,6 +723,17 @@ pub trait DataEngine: Send + Sync {
         ))
     }
 
+    /// Reports whether a transaction is currently active on the session.
+    ///
+    /// Lets callers detect transactions left open by a crashed client
+    /// and offer to roll them back.
+    async fn transaction_status(&self, session: SessionId) -> EngineResult<TransactionStatus> {
+        let _ = session;
+        Err(crate::engine::error::EngineError::not_supported(
+            "Transactions are not supported by this driver"
+        ))
+    }
+
     /// Check if the driver supports transactions.
     fn supports_transactions(&self) -> bool {
         false
@@ -175,6 +766,29 @@ pub trait DataEngine: Send + Sync {
         ))
     }
 
+    /// Insert a new row and read it back, with server-generated values.
+    ///
+    /// # Arguments
+    /// * `session` - The session ID
+    /// * `namespace` - The namespace (database/schema) containing the table
+    /// * `table` - The table name
+    /// * `data` - The row data to insert (column name -> value mapping)
+    ///
+    /// # Returns
+    /// QueryResult with affected_rows = 1 and the inserted row in `rows`
+    async fn insert_row_returning(
+        &self,
+        session: SessionId,
+        namespace: &Namespace,
+        table: &str,
+        data: &RowData,
+    ) -> EngineResult<QueryResult> {
+        let _ = (session, namespace, table, data);
+        Err(crate::engine::error::EngineError::not_supported(
+            "Insert operations are not supported by this driver"
+        ))
+    }
+
     /// Update a row identified by primary key.
     ///
     /// # Arguments
@@ -185,7 +799,9 @@ pub trait DataEngine: Send + Sync {
     /// * `data` - The columns to update (column name -> new value mapping)
     ///
     /// # Returns
-    /// QueryResult with affected_rows indicating how many rows were updated
+    /// QueryResult with affected_rows indicating how many rows were updated.
+    /// When `supports_returning_keys()` is true, `rows` also holds the
+    /// primary key values of the updated rows.
     async fn update_row(
         &self,
         session: SessionId,
@@ -209,7 +825,9 @@ pub trait DataEngine: Send + Sync {
     /// * `primary_key` - The primary key columns and their values
     ///
     /// # Returns
-    /// QueryResult with affected_rows indicating how many rows were deleted
+    /// QueryResult with affected_rows indicating how many rows were deleted.
+    /// When `supports_returning_keys()` is true, `rows` also holds the
+    /// primary key values of the deleted rows.
     async fn delete_row(
         &self,
         session: SessionId,
@@ -223,8 +841,61 @@ pub trait DataEngine: Send + Sync {
         ))
     }
 
+    /// Delete every row matching a filter.
+    ///
+    /// # Arguments
+    /// * `session` - The session ID
+    /// * `namespace` - The namespace (database/schema) containing the table
+    /// * `table` - The table name
+    /// * `filter` - SQL condition for `WHERE` (a JSON query document for
+    ///   MongoDB); an empty filter deletes every row
+    ///
+    /// # Returns
+    /// QueryResult with affected_rows indicating how many rows were deleted.
+    async fn delete_rows_where(
+        &self,
+        session: SessionId,
+        namespace: &Namespace,
+        table: &str,
+        filter: &str,
+    ) -> EngineResult<QueryResult> {
+        let _ = (session, namespace, table, filter);
+        Err(crate::engine::error::EngineError::not_supported(
+            "Delete operations are not supported by this driver"
+        ))
+    }
+
+    /// Remove every row of a table, faster than an unfiltered delete.
+    ///
+    /// # Arguments
+    /// * `session` - The session ID
+    /// * `namespace` - The namespace (database/schema) containing the table
+    /// * `table` - The table name
+    /// * `cascade` - Also truncate tables referencing this one through
+    ///   foreign keys, where the database supports it
+    ///
+    /// # Returns
+    /// QueryResult with affected_rows when the database reports a count.
+    async fn truncate_table(
+        &self,
+        session: SessionId,
+        namespace: &Namespace,
+        table: &str,
+        cascade: bool,
+    ) -> EngineResult<QueryResult> {
+        let _ = (session, namespace, table, cascade);
+        Err(crate::engine::error::EngineError::not_supported(
+            "Truncate is not supported by this driver"
+        ))
+    }
+
     /// Check if the driver supports CRUD mutations.
     fn supports_mutations(&self) -> bool {
         false
     }
+
+    /// Check if `update_row`/`delete_row` return the affected primary keys.
+    fn supports_returning_keys(&self) -> bool {
+        false
+    }
 }
@@ -3,7 +3,7 @@
 //! These types provide a normalized representation of database concepts
 //! across SQL and NoSQL engines.
 
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
 use uuid::Uuid;
 
 /// Unique identifier for a database session
@@ -48,10 +48,222 @@ pub struct ConnectionConfig {
     #[serde(skip_serializing)]
     pub password: String,
     pub database: Option<String>,
-    pub ssl: bool,
+    /// TLS mode. Also accepts the legacy boolean `ssl` field.
+    #[serde(alias = "ssl", default, deserialize_with = "deserialize_ssl_mode")]
+    pub ssl_mode: SslMode,
+    /// Optional CA certificate path, used by the verifying SSL modes.
+    #[serde(default)]
+    pub ssl_ca_path: Option<String>,
     pub environment: String,
     pub read_only: bool,
     pub ssh_tunnel: Option<SshTunnelConfig>,
+    /// Seconds a pooled connection may sit idle before it is closed (0 disables)
+    #[serde(default = "default_pool_idle_timeout_secs")]
+    pub pool_idle_timeout_secs: u64,
+    /// Seconds before a pooled connection is recycled (0 disables)
+    #[serde(default = "default_pool_max_lifetime_secs")]
+    pub pool_max_lifetime_secs: u64,
+    /// PlanetScale: query over the HTTP API (port 443) instead of the MySQL protocol
+    #[serde(default)]
+    pub use_http_api: bool,
+    /// PlanetScale: serve reads from PlanetScale Boost caches when available
+    #[serde(default)]
+    pub boost: Option<bool>,
+    /// MongoDB: database holding the user's credentials (default `admin`)
+    #[serde(default)]
+    pub mongo_auth_source: Option<String>,
+    /// Seconds between keepalive pings on an idle session (`None` or 0 disables)
+    #[serde(default)]
+    pub keepalive_query_secs: Option<u64>,
+    /// Custom session name, shown instead of `user@host:db`
+    #[serde(default)]
+    pub label: Option<String>,
+    /// MySQL: connection character set (default `utf8mb4`)
+    #[serde(default)]
+    pub charset: Option<String>,
+    /// Connection string used verbatim instead of the one built from the
+    /// fields above, for options they cannot express (`target_session_attrs`,
+    /// several hosts for failover, ...). Rejected together with an SSH
+    /// tunnel, which works by rewriting the host and port. May hold the
+    /// password, so it is never sent back to the frontend.
+    #[serde(default, skip_serializing)]
+    pub raw_connection_string: Option<String>,
+    /// Retries of the initial connect on transient network errors
+    #[serde(default)]
+    pub retry_policy: Option<RetryPolicy>,
+    /// Prepared statements cached per pooled connection (SQL drivers,
+    /// default `DEFAULT_STATEMENT_CACHE_CAPACITY`, 0 disables)
+    #[serde(default)]
+    pub statement_cache_capacity: Option<usize>,
+}
+
+/// Closes idle connections before typical server-side idle timeouts
+pub const DEFAULT_POOL_IDLE_TIMEOUT_SECS: u64 = 300;
+/// Recycles connections before proxies and servers drop them
+pub const DEFAULT_POOL_MAX_LIFETIME_SECS: u64 = 1800;
+
+/// Backoff for retrying a connect that failed with `ConnectionFailed`
+///
+/// Authentication and other errors are never retried.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct RetryPolicy {
+    /// Connect attempts in total, the first one included
+    pub max_attempts: u32,
+    /// Wait before the first retry, doubled for each further one
+    pub initial_delay_ms: u64,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            initial_delay_ms: 500,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Wait after the failed attempt `attempt` (0-based):
+    /// `initial_delay_ms * 2^attempt`
+    pub fn delay_ms(&self, attempt: u32) -> u64 {
+        self.initial_delay_ms
+            .saturating_mul(1u64.checked_shl(attempt).unwrap_or(u64::MAX))
+    }
+}
+
+/// Least recently used statements are closed beyond this many per connection
+pub const DEFAULT_STATEMENT_CACHE_CAPACITY: usize = 100;
+
+fn default_pool_idle_timeout_secs() -> u64 {
+    DEFAULT_POOL_IDLE_TIMEOUT_SECS
+}
+
+fn default_pool_max_lifetime_secs() -> u64 {
+    DEFAULT_POOL_MAX_LIFETIME_SECS
+}
+
+impl ConnectionConfig {
+    /// Idle timeout for pooled connections, `None` when disabled
+    pub fn pool_idle_timeout(&self) -> Option<std::time::Duration> {
+        (self.pool_idle_timeout_secs > 0)
+            .then(|| std::time::Duration::from_secs(self.pool_idle_timeout_secs))
+    }
+
+    /// Prepared statements kept per pooled connection
+    pub fn statement_cache_capacity(&self) -> usize {
+        self.statement_cache_capacity.unwrap_or(DEFAULT_STATEMENT_CACHE_CAPACITY)
+    }
+
+    /// Interval of the session keepalive pings, `None` when disabled
+    pub fn keepalive_interval(&self) -> Option<std::time::Duration> {
+        self.keepalive_query_secs
+            .filter(|secs| *secs > 0)
+            .map(std::time::Duration::from_secs)
+    }
+
+    /// Raw connection string to connect with, if one is set
+    ///
+    /// Fails when the string does not start with one of the driver's
+    /// `schemes`, or when an SSH tunnel is configured.
+    pub fn connection_string_override(
+        &self,
+        schemes: &[&str],
+    ) -> crate::engine::error::EngineResult<Option<&str>> {
+        use crate::engine::error::EngineError;
+
+        let Some(raw) = self
+            .raw_connection_string
+            .as_deref()
+            .map(str::trim)
+            .filter(|raw| !raw.is_empty())
+        else {
+            return Ok(None);
+        };
+        if self.ssh_tunnel.is_some() {
+            return Err(EngineError::connection_failed(
+                "A raw connection string cannot be combined with an SSH tunnel",
+            ));
+        }
+        let lower = raw.to_ascii_lowercase();
+        if !schemes.iter().any(|scheme| lower.starts_with(scheme)) {
+            return Err(EngineError::connection_failed(format!(
+                "Connection string must start with {}",
+                schemes.join(" or ")
+            )));
+        }
+        Ok(Some(raw))
+    }
+
+    /// MySQL character set of the connection; `utf8` (an alias of `utf8mb3`)
+    /// cannot carry 4-byte characters such as emoji
+    pub fn mysql_charset(&self) -> &str {
+        self.charset
+            .as_deref()
+            .map(str::trim)
+            .filter(|charset| !charset.is_empty())
+            .unwrap_or("utf8mb4")
+    }
+
+    /// Maximum lifetime of pooled connections, `None` when disabled
+    pub fn pool_max_lifetime(&self) -> Option<std::time::Duration> {
+        (self.pool_max_lifetime_secs > 0)
+            .then(|| std::time::Duration::from_secs(self.pool_max_lifetime_secs))
+    }
+}
+
+/// TLS mode for database connections
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SslMode {
+    #[default]
+    Disabled,
+    Preferred,
+    Required,
+    /// Require TLS and verify the server certificate against a CA.
+    VerifyCa,
+    /// Like `VerifyCa`, and also check the server hostname.
+    VerifyIdentity,
+}
+
+impl SslMode {
+    /// Returns true if the mode requires an encrypted connection.
+    pub fn is_required(&self) -> bool {
+        matches!(self, Self::Required | Self::VerifyCa | Self::VerifyIdentity)
+    }
+
+    /// Returns true if the mode verifies the server certificate.
+    pub fn verifies_certificate(&self) -> bool {
+        matches!(self, Self::VerifyCa | Self::VerifyIdentity)
+    }
+}
+
+impl From<bool> for SslMode {
+    fn from(ssl: bool) -> Self {
+        if ssl {
+            Self::Required
+        } else {
+            Self::Disabled
+        }
+    }
+}
+
+/// Accepts either an `SslMode` or the legacy `ssl: bool` flag.
+fn deserialize_ssl_mode<'de, D>(deserializer: D) -> Result<SslMode, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum SslModeRepr {
+        Legacy(bool),
+        Mode(SslMode),
+    }
+
+    Ok(match SslModeRepr::deserialize(deserializer)? {
+        SslModeRepr::Legacy(ssl) => SslMode::from(ssl),
+        SslModeRepr::Mode(mode) => mode,
+    })
 }
 
 /// SSH tunnel configuration
@@ -80,6 +292,31 @@ pub struct SshTunnelConfig {
 
     /// Max number of keepalive failures before disconnect.
     pub keepalive_count_max: u32,
+
+    /// How long to wait for the tunnel to accept connections, in milliseconds.
+    #[serde(default = "default_ssh_startup_timeout_ms")]
+    pub startup_timeout_ms: u64,
+
+    /// How often the local port is probed while the tunnel starts, in milliseconds.
+    #[serde(default = "default_ssh_startup_poll_interval_ms")]
+    pub startup_poll_interval_ms: u64,
+
+    /// Re-open the tunnel and reconnect when a query fails because it went down (opt-in).
+    #[serde(default)]
+    pub auto_restart: bool,
+}
+
+/// Default wait for an SSH tunnel to become ready
+pub const DEFAULT_SSH_STARTUP_TIMEOUT_MS: u64 = 5_000;
+/// Default probe interval while an SSH tunnel starts
+pub const DEFAULT_SSH_STARTUP_POLL_INTERVAL_MS: u64 = 50;
+
+fn default_ssh_startup_timeout_ms() -> u64 {
+    DEFAULT_SSH_STARTUP_TIMEOUT_MS
+}
+
+fn default_ssh_startup_poll_interval_ms() -> u64 {
+    DEFAULT_SSH_STARTUP_POLL_INTERVAL_MS
 }
 
 /// Host key verification policy for SSH.
@@ -115,8 +352,12 @@ pub enum CancelSupport {
 pub struct DriverCapabilities {
     pub transactions: bool,
     pub mutations: bool,
+    /// Whether update/delete results include the affected primary keys
+    pub returning_keys: bool,
     pub cancel: CancelSupport,
     pub supports_ssh: bool,
+    /// Whether `execute_stream` reads rows from a cursor instead of buffering
+    pub streaming: bool,
 }
 
 #[cfg(test)]
@@ -139,13 +380,207 @@ mod tests {
             other => panic!("unexpected auth variant: {other:?}"),
         }
     }
+
+    #[test]
+    fn connection_config_accepts_legacy_ssl_flag() {
+        let json = r#"{"driver":"mysql","host":"localhost","port":3306,"username":"root","password":"","database":null,"ssl":true,"environment":"development","read_only":false,"ssh_tunnel":null}"#;
+        let config: ConnectionConfig = serde_json::from_str(json).expect("should parse");
+        assert_eq!(config.ssl_mode, SslMode::Required);
+        assert!(config.ssl_ca_path.is_none());
+        assert_eq!(
+            config.pool_idle_timeout(),
+            Some(std::time::Duration::from_secs(DEFAULT_POOL_IDLE_TIMEOUT_SECS))
+        );
+        assert_eq!(
+            config.pool_max_lifetime(),
+            Some(std::time::Duration::from_secs(DEFAULT_POOL_MAX_LIFETIME_SECS))
+        );
+    }
+
+    #[test]
+    fn connection_config_accepts_ssl_mode() {
+        let json = r#"{"driver":"mysql","host":"localhost","port":3306,"username":"root","password":"","database":null,"ssl_mode":"verify_identity","ssl_ca_path":"/etc/ssl/ca.pem","environment":"development","read_only":false,"ssh_tunnel":null}"#;
+        let config: ConnectionConfig = serde_json::from_str(json).expect("should parse");
+        assert_eq!(config.ssl_mode, SslMode::VerifyIdentity);
+        assert_eq!(config.ssl_ca_path.as_deref(), Some("/etc/ssl/ca.pem"));
+    }
+
+    #[test]
+    fn validates_raw_connection_strings() {
+        let json = r#"{"driver":"postgres","host":"localhost","port":5432,"username":"app","password":"","database":null,"environment":"development","read_only":false,"ssh_tunnel":null,"raw_connection_string":"postgresql://app@db1,db2/app?target_session_attrs=read-write"}"#;
+        let mut config: ConnectionConfig = serde_json::from_str(json).expect("should parse");
+        let schemes = ["postgres://", "postgresql://"];
+        assert_eq!(
+            config.connection_string_override(&schemes).unwrap(),
+            Some("postgresql://app@db1,db2/app?target_session_attrs=read-write")
+        );
+        assert!(!serde_json::to_string(&config).unwrap().contains("raw_connection_string"));
+
+        assert!(config.connection_string_override(&["mysql://"]).is_err());
+
+        config.raw_connection_string = Some("  ".to_string());
+        assert_eq!(config.connection_string_override(&schemes).unwrap(), None);
+    }
+
+    #[test]
+    fn non_finite_floats_serialize_as_strings() {
+        let row = vec![
+            Value::Float(f64::NAN),
+            Value::Float(f64::INFINITY),
+            Value::Float(f64::NEG_INFINITY),
+            Value::Float(1.5),
+        ];
+        let json = serde_json::to_string(&row).expect("should serialize");
+        assert_eq!(json, r#"["NaN","Infinity","-Infinity",1.5]"#);
+    }
+
+    #[test]
+    fn row_lock_wraps_query() {
+        assert_eq!(
+            RowLockMode::NoKeyUpdate.wrap_query(" SELECT * FROM users WHERE id = 1; "),
+            "SELECT * FROM (SELECT * FROM users WHERE id = 1) __q FOR NO KEY UPDATE"
+        );
+    }
+
+    #[test]
+    fn column_display_size_is_inferred_from_type_names() {
+        assert_eq!(ColumnInfo::display_size_for("int4"), Some(11));
+        assert_eq!(ColumnInfo::display_size_for("BIGINT UNSIGNED"), Some(20));
+        assert_eq!(ColumnInfo::display_size_for("UUID"), Some(36));
+        assert_eq!(ColumnInfo::display_size_for("TEXT"), None);
+    }
+
+    #[test]
+    fn truncates_long_values_on_char_boundaries() {
+        let mut result = QueryResult::empty();
+        result.rows = vec![Row {
+            values: vec![
+                Value::Int(1),
+                Value::Text("héllo".to_string()),
+                Value::Bytes(vec![0; 10]),
+                Value::Text("ok".to_string()),
+            ],
+        }];
+
+        result.truncate_values(2);
+
+        let values = &result.rows[0].values;
+        assert!(matches!(&values[1], Value::Text(text) if text == "h"));
+        assert!(matches!(&values[2], Value::Bytes(bytes) if bytes.len() == 2));
+        assert!(matches!(&values[3], Value::Text(text) if text == "ok"));
+        assert_eq!(
+            result.truncated_values,
+            vec![
+                TruncatedValue { row: 0, column: 1, full_bytes: 6 },
+                TruncatedValue { row: 0, column: 2, full_bytes: 10 },
+            ]
+        );
+    }
+
+    fn text(value: &str) -> Value {
+        Value::Text(value.to_string())
+    }
+
+    #[test]
+    fn maps_data_types_to_logical_types() {
+        assert_eq!(LogicalType::from_data_type("int4"), LogicalType::Int);
+        assert_eq!(LogicalType::from_data_type("BIGINT UNSIGNED"), LogicalType::Int);
+        assert_eq!(LogicalType::from_data_type("tinyint(1)"), LogicalType::Bool);
+        assert_eq!(LogicalType::from_data_type("tinyint(4)"), LogicalType::Int);
+        assert_eq!(LogicalType::from_data_type("double precision"), LogicalType::Float);
+        assert_eq!(
+            LogicalType::from_data_type("timestamp with time zone"),
+            LogicalType::Timestamp
+        );
+        assert_eq!(LogicalType::from_data_type("datetime(6)"), LogicalType::Timestamp);
+        assert_eq!(LogicalType::from_data_type("varchar(255)"), LogicalType::Text);
+        assert_eq!(LogicalType::from_data_type("numeric(10,2)"), LogicalType::Other);
+        assert_eq!(LogicalType::from_data_type("uuid"), LogicalType::Uuid);
+    }
+
+    #[test]
+    fn coerces_text_to_int_and_float() {
+        assert!(matches!(coerce_value(&text(" 42 "), LogicalType::Int), Value::Int(42)));
+        assert!(matches!(coerce_value(&text("-7"), LogicalType::Int), Value::Int(-7)));
+        assert!(matches!(
+            coerce_value(&text("4.2"), LogicalType::Int),
+            Value::Text(t) if t == "4.2"
+        ));
+        assert!(matches!(
+            coerce_value(&text("1.5"), LogicalType::Float),
+            Value::Float(f) if f == 1.5
+        ));
+        assert!(matches!(
+            coerce_value(&text("abc"), LogicalType::Float),
+            Value::Text(t) if t == "abc"
+        ));
+    }
+
+    #[test]
+    fn coerces_text_to_bool() {
+        assert!(matches!(coerce_value(&text("true"), LogicalType::Bool), Value::Bool(true)));
+        assert!(matches!(coerce_value(&text("0"), LogicalType::Bool), Value::Bool(false)));
+        assert!(matches!(coerce_value(&text("OFF"), LogicalType::Bool), Value::Bool(false)));
+        assert!(matches!(
+            coerce_value(&text("maybe"), LogicalType::Bool),
+            Value::Text(t) if t == "maybe"
+        ));
+    }
+
+    #[test]
+    fn coerces_text_to_timestamp() {
+        let expected = chrono::NaiveDate::from_ymd_opt(2024, 3, 1)
+            .unwrap()
+            .and_hms_opt(12, 30, 0)
+            .unwrap();
+
+        for input in [
+            "2024-03-01 12:30:00",
+            "2024-03-01T12:30",
+            "2024-03-01T14:30:00+02:00",
+            "2024-03-01 14:30:00+02",
+        ] {
+            let coerced = coerce_value(&text(input), LogicalType::Timestamp);
+            assert!(matches!(coerced, Value::Timestamp(at) if at == expected), "{input}");
+        }
+        assert!(matches!(
+            coerce_value(&text("2024-03-01"), LogicalType::Timestamp),
+            Value::Timestamp(at) if at == expected.date().and_hms_opt(0, 0, 0).unwrap()
+        ));
+        assert!(matches!(
+            coerce_value(&text("2024-13-01"), LogicalType::Timestamp),
+            Value::Text(t) if t == "2024-13-01"
+        ));
+    }
+
+    #[test]
+    fn coerces_text_to_uuid() {
+        let id = "5f0c6a3e-8d1b-4c2a-9e7f-0a1b2c3d4e5f";
+        assert!(matches!(
+            coerce_value(&text(id), LogicalType::Uuid),
+            Value::Uuid(uuid) if uuid.to_string() == id
+        ));
+        assert!(matches!(
+            coerce_value(&text("42"), LogicalType::Uuid),
+            Value::Text(t) if t == "42"
+        ));
+    }
+
+    #[test]
+    fn leaves_text_columns_and_non_text_values_unchanged() {
+        for target in [LogicalType::Text, LogicalType::Other] {
+            assert!(matches!(coerce_value(&text("42"), target), Value::Text(t) if t == "42"));
+        }
+        assert!(matches!(coerce_value(&Value::Int(1), LogicalType::Bool), Value::Int(1)));
+        assert!(matches!(coerce_value(&Value::Null, LogicalType::Int), Value::Null));
+    }
 }
 
 /// Namespace represents the hierarchy level above collections
 /// - For PostgreSQL: database + schema
 /// - For MySQL: database
 /// - For MongoDB: database
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
 pub struct Namespace {
     pub database: String,
     pub schema: Option<String>,
@@ -175,6 +610,25 @@ pub struct Collection {
     pub collection_type: CollectionType,
 }
 
+/// Stored function or procedure, as reported by `list_routines`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Routine {
+    pub namespace: Namespace,
+    pub name: String,
+    pub routine_type: RoutineType,
+    /// Return type of a function; `None` for procedures
+    pub return_type: Option<String>,
+    /// Argument list as declared, e.g. `IN id integer, OUT total numeric`
+    pub arguments: String,
+}
+
+/// Kind of stored routine
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RoutineType {
+    Function,
+    Procedure,
+}
+
 /// Type of collection
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum CollectionType {
@@ -191,13 +645,137 @@ pub enum Value {
     Null,
     Bool(bool),
     Int(i64),
-    Float(f64),
+    Float(#[serde(serialize_with = "non_finite_float::serialize")] f64),
     Text(String),
+    /// Produced by `coerce_value`; incoming strings always deserialize as `Text`
+    Timestamp(chrono::NaiveDateTime),
+    /// Produced by `coerce_value` for uuid columns, which reject text params
+    Uuid(uuid::Uuid),
     Bytes(#[serde(with = "base64_bytes")] Vec<u8>),
     Json(serde_json::Value),
     Array(Vec<Value>),
 }
 
+impl Value {
+    /// Approximate bytes held in memory, including heap allocations
+    pub fn estimated_size(&self) -> usize {
+        let heap = match self {
+            Value::Text(text) => text.len(),
+            Value::Bytes(bytes) => bytes.len(),
+            Value::Json(json) => json.to_string().len(),
+            Value::Array(values) => values.iter().map(Value::estimated_size).sum(),
+            Value::Null
+            | Value::Bool(_)
+            | Value::Int(_)
+            | Value::Float(_)
+            | Value::Timestamp(_)
+            | Value::Uuid(_) => 0,
+        };
+        std::mem::size_of::<Value>() + heap
+    }
+}
+
+/// Engine-independent category of a column type
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LogicalType {
+    Bool,
+    Int,
+    Float,
+    Timestamp,
+    Text,
+    Uuid,
+    /// Any type values are passed through for, such as numeric or JSON
+    Other,
+}
+
+impl LogicalType {
+    /// Maps a database type name (`int4`, `VARCHAR(255)`, `tinyint(1)`, ...)
+    /// to its logical type
+    pub fn from_data_type(data_type: &str) -> Self {
+        let upper = data_type.trim().to_uppercase();
+        // MySQL reports BOOLEAN columns as TINYINT(1)
+        if upper == "TINYINT(1)" {
+            return LogicalType::Bool;
+        }
+        let base = upper
+            .split('(')
+            .next()
+            .unwrap_or_default()
+            .trim_end_matches(" UNSIGNED")
+            .trim();
+
+        match base {
+            "BOOL" | "BOOLEAN" => LogicalType::Bool,
+            "TINYINT" | "SMALLINT" | "MEDIUMINT" | "INT" | "INTEGER" | "BIGINT" | "INT2"
+            | "INT4" | "INT8" | "SMALLSERIAL" | "SERIAL" | "BIGSERIAL" => LogicalType::Int,
+            "REAL" | "FLOAT" | "FLOAT4" | "FLOAT8" | "DOUBLE" | "DOUBLE PRECISION" => {
+                LogicalType::Float
+            }
+            "DATETIME" | "TIMESTAMPTZ" => LogicalType::Timestamp,
+            _ if base.starts_with("TIMESTAMP") => LogicalType::Timestamp,
+            "TEXT" | "VARCHAR" | "CHAR" | "CHARACTER" | "CHARACTER VARYING" | "BPCHAR"
+            | "NAME" | "CITEXT" | "TINYTEXT" | "MEDIUMTEXT" | "LONGTEXT" | "STRING" => {
+                LogicalType::Text
+            }
+            "UUID" | "UNIQUEIDENTIFIER" => LogicalType::Uuid,
+            _ => LogicalType::Other,
+        }
+    }
+}
+
+/// Timestamp layouts accepted by `coerce_value` besides RFC 3339
+const TIMESTAMP_FORMATS: &[&str] = &[
+    "%Y-%m-%d %H:%M:%S%.f",
+    "%Y-%m-%dT%H:%M:%S%.f",
+    "%Y-%m-%d %H:%M",
+    "%Y-%m-%dT%H:%M",
+];
+
+/// Converts a text value to the variant matching a column's logical type
+///
+/// Cells edited in the grid arrive as strings, which typed columns reject
+/// when bound as text. Values that are not text, or that do not parse as
+/// `target`, are returned unchanged so the server reports the error.
+/// Timestamps with an offset are converted to UTC.
+pub fn coerce_value(raw: &Value, target: LogicalType) -> Value {
+    let Value::Text(text) = raw else {
+        return raw.clone();
+    };
+    let trimmed = text.trim();
+
+    let coerced = match target {
+        LogicalType::Bool => match trimmed.to_lowercase().as_str() {
+            "true" | "t" | "1" | "yes" | "y" | "on" => Some(Value::Bool(true)),
+            "false" | "f" | "0" | "no" | "n" | "off" => Some(Value::Bool(false)),
+            _ => None,
+        },
+        LogicalType::Int => trimmed.parse::<i64>().ok().map(Value::Int),
+        LogicalType::Float => trimmed.parse::<f64>().ok().map(Value::Float),
+        LogicalType::Timestamp => parse_timestamp(trimmed).map(Value::Timestamp),
+        LogicalType::Uuid => uuid::Uuid::parse_str(trimmed).ok().map(Value::Uuid),
+        LogicalType::Text | LogicalType::Other => None,
+    };
+    coerced.unwrap_or_else(|| raw.clone())
+}
+
+fn parse_timestamp(text: &str) -> Option<chrono::NaiveDateTime> {
+    if let Ok(at) = chrono::DateTime::parse_from_rfc3339(text) {
+        return Some(at.naive_utc());
+    }
+    if let Ok(at) = chrono::DateTime::parse_from_str(text, "%Y-%m-%d %H:%M:%S%.f%#z") {
+        return Some(at.naive_utc());
+    }
+    TIMESTAMP_FORMATS
+        .iter()
+        .find_map(|format| chrono::NaiveDateTime::parse_from_str(text, format).ok())
+        .or_else(|| {
+            chrono::NaiveDate::parse_from_str(text, "%Y-%m-%d")
+                .ok()
+                .and_then(|date| date.and_hms_opt(0, 0, 0))
+        })
+}
+
 mod base64_bytes {
     use serde::{Deserialize, Deserializer, Serializer};
     use base64::{Engine, engine::general_purpose::STANDARD};
@@ -218,12 +796,70 @@ mod base64_bytes {
     }
 }
 
+/// JSON has no NaN or infinity: they are sent as the strings `"NaN"`,
+/// `"Infinity"` and `"-Infinity"` instead of failing the whole response
+mod non_finite_float {
+    use serde::Serializer;
+
+    pub fn serialize<S>(value: &f64, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        if value.is_nan() {
+            serializer.serialize_str("NaN")
+        } else if value.is_infinite() {
+            serializer.serialize_str(if *value > 0.0 { "Infinity" } else { "-Infinity" })
+        } else {
+            serializer.serialize_f64(*value)
+        }
+    }
+}
+
 /// Column metadata
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct ColumnInfo {
     pub name: String,
     pub data_type: String,
     pub nullable: bool,
+    /// Maximum display width in characters, when known
+    #[serde(default)]
+    pub display_size: Option<u32>,
+    #[serde(default)]
+    pub is_auto_increment: bool,
+    /// Whether the value is computed by the database (generated column)
+    #[serde(default)]
+    pub is_generated: bool,
+    /// Column comment from the schema, when the column maps to a table
+    #[serde(default)]
+    pub comment: Option<String>,
+    #[serde(default)]
+    pub character_set: Option<String>,
+}
+
+impl ColumnInfo {
+    /// Infers a display width from a type name, for fixed-width types only
+    pub fn display_size_for(data_type: &str) -> Option<u32> {
+        let upper = data_type.to_uppercase();
+        let base = upper.trim_end_matches(" UNSIGNED");
+        match base {
+            "BOOL" | "BOOLEAN" => Some(5),
+            "TINYINT" => Some(4),
+            "INT2" | "SMALLINT" => Some(6),
+            "MEDIUMINT" => Some(8),
+            "INT" | "INT4" | "INTEGER" => Some(11),
+            "INT8" | "BIGINT" => Some(20),
+            "FLOAT4" | "FLOAT" | "REAL" => Some(14),
+            "FLOAT8" | "DOUBLE" => Some(24),
+            "UUID" => Some(36),
+            "DATE" => Some(10),
+            "YEAR" => Some(4),
+            "TIME" => Some(15),
+            "TIMETZ" => Some(21),
+            "TIMESTAMP" | "DATETIME" => Some(26),
+            "TIMESTAMPTZ" => Some(32),
+            _ => None,
+        }
+    }
 }
 
 /// A single row of data (indexed by column order)
@@ -260,6 +896,15 @@ impl Default for RowData {
     }
 }
 
+/// Planner estimate for a query, read from a plain `EXPLAIN`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PlanEstimate {
+    /// Estimated total cost, in the planner's own units
+    pub total_cost: Option<f64>,
+    /// Estimated number of rows returned
+    pub estimated_rows: Option<f64>,
+}
+
 /// Query execution result
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct QueryResult {
@@ -271,6 +916,27 @@ pub struct QueryResult {
     pub affected_rows: Option<u64>,
     /// Execution time in milliseconds
     pub execution_time_ms: f64,
+    /// Planner estimate, when requested with `include_plan_cost`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub plan_estimate: Option<PlanEstimate>,
+    /// Rows were cut off at the `max_rows_without_limit` cap
+    #[serde(default)]
+    pub truncated: bool,
+    /// Approximate memory taken by `rows`, set by `execute_query`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub estimated_memory_bytes: Option<u64>,
+    /// Values cut short by `truncate_values`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub truncated_values: Vec<TruncatedValue>,
+}
+
+/// Position and full size of a value shortened by `QueryResult::truncate_values`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TruncatedValue {
+    pub row: usize,
+    pub column: usize,
+    /// Size of the complete value in bytes
+    pub full_bytes: usize,
 }
 
 impl QueryResult {
@@ -280,6 +946,10 @@ impl QueryResult {
             rows: Vec::new(),
             affected_rows: None,
             execution_time_ms: 0.0,
+            plan_estimate: None,
+            truncated: false,
+            estimated_memory_bytes: None,
+            truncated_values: Vec::new(),
         }
     }
 
@@ -289,10 +959,164 @@ impl QueryResult {
             rows: Vec::new(),
             affected_rows: Some(affected),
             execution_time_ms: time_ms,
+            plan_estimate: None,
+            truncated: false,
+            estimated_memory_bytes: None,
+            truncated_values: Vec::new(),
+        }
+    }
+
+    /// Cuts text and binary values down to `max_bytes`, recording each one
+    /// in `truncated_values`
+    ///
+    /// Text is cut on a character boundary, so it may end up a little shorter.
+    pub fn truncate_values(&mut self, max_bytes: usize) {
+        for (row_index, row) in self.rows.iter_mut().enumerate() {
+            for (column, value) in row.values.iter_mut().enumerate() {
+                let full_bytes = match value {
+                    Value::Text(text) if text.len() > max_bytes => {
+                        let full_bytes = text.len();
+                        let mut end = max_bytes;
+                        while !text.is_char_boundary(end) {
+                            end -= 1;
+                        }
+                        text.truncate(end);
+                        full_bytes
+                    }
+                    Value::Bytes(bytes) if bytes.len() > max_bytes => {
+                        let full_bytes = bytes.len();
+                        bytes.truncate(max_bytes);
+                        full_bytes
+                    }
+                    _ => continue,
+                };
+                self.truncated_values.push(TruncatedValue {
+                    row: row_index,
+                    column,
+                    full_bytes,
+                });
+            }
+        }
+    }
+
+    /// Rows to sample when estimating the size of a result
+    const MEMORY_SAMPLE_ROWS: usize = 10;
+
+    /// Estimates the memory taken by `rows` from the average size of the first rows
+    pub fn estimate_memory_bytes(&self) -> u64 {
+        let sample = &self.rows[..self.rows.len().min(Self::MEMORY_SAMPLE_ROWS)];
+        if sample.is_empty() {
+            return 0;
         }
+        let sample_bytes: usize = sample
+            .iter()
+            .map(|row| {
+                std::mem::size_of::<Row>()
+                    + row.values.iter().map(Value::estimated_size).sum::<usize>()
+            })
+            .sum();
+        let average_row_bytes = (sample_bytes / sample.len()) as u64;
+        average_row_bytes.saturating_mul(self.rows.len() as u64)
     }
 }
 
+/// Row lock requested on a SELECT (`FOR UPDATE`, `FOR SHARE`, ...)
+///
+/// Locks are held until the surrounding transaction ends. MongoDB has no
+/// row locks; the closest equivalent is reading inside a transaction with
+/// majority read concern.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RowLockMode {
+    Update,
+    Share,
+    /// PostgreSQL only: like `Update`, but does not block `KeyShare`
+    NoKeyUpdate,
+    /// PostgreSQL only: only blocks changes to key columns
+    KeyShare,
+}
+
+impl RowLockMode {
+    pub fn sql_clause(&self) -> &'static str {
+        match self {
+            Self::Update => "FOR UPDATE",
+            Self::Share => "FOR SHARE",
+            Self::NoKeyUpdate => "FOR NO KEY UPDATE",
+            Self::KeyShare => "FOR KEY SHARE",
+        }
+    }
+
+    /// Wraps a SELECT so every row it returns is locked
+    pub fn wrap_query(&self, query: &str) -> String {
+        let query = query.trim().trim_end_matches(';').trim_end();
+        format!("SELECT * FROM ({}) __q {}", query, self.sql_clause())
+    }
+}
+
+/// Scope of a `SET` issued through `set_session_variable`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VariableScope {
+    /// Lasts for the session
+    Session,
+    /// Lasts until the end of the active transaction (PostgreSQL `SET LOCAL`)
+    Local,
+    /// Server-wide (MySQL `SET GLOBAL`)
+    Global,
+}
+
+/// Server variable reported by `list_server_variables`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ServerVariable {
+    pub name: String,
+    pub value: String,
+    pub scope: VariableScope,
+}
+
+/// Client session on the server and its current query, as reported by
+/// `list_server_queries`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ServerQuery {
+    /// Server process ID, usable with `cancel_backend`
+    pub pid: i64,
+    pub username: Option<String>,
+    /// Database the session is connected to
+    pub database: Option<String>,
+    /// Client host (and port on MySQL)
+    pub client_addr: Option<String>,
+    /// Backend state (e.g. "active", "idle in transaction"; the command
+    /// such as "Query" or "Sleep" on MySQL)
+    pub state: Option<String>,
+    pub query: Option<String>,
+    /// Time since the current query started
+    pub duration_ms: Option<f64>,
+}
+
+/// Transaction state of a session
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TransactionStatus {
+    /// Whether the session currently holds a dedicated transaction connection
+    pub active: bool,
+    /// Server-reported state of that connection (e.g. "idle in transaction"), if available
+    pub server_state: Option<String>,
+}
+
+/// Statistics of the queries run on a session since it (re)connected
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SessionStats {
+    pub total_queries: u64,
+    pub failed_queries: u64,
+    /// Rows reported as affected by mutations
+    pub total_rows_affected: u64,
+    /// Rows returned by queries
+    pub total_rows_fetched: u64,
+    pub total_execution_time_ms: f64,
+    /// RFC 3339 timestamp of the last (re)connect
+    pub connected_at: String,
+    /// RFC 3339 timestamp of the last query, if any ran
+    pub last_query_at: Option<String>,
+}
+
 /// Table schema metadata
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TableSchema {
@@ -302,6 +1126,51 @@ pub struct TableSchema {
     pub primary_key: Option<Vec<String>>,
     /// Estimated row count (if available)
     pub row_count_estimate: Option<u64>,
+    /// Table comment (if any)
+    #[serde(default)]
+    pub comment: Option<String>,
+}
+
+/// Disk usage statistics for a table or collection
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TableSizeInfo {
+    pub table_name: String,
+    /// Row count (an estimate on PostgreSQL and InnoDB)
+    pub row_count: i64,
+    /// Table, index and TOAST bytes combined
+    pub total_bytes: u64,
+    pub table_bytes: u64,
+    pub index_bytes: u64,
+    /// TOAST storage (PostgreSQL only)
+    pub toast_bytes: Option<u64>,
+}
+
+/// What the connected user is allowed to do, as reported by the server
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PrivilegeReport {
+    pub can_select: bool,
+    pub can_insert: bool,
+    pub can_update: bool,
+    pub can_delete: bool,
+    pub can_create: bool,
+    pub can_drop: bool,
+    pub is_superuser: bool,
+    /// Databases the user can connect to or holds privileges on
+    pub granted_databases: Vec<String>,
+}
+
+/// Write-ahead log and checkpoint position of a server
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WalStatus {
+    /// Current WAL write position, or the last replayed position on a standby
+    pub current_lsn: String,
+    /// Redo position of the last checkpoint
+    pub last_checkpoint_lsn: String,
+    /// Configured point-in-time recovery target, if any
+    pub recovery_target_lsn: Option<String>,
+    pub is_in_recovery: bool,
+    /// Replay lag in seconds (standbys only)
+    pub standby_delay: Option<f64>,
 }
 
 /// Column metadata for table schema
@@ -317,4 +1186,119 @@ pub struct TableColumn {
     pub default_value: Option<String>,
     /// Whether this column is part of the primary key
     pub is_primary_key: bool,
+    /// Column comment (if any)
+    #[serde(default)]
+    pub comment: Option<String>,
+    /// Values accepted by a MySQL `ENUM` or `SET` column
+    #[serde(default)]
+    pub allowed_values: Option<Vec<String>>,
+    /// MySQL character set of a text column; anything but `utf8mb4`
+    /// drops or mangles 4-byte characters such as emoji
+    #[serde(default)]
+    pub charset: Option<String>,
+}
+
+impl TableColumn {
+    pub fn logical_type(&self) -> LogicalType {
+        LogicalType::from_data_type(&self.data_type)
+    }
+}
+
+/// Column definition for a table created with `create_table`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NewColumnDef {
+    pub name: String,
+    /// Data type, in the database's own syntax (e.g. `varchar(255)`)
+    pub data_type: String,
+    pub nullable: bool,
+    /// Default value, as a SQL expression (e.g. `'draft'`, `now()`)
+    pub default_value: Option<String>,
+    pub is_primary_key: bool,
+    pub auto_increment: bool,
+}
+
+impl NewColumnDef {
+    /// Checks the definition before DDL is generated from it
+    ///
+    /// Types and defaults are inserted into the statement as written, so
+    /// they may not contain statement separators or comments.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.name.trim().is_empty() {
+            return Err("Column name is required".to_string());
+        }
+        let data_type = self.data_type.trim();
+        if data_type.is_empty()
+            || !data_type
+                .chars()
+                .all(|c| c.is_alphanumeric() || " _(),.[]".contains(c))
+        {
+            return Err(format!("Invalid data type for column {}", self.name));
+        }
+        if let Some(default_value) = &self.default_value {
+            if self.auto_increment {
+                return Err(format!(
+                    "Column {} cannot have both a default and auto-increment",
+                    self.name
+                ));
+            }
+            if ["--", "/*", ";"].iter().any(|token| default_value.contains(token)) {
+                return Err(format!("Invalid default value for column {}", self.name));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Definition of a table to create with `create_table`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NewTableSchema {
+    pub table_name: String,
+    pub columns: Vec<NewColumnDef>,
+    /// Primary key columns, in order; when unset, the columns flagged
+    /// `is_primary_key` are used
+    pub primary_key: Option<Vec<String>>,
+    pub if_not_exists: bool,
+}
+
+impl NewTableSchema {
+    /// Returns the primary key columns, in key order
+    pub fn primary_key_columns(&self) -> Vec<&str> {
+        match &self.primary_key {
+            Some(columns) => columns.iter().map(String::as_str).collect(),
+            None => self
+                .columns
+                .iter()
+                .filter(|column| column.is_primary_key)
+                .map(|column| column.name.as_str())
+                .collect(),
+        }
+    }
+
+    /// Checks the definition before DDL is generated from it
+    ///
+    /// See `NewColumnDef::validate` for the column checks.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.table_name.trim().is_empty() {
+            return Err("Table name is required".to_string());
+        }
+        if self.columns.is_empty() {
+            return Err("A table needs at least one column".to_string());
+        }
+
+        let mut names = std::collections::HashSet::new();
+        for column in &self.columns {
+            column.validate()?;
+            if !names.insert(column.name.as_str()) {
+                return Err(format!("Duplicate column: {}", column.name));
+            }
+        }
+
+        for key in self.primary_key_columns() {
+            if !names.contains(key) {
+                return Err(format!("Primary key column not found: {}", key));
+            }
+        }
+
+        Ok(())
+    }
 }
@@ -8,12 +8,19 @@ pub mod policy;
 pub mod vault;
 
 use std::sync::Arc;
+use std::time::Instant;
+use tauri::{Manager, WindowEvent};
 use tokio::sync::Mutex;
 
+use engine::drivers::cockroachdb::CockroachDbDriver;
 use engine::drivers::mongodb::MongoDriver;
 use engine::drivers::mysql::MySqlDriver;
+use engine::drivers::neon::NeonDriver;
+use engine::drivers::planetscale::PlanetScaleDriver;
 use engine::drivers::postgres::PostgresDriver;
-use engine::{DriverRegistry, QueryManager, SessionManager};
+use engine::drivers::redis::RedisDriver;
+use engine::export::ExportManager;
+use engine::{DriverRegistry, QueryHistory, QueryManager, SessionManager};
 use policy::SafetyPolicy;
 use vault::VaultLock;
 
@@ -24,15 +31,25 @@ pub struct AppState {
     pub vault_lock: VaultLock,
     pub policy: SafetyPolicy,
     pub query_manager: Arc<QueryManager>,
+    /// Execution times of past queries, used to suggest timeouts
+    pub query_history: Arc<QueryHistory>,
+    /// Last window focus change, used by the vault auto-lock timer
+    pub last_interaction: Arc<std::sync::Mutex<Instant>>,
+    pub export_manager: Arc<ExportManager>,
 }
 
 impl AppState {
     pub fn new() -> Self {
-        let mut registry = DriverRegistry::new();
+        let registry = DriverRegistry::new();
 
         registry.register(Arc::new(PostgresDriver::new()));
         registry.register(Arc::new(MySqlDriver::new()));
         registry.register(Arc::new(MongoDriver::new()));
+        registry.register(Arc::new(RedisDriver::new()));
+        registry.register(Arc::new(CockroachDbDriver::new()));
+        registry.register(Arc::new(PlanetScaleDriver::new()));
+        registry.register(Arc::new(NeonDriver::new()));
+        registry.register_alias("mariadb", "mysql");
 
         let registry = Arc::new(registry);
         let session_manager = Arc::new(SessionManager::new(Arc::clone(&registry)));
@@ -48,6 +65,9 @@ impl AppState {
             vault_lock,
             policy,
             query_manager,
+            query_history: Arc::new(QueryHistory::new()),
+            last_interaction: Arc::new(std::sync::Mutex::new(Instant::now())),
+            export_manager: Arc::new(ExportManager::new()),
         }
     }
 }
@@ -60,51 +80,139 @@ impl Default for AppState {
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
-    observability::init_tracing();
+    observability::init_tracing(&SafetyPolicy::load().logging);
     let state: SharedState = Arc::new(Mutex::new(AppState::new()));
+    let auto_lock_state = Arc::downgrade(&state);
 
     tauri::Builder::default()
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_fs::init())
         .manage(state)
+        .on_window_event(move |window, event| {
+            if let WindowEvent::Focused(focused) = event {
+                vault::auto_lock::on_focus_changed(
+                    window.app_handle().clone(),
+                    auto_lock_state.clone(),
+                    *focused,
+                );
+            }
+        })
         .invoke_handler(tauri::generate_handler![
             // Connection commands
             commands::connection::test_connection,
             commands::connection::test_saved_connection,
+            commands::connection::check_connection_health,
             commands::connection::connect,
             commands::connection::connect_saved_connection,
+            commands::connection::cancel_connect,
+            commands::connection::load_driver_plugin,
+            commands::connection::list_registered_drivers,
+            commands::connection::list_available_drivers,
             commands::connection::disconnect,
+            commands::connection::reconnect_session,
+            commands::connection::reconnect_all_sessions,
             commands::connection::list_sessions,
+            commands::connection::rename_session,
+            commands::connection::set_environment,
+            commands::connection::get_session_stats,
+            commands::connection::check_session_privileges,
+            commands::connection::check_replica_status,
+            commands::connection::check_tunnel_health,
+            commands::connection::set_session_variable,
             // Query commands
             commands::query::execute_query,
+            commands::query::execute_query_params,
+            commands::query::suggest_query_timeout,
+            commands::query::execute_multi_query,
             commands::query::cancel_query,
             commands::query::list_namespaces,
             commands::query::list_collections,
+            commands::query::list_routines,
+            commands::query::call_routine,
+            commands::query::list_all_collections,
             commands::query::describe_table,
+            commands::query::describe_namespace,
+            commands::query::get_table_sizes,
+            commands::query::get_wal_status,
+            commands::query::force_checkpoint,
+            commands::query::list_server_variables,
+            commands::query::set_server_variable,
+            commands::query::list_server_queries,
+            commands::query::cancel_by_pid,
+            commands::query::list_server_sessions,
+            commands::query::terminate_server_session,
+            commands::query::clear_statement_cache,
+            commands::query::send_db_notification,
+            commands::query::create_namespace,
+            commands::query::drop_namespace,
+            commands::query::create_table,
+            commands::query::add_table_column,
+            commands::query::drop_table_column,
+            commands::query::rename_column,
             commands::query::preview_table,
+            commands::query::fetch_full_value,
+            commands::query::read_large_object,
+            // Explain commands
+            commands::explain::explain_analyze_stream,
+            commands::explain::cancel_explain,
+            commands::locks::acquire_advisory_lock,
+            commands::locks::release_advisory_lock,
+            // Export commands
+            commands::export::export_result,
+            commands::export::cancel_export,
+            // Import commands
+            commands::import::import_copy,
             // Transaction commands
             commands::query::begin_transaction,
             commands::query::commit_transaction,
             commands::query::rollback_transaction,
+            commands::query::transaction_status,
             commands::query::supports_transactions,
             // Mutation commands
             commands::mutation::insert_row,
+            commands::mutation::insert_row_returning,
             commands::mutation::update_row,
             commands::mutation::delete_row,
+            commands::mutation::delete_rows_where,
+            commands::mutation::truncate_table,
             commands::mutation::supports_mutations,
+            commands::mutation::supports_returning_keys,
             // Vault commands
             commands::vault::get_vault_status,
             commands::vault::setup_master_password,
+            commands::vault::benchmark_argon2,
             commands::vault::unlock_vault,
             commands::vault::lock_vault,
             commands::vault::save_connection,
             commands::vault::list_saved_connections,
             commands::vault::delete_saved_connection,
+            commands::vault::list_connection_groups,
+            commands::vault::create_connection_group,
+            commands::vault::rename_connection_group,
+            commands::vault::delete_connection_group,
+            commands::vault::move_connection_to_group,
+            commands::vault::list_projects,
+            commands::vault::create_project,
+            commands::vault::delete_project,
             commands::vault::get_connection_credentials,
+            commands::vault::export_connection_uri,
             // Policy commands
             commands::policy::get_safety_policy,
             commands::policy::set_safety_policy,
+            commands::policy::get_log_directory,
+            // Snippet commands
+            commands::snippets::save_snippet,
+            commands::snippets::list_snippets,
+            commands::snippets::get_snippet,
+            commands::snippets::update_snippet,
+            commands::snippets::delete_snippet,
+            commands::snippets::render_snippet,
+            // SSH commands
+            commands::ssh::list_known_hosts,
+            commands::ssh::add_known_host,
+            commands::ssh::remove_known_host,
+            commands::ssh::clear_known_hosts,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
@@ -1,43 +1,265 @@
 //! Logging and observability helpers.
 
-use std::fs;
-use std::path::PathBuf;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::SystemTime;
 
-use tracing_appender::rolling::RollingFileAppender;
+use serde::{Deserialize, Serialize};
 use tracing_subscriber::fmt::format::FmtSpan;
 use tracing_subscriber::EnvFilter;
 
+use crate::policy::SafetyPolicy;
+
 const LOG_FILE_PREFIX: &str = "qoredb.log";
 
-pub fn init_tracing() {
+/// Log file settings, stored under `logging` in the policy config file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LogConfig {
+    /// Log files kept in the log directory, the current one included
+    pub max_files: usize,
+    /// Size at which the current file is rotated, in megabytes (0 disables)
+    pub max_file_size_mb: u64,
+    /// `EnvFilter` directives (e.g. `qoredb=debug`); `RUST_LOG` takes precedence
+    pub log_level: String,
+}
+
+impl Default for LogConfig {
+    fn default() -> Self {
+        Self {
+            max_files: 7,
+            max_file_size_mb: 50,
+            log_level: "qoredb=info,tauri=info".to_string(),
+        }
+    }
+}
+
+pub fn init_tracing(config: &LogConfig) {
     let log_dir = log_directory();
     let _ = fs::create_dir_all(&log_dir);
 
-    let file_appender: RollingFileAppender = tracing_appender::rolling::daily(log_dir, LOG_FILE_PREFIX);
+    let file_writer = RotatingFile::new(log_dir, config);
     let env_filter = EnvFilter::try_from_default_env()
-        .unwrap_or_else(|_| EnvFilter::new("qoredb=info,tauri=info"));
+        .or_else(|_| EnvFilter::try_new(&config.log_level))
+        .unwrap_or_else(|_| EnvFilter::new(LogConfig::default().log_level));
 
     let _ = tracing_subscriber::fmt()
         .with_env_filter(env_filter)
-        .with_writer(file_appender)
+        .with_writer(Mutex::new(file_writer))
         .with_ansi(false)
         .with_span_events(FmtSpan::CLOSE)
         .try_init();
 }
 
-fn log_directory() -> PathBuf {
-    if cfg!(windows) {
-        let appdata = std::env::var_os("APPDATA")
-            .unwrap_or_else(|| std::env::var_os("USERPROFILE").unwrap_or_default());
-        let mut path = PathBuf::from(appdata);
-        path.push("QoreDB");
-        path.push("logs");
-        path
-    } else {
-        let home = std::env::var_os("HOME").unwrap_or_default();
-        let mut path = PathBuf::from(home);
-        path.push(".qoredb");
-        path.push("logs");
-        path
+/// Directory holding the log files
+pub fn log_directory() -> PathBuf {
+    crate::policy::config_dir().join("logs")
+}
+
+/// Log file rotated every day (UTC) and whenever it reaches `max_bytes`
+///
+/// Files are named `qoredb.log.YYYY-MM-DD`, then `qoredb.log.YYYY-MM-DD.1`,
+/// `.2`, ... when a day's file fills up. Old files beyond `max_files` are
+/// deleted each time a file is opened.
+struct RotatingFile {
+    dir: PathBuf,
+    max_bytes: u64,
+    max_files: usize,
+    date: String,
+    index: u32,
+    size: u64,
+    file: Option<File>,
+}
+
+impl RotatingFile {
+    fn new(dir: PathBuf, config: &LogConfig) -> Self {
+        Self {
+            dir,
+            max_bytes: config.max_file_size_mb.saturating_mul(1024 * 1024),
+            max_files: config.max_files.max(1),
+            date: current_date(),
+            index: 0,
+            size: 0,
+            file: None,
+        }
+    }
+
+    fn path(&self) -> PathBuf {
+        let name = match self.index {
+            0 => format!("{}.{}", LOG_FILE_PREFIX, self.date),
+            index => format!("{}.{}.{}", LOG_FILE_PREFIX, self.date, index),
+        };
+        self.dir.join(name)
+    }
+
+    /// Opens the current file, skipping files of the day that are already full
+    fn open(&mut self) -> io::Result<File> {
+        loop {
+            let file = OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(self.path())?;
+            let size = file.metadata()?.len();
+            if self.max_bytes > 0 && size >= self.max_bytes {
+                self.index += 1;
+                continue;
+            }
+            self.size = size;
+            prune_log_files(&self.dir, self.max_files);
+            return Ok(file);
+        }
+    }
+}
+
+impl Write for RotatingFile {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let today = current_date();
+        if today != self.date {
+            self.date = today;
+            self.index = 0;
+            self.file = None;
+        } else if self.max_bytes > 0 && self.size + buf.len() as u64 > self.max_bytes {
+            self.index += 1;
+            self.file = None;
+        }
+
+        let file = match self.file.take() {
+            Some(file) => file,
+            None => self.open()?,
+        };
+        let written = self.file.insert(file).write(buf)?;
+        self.size += written as u64;
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self.file.as_mut() {
+            Some(file) => file.flush(),
+            None => Ok(()),
+        }
+    }
+}
+
+fn current_date() -> String {
+    chrono::Utc::now().format("%Y-%m-%d").to_string()
+}
+
+/// Deletes the oldest `qoredb.log.*` files so that at most `max_files` remain
+///
+/// Failures are ignored: logging must not stop because of a stale file.
+fn prune_log_files(dir: &Path, max_files: usize) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    let files: Vec<(PathBuf, SystemTime)> = entries
+        .flatten()
+        .filter(|entry| {
+            entry
+                .file_name()
+                .to_str()
+                .is_some_and(|name| name.starts_with(LOG_FILE_PREFIX))
+        })
+        .filter_map(|entry| {
+            let modified = entry.metadata().and_then(|m| m.modified()).ok()?;
+            Some((entry.path(), modified))
+        })
+        .collect();
+
+    for path in files_to_prune(files, max_files) {
+        let _ = fs::remove_file(path);
+    }
+}
+
+/// Returns the files beyond the `max_files` most recently modified ones
+fn files_to_prune(mut files: Vec<(PathBuf, SystemTime)>, max_files: usize) -> Vec<PathBuf> {
+    files.sort_by_key(|f| std::cmp::Reverse(f.1));
+    files
+        .into_iter()
+        .skip(max_files)
+        .map(|(path, _)| path)
+        .collect()
+}
+
+/// Logs a query at DEBUG level if the policy allows it
+///
+/// String literals are masked first, and the masked text is also recorded
+/// on the current span's `query_masked` field (when the span declares it).
+pub fn log_queries(policy: &SafetyPolicy, query: &str) {
+    if !policy.log_queries {
+        return;
+    }
+    let masked = mask_string_literals(query);
+    tracing::Span::current().record("query_masked", masked.as_str());
+    tracing::debug!(query = %masked, "Executing query");
+}
+
+/// Replaces the content of single-quoted string literals with `<redacted>`
+///
+/// Handles both `''` and backslash escapes inside literals.
+pub fn mask_string_literals(query: &str) -> String {
+    let mut masked = String::with_capacity(query.len());
+    let mut chars = query.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        masked.push(c);
+        if c != '\'' {
+            continue;
+        }
+
+        // Skip to the closing quote
+        while let Some(c) = chars.next() {
+            match c {
+                '\\' => {
+                    chars.next();
+                }
+                '\'' if chars.peek() == Some(&'\'') => {
+                    chars.next();
+                }
+                '\'' => break,
+                _ => {}
+            }
+        }
+        masked.push_str("<redacted>'");
+    }
+
+    masked
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn masks_string_literals() {
+        assert_eq!(
+            mask_string_literals("SELECT * FROM users WHERE email = 'a@b.c' AND id = 4"),
+            "SELECT * FROM users WHERE email = '<redacted>' AND id = 4"
+        );
+        assert_eq!(
+            mask_string_literals(r"UPDATE t SET a = 'it''s', b = 'x\'y' WHERE c = ''"),
+            "UPDATE t SET a = '<redacted>', b = '<redacted>' WHERE c = '<redacted>'"
+        );
+    }
+
+    #[test]
+    fn prunes_oldest_log_files() {
+        let at = |secs| SystemTime::UNIX_EPOCH + std::time::Duration::from_secs(secs);
+        let files = vec![
+            (PathBuf::from("qoredb.log.2026-01-02"), at(200)),
+            (PathBuf::from("qoredb.log.2026-01-01"), at(100)),
+            (PathBuf::from("qoredb.log.2026-01-03.1"), at(400)),
+            (PathBuf::from("qoredb.log.2026-01-03"), at(300)),
+        ];
+
+        assert_eq!(
+            files_to_prune(files.clone(), 2),
+            vec![
+                PathBuf::from("qoredb.log.2026-01-02"),
+                PathBuf::from("qoredb.log.2026-01-01"),
+            ]
+        );
+        assert!(files_to_prune(files, 7).is_empty());
     }
 }
@@ -7,10 +7,98 @@ use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::PathBuf;
 
+use crate::observability::LogConfig;
+use crate::vault::credentials::Environment;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SafetyPolicy {
+    /// Confirm dangerous statements on production sessions. On unless the
+    /// stored policy or `QOREDB_PROD_REQUIRE_CONFIRMATION` turns it off.
+    #[serde(default = "default_prod_require_confirmation")]
     pub prod_require_confirmation: bool,
     pub prod_block_dangerous_sql: bool,
+    /// Confirm dangerous statements on staging sessions, as on production
+    #[serde(default)]
+    pub staging_require_confirmation: bool,
+    /// Reject dangerous statements on staging sessions outright
+    #[serde(default)]
+    pub staging_block_dangerous_sql: bool,
+    /// Lock the vault after the window has been unfocused this long.
+    #[serde(default)]
+    pub vault_auto_lock_timeout_secs: Option<u64>,
+    /// Host patterns (`*` globs, case-insensitive) always treated as production
+    /// for writes, regardless of the connection's environment label.
+    #[serde(default)]
+    pub protected_host_patterns: Vec<String>,
+    /// Log the text of executed queries at DEBUG level, with string literals
+    /// masked. Off by default since queries may still contain sensitive data.
+    #[serde(default)]
+    pub log_queries: bool,
+    /// Show production sessions by their saved name (or `***@alias`) instead
+    /// of `user@host:db`, in the UI and in logs.
+    #[serde(default)]
+    pub mask_prod_connection_details: bool,
+    /// Prefix queries run without an explicit comment with
+    /// `/* qoredb:query_id=...,tab=... */`, so they can be traced back to a
+    /// tab in `pg_stat_activity` and similar views.
+    #[serde(default)]
+    pub inject_query_comments: bool,
+    /// Cap on the rows returned by a SQL query without its own `LIMIT`.
+    /// `None` (or `0` through the environment) returns every row.
+    #[serde(default = "default_max_rows_without_limit")]
+    pub max_rows_without_limit: Option<u64>,
+    /// Queries whose result is estimated above this many megabytes fail
+    /// instead of being sent to the UI. `None` (or `0` through the
+    /// environment) disables the check.
+    #[serde(default = "default_max_result_memory_mb")]
+    pub max_result_memory_mb: Option<u64>,
+    /// Upper bound on the rows `preview_table` returns, whatever limit the
+    /// UI asks for.
+    #[serde(default = "default_preview_max_rows")]
+    pub preview_max_rows: u32,
+    /// Argon2 cost of the master password hash. `None` uses
+    /// `Argon2Params::default()`. Existing hashes keep the parameters they
+    /// were created with.
+    #[serde(default)]
+    pub argon2_params: Option<Argon2Params>,
+    /// Log file rotation and level, applied at the next start
+    #[serde(default)]
+    pub logging: LogConfig,
+}
+
+/// Argon2id cost parameters
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Argon2Params {
+    pub memory_kib: u32,
+    pub iterations: u32,
+    pub parallelism: u32,
+}
+
+impl Default for Argon2Params {
+    /// OWASP recommendation: 64 MiB, 3 iterations, 4 lanes
+    fn default() -> Self {
+        Self {
+            memory_kib: 65536,
+            iterations: 3,
+            parallelism: 4,
+        }
+    }
+}
+
+fn default_prod_require_confirmation() -> bool {
+    true
+}
+
+fn default_max_rows_without_limit() -> Option<u64> {
+    Some(10_000)
+}
+
+fn default_max_result_memory_mb() -> Option<u64> {
+    Some(512)
+}
+
+fn default_preview_max_rows() -> u32 {
+    10_000
 }
 
 fn env_bool_opt(key: &str) -> Option<bool> {
@@ -22,33 +110,88 @@ fn env_bool_opt(key: &str) -> Option<bool> {
     })
 }
 
-fn config_path() -> PathBuf {
+fn env_u64_opt(key: &str) -> Option<u64> {
+    std::env::var(key)
+        .ok()
+        .and_then(|value| value.trim().parse::<u64>().ok())
+}
+
+fn env_list_opt(key: &str) -> Option<Vec<String>> {
+    std::env::var(key).ok().map(|value| {
+        value
+            .split(',')
+            .map(|item| item.trim().to_string())
+            .filter(|item| !item.is_empty())
+            .collect()
+    })
+}
+
+/// Matches `text` against a pattern where `*` matches any sequence of characters.
+fn glob_matches(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.to_lowercase().chars().collect();
+    let text: Vec<char> = text.to_lowercase().chars().collect();
+
+    let (mut p, mut t) = (0, 0);
+    let mut backtrack: Option<(usize, usize)> = None;
+
+    while t < text.len() {
+        if p < pattern.len() && pattern[p] == '*' {
+            backtrack = Some((p, t));
+            p += 1;
+        } else if p < pattern.len() && pattern[p] == text[t] {
+            p += 1;
+            t += 1;
+        } else if let Some((star_p, star_t)) = backtrack {
+            p = star_p + 1;
+            t = star_t + 1;
+            backtrack = Some((star_p, star_t + 1));
+        } else {
+            return false;
+        }
+    }
+
+    pattern[p..].iter().all(|c| *c == '*')
+}
+
+/// Per-user directory of the app's files
+/// (Windows: `%APPDATA%\QoreDB`, others: `$HOME/.qoredb`)
+pub fn config_dir() -> PathBuf {
     if cfg!(windows) {
         let appdata = std::env::var_os("APPDATA")
             .unwrap_or_else(|| std::env::var_os("USERPROFILE").unwrap_or_default());
-        let mut path = PathBuf::from(appdata);
-        path.push("QoreDB");
-        path.push("config.json");
-        path
+        PathBuf::from(appdata).join("QoreDB")
     } else {
         let home = std::env::var_os("HOME").unwrap_or_default();
-        let mut path = PathBuf::from(home);
-        path.push(".qoredb");
-        path.push("config.json");
-        path
+        PathBuf::from(home).join(".qoredb")
     }
 }
 
+fn config_path() -> PathBuf {
+    config_dir().join("config.json")
+}
+
 fn load_from_file(path: &PathBuf) -> Option<SafetyPolicy> {
     let raw = fs::read_to_string(path).ok()?;
     serde_json::from_str(&raw).ok()
 }
 
 impl SafetyPolicy {
-    fn defaults() -> Self {
+    pub(crate) fn defaults() -> Self {
         Self {
             prod_require_confirmation: true,
             prod_block_dangerous_sql: false,
+            staging_require_confirmation: false,
+            staging_block_dangerous_sql: false,
+            vault_auto_lock_timeout_secs: None,
+            protected_host_patterns: Vec::new(),
+            log_queries: false,
+            mask_prod_connection_details: false,
+            inject_query_comments: false,
+            max_rows_without_limit: default_max_rows_without_limit(),
+            max_result_memory_mb: default_max_result_memory_mb(),
+            preview_max_rows: default_preview_max_rows(),
+            argon2_params: None,
+            logging: LogConfig::default(),
         }
     }
 
@@ -59,15 +202,71 @@ impl SafetyPolicy {
         if let Some(value) = env_bool_opt("QOREDB_PROD_BLOCK_DANGEROUS") {
             self.prod_block_dangerous_sql = value;
         }
+        if let Some(value) = env_bool_opt("QOREDB_STAGING_REQUIRE_CONFIRMATION") {
+            self.staging_require_confirmation = value;
+        }
+        if let Some(value) = env_bool_opt("QOREDB_STAGING_BLOCK_DANGEROUS") {
+            self.staging_block_dangerous_sql = value;
+        }
+        if let Some(value) = env_u64_opt("QOREDB_VAULT_AUTO_LOCK_SECS") {
+            self.vault_auto_lock_timeout_secs = Some(value);
+        }
+        if let Some(value) = env_list_opt("QOREDB_PROTECTED_HOSTS") {
+            self.protected_host_patterns = value;
+        }
+        if let Some(value) = env_bool_opt("QOREDB_LOG_QUERIES") {
+            self.log_queries = value;
+        }
+        if let Some(value) = env_bool_opt("QOREDB_MASK_PROD_CONNECTIONS") {
+            self.mask_prod_connection_details = value;
+        }
+        if let Some(value) = env_bool_opt("QOREDB_INJECT_QUERY_COMMENTS") {
+            self.inject_query_comments = value;
+        }
+        if let Some(value) = env_u64_opt("QOREDB_MAX_ROWS_WITHOUT_LIMIT") {
+            self.max_rows_without_limit = (value > 0).then_some(value);
+        }
+        if let Some(value) = env_u64_opt("QOREDB_MAX_RESULT_MEMORY_MB") {
+            self.max_result_memory_mb = (value > 0).then_some(value);
+        }
+        if let Some(value) = env_u64_opt("QOREDB_PREVIEW_MAX_ROWS") {
+            self.preview_max_rows = u32::try_from(value).unwrap_or(u32::MAX);
+        }
     }
 
     pub fn load() -> Self {
-        let path = config_path();
-        let mut policy = load_from_file(&path).unwrap_or_else(Self::defaults);
+        Self::resolve(load_from_file(&config_path()))
+    }
+
+    /// Builds the effective policy from the stored one
+    fn resolve(stored: Option<SafetyPolicy>) -> Self {
+        let mut policy = stored.unwrap_or_else(Self::defaults);
         policy.apply_env_overrides();
         policy
     }
 
+    /// Clamps the row limit requested for a table preview to
+    /// `preview_max_rows` (at least one row).
+    pub fn preview_limit(&self, requested: u32) -> u32 {
+        requested.min(self.preview_max_rows.max(1))
+    }
+
+    /// Returns true if the host matches one of the protected host patterns.
+    pub fn is_protected_host(&self, host: &str) -> bool {
+        let host = host.trim();
+        self.protected_host_patterns
+            .iter()
+            .map(|pattern| pattern.trim())
+            .filter(|pattern| !pattern.is_empty())
+            .any(|pattern| glob_matches(pattern, host))
+    }
+
+    /// Returns true if writes to a connection go through the protected host
+    /// guards. Local connections are exempt from every guardrail.
+    pub fn is_protected_connection(&self, environment: &str, host: &str) -> bool {
+        Environment::parse(environment) != Some(Environment::Local) && self.is_protected_host(host)
+    }
+
     pub fn save_to_file(&self) -> Result<(), String> {
         let path = config_path();
         if let Some(parent) = path.parent() {
@@ -87,3 +286,63 @@ impl Default for SafetyPolicy {
         Self::load()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_protected_hosts_case_insensitively() {
+        let mut policy = SafetyPolicy::defaults();
+        policy.protected_host_patterns = vec!["*.prod.example.com".to_string(), "db-main".to_string()];
+
+        assert!(policy.is_protected_host("pg1.PROD.example.com"));
+        assert!(policy.is_protected_host("DB-MAIN"));
+        assert!(!policy.is_protected_host("pg1.staging.example.com"));
+        assert!(!policy.is_protected_host("db-main-replica"));
+    }
+
+    #[test]
+    fn local_connections_are_never_protected() {
+        let mut policy = SafetyPolicy::defaults();
+        policy.protected_host_patterns = vec!["localhost".to_string()];
+
+        assert!(policy.is_protected_connection("development", "localhost"));
+        assert!(!policy.is_protected_connection("local", "localhost"));
+    }
+
+    #[test]
+    fn production_confirmation_defaults_on_and_respects_stored_value() {
+        if std::env::var_os("QOREDB_PROD_REQUIRE_CONFIRMATION").is_some() {
+            return;
+        }
+        let legacy: SafetyPolicy =
+            serde_json::from_str(r#"{"prod_block_dangerous_sql": false}"#).unwrap();
+        assert!(SafetyPolicy::resolve(Some(legacy)).prod_require_confirmation);
+
+        let mut stored = SafetyPolicy::defaults();
+        stored.prod_require_confirmation = false;
+        let policy = SafetyPolicy::resolve(Some(stored));
+        assert!(!policy.prod_require_confirmation);
+    }
+
+    #[test]
+    fn preview_limit_is_clamped_to_policy() {
+        let mut policy = SafetyPolicy::defaults();
+        policy.preview_max_rows = 500;
+
+        assert_eq!(policy.preview_limit(100), 100);
+        assert_eq!(policy.preview_limit(1_000_000), 500);
+
+        policy.preview_max_rows = 0;
+        assert_eq!(policy.preview_limit(100), 1);
+    }
+
+    #[test]
+    fn glob_supports_multiple_wildcards() {
+        assert!(glob_matches("*prod*", "eu-prod-1"));
+        assert!(glob_matches("db-*-*.internal", "db-eu-1.internal"));
+        assert!(glob_matches("*", "anything"));
+        assert!(!glob_matches("db-*.internal", "db-eu-1.internal.example"));
+    }
+}
@@ -0,0 +1,83 @@
+//! Vault Auto-Lock
+//!
+//! Locks the vault once the application window has been unfocused for
+//! longer than the policy's `vault_auto_lock_timeout_secs`.
+
+use std::sync::{Arc, Weak};
+use std::time::{Duration, Instant};
+
+use tauri::{AppHandle, Emitter};
+use tokio::sync::Mutex;
+
+use crate::vault::VaultLock;
+use crate::AppState;
+
+/// Event emitted to the frontend when the vault is locked automatically
+pub const VAULT_LOCKED_EVENT: &str = "vault_locked";
+
+/// Handles a window focus change.
+///
+/// Every focus change counts as an interaction and resets the timer.
+/// Losing focus additionally starts a countdown; if no interaction
+/// happened when it expires, the vault is locked.
+pub fn on_focus_changed(app: AppHandle, state: Weak<Mutex<AppState>>, focused: bool) {
+    tauri::async_runtime::spawn(async move {
+        let (last_interaction, timeout_secs) = {
+            let state = match state.upgrade() {
+                Some(state) => state,
+                None => return,
+            };
+            let state = state.lock().await;
+            (
+                Arc::clone(&state.last_interaction),
+                state.policy.vault_auto_lock_timeout_secs,
+            )
+        };
+
+        touch(&last_interaction);
+
+        if focused {
+            return;
+        }
+
+        let timeout = match timeout_secs {
+            Some(secs) => Duration::from_secs(secs),
+            None => return,
+        };
+
+        tokio::time::sleep(timeout).await;
+
+        // Focus came back (or was lost again) since this countdown started.
+        if idle_for(&last_interaction) < timeout {
+            return;
+        }
+
+        let state = match state.upgrade() {
+            Some(state) => state,
+            None => return,
+        };
+        let mut state = state.lock().await;
+
+        // Without a master password the vault could not be unlocked again.
+        if state.vault_lock.is_locked() || !VaultLock::has_master_password().unwrap_or(false) {
+            return;
+        }
+
+        state.vault_lock.lock();
+        tracing::info!(timeout_secs = timeout.as_secs(), "Vault auto-locked after focus loss");
+        let _ = app.emit(VAULT_LOCKED_EVENT, ());
+    });
+}
+
+fn touch(last_interaction: &std::sync::Mutex<Instant>) {
+    if let Ok(mut guard) = last_interaction.lock() {
+        *guard = Instant::now();
+    }
+}
+
+fn idle_for(last_interaction: &std::sync::Mutex<Instant>) -> Duration {
+    last_interaction
+        .lock()
+        .map(|guard| guard.elapsed())
+        .unwrap_or_default()
+}
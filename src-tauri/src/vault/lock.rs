@@ -1,14 +1,21 @@
 //! Vault Lock
 //!
 //! Master password protection for the vault at startup.
+//!
+//! With the encrypted file backend there is no stored hash: the master
+//! password is the file key, and a successful decryption unlocks the vault.
+
+use std::time::{Duration, Instant};
 
 use argon2::{
     password_hash::{rand_core::OsRng, PasswordHash, PasswordHasher, PasswordVerifier, SaltString},
-    Argon2,
+    Algorithm, Argon2, Params, Version,
 };
 use keyring::Entry;
 
 use crate::engine::error::{EngineError, EngineResult};
+use crate::policy::Argon2Params;
+use crate::vault::storage::{self, StorageBackend};
 
 const SERVICE_NAME: &str = "qoredb";
 const MASTER_PASSWORD_KEY: &str = "__master_password_hash__";
@@ -25,6 +32,10 @@ impl VaultLock {
 
     /// Checks if a master password has been set
     pub fn has_master_password() -> EngineResult<bool> {
+        if storage::active_backend() == StorageBackend::EncryptedFile {
+            return Ok(storage::file_vault_exists());
+        }
+
         let entry = Entry::new(SERVICE_NAME, MASTER_PASSWORD_KEY)
             .map_err(|e| EngineError::internal(format!("Keyring error: {}", e)))?;
 
@@ -35,11 +46,43 @@ impl VaultLock {
         }
     }
 
+    /// Builds the Argon2id hasher for new master password hashes
+    fn hasher(params: &Argon2Params) -> EngineResult<Argon2<'static>> {
+        let params = Params::new(params.memory_kib, params.iterations, params.parallelism, None)
+            .map_err(|e| EngineError::internal(format!("Invalid Argon2 parameters: {}", e)))?;
+        Ok(Argon2::new(Algorithm::Argon2id, Version::V0x13, params))
+    }
+
+    /// Hashes a throwaway password with `params` and returns how long it took
+    pub fn benchmark(params: &Argon2Params) -> EngineResult<Duration> {
+        let argon2 = Self::hasher(params)?;
+        let salt = SaltString::generate(&mut OsRng);
+
+        let start = Instant::now();
+        argon2
+            .hash_password(b"qoredb-benchmark", &salt)
+            .map_err(|e| EngineError::internal(format!("Hashing error: {}", e)))?;
+        Ok(start.elapsed())
+    }
+
     /// Sets up a new master password
-    pub fn setup_master_password(&mut self, password: &str) -> EngineResult<()> {
+    ///
+    /// `params` sets the cost of the keychain hash; the encrypted file backend
+    /// derives its key separately.
+    pub fn setup_master_password(
+        &mut self,
+        password: &str,
+        params: &Argon2Params,
+    ) -> EngineResult<()> {
+        if storage::active_backend() == StorageBackend::EncryptedFile {
+            storage::set_file_vault_password(password)?;
+            self.is_unlocked = true;
+            return Ok(());
+        }
+
         // Hash the password with Argon2
         let salt = SaltString::generate(&mut OsRng);
-        let argon2 = Argon2::default();
+        let argon2 = Self::hasher(params)?;
         
         let hash = argon2
             .hash_password(password.as_bytes(), &salt)
@@ -60,6 +103,12 @@ impl VaultLock {
 
     /// Attempts to unlock the vault with the given password
     pub fn unlock(&mut self, password: &str) -> EngineResult<bool> {
+        if storage::active_backend() == StorageBackend::EncryptedFile {
+            let unlocked = storage::open_file_vault(password)?;
+            self.is_unlocked |= unlocked;
+            return Ok(unlocked);
+        }
+
         let entry = Entry::new(SERVICE_NAME, MASTER_PASSWORD_KEY)
             .map_err(|e| EngineError::internal(format!("Keyring error: {}", e)))?;
 
@@ -70,6 +119,7 @@ impl VaultLock {
         let parsed_hash = PasswordHash::new(&stored_hash)
             .map_err(|e| EngineError::internal(format!("Invalid stored hash: {}", e)))?;
 
+        // Verification uses the algorithm and parameters stored in the hash
         let argon2 = Argon2::default();
         
         if argon2.verify_password(password.as_bytes(), &parsed_hash).is_ok() {
@@ -83,6 +133,7 @@ impl VaultLock {
     /// Locks the vault
     pub fn lock(&mut self) {
         self.is_unlocked = false;
+        storage::close_file_vault();
     }
 
     /// Checks if the vault is currently unlocked
@@ -97,6 +148,12 @@ impl VaultLock {
 
     /// Removes the master password (requires current password)
     pub fn remove_master_password(&mut self, password: &str) -> EngineResult<()> {
+        if storage::active_backend() == StorageBackend::EncryptedFile {
+            return Err(EngineError::internal(
+                "The encrypted vault file cannot be used without a master password",
+            ));
+        }
+
         // Verify current password first
         if !self.unlock(password)? {
             return Err(EngineError::auth_failed("Invalid password"));
@@ -127,3 +184,38 @@ impl Default for VaultLock {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hashes_with_configured_params() {
+        let params = Argon2Params {
+            memory_kib: 1024,
+            iterations: 1,
+            parallelism: 1,
+        };
+        let salt = SaltString::generate(&mut OsRng);
+        let hash = VaultLock::hasher(&params)
+            .unwrap()
+            .hash_password(b"secret", &salt)
+            .unwrap()
+            .to_string();
+        assert!(hash.starts_with("$argon2id$v=19$m=1024,t=1,p=1$"));
+
+        let parsed = PasswordHash::new(&hash).unwrap();
+        assert!(Argon2::default().verify_password(b"secret", &parsed).is_ok());
+        assert!(Argon2::default().verify_password(b"wrong", &parsed).is_err());
+    }
+
+    #[test]
+    fn rejects_invalid_params() {
+        let params = Argon2Params {
+            memory_kib: 1024,
+            iterations: 0,
+            parallelism: 1,
+        };
+        assert!(VaultLock::benchmark(&params).is_err());
+    }
+}
@@ -1,11 +1,14 @@
 //! Vault Module
 //!
-//! Secure credential storage using OS-native keychain.
+//! Secure credential storage using OS-native keychain, with an encrypted
+//! file fallback when no keychain is available.
 
+pub mod auto_lock;
 pub mod credentials;
 pub mod lock;
+pub mod snippets;
 pub mod storage;
 
 pub use credentials::SavedConnection;
 pub use lock::VaultLock;
-pub use storage::VaultStorage;
+pub use storage::{StorageBackend, VaultStorage};
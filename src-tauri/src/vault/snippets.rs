@@ -0,0 +1,112 @@
+//! Vault Snippets
+//!
+//! Keeps the query snippets shared within a project in the vault, next to
+//! the project's saved connections. Used through `engine::snippets::SnippetStore`.
+
+use crate::engine::error::{EngineError, EngineResult};
+use crate::engine::snippets::{Snippet, SnippetBackend};
+use crate::vault::storage::{delete_secret, project_service_name, read_secret, write_secret};
+
+/// Key of the snippet ID list
+const SNIPPETS_KEY: &str = "__snippets__";
+
+/// Vault storage for the snippets of a project
+pub struct SnippetStorage {
+    project_id: String,
+}
+
+impl SnippetStorage {
+    pub fn new(project_id: &str) -> Self {
+        Self {
+            project_id: project_id.to_string(),
+        }
+    }
+
+    fn service_name(&self) -> String {
+        project_service_name(&self.project_id)
+    }
+
+    fn snippet_key(&self, snippet_id: &str) -> String {
+        format!("snippet_{}", snippet_id)
+    }
+
+    /// Deletes every snippet of the project
+    pub fn delete_all(&self) -> EngineResult<()> {
+        let service = self.service_name();
+        for id in self.list_ids()? {
+            let _ = delete_secret(&service, &self.snippet_key(&id));
+        }
+        let _ = delete_secret(&service, SNIPPETS_KEY);
+        Ok(())
+    }
+
+    fn get(&self, snippet_id: &str) -> EngineResult<Option<Snippet>> {
+        let Some(snippet_json) = read_secret(&self.service_name(), &self.snippet_key(snippet_id))
+            .map_err(|e| EngineError::internal(format!("Failed to get snippet: {}", e)))?
+        else {
+            return Ok(None);
+        };
+
+        serde_json::from_str(&snippet_json)
+            .map(Some)
+            .map_err(|e| EngineError::internal(format!("Invalid snippet JSON in keyring: {}", e)))
+    }
+
+    fn list_ids(&self) -> EngineResult<Vec<String>> {
+        match read_secret(&self.service_name(), SNIPPETS_KEY)
+            .map_err(|e| EngineError::internal(format!("Failed to get snippets: {}", e)))?
+        {
+            Some(ids_json) => serde_json::from_str(&ids_json).map_err(|e| {
+                EngineError::internal(format!("Invalid snippet list JSON in keyring: {}", e))
+            }),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    fn save_ids(&self, ids: &[String]) -> EngineResult<()> {
+        let ids_json = serde_json::to_string(ids)
+            .map_err(|e| EngineError::internal(format!("Serialization error: {}", e)))?;
+
+        write_secret(&self.service_name(), SNIPPETS_KEY, &ids_json)
+            .map_err(|e| EngineError::internal(format!("Failed to save snippets: {}", e)))
+    }
+}
+
+impl SnippetBackend for SnippetStorage {
+    fn load(&self) -> EngineResult<Vec<Snippet>> {
+        let mut snippets = Vec::new();
+        for id in self.list_ids()? {
+            if let Ok(Some(snippet)) = self.get(&id) {
+                snippets.push(snippet);
+            }
+        }
+        Ok(snippets)
+    }
+
+    fn put(&self, snippet: &Snippet) -> EngineResult<()> {
+        let snippet_json = serde_json::to_string(snippet)
+            .map_err(|e| EngineError::internal(format!("Serialization error: {}", e)))?;
+
+        write_secret(&self.service_name(), &self.snippet_key(&snippet.id), &snippet_json)
+            .map_err(|e| EngineError::internal(format!("Failed to save snippet: {}", e)))?;
+
+        let mut ids = self.list_ids()?;
+        if !ids.contains(&snippet.id) {
+            ids.push(snippet.id.clone());
+            self.save_ids(&ids)?;
+        }
+        Ok(())
+    }
+
+    fn remove(&self, id: &str) -> EngineResult<bool> {
+        let mut ids = self.list_ids()?;
+        if !ids.iter().any(|existing| existing == id) {
+            return Ok(false);
+        }
+
+        let _ = delete_secret(&self.service_name(), &self.snippet_key(id));
+        ids.retain(|existing| existing != id);
+        self.save_ids(&ids)?;
+        Ok(true)
+    }
+}
@@ -1,14 +1,347 @@
 //! Vault Storage
 //!
 //! Secure storage for database credentials using OS keychain.
-
+//!
+//! When no keychain is available (typically headless Linux and CI), secrets
+//! go to an AES-GCM encrypted file instead, keyed off the master password.
+//! The keychain stays the default whenever it works.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::{Mutex, OnceLock};
+
+use aes_gcm::aead::{Aead, KeyInit};
+use aes_gcm::{Aes256Gcm, Nonce};
+use argon2::Argon2;
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
 use keyring::Entry;
+use rand::RngCore;
 use serde::{Deserialize, Serialize};
+use zeroize::Zeroizing;
 
 use crate::engine::error::{EngineError, EngineResult};
-use crate::vault::credentials::{SavedConnection, StoredCredentials};
+use crate::vault::credentials::{
+    ConnectionGroup, ProjectEntry, SavedConnection, StoredCredentials,
+};
+use crate::vault::snippets::SnippetStorage;
 
 const SERVICE_PREFIX: &str = "qoredb";
+const PROBE_KEY: &str = "__backend_probe__";
+/// Key of the project list, stored in the base service (not project-scoped)
+const PROJECTS_KEY: &str = "__projects__";
+const VAULT_FILE_VERSION: u32 = 1;
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 12;
+
+/// Where vault secrets are stored
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StorageBackend {
+    /// OS keychain (default)
+    Keychain,
+    /// AES-GCM encrypted file, used when the keychain is unavailable
+    EncryptedFile,
+}
+
+static BACKEND: OnceLock<StorageBackend> = OnceLock::new();
+
+/// Key of the unlocked vault file, `None` while locked
+static FILE_KEY: Mutex<Option<FileKey>> = Mutex::new(None);
+
+struct FileKey {
+    salt: [u8; SALT_LEN],
+    key: Zeroizing<[u8; 32]>,
+}
+
+/// Returns the backend in use, probing the keychain on first call
+pub fn active_backend() -> StorageBackend {
+    *BACKEND.get_or_init(|| {
+        let probe =
+            Entry::new(SERVICE_PREFIX, PROBE_KEY).and_then(|entry| match entry.get_password() {
+                Ok(_) | Err(keyring::Error::NoEntry) => Ok(()),
+                Err(e) => Err(e),
+            });
+
+        match probe {
+            Err(keyring::Error::PlatformFailure(e)) | Err(keyring::Error::NoStorageAccess(e)) => {
+                tracing::warn!(
+                    error = %e,
+                    "OS keychain unavailable, using the encrypted vault file"
+                );
+                StorageBackend::EncryptedFile
+            }
+            _ => StorageBackend::Keychain,
+        }
+    })
+}
+
+/// Reads a secret from the active backend
+pub(crate) fn read_secret(service: &str, key: &str) -> EngineResult<Option<String>> {
+    match active_backend() {
+        StorageBackend::Keychain => {
+            let entry = Entry::new(service, key)
+                .map_err(|e| EngineError::internal(format!("Keyring error: {}", e)))?;
+
+            match entry.get_password() {
+                Ok(value) => Ok(Some(value)),
+                Err(keyring::Error::NoEntry) => Ok(None),
+                Err(e) => Err(EngineError::internal(format!("Keyring error: {}", e))),
+            }
+        }
+        StorageBackend::EncryptedFile => {
+            if !file_vault_exists() {
+                return Ok(None);
+            }
+            let guard = FILE_KEY.lock().unwrap();
+            let file_key = guard
+                .as_ref()
+                .ok_or_else(|| EngineError::internal("Vault file is locked"))?;
+            let secrets = read_vault_file(file_key)?;
+            Ok(secrets.get(&secret_id(service, key)).cloned())
+        }
+    }
+}
+
+/// Writes a secret to the active backend
+pub(crate) fn write_secret(service: &str, key: &str, value: &str) -> EngineResult<()> {
+    match active_backend() {
+        StorageBackend::Keychain => {
+            let entry = Entry::new(service, key)
+                .map_err(|e| EngineError::internal(format!("Keyring error: {}", e)))?;
+
+            entry
+                .set_password(value)
+                .map_err(|e| EngineError::internal(format!("Keyring error: {}", e)))
+        }
+        StorageBackend::EncryptedFile => {
+            let guard = FILE_KEY.lock().unwrap();
+            let file_key = guard.as_ref().ok_or_else(file_vault_unavailable)?;
+            let mut secrets = read_vault_file(file_key)?;
+            secrets.insert(secret_id(service, key), value.to_string());
+            write_vault_file(file_key, &secrets)
+        }
+    }
+}
+
+/// Deletes a secret from the active backend; missing secrets are ignored
+pub(crate) fn delete_secret(service: &str, key: &str) -> EngineResult<()> {
+    match active_backend() {
+        StorageBackend::Keychain => {
+            let entry = Entry::new(service, key)
+                .map_err(|e| EngineError::internal(format!("Keyring error: {}", e)))?;
+
+            match entry.delete_credential() {
+                Ok(()) | Err(keyring::Error::NoEntry) => Ok(()),
+                Err(e) => Err(EngineError::internal(format!("Failed to delete: {}", e))),
+            }
+        }
+        StorageBackend::EncryptedFile => {
+            let guard = FILE_KEY.lock().unwrap();
+            let file_key = guard.as_ref().ok_or_else(file_vault_unavailable)?;
+            let mut secrets = read_vault_file(file_key)?;
+            if secrets.remove(&secret_id(service, key)).is_some() {
+                write_vault_file(file_key, &secrets)?;
+            }
+            Ok(())
+        }
+    }
+}
+
+fn secret_id(service: &str, key: &str) -> String {
+    format!("{}/{}", service, key)
+}
+
+fn file_vault_unavailable() -> EngineError {
+    if file_vault_exists() {
+        EngineError::internal("Vault file is locked")
+    } else {
+        EngineError::internal(
+            "OS keychain unavailable: set a master password to store credentials in the encrypted vault file",
+        )
+    }
+}
+
+/// Returns true if the encrypted vault file has been created
+pub(crate) fn file_vault_exists() -> bool {
+    vault_file_path().exists()
+}
+
+/// Sets the vault file password
+///
+/// Creates the file if needed; an open vault is re-encrypted under the new
+/// password. Fails if the file exists but has not been unlocked.
+pub(crate) fn set_file_vault_password(password: &str) -> EngineResult<()> {
+    let mut guard = FILE_KEY.lock().unwrap();
+    let secrets = match guard.as_ref() {
+        Some(file_key) => read_vault_file(file_key)?,
+        None if file_vault_exists() => {
+            return Err(EngineError::internal(
+                "Unlock the vault before changing its password",
+            ));
+        }
+        None => HashMap::new(),
+    };
+
+    let mut salt = [0u8; SALT_LEN];
+    rand::rngs::OsRng.fill_bytes(&mut salt);
+    let file_key = FileKey {
+        salt,
+        key: derive_key(password, &salt)?,
+    };
+    write_vault_file(&file_key, &secrets)?;
+    *guard = Some(file_key);
+    Ok(())
+}
+
+/// Unlocks the vault file; returns false if the password is wrong
+pub(crate) fn open_file_vault(password: &str) -> EngineResult<bool> {
+    let file = load_vault_file()?;
+    let salt: [u8; SALT_LEN] = BASE64
+        .decode(&file.salt)
+        .ok()
+        .and_then(|salt| salt.try_into().ok())
+        .ok_or_else(|| EngineError::internal("Invalid vault file salt"))?;
+    let file_key = FileKey {
+        salt,
+        key: derive_key(password, &salt)?,
+    };
+
+    if decrypt_secrets(&file_key.key, &file).is_none() {
+        return Ok(false);
+    }
+    *FILE_KEY.lock().unwrap() = Some(file_key);
+    Ok(true)
+}
+
+/// Forgets the vault file key
+pub(crate) fn close_file_vault() {
+    FILE_KEY.lock().unwrap().take();
+}
+
+/// On-disk format of the encrypted vault file
+#[derive(Serialize, Deserialize)]
+struct EncryptedVaultFile {
+    version: u32,
+    salt: String,
+    nonce: String,
+    ciphertext: String,
+}
+
+fn derive_key(password: &str, salt: &[u8]) -> EngineResult<Zeroizing<[u8; 32]>> {
+    let mut key = Zeroizing::new([0u8; 32]);
+    Argon2::default()
+        .hash_password_into(password.as_bytes(), salt, key.as_mut())
+        .map_err(|e| EngineError::internal(format!("Key derivation error: {}", e)))?;
+    Ok(key)
+}
+
+fn encrypt_secrets(
+    file_key: &FileKey,
+    secrets: &HashMap<String, String>,
+) -> EngineResult<EncryptedVaultFile> {
+    let plaintext = Zeroizing::new(
+        serde_json::to_vec(secrets)
+            .map_err(|e| EngineError::internal(format!("Serialization error: {}", e)))?,
+    );
+
+    let mut nonce = [0u8; NONCE_LEN];
+    rand::rngs::OsRng.fill_bytes(&mut nonce);
+
+    let cipher = Aes256Gcm::new_from_slice(file_key.key.as_ref())
+        .map_err(|e| EngineError::internal(format!("Encryption error: {}", e)))?;
+    let ciphertext = cipher
+        .encrypt(Nonce::from_slice(&nonce), plaintext.as_slice())
+        .map_err(|e| EngineError::internal(format!("Encryption error: {}", e)))?;
+
+    Ok(EncryptedVaultFile {
+        version: VAULT_FILE_VERSION,
+        salt: BASE64.encode(file_key.salt),
+        nonce: BASE64.encode(nonce),
+        ciphertext: BASE64.encode(ciphertext),
+    })
+}
+
+/// Returns `None` if the key is wrong or the file was tampered with
+fn decrypt_secrets(key: &[u8; 32], file: &EncryptedVaultFile) -> Option<HashMap<String, String>> {
+    let nonce = BASE64.decode(&file.nonce).ok()?;
+    if nonce.len() != NONCE_LEN {
+        return None;
+    }
+    let ciphertext = BASE64.decode(&file.ciphertext).ok()?;
+
+    let cipher = Aes256Gcm::new_from_slice(key).ok()?;
+    let plaintext = Zeroizing::new(
+        cipher
+            .decrypt(Nonce::from_slice(&nonce), ciphertext.as_slice())
+            .ok()?,
+    );
+    serde_json::from_slice(&plaintext).ok()
+}
+
+fn load_vault_file() -> EngineResult<EncryptedVaultFile> {
+    let content = fs::read_to_string(vault_file_path())
+        .map_err(|e| EngineError::internal(format!("Failed to read vault file: {}", e)))?;
+    let file: EncryptedVaultFile = serde_json::from_str(&content)
+        .map_err(|e| EngineError::internal(format!("Invalid vault file: {}", e)))?;
+
+    if file.version != VAULT_FILE_VERSION {
+        return Err(EngineError::internal(format!(
+            "Unsupported vault file version {}",
+            file.version
+        )));
+    }
+    Ok(file)
+}
+
+fn read_vault_file(file_key: &FileKey) -> EngineResult<HashMap<String, String>> {
+    if !file_vault_exists() {
+        return Ok(HashMap::new());
+    }
+    let file = load_vault_file()?;
+    decrypt_secrets(&file_key.key, &file)
+        .ok_or_else(|| EngineError::internal("Failed to decrypt vault file"))
+}
+
+fn write_vault_file(file_key: &FileKey, secrets: &HashMap<String, String>) -> EngineResult<()> {
+    let file = encrypt_secrets(file_key, secrets)?;
+    let content = serde_json::to_string(&file)
+        .map_err(|e| EngineError::internal(format!("Serialization error: {}", e)))?;
+
+    let path = vault_file_path();
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| {
+            EngineError::internal(format!("Failed to create vault directory: {}", e))
+        })?;
+    }
+
+    // Write next to the vault and rename, so a crash never leaves a truncated file
+    let tmp_path = path.with_extension("enc.tmp");
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let mut tmp = options
+        .open(&tmp_path)
+        .map_err(|e| EngineError::internal(format!("Failed to write vault file: {}", e)))?;
+    std::io::Write::write_all(&mut tmp, content.as_bytes())
+        .and_then(|_| tmp.sync_all())
+        .map_err(|e| EngineError::internal(format!("Failed to write vault file: {}", e)))?;
+    fs::rename(&tmp_path, &path)
+        .map_err(|e| EngineError::internal(format!("Failed to write vault file: {}", e)))
+}
+
+fn vault_file_path() -> PathBuf {
+    crate::policy::config_dir().join("vault.enc")
+}
+
+/// Keyring service holding the secrets of a project
+pub(crate) fn project_service_name(project_id: &str) -> String {
+    format!("{}_{}", SERVICE_PREFIX, project_id)
+}
 
 /// Storage for saved connections and their credentials
 pub struct VaultStorage {
@@ -23,9 +356,87 @@ impl VaultStorage {
         }
     }
 
+    /// Project this storage reads and writes
+    pub fn project_id(&self) -> &str {
+        &self.project_id
+    }
+
+    /// Lists the IDs of all known projects
+    pub fn list_projects() -> EngineResult<Vec<String>> {
+        Ok(Self::list_project_entries()?
+            .into_iter()
+            .map(|project| project.id)
+            .collect())
+    }
+
+    /// Lists all known projects with their display names
+    pub fn list_project_entries() -> EngineResult<Vec<ProjectEntry>> {
+        match read_secret(SERVICE_PREFIX, PROJECTS_KEY)
+            .map_err(|e| EngineError::internal(format!("Failed to get projects: {}", e)))?
+        {
+            Some(projects_json) => serde_json::from_str(&projects_json).map_err(|e| {
+                EngineError::internal(format!("Invalid project list JSON in keyring: {}", e))
+            }),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    /// Registers a project
+    pub fn create_project(project_id: &str, display_name: &str) -> EngineResult<()> {
+        let mut projects = Self::list_project_entries()?;
+
+        if projects.iter().any(|p| p.id == project_id) {
+            return Err(EngineError::internal(format!(
+                "Project '{}' already exists",
+                project_id
+            )));
+        }
+
+        projects.push(ProjectEntry {
+            id: project_id.to_string(),
+            display_name: display_name.to_string(),
+        });
+        Self::save_projects(&projects)
+    }
+
+    /// Removes a project from the list
+    ///
+    /// With `delete_connections`, its saved connections, credentials, groups
+    /// and snippets are deleted too; otherwise they stay in the vault and
+    /// reappear if a project with the same ID is created again.
+    pub fn delete_project(project_id: &str, delete_connections: bool) -> EngineResult<()> {
+        let mut projects = Self::list_project_entries()?;
+
+        if !projects.iter().any(|p| p.id == project_id) {
+            return Err(EngineError::internal("Project not found"));
+        }
+
+        if delete_connections {
+            let storage = Self::new(project_id);
+            for connection_id in storage.list_connections()? {
+                storage.delete_connection(&connection_id)?;
+            }
+            let service = storage.service_name();
+            let _ = delete_secret(&service, &storage.list_key());
+            let _ = delete_secret(&service, &storage.groups_key());
+            SnippetStorage::new(project_id).delete_all()?;
+        }
+
+        projects.retain(|p| p.id != project_id);
+        Self::save_projects(&projects)
+    }
+
+    fn save_projects(projects: &[ProjectEntry]) -> EngineResult<()> {
+        let projects_json = serde_json::to_string(projects)
+            .map_err(|e| EngineError::internal(format!("Serialization error: {}", e)))?;
+
+        write_secret(SERVICE_PREFIX, PROJECTS_KEY, &projects_json)
+            .map_err(|e| EngineError::internal(format!("Failed to save projects: {}", e)))
+    }
+
     /// Gets the keyring service name for this project
     fn service_name(&self) -> String {
-        format!("{}_{}", SERVICE_PREFIX, self.project_id)
+        project_service_name(&self.project_id)
     }
 
     /// Gets the keyring key for connection metadata
@@ -43,38 +454,37 @@ impl VaultStorage {
         "__connection_list__".to_string()
     }
 
+    /// Gets the keyring key for the connection groups
+    fn groups_key(&self) -> String {
+        "__groups__".to_string()
+    }
+
     /// Saves a connection with its credentials
+    ///
+    /// Fails if the connection belongs to another project.
     pub fn save_connection(
         &self,
         connection: &SavedConnection,
         credentials: &StoredCredentials,
     ) -> EngineResult<()> {
+        if connection.project_id != self.project_id {
+            return Err(EngineError::internal("Project ID mismatch"));
+        }
         let service = self.service_name();
 
         // Save metadata (safe to expose)
-        let meta_entry = Entry::new(&service, &self.metadata_key(&connection.id))
-            .map_err(|e| EngineError::internal(format!("Keyring error: {}", e)))?;
-
-        let meta_json = serde_json::to_string(connection)
-            .map_err(|e| EngineError::internal(format!("Serialization error: {}", e)))?;
-
-        meta_entry
-            .set_password(&meta_json)
-            .map_err(|e| EngineError::internal(format!("Failed to save metadata: {}", e)))?;
+        self.save_metadata(connection)?;
 
         // Save credentials (secrets)
-        let creds_entry = Entry::new(&service, &self.credentials_key(&connection.id))
-            .map_err(|e| EngineError::internal(format!("Keyring error: {}", e)))?;
-
         let creds_json = serde_json::to_string(&CredsJson {
             db_password: credentials.db_password.clone(),
             ssh_password: credentials.ssh_password.clone(),
             ssh_key_passphrase: credentials.ssh_key_passphrase.clone(),
+            raw_connection_string: credentials.raw_connection_string.clone(),
         })
         .map_err(|e| EngineError::internal(format!("Serialization error: {}", e)))?;
 
-        creds_entry
-            .set_password(&creds_json)
+        write_secret(&service, &self.credentials_key(&connection.id), &creds_json)
             .map_err(|e| EngineError::internal(format!("Failed to save credentials: {}", e)))?;
 
         // Update connection list
@@ -87,12 +497,8 @@ impl VaultStorage {
     pub fn get_connection(&self, connection_id: &str) -> EngineResult<SavedConnection> {
         let service = self.service_name();
 
-        let entry = Entry::new(&service, &self.metadata_key(connection_id))
-            .map_err(|e| EngineError::internal(format!("Keyring error: {}", e)))?;
-
-        let meta_json = entry
-            .get_password()
-            .map_err(|_| EngineError::internal("Connection not found"))?;
+        let meta_json = read_secret(&service, &self.metadata_key(connection_id))?
+            .ok_or_else(|| EngineError::internal("Connection not found"))?;
 
         let connection: SavedConnection = serde_json::from_str(&meta_json)
             .map_err(|e| EngineError::internal(format!("Deserialization error: {}", e)))?;
@@ -104,12 +510,8 @@ impl VaultStorage {
     pub fn get_credentials(&self, connection_id: &str) -> EngineResult<StoredCredentials> {
         let service = self.service_name();
 
-        let entry = Entry::new(&service, &self.credentials_key(connection_id))
-            .map_err(|e| EngineError::internal(format!("Keyring error: {}", e)))?;
-
-        let creds_json = entry
-            .get_password()
-            .map_err(|_| EngineError::internal("Credentials not found"))?;
+        let creds_json = read_secret(&service, &self.credentials_key(connection_id))?
+            .ok_or_else(|| EngineError::internal("Credentials not found"))?;
 
         let creds: CredsJson = serde_json::from_str(&creds_json)
             .map_err(|e| EngineError::internal(format!("Deserialization error: {}", e)))?;
@@ -118,6 +520,7 @@ impl VaultStorage {
             db_password: creds.db_password,
             ssh_password: creds.ssh_password,
             ssh_key_passphrase: creds.ssh_key_passphrase,
+            raw_connection_string: creds.raw_connection_string,
         })
     }
 
@@ -126,14 +529,10 @@ impl VaultStorage {
         let service = self.service_name();
 
         // Delete metadata
-        if let Ok(entry) = Entry::new(&service, &self.metadata_key(connection_id)) {
-            let _ = entry.delete_credential();
-        }
+        let _ = delete_secret(&service, &self.metadata_key(connection_id));
 
         // Delete credentials
-        if let Ok(entry) = Entry::new(&service, &self.credentials_key(connection_id)) {
-            let _ = entry.delete_credential();
-        }
+        let _ = delete_secret(&service, &self.credentials_key(connection_id));
 
         // Remove from list
         self.remove_from_list(connection_id)?;
@@ -141,15 +540,61 @@ impl VaultStorage {
         Ok(())
     }
 
+    /// Moves a saved connection and its credentials to another project
+    ///
+    /// The connection leaves its group, since groups belong to a project.
+    /// The source is only deleted once the target copy is saved. If the
+    /// source cannot be unlisted, the target copy is removed again so the
+    /// connection is never left in both projects; once the source is gone,
+    /// the target copy is always kept.
+    pub fn migrate_connection_to_project(
+        connection_id: &str,
+        from_project_id: &str,
+        to_project_id: &str,
+    ) -> EngineResult<()> {
+        if from_project_id == to_project_id {
+            return Ok(());
+        }
+        let source = Self::new(from_project_id);
+        let target = Self::new(to_project_id);
+        if target.list_connections()?.iter().any(|id| id == connection_id) {
+            return Err(EngineError::internal(format!(
+                "Connection '{}' already exists in project '{}'",
+                connection_id, to_project_id
+            )));
+        }
+
+        let mut connection = source.get_connection(connection_id)?;
+        let credentials = source.get_credentials(connection_id)?;
+        connection.project_id = to_project_id.to_string();
+        connection.group_id = None;
+        connection.group_name = None;
+
+        if let Err(e) = target.save_connection(&connection, &credentials) {
+            let _ = target.delete_connection(connection_id);
+            return Err(e);
+        }
+
+        // Unlisting is the only step of the deletion that can fail, and the
+        // source is still complete until it succeeds
+        if let Err(e) = source.remove_from_list(connection_id) {
+            let _ = target.delete_connection(connection_id);
+            return Err(e);
+        }
+        let service = source.service_name();
+        let _ = delete_secret(&service, &source.metadata_key(connection_id));
+        let _ = delete_secret(&service, &source.credentials_key(connection_id));
+        Ok(())
+    }
+
     /// Lists all saved connection IDs
     pub fn list_connections(&self) -> EngineResult<Vec<String>> {
         let service = self.service_name();
 
-        let entry = Entry::new(&service, &self.list_key())
-            .map_err(|e| EngineError::internal(format!("Keyring error: {}", e)))?;
-
-        match entry.get_password() {
-            Ok(list_json) => {
+        match read_secret(&service, &self.list_key())
+            .map_err(|e| EngineError::internal(format!("Failed to get list: {}", e)))?
+        {
+            Some(list_json) => {
                 let list: Vec<String> = serde_json::from_str(&list_json).map_err(|e| {
                     EngineError::internal(format!(
                         "Invalid connection list JSON in keyring: {}",
@@ -158,12 +603,11 @@ impl VaultStorage {
                 })?;
                 Ok(list)
             }
-            Err(keyring::Error::NoEntry) => Ok(Vec::new()),
-            Err(e) => Err(EngineError::internal(format!("Failed to get list: {}", e))),
+            None => Ok(Vec::new()),
         }
     }
 
-    /// Lists all saved connections with metadata
+    /// Lists all saved connections with metadata and their group names
     pub fn list_connections_full(&self) -> EngineResult<Vec<SavedConnection>> {
         let ids = self.list_connections()?;
         let mut connections = Vec::new();
@@ -174,9 +618,117 @@ impl VaultStorage {
             }
         }
 
+        resolve_groups(&mut connections, &self.list_groups()?);
         Ok(connections)
     }
 
+    /// Lists the connection groups
+    pub fn list_groups(&self) -> EngineResult<Vec<ConnectionGroup>> {
+        let service = self.service_name();
+
+        match read_secret(&service, &self.groups_key())
+            .map_err(|e| EngineError::internal(format!("Failed to get groups: {}", e)))?
+        {
+            Some(groups_json) => serde_json::from_str(&groups_json).map_err(|e| {
+                EngineError::internal(format!(
+                    "Invalid connection groups JSON in keyring: {}",
+                    e
+                ))
+            }),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    /// Creates a connection group
+    pub fn create_group(&self, group: ConnectionGroup) -> EngineResult<()> {
+        let mut groups = self.list_groups()?;
+
+        if groups.iter().any(|g| g.id == group.id) {
+            return Err(EngineError::internal(format!(
+                "Connection group '{}' already exists",
+                group.id
+            )));
+        }
+
+        groups.push(group);
+        self.save_groups(&groups)
+    }
+
+    /// Renames a connection group
+    pub fn rename_group(&self, group_id: &str, new_name: &str) -> EngineResult<()> {
+        let mut groups = self.list_groups()?;
+
+        let group = groups
+            .iter_mut()
+            .find(|g| g.id == group_id)
+            .ok_or_else(|| EngineError::internal("Connection group not found"))?;
+        group.name = new_name.to_string();
+
+        self.save_groups(&groups)
+    }
+
+    /// Deletes a connection group, moving its connections to another group
+    /// or to the top level
+    pub fn delete_group(
+        &self,
+        group_id: &str,
+        move_connections_to: Option<&str>,
+    ) -> EngineResult<()> {
+        let mut groups = self.list_groups()?;
+
+        if !groups.iter().any(|g| g.id == group_id) {
+            return Err(EngineError::internal("Connection group not found"));
+        }
+        if let Some(target) = move_connections_to {
+            if target == group_id || !groups.iter().any(|g| g.id == target) {
+                return Err(EngineError::internal("Target connection group not found"));
+            }
+        }
+
+        for mut connection in self.list_connections_full()? {
+            if connection.group_id.as_deref() == Some(group_id) {
+                connection.group_id = move_connections_to.map(str::to_string);
+                self.save_metadata(&connection)?;
+            }
+        }
+
+        groups.retain(|g| g.id != group_id);
+        self.save_groups(&groups)
+    }
+
+    /// Moves a connection into a group, or to the top level with `None`
+    pub fn move_connection_to_group(
+        &self,
+        connection_id: &str,
+        group_id: Option<&str>,
+    ) -> EngineResult<()> {
+        if let Some(group_id) = group_id {
+            if !self.list_groups()?.iter().any(|g| g.id == group_id) {
+                return Err(EngineError::internal("Connection group not found"));
+            }
+        }
+
+        let mut connection = self.get_connection(connection_id)?;
+        connection.group_id = group_id.map(str::to_string);
+        self.save_metadata(&connection)
+    }
+
+    fn save_metadata(&self, connection: &SavedConnection) -> EngineResult<()> {
+        let meta_json = serde_json::to_string(connection)
+            .map_err(|e| EngineError::internal(format!("Serialization error: {}", e)))?;
+
+        write_secret(&self.service_name(), &self.metadata_key(&connection.id), &meta_json)
+            .map_err(|e| EngineError::internal(format!("Failed to save metadata: {}", e)))
+    }
+
+    fn save_groups(&self, groups: &[ConnectionGroup]) -> EngineResult<()> {
+        let groups_json = serde_json::to_string(groups)
+            .map_err(|e| EngineError::internal(format!("Serialization error: {}", e)))?;
+
+        write_secret(&self.service_name(), &self.groups_key(), &groups_json)
+            .map_err(|e| EngineError::internal(format!("Failed to save groups: {}", e)))
+    }
+
     fn add_to_list(&self, connection_id: &str) -> EngineResult<()> {
         let mut list = self.list_connections()?;
         
@@ -197,24 +749,133 @@ impl VaultStorage {
     fn save_list(&self, list: &[String]) -> EngineResult<()> {
         let service = self.service_name();
 
-        let entry = Entry::new(&service, &self.list_key())
-            .map_err(|e| EngineError::internal(format!("Keyring error: {}", e)))?;
-
         let list_json = serde_json::to_string(list)
             .map_err(|e| EngineError::internal(format!("Serialization error: {}", e)))?;
 
-        entry
-            .set_password(&list_json)
+        write_secret(&service, &self.list_key(), &list_json)
             .map_err(|e| EngineError::internal(format!("Failed to save list: {}", e)))?;
 
         Ok(())
     }
 }
 
+/// Fills in group names and moves connections whose group no longer exists
+/// to the top level
+fn resolve_groups(connections: &mut [SavedConnection], groups: &[ConnectionGroup]) {
+    for connection in connections {
+        let group = connection
+            .group_id
+            .as_deref()
+            .and_then(|id| groups.iter().find(|g| g.id == id));
+        connection.group_id = group.map(|g| g.id.clone());
+        connection.group_name = group.map(|g| g.name.clone());
+    }
+}
+
 /// Internal struct for serializing credentials
 #[derive(Serialize, Deserialize)]
 struct CredsJson {
     db_password: String,
     ssh_password: Option<String>,
     ssh_key_passphrase: Option<String>,
+    #[serde(default)]
+    raw_connection_string: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn vault_file_round_trips_and_rejects_wrong_password() {
+        let salt = [7u8; SALT_LEN];
+        let file_key = FileKey {
+            salt,
+            key: derive_key("correct horse", &salt).unwrap(),
+        };
+        let secrets = HashMap::from([("qoredb_p/creds_1".to_string(), "s3cret".to_string())]);
+
+        let file = encrypt_secrets(&file_key, &secrets).unwrap();
+        assert!(!file.ciphertext.contains("s3cret"));
+
+        let decrypted = decrypt_secrets(&file_key.key, &file).unwrap();
+        assert_eq!(decrypted, secrets);
+
+        let wrong_key = derive_key("wrong", &salt).unwrap();
+        assert!(decrypt_secrets(&wrong_key, &file).is_none());
+    }
+
+    #[test]
+    fn resolves_group_names_and_ungroups_orphans() {
+        let connection = |id: &str, group_id: Option<&str>| -> SavedConnection {
+            serde_json::from_value(serde_json::json!({
+                "id": id,
+                "name": id,
+                "driver": "postgres",
+                "environment": "development",
+                "read_only": false,
+                "host": "localhost",
+                "port": 5432,
+                "username": "app",
+                "database": null,
+                "ssl": false,
+                "ssh_tunnel": null,
+                "project_id": "default",
+                "group_id": group_id,
+                "group_name": "stale",
+            }))
+            .unwrap()
+        };
+        let mut connections = vec![
+            connection("a", Some("g1")),
+            connection("b", Some("deleted")),
+            connection("c", None),
+        ];
+        let groups = vec![ConnectionGroup {
+            id: "g1".to_string(),
+            name: "Analytics".to_string(),
+            color: None,
+        }];
+
+        resolve_groups(&mut connections, &groups);
+
+        assert_eq!(connections[0].group_name.as_deref(), Some("Analytics"));
+        assert_eq!(connections[1].group_id, None);
+        assert_eq!(connections[1].group_name, None);
+        assert_eq!(connections[2].group_name, None);
+    }
+
+    #[test]
+    fn rejects_connections_of_another_project() {
+        let connection: SavedConnection = serde_json::from_value(serde_json::json!({
+            "id": "c1",
+            "name": "c1",
+            "driver": "postgres",
+            "environment": "development",
+            "read_only": false,
+            "host": "localhost",
+            "port": 5432,
+            "username": "app",
+            "database": null,
+            "ssl": false,
+            "ssh_tunnel": null,
+            "project_id": "other",
+        }))
+        .unwrap();
+        let storage = VaultStorage::new("default");
+        assert_eq!(storage.project_id(), "default");
+
+        let credentials = StoredCredentials {
+            db_password: "secret".to_string(),
+            ssh_password: None,
+            ssh_key_passphrase: None,
+            raw_connection_string: None,
+        };
+
+        let err = storage.save_connection(&connection, &credentials).unwrap_err();
+        assert!(matches!(
+            err,
+            EngineError::Internal { ref message } if message == "Project ID mismatch"
+        ));
+    }
 }
@@ -443,7 +443,7 @@ export function ConnectionModal({
 									{t("environment.label")}
 								</label>
 								<div className="flex gap-2">
-									{(["development", "staging", "production"] as const).map((env) => {
+									{(["local", "development", "staging", "production"] as const).map((env) => {
 										const config = ENVIRONMENT_CONFIG[env];
 										const isSelected = formData.environment === env;
 										return (
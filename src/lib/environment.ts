@@ -2,7 +2,7 @@
  * Environment utilities for connection classification
  */
 
-export type Environment = 'development' | 'staging' | 'production';
+export type Environment = 'local' | 'development' | 'staging' | 'production';
 
 export interface EnvironmentConfig {
   color: string;
@@ -12,6 +12,12 @@ export interface EnvironmentConfig {
 }
 
 export const ENVIRONMENT_CONFIG: Record<Environment, EnvironmentConfig> = {
+  local: {
+    color: 'var(--q-env-local)',
+    bgSoft: 'var(--q-env-local-soft)',
+    label: 'Local',
+    labelShort: 'LOCAL',
+  },
   development: {
     color: 'var(--q-env-dev)',
     bgSoft: 'var(--q-env-dev-soft)',
@@ -7,7 +7,15 @@ import { invoke } from '@tauri-apps/api/core';
 // TYPES
 // ============================================
 
-export type Environment = 'development' | 'staging' | 'production';
+export type Environment = 'local' | 'development' | 'staging' | 'production';
+
+/** Backoff between connect retries: `initial_delay_ms * 2^attempt` */
+export interface RetryPolicy {
+	/** Connect attempts in total, the first one included (default 3) */
+	max_attempts?: number;
+	/** Wait before the first retry (default 500) */
+	initial_delay_ms?: number;
+}
 
 export interface ConnectionConfig {
 	driver: string;
@@ -20,6 +28,31 @@ export interface ConnectionConfig {
 	environment: Environment;
 	read_only: boolean;
 	ssh_tunnel?: SshTunnelConfig;
+	/** Seconds before idle pooled connections are closed (default 300, 0 disables) */
+	pool_idle_timeout_secs?: number;
+	/** Seconds before pooled connections are recycled (default 1800, 0 disables) */
+	pool_max_lifetime_secs?: number;
+	/** PlanetScale: use the HTTP API (port 443) instead of the MySQL protocol */
+	use_http_api?: boolean;
+	/** PlanetScale: serve reads from Boost caches */
+	boost?: boolean;
+	/** MongoDB: database holding the user's credentials (default "admin") */
+	mongo_auth_source?: string;
+	/** Seconds between keepalive pings on idle sessions (unset or 0 disables) */
+	keepalive_query_secs?: number;
+	/** Custom session name, shown instead of user@host:db */
+	label?: string;
+	/** MySQL: connection character set (default "utf8mb4") */
+	charset?: string;
+	/** Retries of the initial connect on transient network errors */
+	retry_policy?: RetryPolicy;
+	/** Prepared statements cached per pooled connection (SQL drivers, default 100, 0 disables) */
+	statement_cache_capacity?: number;
+	/**
+	 * Used verbatim instead of the fields above (e.g. several hosts with
+	 * target_session_attrs). Cannot be combined with an SSH tunnel.
+	 */
+	raw_connection_string?: string;
 }
 
 export interface SshTunnelConfig {
@@ -36,23 +69,75 @@ export interface SshTunnelConfig {
 	connect_timeout_secs: number;
 	keepalive_interval_secs: number;
 	keepalive_count_max: number;
+	/** Wait for the tunnel to become ready, in ms (default 5000) */
+	startup_timeout_ms?: number;
+	/** Probe interval while the tunnel starts, in ms (default 50) */
+	startup_poll_interval_ms?: number;
+	/** Re-open the tunnel when it goes down during a session (default false) */
+	auto_restart?: boolean;
 }
 
 export type SshAuth =
 	| { Password: { password: string } }
 	| { Key: { private_key_path: string; passphrase?: string } };
 
+/** Kind of a backend error, set alongside `error` when known */
+export type EngineErrorCode =
+	| "connection_failed"
+	| "auth_failed"
+	| "session_not_found"
+	| "driver_not_found"
+	| "execution_error"
+	| "syntax_error"
+	| "transaction_error"
+	| "not_supported"
+	| "timeout"
+	| "ssh_error"
+	| "ssl_error"
+	| "constraint_violation"
+	| "permission_denied"
+	| "query_cancelled"
+	| "cancel_not_supported"
+	| "deadlock"
+	| "serialization_failure"
+	| "lock_timeout"
+	| "internal";
+
+export type TransactionConflictKind =
+	| "deadlock"
+	| "serialization_failure"
+	| "lock_timeout";
+
 export interface ConnectionResponse {
 	success: boolean;
 	session_id?: string;
 	error?: string;
+	error_code?: EngineErrorCode;
+}
+
+export interface ConnectionHealth {
+	reachable: boolean;
+	latency_ms?: number;
+	error?: string;
+}
+
+export interface ConnectionHealthResponse {
+	success: boolean;
+	health?: ConnectionHealth;
+	error?: string;
+	error_code?: EngineErrorCode;
 }
 
 export interface SessionListItem {
 	id: string;
+	/** The custom label if set, otherwise the generated user@host:db name */
 	display_name: string;
+	label?: string;
+	stats?: SessionStats;
 }
 
+export type SslMode = "disabled" | "preferred" | "required" | "verify_ca" | "verify_identity";
+
 export interface SavedConnection {
 	id: string;
 	name: string;
@@ -64,7 +149,33 @@ export interface SavedConnection {
 	username: string;
 	database?: string;
 	ssl: boolean;
+	/** TLS mode; takes precedence over `ssl` when set */
+	ssl_mode?: SslMode;
+	/** CA certificate checked by `verify_ca` and `verify_identity` */
+	ssl_ca_path?: string;
 	project_id: string;
+	/** Shown instead of the host when production details are masked */
+	display_alias?: string;
+	/** MongoDB: database holding the user's credentials (default "admin") */
+	mongo_auth_source?: string;
+	/** Seconds between keepalive pings on idle sessions (unset or 0 disables) */
+	keepalive_query_secs?: number;
+	/** Custom session name, shown instead of user@host:db */
+	label?: string;
+	/** MySQL: connection character set (default "utf8mb4") */
+	charset?: string;
+	/** Retries of the initial connect on transient network errors */
+	retry_policy?: RetryPolicy;
+	/** Prepared statements cached per pooled connection (SQL drivers, default 100, 0 disables) */
+	statement_cache_capacity?: number;
+	/** Group (folder) holding this connection, unset at the top level */
+	group_id?: string;
+	/** Accent color as a hex string, e.g. "#3b82f6" */
+	color?: string;
+	tags?: string[];
+	group_name?: string;
+	/** Set when listed with `includeHealth` */
+	health?: ConnectionHealth;
 	ssh_tunnel?: {
 		host: string;
 		port: number;
@@ -77,28 +188,84 @@ export interface SavedConnection {
 		connect_timeout_secs: number;
 		keepalive_interval_secs: number;
 		keepalive_count_max: number;
+		/** Wait for the tunnel to become ready, in ms (default 5000) */
+		startup_timeout_ms?: number;
+		/** Probe interval while the tunnel starts, in ms (default 50) */
+		startup_poll_interval_ms?: number;
+		/** Re-open the tunnel when it goes down during a session (default false) */
+		auto_restart?: boolean;
 	};
 }
 
+export type VaultStorageBackend = "keychain" | "encrypted_file";
+
 export interface VaultStatus {
 	is_locked: boolean;
 	has_master_password: boolean;
+	storage_backend: VaultStorageBackend;
 }
 
 export interface VaultResponse {
 	success: boolean;
 	error?: string;
+	error_code?: EngineErrorCode;
 }
 
 export interface SafetyPolicy {
 	prod_require_confirmation: boolean;
 	prod_block_dangerous_sql: boolean;
+	/** Confirm dangerous statements on staging sessions (default false) */
+	staging_require_confirmation?: boolean;
+	/** Reject dangerous statements on staging sessions (default false) */
+	staging_block_dangerous_sql?: boolean;
+	vault_auto_lock_timeout_secs?: number | null;
+	protected_host_patterns?: string[];
+	/** Log query text (string literals masked) at debug level */
+	log_queries?: boolean;
+	/** Show production sessions by name instead of user@host */
+	mask_prod_connection_details?: boolean;
+	/** Prefix queries with a comment carrying the query and tab IDs */
+	inject_query_comments?: boolean;
+	/** Row cap for SQL queries without a LIMIT; null returns every row */
+	max_rows_without_limit?: number | null;
+	/** Size above which query results are rejected; null disables the check */
+	max_result_memory_mb?: number | null;
+	/** Most rows a table preview returns, whatever limit is requested (default 10000) */
+	preview_max_rows?: number;
+	/** Cost of new master password hashes; null uses m=65536, t=3, p=4 */
+	argon2_params?: Argon2Params | null;
+	/** Log rotation and level, applied at the next start */
+	logging?: LogConfig;
+}
+
+export interface LogConfig {
+	/** Log files kept, the current one included (default 7) */
+	max_files: number;
+	/** Size at which the current file is rotated, in MB; 0 disables (default 50) */
+	max_file_size_mb: number;
+	/** Filter directives, e.g. "qoredb=debug" (default "qoredb=info,tauri=info") */
+	log_level: string;
+}
+
+export interface Argon2Params {
+	memory_kib: number;
+	iterations: number;
+	parallelism: number;
+}
+
+export interface Argon2BenchmarkResponse {
+	success: boolean;
+	params: Argon2Params;
+	duration_ms?: number;
+	error?: string;
+	error_code?: EngineErrorCode;
 }
 
 export interface SafetyPolicyResponse {
 	success: boolean;
 	policy?: SafetyPolicy;
 	error?: string;
+	error_code?: EngineErrorCode;
 }
 
 export interface Namespace {
@@ -112,17 +279,51 @@ export interface Collection {
 	collection_type: "Table" | "View" | "Collection";
 }
 
+export interface Routine {
+	namespace: Namespace;
+	name: string;
+	routine_type: "Function" | "Procedure";
+	/** Unset for procedures */
+	return_type?: string;
+	/** Argument list as declared, e.g. "IN id integer, OUT total numeric" */
+	arguments: string;
+}
+
 export interface QueryResult {
 	columns: ColumnInfo[];
 	rows: Row[];
 	affected_rows?: number;
 	execution_time_ms: number;
+	/** Present when requested with `includePlanCost` */
+	plan_estimate?: PlanEstimate;
+	/** Rows were cut off at the max_rows_without_limit cap */
+	truncated?: boolean;
+	/** Approximate memory taken by the rows, set by executeQuery */
+	estimated_memory_bytes?: number;
+	/** Values cut at `maxValueBytes`; load them with fetchFullValue */
+	truncated_values?: TruncatedValue[];
+}
+
+export interface TruncatedValue {
+	row: number;
+	column: number;
+	full_bytes: number;
+}
+
+export interface PlanEstimate {
+	total_cost?: number | null;
+	estimated_rows?: number | null;
 }
 
 export interface ColumnInfo {
 	name: string;
 	data_type: string;
 	nullable: boolean;
+	display_size?: number | null;
+	is_auto_increment?: boolean;
+	is_generated?: boolean;
+	comment?: string | null;
+	character_set?: string | null;
 }
 
 export type Row = { values: Value[] };
@@ -145,17 +346,75 @@ export async function testSavedConnection(
 	return invoke("test_saved_connection", { projectId, connectionId });
 }
 
+export async function checkConnectionHealth(
+	projectId: string,
+	connectionId: string,
+): Promise<ConnectionHealthResponse> {
+	return invoke("check_connection_health", { projectId, connectionId });
+}
+
 export async function connect(
 	config: ConnectionConfig,
+	attemptId?: string,
 ): Promise<ConnectionResponse> {
-	return invoke("connect", { config });
+	return invoke("connect", { config, attemptId });
 }
 
 export async function connectSavedConnection(
 	projectId: string,
 	connectionId: string,
+	attemptId?: string,
+): Promise<ConnectionResponse> {
+	return invoke("connect_saved_connection", {
+		projectId,
+		connectionId,
+		attemptId,
+	});
+}
+
+export async function cancelConnect(
+	attemptId: string,
 ): Promise<ConnectionResponse> {
-	return invoke("connect_saved_connection", { projectId, connectionId });
+	return invoke("cancel_connect", { attemptId });
+}
+
+export type CancelSupport = "none" | "best_effort" | "driver";
+
+export interface DriverCapabilities {
+	transactions: boolean;
+	mutations: boolean;
+	/** Whether update/delete results include the affected primary keys */
+	returning_keys: boolean;
+	cancel: CancelSupport;
+	supports_ssh: boolean;
+	/** Whether query results are read from a cursor instead of buffered */
+	streaming: boolean;
+}
+
+export interface DriverInfo {
+	driver_id: string;
+	driver_name: string;
+	is_builtin: boolean;
+	default_port: number;
+	/** Whether namespaces are schemas (PostgreSQL) rather than databases */
+	supports_schemas: boolean;
+	supports_multiple_databases: boolean;
+	requires_database: boolean;
+	capabilities: DriverCapabilities;
+}
+
+/** Loads a driver plugin from a dynamic library (.so, .dylib, .dll) */
+export async function loadDriverPlugin(path: string): Promise<VaultResponse> {
+	return invoke("load_driver_plugin", { path });
+}
+
+export async function listRegisteredDrivers(): Promise<DriverInfo[]> {
+	return invoke("list_registered_drivers");
+}
+
+/** Drivers with the metadata needed to build the connection form */
+export async function listAvailableDrivers(): Promise<DriverInfo[]> {
+	return invoke("list_available_drivers");
 }
 
 export async function disconnect(
@@ -164,10 +423,132 @@ export async function disconnect(
 	return invoke("disconnect", { sessionId });
 }
 
+/** Reopens a session's connection with its saved credentials; the session ID is kept */
+export async function reconnectSession(
+	sessionId: string,
+): Promise<ConnectionResponse> {
+	return invoke("reconnect_session", { sessionId });
+}
+
+/** Reconnects every active session, e.g. after the vault was unlocked again */
+export async function reconnectAllSessions(): Promise<ConnectionResponse[]> {
+	return invoke("reconnect_all_sessions");
+}
+
+export interface PrivilegeReport {
+	can_select: boolean;
+	can_insert: boolean;
+	can_update: boolean;
+	can_delete: boolean;
+	can_create: boolean;
+	can_drop: boolean;
+	is_superuser: boolean;
+	granted_databases: string[];
+}
+
+export async function checkSessionPrivileges(sessionId: string): Promise<{
+	success: boolean;
+	privileges?: PrivilegeReport;
+	error?: string;
+	error_code?: EngineErrorCode;
+}> {
+	return invoke("check_session_privileges", { sessionId });
+}
+
+/** Re-checks whether the session is on a read replica (which makes it read-only) */
+export async function checkReplicaStatus(sessionId: string): Promise<{
+	success: boolean;
+	read_replica?: boolean;
+	error?: string;
+	error_code?: EngineErrorCode;
+}> {
+	return invoke("check_replica_status", { sessionId });
+}
+
+export async function checkTunnelHealth(sessionId: string): Promise<{
+	success: boolean;
+	/** True as well for sessions without an SSH tunnel */
+	healthy?: boolean;
+	error?: string;
+	error_code?: EngineErrorCode;
+}> {
+	return invoke("check_tunnel_health", { sessionId });
+}
+
+/**
+ * Payload of the `session_reconnecting` event, emitted before each retry of
+ * a connect or connection test; `session_id` is unset while connecting
+ */
+export interface SessionReconnectingEvent {
+	session_id?: string;
+	attempt_id?: string;
+	/** The failed attempt, starting at 1 */
+	attempt: number;
+	next_delay_ms: number;
+}
+
+/** Payload of the `tunnel://reconnecting` event */
+export interface TunnelReconnectingEvent {
+	session_id: string;
+}
+
+export type VariableScope = "session" | "local" | "global";
+
+export async function setSessionVariable(
+	sessionId: string,
+	name: string,
+	value: string,
+	scope: VariableScope = "session",
+): Promise<VaultResponse> {
+	return invoke("set_session_variable", { sessionId, name, value, scope });
+}
+
 export async function listSessions(): Promise<SessionListItem[]> {
 	return invoke("list_sessions");
 }
 
+/** A missing or blank label restores the generated user@host:db name */
+export async function renameSession(
+	sessionId: string,
+	label?: string,
+): Promise<VaultResponse> {
+	return invoke("rename_session", { sessionId, label });
+}
+
+/**
+ * Reclassifies a session's environment without reconnecting; leaving
+ * production or staging needs `acknowledgedDangerous` where the policy
+ * requires confirmation
+ */
+export async function setEnvironment(
+	sessionId: string,
+	environment: Environment,
+	acknowledgedDangerous?: boolean,
+): Promise<VaultResponse> {
+	return invoke("set_environment", { sessionId, environment, acknowledgedDangerous });
+}
+
+export interface SessionStats {
+	total_queries: number;
+	failed_queries: number;
+	total_rows_affected: number;
+	total_rows_fetched: number;
+	total_execution_time_ms: number;
+	/** RFC 3339 timestamp of the last (re)connect */
+	connected_at: string;
+	last_query_at?: string;
+}
+
+/** Statistics of the queries run since the session (re)connected */
+export async function getSessionStats(sessionId: string): Promise<{
+	success: boolean;
+	stats?: SessionStats;
+	error?: string;
+	error_code?: EngineErrorCode;
+}> {
+	return invoke("get_session_stats", { sessionId });
+}
+
 // ============================================
 // POLICY COMMANDS
 // ============================================
@@ -182,30 +563,103 @@ export async function setSafetyPolicy(
 	return invoke("set_safety_policy", { policy });
 }
 
+export async function getLogDirectory(): Promise<string> {
+	return invoke("get_log_directory");
+}
+
 // ============================================
 // QUERY COMMANDS
 // ============================================
 
+export type RowLockMode = "update" | "share" | "no_key_update" | "key_share";
+
+export interface ExecuteOptions {
+	acknowledgedDangerous?: boolean;
+	timeoutMs?: number;
+	queryId?: string;
+	/** Locks the returned rows; requires an active transaction */
+	lockRows?: RowLockMode;
+	/** Comment sent with the query (executeQuery only) */
+	comment?: string;
+	/** Tab that issued the query, used in the default comment */
+	tabId?: string;
+	/** Attach planner cost/row estimates to SELECT results (PostgreSQL, MySQL) */
+	includePlanCost?: boolean;
+	/** Cut text and binary values longer than this many bytes */
+	maxValueBytes?: number;
+}
+
 export async function executeQuery(
 	sessionId: string,
 	query: string,
-	options?: {
-		acknowledgedDangerous?: boolean;
-		timeoutMs?: number;
-		queryId?: string;
-	},
+	options?: ExecuteOptions,
 ): Promise<{
 	success: boolean;
 	result?: QueryResult;
 	error?: string;
+	error_code?: EngineErrorCode;
 	query_id?: string;
+	/** Set when the query lost a conflict with another transaction */
+	conflict_type?: TransactionConflictKind | null;
 }> {
 	return invoke("execute_query", {
 		sessionId,
 		query,
-		acknowledgedDangerous: options?.acknowledgedDangerous,
-		queryId: options?.queryId,
-		timeoutMs: options?.timeoutMs,
+		options,
+	});
+}
+
+export interface TimeoutSuggestion {
+	suggested_ms: number | null;
+	based_on_samples: number;
+}
+
+/** Suggests a timeout from past execution times of similar queries */
+export async function suggestQueryTimeout(
+	sessionId: string,
+	query: string,
+): Promise<TimeoutSuggestion> {
+	return invoke("suggest_query_timeout", { sessionId, query });
+}
+
+/** Runs a query with `:name` placeholders bound from `params` */
+export async function executeQueryParams(
+	sessionId: string,
+	query: string,
+	params: Record<string, Value>,
+	options?: ExecuteOptions,
+): Promise<{
+	success: boolean;
+	result?: QueryResult;
+	error?: string;
+	error_code?: EngineErrorCode;
+	query_id?: string;
+}> {
+	return invoke("execute_query_params", {
+		sessionId,
+		query,
+		params,
+		options,
+	});
+}
+
+export async function executeMultiQuery(
+	sessionId: string,
+	query: string,
+	options?: ExecuteOptions,
+): Promise<
+	{
+		success: boolean;
+		result?: QueryResult;
+		error?: string;
+		error_code?: EngineErrorCode;
+		query_id?: string;
+	}[]
+> {
+	return invoke("execute_multi_query", {
+		sessionId,
+		query,
+		options,
 	});
 }
 
@@ -213,6 +667,7 @@ export async function listNamespaces(sessionId: string): Promise<{
 	success: boolean;
 	namespaces?: Namespace[];
 	error?: string;
+	error_code?: EngineErrorCode;
 }> {
 	return invoke("list_namespaces", { sessionId });
 }
@@ -224,16 +679,75 @@ export async function listCollections(
 	success: boolean;
 	collections?: Collection[];
 	error?: string;
+	error_code?: EngineErrorCode;
 }> {
 	return invoke("list_collections", { sessionId, namespace });
 }
 
+export async function listRoutines(
+	sessionId: string,
+	namespace: Namespace,
+): Promise<{
+	success: boolean;
+	routines?: Routine[];
+	error?: string;
+	error_code?: EngineErrorCode;
+}> {
+	return invoke("list_routines", { sessionId, namespace });
+}
+
+/** Returns one response per result set; MySQL procedures may return several */
+export async function callRoutine(
+	sessionId: string,
+	namespace: Namespace,
+	routine: string,
+	routineType: Routine["routine_type"],
+	args: Value[],
+	options?: ExecuteOptions,
+): Promise<
+	{
+		success: boolean;
+		result?: QueryResult;
+		error?: string;
+		error_code?: EngineErrorCode;
+		query_id?: string;
+	}[]
+> {
+	return invoke("call_routine", {
+		sessionId,
+		namespace,
+		routine,
+		routineType,
+		args,
+		options,
+	});
+}
+
+export interface NamespaceCollections {
+	namespace: Namespace;
+	collections: Collection[];
+}
+
+export async function listAllCollections(sessionId: string): Promise<{
+	success: boolean;
+	namespaces?: NamespaceCollections[];
+	error?: string;
+	error_code?: EngineErrorCode;
+}> {
+	return invoke("list_all_collections", { sessionId });
+}
+
+/**
+ * Fails with `cancel_not_supported` for drivers whose capabilities report
+ * `cancel: "none"`
+ */
 export async function cancelQuery(
 	sessionId: string,
 	queryId?: string,
 ): Promise<{
 	success: boolean;
 	error?: string;
+	error_code?: EngineErrorCode;
 	query_id?: string;
 }> {
 	return invoke("cancel_query", { sessionId, queryId });
@@ -247,6 +761,7 @@ export interface TableSchema {
 	columns: TableColumn[];
 	primary_key?: string[];
 	row_count_estimate?: number;
+	comment?: string;
 }
 
 export interface TableColumn {
@@ -255,6 +770,11 @@ export interface TableColumn {
 	nullable: boolean;
 	default_value?: string;
 	is_primary_key: boolean;
+	comment?: string;
+	/** Values accepted by a MySQL ENUM or SET column */
+	allowed_values?: string[];
+	/** MySQL character set of a text column; anything but utf8mb4 mangles emoji */
+	charset?: string;
 }
 
 export async function describeTable(
@@ -265,21 +785,421 @@ export async function describeTable(
 	success: boolean;
 	schema?: TableSchema;
 	error?: string;
+	error_code?: EngineErrorCode;
 }> {
 	return invoke("describe_table", { sessionId, namespace, table });
 }
 
+export async function describeNamespace(
+	sessionId: string,
+	namespace: Namespace,
+): Promise<{
+	success: boolean;
+	tables?: Record<string, TableSchema>;
+	error?: string;
+	error_code?: EngineErrorCode;
+}> {
+	return invoke("describe_namespace", { sessionId, namespace });
+}
+
+export interface TableSizeInfo {
+	table_name: string;
+	row_count: number;
+	total_bytes: number;
+	table_bytes: number;
+	index_bytes: number;
+	toast_bytes?: number | null;
+}
+
+export async function getTableSizes(
+	sessionId: string,
+	namespace: Namespace,
+): Promise<{
+	success: boolean;
+	sizes?: TableSizeInfo[];
+	error?: string;
+	error_code?: EngineErrorCode;
+}> {
+	return invoke("get_table_sizes", { sessionId, namespace });
+}
+
+export interface WalStatus {
+	current_lsn: string;
+	last_checkpoint_lsn: string;
+	recovery_target_lsn?: string | null;
+	is_in_recovery: boolean;
+	/** Replay lag in seconds (standbys only) */
+	standby_delay?: number | null;
+}
+
+export async function getWalStatus(sessionId: string): Promise<{
+	success: boolean;
+	status?: WalStatus;
+	error?: string;
+	error_code?: EngineErrorCode;
+}> {
+	return invoke("get_wal_status", { sessionId });
+}
+
+export async function forceCheckpoint(
+	sessionId: string,
+	acknowledgedDangerous?: boolean,
+): Promise<{ success: boolean; error?: string; error_code?: EngineErrorCode }> {
+	return invoke("force_checkpoint", { sessionId, acknowledgedDangerous });
+}
+
+export interface ServerVariable {
+	name: string;
+	value: string;
+	scope: VariableScope;
+}
+
+/** `filter` is a LIKE pattern on the variable name */
+export async function listServerVariables(
+	sessionId: string,
+	scope: VariableScope = "session",
+	filter?: string,
+): Promise<{
+	success: boolean;
+	variables?: ServerVariable[];
+	error?: string;
+	error_code?: EngineErrorCode;
+}> {
+	return invoke("list_server_variables", { sessionId, scope, filter });
+}
+
+/** Global changes need `acknowledgedDangerous` on production connections */
+export async function setServerVariable(
+	sessionId: string,
+	name: string,
+	value: string,
+	scope: VariableScope = "session",
+	acknowledgedDangerous?: boolean,
+): Promise<{ success: boolean; error?: string; error_code?: EngineErrorCode }> {
+	return invoke("set_server_variable", {
+		sessionId,
+		name,
+		value,
+		scope,
+		acknowledgedDangerous,
+	});
+}
+
+export interface ServerQuery {
+	pid: number;
+	username?: string;
+	database?: string;
+	client_addr?: string;
+	state?: string;
+	query?: string;
+	duration_ms?: number;
+}
+
+/** Client sessions on the server and their current queries (PostgreSQL, MySQL) */
+export async function listServerQueries(sessionId: string): Promise<{
+	success: boolean;
+	queries?: ServerQuery[];
+	error?: string;
+	error_code?: EngineErrorCode;
+}> {
+	return invoke("list_server_queries", { sessionId });
+}
+
+/**
+ * Cancels the query of a server process; `force` terminates the whole
+ * session, is refused on read-only connections and needs
+ * `acknowledgedDangerous` on production connections
+ */
+export async function cancelByPid(
+	sessionId: string,
+	pid: number,
+	force?: boolean,
+	acknowledgedDangerous?: boolean,
+): Promise<{ success: boolean; error?: string; error_code?: EngineErrorCode }> {
+	return invoke("cancel_by_pid", { sessionId, pid, force, acknowledgedDangerous });
+}
+
+/** Client sessions connected to the server (PostgreSQL, MySQL) */
+export async function listServerSessions(sessionId: string): Promise<{
+	success: boolean;
+	sessions?: ServerQuery[];
+	error?: string;
+	error_code?: EngineErrorCode;
+}> {
+	return invoke("list_server_sessions", { sessionId });
+}
+
+/**
+ * Terminates another client's session on the server; refused on read-only
+ * connections and needs `acknowledgedDangerous` where dangerous statements do
+ */
+export async function terminateServerSession(
+	sessionId: string,
+	pid: number,
+	acknowledgedDangerous?: boolean,
+): Promise<{ success: boolean; error?: string; error_code?: EngineErrorCode }> {
+	return invoke("terminate_server_session", { sessionId, pid, acknowledgedDangerous });
+}
+
+/** Drops the prepared statements cached by a session, after schema changes by other clients */
+export async function clearStatementCache(
+	sessionId: string,
+): Promise<{ success: boolean; error?: string; error_code?: EngineErrorCode }> {
+	return invoke("clear_statement_cache", { sessionId });
+}
+
+export interface AdvisoryLockResponse {
+	success: boolean;
+	/** Whether the lock was acquired, or released */
+	granted: boolean;
+	error?: string;
+	error_code?: EngineErrorCode;
+}
+
+/**
+ * Takes a PostgreSQL advisory lock, held until released or disconnected.
+ * Without `timeoutMs` the lock is only tried; with it, waits that long (0: forever).
+ */
+export async function acquireAdvisoryLock(
+	sessionId: string,
+	key: number,
+	shared?: boolean,
+	timeoutMs?: number,
+): Promise<AdvisoryLockResponse> {
+	return invoke("acquire_advisory_lock", { sessionId, key, shared, timeoutMs });
+}
+
+export async function releaseAdvisoryLock(
+	sessionId: string,
+	key: number,
+	shared?: boolean,
+): Promise<AdvisoryLockResponse> {
+	return invoke("release_advisory_lock", { sessionId, key, shared });
+}
+
+/** PostgreSQL NOTIFY; `channel` must be a plain identifier */
+export async function sendDbNotification(
+	sessionId: string,
+	channel: string,
+	payload?: string,
+	acknowledgedDangerous?: boolean,
+): Promise<{ success: boolean; error?: string; error_code?: EngineErrorCode }> {
+	return invoke("send_db_notification", { sessionId, channel, payload, acknowledgedDangerous });
+}
+
+/** For MongoDB, `namespace.schema` names the first collection to create */
+export async function createNamespace(
+	sessionId: string,
+	namespace: Namespace,
+	acknowledgedDangerous?: boolean,
+): Promise<{ success: boolean; error?: string; error_code?: EngineErrorCode }> {
+	return invoke("create_namespace", { sessionId, namespace, acknowledgedDangerous });
+}
+
+export async function dropNamespace(
+	sessionId: string,
+	namespace: Namespace,
+	cascade?: boolean,
+	acknowledgedDangerous?: boolean,
+): Promise<{ success: boolean; error?: string; error_code?: EngineErrorCode }> {
+	return invoke("drop_namespace", {
+		sessionId,
+		namespace,
+		cascade,
+		acknowledgedDangerous,
+	});
+}
+
+export interface NewColumnDef {
+	name: string;
+	/** Type in the database's own syntax, e.g. `varchar(255)` */
+	data_type: string;
+	nullable: boolean;
+	/** SQL expression, e.g. `'draft'` or `now()` */
+	default_value?: string | null;
+	is_primary_key: boolean;
+	auto_increment: boolean;
+}
+
+export interface NewTableSchema {
+	table_name: string;
+	columns: NewColumnDef[];
+	/** Key columns in order; defaults to the columns flagged is_primary_key */
+	primary_key?: string[] | null;
+	if_not_exists: boolean;
+}
+
+export async function createTable(
+	sessionId: string,
+	namespace: Namespace,
+	schema: NewTableSchema,
+	acknowledgedDangerous?: boolean,
+): Promise<{ success: boolean; error?: string; error_code?: EngineErrorCode }> {
+	return invoke("create_table", {
+		sessionId,
+		namespace,
+		schema,
+		acknowledgedDangerous,
+	});
+}
+
+export async function addTableColumn(
+	sessionId: string,
+	namespace: Namespace,
+	table: string,
+	column: NewColumnDef,
+	acknowledgedDangerous?: boolean,
+): Promise<{ success: boolean; error?: string; error_code?: EngineErrorCode }> {
+	return invoke("add_table_column", {
+		sessionId,
+		namespace,
+		table,
+		column,
+		acknowledgedDangerous,
+	});
+}
+
+export async function dropTableColumn(
+	sessionId: string,
+	namespace: Namespace,
+	table: string,
+	columnName: string,
+	acknowledgedDangerous?: boolean,
+): Promise<{ success: boolean; error?: string; error_code?: EngineErrorCode }> {
+	return invoke("drop_table_column", {
+		sessionId,
+		namespace,
+		table,
+		columnName,
+		acknowledgedDangerous,
+	});
+}
+
+export async function renameColumn(
+	sessionId: string,
+	namespace: Namespace,
+	table: string,
+	oldName: string,
+	newName: string,
+	acknowledgedDangerous?: boolean,
+): Promise<{ success: boolean; error?: string; error_code?: EngineErrorCode }> {
+	return invoke("rename_column", {
+		sessionId,
+		namespace,
+		table,
+		oldName,
+		newName,
+		acknowledgedDangerous,
+	});
+}
+
+export async function exportResult(
+	result: QueryResult,
+	format: "parquet",
+	path: string,
+	exportId?: string,
+): Promise<{
+	success: boolean;
+	export_id?: string;
+	error?: string;
+	error_code?: EngineErrorCode;
+}> {
+	return invoke("export_result", { result, format, path, exportId });
+}
+
+export async function cancelExport(exportId: string): Promise<{
+	success: boolean;
+	export_id?: string;
+	error?: string;
+	error_code?: EngineErrorCode;
+}> {
+	return invoke("cancel_export", { exportId });
+}
+
+export interface ImportProgressEvent {
+	import_id: string;
+	rows_sent: number;
+}
+
+/**
+ * Imports a CSV file with COPY (PostgreSQL). Columns default to the header
+ * line; fields equal to `nullMarker` (empty by default) become NULL.
+ * Progress arrives as `import_progress` events.
+ */
+export async function importCopy(
+	sessionId: string,
+	namespace: Namespace,
+	table: string,
+	path: string,
+	options: {
+		columns?: string[];
+		hasHeader?: boolean;
+		nullMarker?: string;
+		importId?: string;
+		acknowledgedDangerous?: boolean;
+	} = {},
+): Promise<{
+	success: boolean;
+	import_id?: string;
+	rows_imported?: number;
+	error?: string;
+	error_code?: EngineErrorCode;
+}> {
+	return invoke("import_copy", { sessionId, namespace, table, path, ...options });
+}
+
 export async function previewTable(
 	sessionId: string,
 	namespace: Namespace,
 	table: string,
 	limit: number = 100,
+	lockRows?: RowLockMode,
 ): Promise<{
 	success: boolean;
 	result?: QueryResult;
 	error?: string;
+	error_code?: EngineErrorCode;
+	/** Limit actually applied, clamped to the policy's preview_max_rows */
+	limit: number;
+	limit_capped: boolean;
+}> {
+	return invoke("preview_table", { sessionId, namespace, table, limit, lockRows });
+}
+
+/** Loads a value truncated by `maxValueBytes` in full */
+export async function fetchFullValue(
+	sessionId: string,
+	namespace: Namespace,
+	table: string,
+	primaryKey: RowData,
+	column: string,
+): Promise<{
+	success: boolean;
+	value?: Value;
+	error?: string;
+	error_code?: EngineErrorCode;
+}> {
+	return invoke("fetch_full_value", {
+		sessionId,
+		namespace,
+		table,
+		primaryKey,
+		column,
+	});
+}
+
+/** Reads the PostgreSQL large object referenced by an `lo` column value */
+export async function readLargeObject(
+	sessionId: string,
+	oid: number,
+): Promise<{
+	success: boolean;
+	/** Base64-encoded content */
+	data?: string;
+	error?: string;
+	error_code?: EngineErrorCode;
 }> {
-	return invoke("preview_table", { sessionId, namespace, table, limit });
+	return invoke("read_large_object", { sessionId, oid });
 }
 
 // ============================================
@@ -289,6 +1209,7 @@ export async function previewTable(
 export async function beginTransaction(sessionId: string): Promise<{
 	success: boolean;
 	error?: string;
+	error_code?: EngineErrorCode;
 }> {
 	return invoke("begin_transaction", { sessionId });
 }
@@ -296,6 +1217,7 @@ export async function beginTransaction(sessionId: string): Promise<{
 export async function commitTransaction(sessionId: string): Promise<{
 	success: boolean;
 	error?: string;
+	error_code?: EngineErrorCode;
 }> {
 	return invoke("commit_transaction", { sessionId });
 }
@@ -303,6 +1225,7 @@ export async function commitTransaction(sessionId: string): Promise<{
 export async function rollbackTransaction(sessionId: string): Promise<{
 	success: boolean;
 	error?: string;
+	error_code?: EngineErrorCode;
 }> {
 	return invoke("rollback_transaction", { sessionId });
 }
@@ -321,10 +1244,22 @@ export interface RowData {
 	columns: Record<string, Value>;
 }
 
+export type MutationType =
+	| "insert"
+	| "update"
+	| "delete"
+	| "upsert"
+	| "bulk_insert";
+
 export interface MutationResponse {
 	success: boolean;
 	result?: QueryResult;
 	error?: string;
+	error_code?: EngineErrorCode;
+	operation: MutationType;
+	database: string;
+	schema?: string | null;
+	table: string;
 }
 
 export async function insertRow(
@@ -333,8 +1268,35 @@ export async function insertRow(
 	schema: string | null | undefined,
 	table: string,
 	data: RowData,
+	acknowledgedDangerous?: boolean,
+): Promise<MutationResponse> {
+	return invoke("insert_row", {
+		sessionId,
+		database,
+		schema,
+		table,
+		data,
+		acknowledgedDangerous,
+	});
+}
+
+/** Like insertRow, but `result.rows` holds the row as stored */
+export async function insertRowReturning(
+	sessionId: string,
+	database: string,
+	schema: string | null | undefined,
+	table: string,
+	data: RowData,
+	acknowledgedDangerous?: boolean,
 ): Promise<MutationResponse> {
-	return invoke("insert_row", { sessionId, database, schema, table, data });
+	return invoke("insert_row_returning", {
+		sessionId,
+		database,
+		schema,
+		table,
+		data,
+		acknowledgedDangerous,
+	});
 }
 
 export async function updateRow(
@@ -344,6 +1306,7 @@ export async function updateRow(
 	table: string,
 	primaryKey: RowData,
 	data: RowData,
+	acknowledgedDangerous?: boolean,
 ): Promise<MutationResponse> {
 	return invoke("update_row", {
 		sessionId,
@@ -352,6 +1315,7 @@ export async function updateRow(
 		table,
 		primaryKey,
 		data,
+		acknowledgedDangerous,
 	});
 }
 
@@ -361,6 +1325,7 @@ export async function deleteRow(
 	schema: string | null | undefined,
 	table: string,
 	primaryKey: RowData,
+	acknowledgedDangerous?: boolean,
 ): Promise<MutationResponse> {
 	return invoke("delete_row", {
 		sessionId,
@@ -368,6 +1333,45 @@ export async function deleteRow(
 		schema,
 		table,
 		primaryKey,
+		acknowledgedDangerous,
+	});
+}
+
+/** Deletes every row matching `filter`; without a filter the table is emptied. */
+export async function deleteRowsWhere(
+	sessionId: string,
+	database: string,
+	schema: string | null | undefined,
+	table: string,
+	filter?: string,
+	acknowledgedDangerous?: boolean,
+): Promise<MutationResponse> {
+	return invoke("delete_rows_where", {
+		sessionId,
+		database,
+		schema,
+		table,
+		filter,
+		acknowledgedDangerous,
+	});
+}
+
+/** Empties a table; on MongoDB every document is deleted */
+export async function truncateTable(
+	sessionId: string,
+	database: string,
+	schema: string | null | undefined,
+	table: string,
+	cascade?: boolean,
+	acknowledgedDangerous?: boolean,
+): Promise<MutationResponse> {
+	return invoke("truncate_table", {
+		sessionId,
+		database,
+		schema,
+		table,
+		cascade,
+		acknowledgedDangerous,
 	});
 }
 
@@ -375,6 +1379,12 @@ export async function supportsMutations(sessionId: string): Promise<boolean> {
 	return invoke("supports_mutations", { sessionId });
 }
 
+export async function supportsReturningKeys(
+	sessionId: string,
+): Promise<boolean> {
+	return invoke("supports_returning_keys", { sessionId });
+}
+
 // ============================================
 
 export async function getVaultStatus(): Promise<VaultStatus> {
@@ -387,6 +1397,11 @@ export async function setupMasterPassword(
 	return invoke("setup_master_password", { password });
 }
 
+/** Times a master password hash with the policy's Argon2 parameters */
+export async function benchmarkArgon2(): Promise<Argon2BenchmarkResponse> {
+	return invoke("benchmark_argon2");
+}
+
 export async function unlockVault(password: string): Promise<VaultResponse> {
 	return invoke("unlock_vault", { password });
 }
@@ -407,7 +1422,35 @@ export async function saveConnection(input: {
 	password: string;
 	database?: string;
 	ssl: boolean;
+	/** TLS mode; takes precedence over `ssl` when set */
+	ssl_mode?: SslMode;
+	/** CA certificate checked by `verify_ca` and `verify_identity` */
+	ssl_ca_path?: string;
 	project_id: string;
+	/** Shown instead of the host when production details are masked */
+	display_alias?: string;
+	/** MongoDB: database holding the user's credentials (default "admin") */
+	mongo_auth_source?: string;
+	/** Seconds between keepalive pings on idle sessions (unset or 0 disables) */
+	keepalive_query_secs?: number;
+	/** Custom session name, shown instead of user@host:db */
+	label?: string;
+	/** MySQL: connection character set (default "utf8mb4") */
+	charset?: string;
+	/** Retries of the initial connect on transient network errors */
+	retry_policy?: RetryPolicy;
+	/** Prepared statements cached per pooled connection (SQL drivers, default 100, 0 disables) */
+	statement_cache_capacity?: number;
+	group_id?: string;
+	/** Hex color such as "#3b82f6"; anything else is rejected */
+	color?: string;
+	/** Trimmed and deduplicated when saved */
+	tags?: string[];
+	/**
+	 * Used verbatim instead of the fields above (e.g. several hosts with
+	 * target_session_attrs). Cannot be combined with an SSH tunnel.
+	 */
+	raw_connection_string?: string;
 	ssh_tunnel?: {
 		host: string;
 		port: number;
@@ -422,23 +1465,195 @@ export async function saveConnection(input: {
 		connect_timeout_secs: number;
 		keepalive_interval_secs: number;
 		keepalive_count_max: number;
+		/** Wait for the tunnel to become ready, in ms (default 5000) */
+		startup_timeout_ms?: number;
+		/** Probe interval while the tunnel starts, in ms (default 50) */
+		startup_poll_interval_ms?: number;
+		/** Re-open the tunnel when it goes down during a session (default false) */
+		auto_restart?: boolean;
 	};
 }): Promise<VaultResponse> {
 	return invoke("save_connection", { input });
 }
 
-export async function listSavedConnections(projectId: string): Promise<SavedConnection[]> {
-  return invoke('list_saved_connections', { projectId });
+export async function listSavedConnections(
+  projectId: string,
+  includeHealth?: boolean
+): Promise<SavedConnection[]> {
+  return invoke('list_saved_connections', { projectId, includeHealth });
 }
 
 export async function getConnectionCredentials(projectId: string, connectionId: string): Promise<{
   success: boolean;
   password?: string;
+  raw_connection_string?: string;
   error?: string;
+  error_code?: EngineErrorCode;
 }> {
   return invoke('get_connection_credentials', { projectId, connectionId });
 }
 
+/** Connection URI of a saved connection; the password is masked unless `includePassword` */
+export async function exportConnectionUri(
+  projectId: string,
+  connectionId: string,
+  includePassword: boolean
+): Promise<string> {
+  return invoke('export_connection_uri', { projectId, connectionId, includePassword });
+}
+
 export async function deleteSavedConnection(projectId: string, connectionId: string): Promise<VaultResponse> {
   return invoke('delete_saved_connection', { projectId, connectionId });
 }
+
+export interface ProjectEntry {
+  id: string;
+  display_name: string;
+}
+
+export async function listProjects(): Promise<ProjectEntry[]> {
+  return invoke('list_projects');
+}
+
+export async function createProject(projectId: string, displayName: string): Promise<VaultResponse> {
+  return invoke('create_project', { projectId, displayName });
+}
+
+/** With `deleteConnections`, the project's saved connections are deleted too */
+export async function deleteProject(projectId: string, deleteConnections: boolean): Promise<VaultResponse> {
+  return invoke('delete_project', { projectId, deleteConnections });
+}
+
+export interface ConnectionGroup {
+  id: string;
+  name: string;
+  color?: string;
+}
+
+export async function listConnectionGroups(projectId: string): Promise<ConnectionGroup[]> {
+  return invoke('list_connection_groups', { projectId });
+}
+
+export async function createConnectionGroup(
+  projectId: string,
+  groupId: string,
+  name: string,
+  color?: string
+): Promise<VaultResponse> {
+  return invoke('create_connection_group', { projectId, groupId, name, color });
+}
+
+export async function renameConnectionGroup(
+  projectId: string,
+  groupId: string,
+  newName: string
+): Promise<VaultResponse> {
+  return invoke('rename_connection_group', { projectId, groupId, newName });
+}
+
+/** Connections of the deleted group move to `moveConnectionsTo`, or to the top level */
+export async function deleteConnectionGroup(
+  projectId: string,
+  groupId: string,
+  moveConnectionsTo?: string
+): Promise<VaultResponse> {
+  return invoke('delete_connection_group', { projectId, groupId, moveConnectionsTo });
+}
+
+export async function moveConnectionToGroup(
+  projectId: string,
+  connectionId: string,
+  groupId?: string
+): Promise<VaultResponse> {
+  return invoke('move_connection_to_group', { projectId, connectionId, groupId });
+}
+
+// ============================================
+// SNIPPETS
+// ============================================
+
+/**
+ * Saved query. Snippets are personal unless a `projectId` is passed, in
+ * which case they are shared within that project and stored in the vault.
+ */
+export interface Snippet {
+  /** Generated on first save when empty */
+  id: string;
+  name: string;
+  description?: string;
+  /** Driver the snippet applies to; omitted for all drivers */
+  driver_id?: string;
+  /** May contain a {{table_name}} placeholder */
+  query: string;
+  /** Set on first save */
+  created_at?: string;
+  tags?: string[];
+}
+
+export interface SnippetResponse {
+  success: boolean;
+  snippet?: Snippet;
+  error?: string;
+  error_code?: EngineErrorCode;
+}
+
+export async function saveSnippet(snippet: Snippet, projectId?: string): Promise<SnippetResponse> {
+  return invoke('save_snippet', { snippet, projectId });
+}
+
+/** `driverIdFilter` takes precedence over the driver of `sessionId` */
+export async function listSnippets(options: {
+  projectId?: string;
+  sessionId?: string;
+  driverIdFilter?: string;
+} = {}): Promise<{
+  success: boolean;
+  snippets: Snippet[];
+  error?: string;
+  error_code?: EngineErrorCode;
+}> {
+  return invoke('list_snippets', options);
+}
+
+export async function getSnippet(id: string, projectId?: string): Promise<SnippetResponse> {
+  return invoke('get_snippet', { id, projectId });
+}
+
+export async function updateSnippet(snippet: Snippet, projectId?: string): Promise<SnippetResponse> {
+  return invoke('update_snippet', { snippet, projectId });
+}
+
+export async function deleteSnippet(id: string, projectId?: string): Promise<SnippetResponse> {
+  return invoke('delete_snippet', { id, projectId });
+}
+
+/** Returns the snippet's query with {{table_name}} replaced */
+export async function renderSnippet(id: string, tableName: string, projectId?: string): Promise<string> {
+  return invoke('render_snippet', { id, tableName, projectId });
+}
+
+// ============================================
+// SSH KNOWN HOSTS
+// ============================================
+
+export interface KnownHostEntry {
+  hostname: string;
+  key_type: string;
+  key_fingerprint: string;
+}
+
+export async function listKnownHosts(): Promise<KnownHostEntry[]> {
+  return invoke('list_known_hosts');
+}
+
+export async function addKnownHost(hostname: string, keyType: string, publicKey: string): Promise<void> {
+  return invoke('add_known_host', { hostname, keyType, publicKey });
+}
+
+export async function removeKnownHost(hostname: string, keyType: string): Promise<void> {
+  return invoke('remove_known_host', { hostname, keyType });
+}
+
+export async function clearKnownHosts(): Promise<void> {
+  return invoke('clear_known_hosts');
+}